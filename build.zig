@@ -124,6 +124,21 @@ pub fn build(b: *std.Build) void {
         run_lib_unit_tests.addArgs(user_args);
     }
 
+    // Large fixtures may be committed as .bin.zst; the Rust helper writes
+    // the raw .bin files next to them
+    const decompress_fixtures = b.addSystemCommand(&.{
+        "cargo",           "run",                        "--quiet",
+        "--manifest-path", "rust_test_helper/Cargo.toml", "--",
+        "decompress",
+    });
+    const fixtures_step = b.step("fixtures", "Decompress the .bin.zst test fixtures");
+    fixtures_step.dependOn(&decompress_fixtures.step);
+
+    const decompress_before_test = b.option(bool, "decompress-fixtures", "Decompress .bin.zst fixtures before running tests") orelse false;
+    if (decompress_before_test) {
+        run_lib_unit_tests.step.dependOn(&decompress_fixtures.step);
+    }
+
     const test_step = b.step("test", "Run unit tests");
     test_step.dependOn(&run_lib_unit_tests.step);
 }
@@ -96,8 +96,8 @@ fn transferSol(
 
     // Optimized: Stack-allocated instruction components
     const ix_accounts = [_]AccountMeta{
-        .{ .pubkey = from_key, .is_writable = true, .is_signer = true },
-        .{ .pubkey = to_key, .is_writable = true, .is_signer = false },
+        .{ .pubkey = from_key.*, .is_writable = true, .is_signer = true },
+        .{ .pubkey = to_key.*, .is_writable = true, .is_signer = false },
     };
 
     // Ultra-optimized: Direct memory layout without runtime conversion
@@ -109,7 +109,7 @@ fn transferSol(
     std.mem.writeInt(u64, ix_data[4..12], lamports, .little);
 
     const transfer_ix = Instruction.from(.{
-        .program_id = &SYSTEM_PROGRAM_ID,
+        .program_id = SYSTEM_PROGRAM_ID,
         .accounts = &ix_accounts,
         .data = &ix_data,
     });
@@ -174,8 +174,8 @@ fn createPdaAccount(
     const pda_key = pda_account.key();
 
     var ix_accounts = [_]AccountMeta{
-        .{ .pubkey = payer_key, .is_writable = true, .is_signer = true },
-        .{ .pubkey = pda_key, .is_writable = true, .is_signer = true },
+        .{ .pubkey = payer_key.*, .is_writable = true, .is_signer = true },
+        .{ .pubkey = pda_key.*, .is_writable = true, .is_signer = true },
     };
 
     // Optimized: Direct memory layout with explicit endianness
@@ -186,7 +186,7 @@ fn createPdaAccount(
     @memcpy(ix_data[20..52], &program_id.bytes);
 
     const create_ix = Instruction.from(.{
-        .program_id = &SYSTEM_PROGRAM_ID,
+        .program_id = SYSTEM_PROGRAM_ID,
         .accounts = &ix_accounts,
         .data = &ix_data,
     });
@@ -251,8 +251,8 @@ fn transferFromPda(
 
     // Create transfer instruction
     var ix_accounts = [_]AccountMeta{
-        .{ .pubkey = pda_key, .is_writable = true, .is_signer = true },
-        .{ .pubkey = to_key, .is_writable = true, .is_signer = false },
+        .{ .pubkey = pda_key.*, .is_writable = true, .is_signer = true },
+        .{ .pubkey = to_key.*, .is_writable = true, .is_signer = false },
     };
 
     // Ultra-optimized: Use extern struct for transfer data
@@ -268,7 +268,7 @@ fn transferFromPda(
     const ix_data = std.mem.asBytes(&ix_data_struct);
 
     const transfer_ix = Instruction.from(.{
-        .program_id = &SYSTEM_PROGRAM_ID,
+        .program_id = SYSTEM_PROGRAM_ID,
         .accounts = &ix_accounts,
         .data = ix_data,
     });
@@ -289,6 +289,9 @@ comptime {
     solana.declareEntrypoint(process_instruction);
 }
 
+// Log panics and abort the transaction
+pub const panic = solana.entrypoint.panic;
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -57,7 +57,7 @@ pub fn process_instruction(
     // Build allocate instruction (system program instruction 8)
     const allocated_key = allocated_info.key();
     const ix_accounts = [_]AccountMeta{
-        .{ .pubkey = allocated_key, .is_writable = true, .is_signer = true },
+        AccountMeta.writable(allocated_key.*, true),
     };
 
     // Allocate instruction data: [8, 0, 0, 0] + space (8 bytes)
@@ -65,11 +65,7 @@ pub fn process_instruction(
     std.mem.writeInt(u32, ix_data[0..4], 8, .little); // Allocate = 8
     std.mem.writeInt(u64, ix_data[4..12], SIZE, .little);
 
-    const allocate_ix = Instruction.from(.{
-        .program_id = &SYSTEM_PROGRAM_ID,
-        .accounts = &ix_accounts,
-        .data = &ix_data,
-    });
+    const allocate_ix = Instruction.init(SYSTEM_PROGRAM_ID, &ix_accounts, &ix_data);
 
     // Invoke with PDA as signer (matching invoke_signed in Rosetta)
     const signer_seeds = [_][]const u8{ seed, &bump_seed };
@@ -84,4 +80,7 @@ pub fn process_instruction(
 // Export entrypoint
 comptime {
     solana.entrypoint.declareEntrypoint(process_instruction);
-}
\ No newline at end of file
+}
+
+// Log panics and abort the transaction
+pub const panic = solana.entrypoint.panic;
\ No newline at end of file
@@ -0,0 +1,107 @@
+// Generate a deterministic corpus of random, valid entrypoint buffers for
+// the Zig fuzz targets
+//
+// Usage: fuzz_corpus_gen [--seed N] [--count M]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rust_test_helper::constants::MAX_PERMITTED_DATA_INCREASE;
+use rust_test_helper::error::{GenerateError, GenerateResult};
+use rust_test_helper::serialize_solana_format::{build_from_accounts, SerializationFormat};
+use rust_test_helper::verify::{verify_round_trip, ExpectedAccount};
+use solana_program::pubkey::Pubkey;
+use std::path::Path;
+
+const MAX_ACCOUNTS: usize = 64;
+/// Instruction data fits in a single transaction packet
+const MAX_INSTRUCTION_DATA_LEN: usize = 1232;
+
+struct Args {
+    seed: u64,
+    count: usize,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { seed: 0, count: 16 };
+    let mut iter = std::env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        let value = iter.next().ok_or(format!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--seed" => args.seed = value.parse().map_err(|e| format!("--seed: {e}"))?,
+            "--count" => args.count = value.parse().map_err(|e| format!("--count: {e}"))?,
+            _ => return Err(format!("unknown argument {flag}")),
+        }
+    }
+    Ok(args)
+}
+
+/// A random account list. Roughly one entry in eight duplicates an
+/// earlier account, as long as the index still fits the dup marker.
+fn random_accounts(rng: &mut StdRng) -> Vec<ExpectedAccount> {
+    let count = rng.gen_range(1..=MAX_ACCOUNTS);
+    let mut accounts: Vec<ExpectedAccount> = Vec::with_capacity(count);
+    for i in 0..count {
+        if i > 0 && rng.gen_ratio(1, 8) {
+            let index = rng.gen_range(0..i) as u8;
+            accounts.push(ExpectedAccount::duplicate_of(&accounts, index));
+            continue;
+        }
+        let data_len = rng.gen_range(0..=MAX_PERMITTED_DATA_INCREASE);
+        accounts.push(ExpectedAccount::new(
+            Pubkey::new_from_array(rng.gen()),
+            rng.gen(), // is_signer
+            rng.gen(), // is_writable
+            rng.gen(), // lamports
+            (0..data_len).map(|_| rng.gen()).collect(),
+            Pubkey::new_from_array(rng.gen()),
+            rng.gen(), // executable
+            rng.gen(), // rent_epoch
+        ));
+    }
+    accounts
+}
+
+fn generate(args: &Args, corpus_dir: &Path) -> GenerateResult<()> {
+    std::fs::create_dir_all(corpus_dir).map_err(|e| GenerateError::new(corpus_dir, e))?;
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    for n in 0..args.count {
+        let accounts = random_accounts(&mut rng);
+        let instruction_data: Vec<u8> = (0..rng.gen_range(0..=MAX_INSTRUCTION_DATA_LEN))
+            .map(|_| rng.gen())
+            .collect();
+        let program_id = Pubkey::new_from_array(rng.gen());
+        let buffer = build_from_accounts(
+            SerializationFormat::Aligned,
+            &accounts,
+            &instruction_data,
+            &program_id,
+        );
+
+        let file_path = corpus_dir.join(format!("corpus_{n:04}.bin"));
+        std::fs::write(&file_path, &buffer).map_err(|e| GenerateError::new(&file_path, e))?;
+        verify_round_trip(&file_path, &accounts);
+    }
+
+    println!(
+        "Generated {} corpus files in {} (seed {})",
+        args.count,
+        corpus_dir.display(),
+        args.seed
+    );
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {message}");
+            eprintln!("usage: fuzz_corpus_gen [--seed N] [--count M]");
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(error) = generate(&args, Path::new("../test_data/fuzz")) {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,156 @@
+// Account data encoded with Borsh, the way most Solana programs store their
+// state, for validating the Zig SDK's Borsh decoder against the Rust crate
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use std::path::Path;
+
+use crate::error::GenerateResult;
+use crate::hexdump::write_hexdump;
+use crate::manifest::write_manifest;
+use crate::offsets::write_offsets;
+use crate::serialize_solana_format::{
+    append_instruction_epilogue, describe_instruction_data, serialize_account_solana_format,
+    write_account_count, FixtureRng, SerializationFormat, TEST_PROGRAM_ID,
+};
+use crate::stable::write_if_changed;
+use crate::verify::{verify_round_trip, ExpectedAccount};
+
+/// A program's counter state account.
+///
+/// Borsh writes the fields in declaration order with no padding or length
+/// prefixes, so the data is always `COUNTER_ACCOUNT_LEN` bytes:
+///
+/// ```text
+/// offset  size  field
+///      0     8  counter    u64, little-endian
+///      8    32  authority  the Pubkey's bytes
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct CounterAccount {
+    pub counter: u64,
+    pub authority: Pubkey,
+}
+
+/// Size of a Borsh-encoded `CounterAccount`
+pub const COUNTER_ACCOUNT_LEN: usize = 8 + 32;
+
+/// The state stored in `borsh_counter_account.bin`. The counter's bytes
+/// all differ, so a decoder that reads it big-endian or at the wrong
+/// offset cannot get it right by accident.
+pub fn counter_account_state() -> CounterAccount {
+    CounterAccount {
+        counter: 0x0000_0123_4567_89AB,
+        authority: Pubkey::new_from_array([0xA7; 32]),
+    }
+}
+
+/// The Borsh encoding of `state`
+pub fn counter_account_data(state: &CounterAccount) -> Vec<u8> {
+    borsh::to_vec(state).expect("CounterAccount serializes")
+}
+
+/// A writable account owned by `TEST_PROGRAM_ID` holding
+/// `counter_account_state()`
+pub fn borsh_counter_account_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0xC0; 32]),
+        false, // is_signer
+        true,  // is_writable
+        1_000_000,
+        counter_account_data(&counter_account_state()),
+        TEST_PROGRAM_ID,
+        false, // executable
+        0,     // rent_epoch
+    )]
+}
+
+/// `borsh_counter_account.bin`, with instruction data 0x00 for "increment"
+pub fn generate_borsh_counter_account(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = [0x00];
+    let expected_accounts = borsh_counter_account_scenario();
+    let account = &expected_accounts[0];
+
+    let mut buffer = Vec::new();
+    write_account_count(&mut buffer, expected_accounts.len());
+    let mut lamports = account.lamports;
+    serialize_account_solana_format(
+        &mut buffer,
+        &account.key,
+        account.is_signer,
+        account.is_writable,
+        &mut lamports,
+        &account.data,
+        &account.owner,
+        account.executable,
+        account.rent_epoch,
+    );
+    append_instruction_epilogue(&mut buffer, &instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("borsh_counter_account.bin");
+    write_if_changed(&file_path, &buffer)?;
+    verify_round_trip(&file_path, &expected_accounts);
+    write_manifest(
+        &file_path,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+    write_hexdump(
+        &file_path,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+    write_offsets(
+        &file_path,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: borsh_counter_account.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::deserialize_input;
+    use rand::SeedableRng;
+
+    #[test]
+    fn counter_account_layout_is_counter_then_authority() {
+        let state = counter_account_state();
+        let data = counter_account_data(&state);
+        assert_eq!(data.len(), COUNTER_ACCOUNT_LEN);
+        assert_eq!(data[..8], state.counter.to_le_bytes());
+        assert_eq!(data[8..], state.authority.to_bytes());
+        assert_eq!(CounterAccount::try_from_slice(&data).unwrap(), state);
+    }
+
+    #[test]
+    fn fixture_data_decodes_to_the_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(crate::serialize_solana_format::DEFAULT_SEED);
+        generate_borsh_counter_account(dir.path(), &mut rng).unwrap();
+
+        let bytes = std::fs::read(dir.path().join("borsh_counter_account.bin")).unwrap();
+        let parsed = deserialize_input(&bytes).unwrap();
+        assert_eq!(parsed.accounts.len(), 1);
+        assert_eq!(
+            CounterAccount::try_from_slice(&parsed.accounts[0].data).unwrap(),
+            counter_account_state()
+        );
+    }
+}
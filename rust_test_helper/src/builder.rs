@@ -0,0 +1,333 @@
+// Building serialized account entries field by field instead of passing
+// serialize_account_solana_format its nine positional arguments, and whole
+// entrypoint buffers out of them
+use solana_program::account_info::AccountInfo;
+use solana_program::pubkey::Pubkey;
+
+use crate::serialize_solana_format::{
+    append_instruction_epilogue, serialize_account, serialize_account_solana_format,
+    serialize_duplicate_account, write_account_count, FieldSink, SerializationFormat,
+};
+use crate::verify::ExpectedAccount;
+
+/// One non-duplicate account entry. Fields that are not set default to zero,
+/// empty or false.
+///
+/// ```
+/// use rust_test_helper::builder::AccountBuilder;
+/// use solana_program::pubkey::Pubkey;
+///
+/// let mut buffer = Vec::new();
+/// AccountBuilder::new()
+///     .key(Pubkey::new_from_array([1; 32]))
+///     .lamports(1_000)
+///     .data(vec![0xAA; 10])
+///     .signer(true)
+///     .writable(true)
+///     .build_into(&mut buffer);
+/// assert_eq!(buffer.len(), 88 + 10 + 10 * 1024 + 6 + 8);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountBuilder {
+    key: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+impl AccountBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(&mut self, key: Pubkey) -> &mut Self {
+        self.key = key;
+        self
+    }
+
+    pub fn lamports(&mut self, lamports: u64) -> &mut Self {
+        self.lamports = lamports;
+        self
+    }
+
+    pub fn data(&mut self, data: Vec<u8>) -> &mut Self {
+        self.data = data;
+        self
+    }
+
+    pub fn owner(&mut self, owner: Pubkey) -> &mut Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn signer(&mut self, is_signer: bool) -> &mut Self {
+        self.is_signer = is_signer;
+        self
+    }
+
+    pub fn writable(&mut self, is_writable: bool) -> &mut Self {
+        self.is_writable = is_writable;
+        self
+    }
+
+    pub fn executable(&mut self, executable: bool) -> &mut Self {
+        self.executable = executable;
+        self
+    }
+
+    pub fn rent_epoch(&mut self, rent_epoch: u64) -> &mut Self {
+        self.rent_epoch = rent_epoch;
+        self
+    }
+
+    /// Append the entry in the aligned format the runtime uses
+    pub fn build_into<S: FieldSink>(&self, buffer: &mut S) {
+        let mut lamports = self.lamports;
+        serialize_account_solana_format(
+            buffer,
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut lamports,
+            &self.data,
+            &self.owner,
+            self.executable,
+            self.rent_epoch,
+        );
+    }
+
+    /// Append the entry in `format`
+    pub fn build_into_format<S: FieldSink>(&self, format: SerializationFormat, buffer: &mut S) {
+        let mut lamports = self.lamports;
+        serialize_account(
+            format,
+            buffer,
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut lamports,
+            &self.data,
+            &self.owner,
+            self.executable,
+            self.rent_epoch,
+        );
+    }
+}
+
+/// The fields of a non-duplicate `ExpectedAccount`. `dup_of` is ignored.
+impl From<&ExpectedAccount> for AccountBuilder {
+    fn from(account: &ExpectedAccount) -> Self {
+        AccountBuilder {
+            key: account.key,
+            lamports: account.lamports,
+            data: account.data.clone(),
+            owner: account.owner,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+    }
+}
+
+/// The fields of an `AccountInfo`, as the runtime would serialize it
+impl From<&AccountInfo<'_>> for AccountBuilder {
+    fn from(account: &AccountInfo<'_>) -> Self {
+        AccountBuilder {
+            key: *account.key,
+            lamports: account.lamports(),
+            data: account.data.borrow().to_vec(),
+            owner: *account.owner,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+    }
+}
+
+/// A complete entrypoint input: the account count, the account entries in
+/// the order they are added, then the instruction data and program ID.
+///
+/// ```
+/// use rust_test_helper::builder::{AccountBuilder, EntrypointBufferBuilder};
+/// use solana_program::pubkey::Pubkey;
+///
+/// let mut builder = EntrypointBufferBuilder::new();
+/// builder
+///     .add_account(AccountBuilder::new().lamports(1_000))
+///     .add_duplicate(0);
+/// let buffer = builder.finish(&Pubkey::default(), &[0x01]);
+/// assert_eq!(buffer[..8], 2u64.to_le_bytes());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrypointBufferBuilder {
+    format: SerializationFormat,
+    buffer: Vec<u8>,
+    /// Entries added so far, duplicates included
+    num_accounts: usize,
+}
+
+impl Default for EntrypointBufferBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntrypointBufferBuilder {
+    /// A buffer in the aligned format the runtime uses
+    pub fn new() -> Self {
+        Self::with_format(SerializationFormat::Aligned)
+    }
+
+    pub fn with_format(format: SerializationFormat) -> Self {
+        let mut buffer = Vec::new();
+        // Patched by `finish` once every account is in
+        write_account_count(&mut buffer, 0);
+        EntrypointBufferBuilder {
+            format,
+            buffer,
+            num_accounts: 0,
+        }
+    }
+
+    /// Append a non-duplicate account entry
+    pub fn add_account(&mut self, account: &AccountBuilder) -> &mut Self {
+        account.build_into_format(self.format, &mut self.buffer);
+        self.num_accounts += 1;
+        self
+    }
+
+    /// Append an entry repeating the account at `dup_index`. Nothing checks
+    /// that such an account exists, so invalid inputs can be built too.
+    pub fn add_duplicate(&mut self, dup_index: u8) -> &mut Self {
+        match self.format {
+            SerializationFormat::Aligned => {
+                serialize_duplicate_account(&mut self.buffer, dup_index)
+            }
+            // The deprecated format has no padding after the index
+            SerializationFormat::Deprecated => self.buffer.field("dup_marker", &[dup_index]),
+        }
+        self.num_accounts += 1;
+        self
+    }
+
+    /// Append the instruction epilogue and fill in the account count
+    pub fn finish(mut self, program_id: &Pubkey, instruction_data: &[u8]) -> Vec<u8> {
+        append_instruction_epilogue(&mut self.buffer, instruction_data, program_id);
+        self.buffer[..8].copy_from_slice(&(self.num_accounts as u64).to_le_bytes());
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::{deserialize_account_buffer, deserialize_input};
+    use crate::serialize_solana_format::{
+        accounts_with_duplicates_scenario, build_from_accounts, TEST_PROGRAM_ID,
+    };
+
+    #[test]
+    fn builder_sets_every_field() {
+        let key = Pubkey::new_from_array([7; 32]);
+        let mut buffer = Vec::new();
+        write_account_count(&mut buffer, 1);
+        AccountBuilder::new()
+            .key(key)
+            .lamports(42)
+            .data(vec![1, 2, 3])
+            .owner(TEST_PROGRAM_ID)
+            .signer(true)
+            .writable(false)
+            .executable(true)
+            .rent_epoch(361)
+            .build_into(&mut buffer);
+
+        let parsed = deserialize_account_buffer(&buffer).unwrap();
+        let expected = ExpectedAccount::new(
+            key,
+            true,  // is_signer
+            false, // is_writable
+            42,
+            vec![1, 2, 3],
+            TEST_PROGRAM_ID,
+            true, // executable
+            361,  // rent_epoch
+        );
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].key, expected.key);
+        assert_eq!(parsed[0].owner, expected.owner);
+        assert_eq!(parsed[0].lamports, expected.lamports);
+        assert_eq!(parsed[0].data, expected.data);
+        assert_eq!(parsed[0].rent_epoch, expected.rent_epoch);
+        assert!(parsed[0].is_signer && !parsed[0].is_writable && parsed[0].executable);
+        assert_eq!(AccountBuilder::from(&expected), {
+            let mut builder = AccountBuilder::new();
+            builder
+                .key(key)
+                .lamports(42)
+                .data(vec![1, 2, 3])
+                .owner(TEST_PROGRAM_ID)
+                .signer(true)
+                .executable(true)
+                .rent_epoch(361);
+            builder
+        });
+    }
+
+    #[test]
+    fn entrypoint_builder_counts_duplicates_and_writes_the_epilogue() {
+        let mut builder = EntrypointBufferBuilder::new();
+        builder
+            .add_account(AccountBuilder::new().lamports(1).data(vec![0xAA; 3]))
+            .add_duplicate(0)
+            .add_account(AccountBuilder::new().lamports(2).executable(true));
+        let buffer = builder.finish(&TEST_PROGRAM_ID, &[7, 8]);
+
+        let parsed = deserialize_input(&buffer).unwrap();
+        assert_eq!(parsed.accounts.len(), 3);
+        assert!(parsed.accounts[1].is_dup);
+        assert_eq!(parsed.accounts[1].data, [0xAA; 3]);
+        assert!(parsed.accounts[2].executable);
+        assert_eq!(parsed.instruction_data, [7, 8]);
+        assert_eq!(parsed.program_id, TEST_PROGRAM_ID);
+    }
+
+    #[test]
+    fn deprecated_duplicates_are_a_single_byte() {
+        let mut aligned = EntrypointBufferBuilder::new();
+        aligned.add_duplicate(0);
+        let mut deprecated = EntrypointBufferBuilder::with_format(SerializationFormat::Deprecated);
+        deprecated.add_duplicate(0);
+        assert_eq!(
+            aligned.finish(&TEST_PROGRAM_ID, &[]).len(),
+            deprecated.finish(&TEST_PROGRAM_ID, &[]).len() + 7
+        );
+    }
+
+    #[test]
+    fn entrypoint_builder_matches_build_from_accounts() {
+        let accounts = accounts_with_duplicates_scenario();
+        let mut builder = EntrypointBufferBuilder::new();
+        for account in &accounts {
+            match account.dup_of {
+                Some(index) => builder.add_duplicate(index),
+                None => builder.add_account(&AccountBuilder::from(account)),
+            };
+        }
+        assert_eq!(
+            builder.finish(&TEST_PROGRAM_ID, &[1]),
+            build_from_accounts(
+                SerializationFormat::Aligned,
+                &accounts,
+                &[1],
+                &TEST_PROGRAM_ID
+            )
+        );
+    }
+}
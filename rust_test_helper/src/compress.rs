@@ -0,0 +1,112 @@
+// zstd-compressed fixtures: `<name>.bin.zst` in place of a large `<name>.bin`
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{GenerateError, GenerateResult};
+use crate::stable::write_if_changed;
+
+/// Fixtures larger than this are compressed by `generate --compress`
+/// unless `--compress-threshold` says otherwise (64 KiB)
+pub const DEFAULT_COMPRESS_THRESHOLD: u64 = 64 * 1024;
+
+/// Fixtures larger than this are always written as `.bin.zst`, with or
+/// without `--compress`, so test_data never holds a raw buffer this big
+/// (1 MiB). `decompress` writes the raw `.bin` back for a reader that
+/// needs it.
+pub const MAX_RAW_FIXTURE_SIZE: u64 = 1024 * 1024;
+
+/// zstd level the fixtures are written with. Fixed so the compressed bytes,
+/// and their hash in index.json, do not change between runs.
+pub const COMPRESSION_LEVEL: i32 = 19;
+
+/// Appended to the `.bin` name of a compressed fixture
+pub const COMPRESSED_SUFFIX: &str = ".zst";
+
+/// `foo/bar.bin` -> `foo/bar.bin.zst`
+pub fn compressed_path(bin_path: &Path) -> PathBuf {
+    let mut path = bin_path.as_os_str().to_owned();
+    path.push(COMPRESSED_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// `foo/bar.bin.zst` -> `foo/bar.bin`, or None for any other file
+pub fn decompressed_path(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let bin = name.strip_suffix(COMPRESSED_SUFFIX)?;
+    bin.ends_with(".bin").then(|| path.with_file_name(bin))
+}
+
+/// Replace the fixture at `bin_path` with `<bin_path>.zst` when it is
+/// larger than `threshold` bytes, returning the compressed path. Smaller
+/// fixtures are left alone, and a compressed copy left over from an
+/// earlier run is removed so it cannot shadow them.
+pub fn compress_fixture(bin_path: &Path, threshold: u64) -> GenerateResult<Option<PathBuf>> {
+    let zst_path = compressed_path(bin_path);
+    let bytes = fs::read(bin_path).map_err(|e| GenerateError::new(bin_path, e))?;
+    if bytes.len() as u64 <= threshold {
+        remove_stale(&zst_path)?;
+        return Ok(None);
+    }
+
+    let compressed = zstd::encode_all(&bytes[..], COMPRESSION_LEVEL)
+        .map_err(|e| GenerateError::new(&zst_path, e))?;
+    write_if_changed(&zst_path, compressed)?;
+    fs::remove_file(bin_path).map_err(|e| GenerateError::new(bin_path, e))?;
+    Ok(Some(zst_path))
+}
+
+/// Remove `<bin_path>.zst` if an earlier `--compress` run left one
+pub fn remove_stale(zst_path: &Path) -> GenerateResult<()> {
+    match fs::remove_file(zst_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(GenerateError::new(zst_path, e)),
+        _ => Ok(()),
+    }
+}
+
+/// Write the raw fixture next to the compressed one at `zst_path`, keeping
+/// the compressed file, and return the raw fixture's path
+pub fn decompress_fixture(zst_path: &Path) -> GenerateResult<PathBuf> {
+    let bin_path = decompressed_path(zst_path).ok_or_else(|| {
+        GenerateError::new(
+            zst_path,
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "not a compressed fixture (expected <name>.bin.zst)",
+            ),
+        )
+    })?;
+    let bytes = read_compressed(zst_path)?;
+    write_if_changed(&bin_path, bytes)?;
+    Ok(bin_path)
+}
+
+/// `decompress_fixture` for every `.bin.zst` in `dir`, sorted by name
+pub fn decompress_dir(dir: &Path) -> GenerateResult<Vec<PathBuf>> {
+    let mut compressed: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| GenerateError::new(dir, e))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| GenerateError::new(dir, e))?;
+    compressed.retain(|path| decompressed_path(path).is_some());
+    compressed.sort();
+    compressed
+        .iter()
+        .map(|path| decompress_fixture(path))
+        .collect()
+}
+
+/// The bytes of the fixture at `bin_path`, decompressed from
+/// `<bin_path>.zst` when only the compressed copy exists
+pub fn read_fixture(bin_path: &Path) -> GenerateResult<Vec<u8>> {
+    let zst_path = compressed_path(bin_path);
+    if !bin_path.exists() && zst_path.exists() {
+        return read_compressed(&zst_path);
+    }
+    fs::read(bin_path).map_err(|e| GenerateError::new(bin_path, e))
+}
+
+/// Decompress the file at `zst_path` in memory
+pub fn read_compressed(zst_path: &Path) -> GenerateResult<Vec<u8>> {
+    let compressed = fs::read(zst_path).map_err(|e| GenerateError::new(zst_path, e))?;
+    zstd::decode_all(&compressed[..]).map_err(|e| GenerateError::new(zst_path, e))
+}
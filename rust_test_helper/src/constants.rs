@@ -0,0 +1,13 @@
+// Constants shared by the test data generators, mirroring the Solana runtime
+
+/// Extra zeroed space the runtime reserves after each account's data so a
+/// program can realloc in place (10 KiB per instruction)
+pub const MAX_PERMITTED_DATA_INCREASE: usize = 1_024 * 10;
+
+/// Most accounts a transaction can lock, and so the most distinct accounts
+/// an entrypoint buffer holds (`MAX_TX_ACCOUNT_LOCKS` in the runtime)
+pub const MAX_TX_ACCOUNT_LOCKS: usize = 64;
+
+/// Largest account data the runtime allows (10 MiB,
+/// `MAX_PERMITTED_DATA_LENGTH` in the runtime)
+pub const MAX_PERMITTED_DATA_LENGTH: usize = 10 * 1_024 * 1_024;
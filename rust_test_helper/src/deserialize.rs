@@ -0,0 +1,626 @@
+// Parse buffers in the Solana runtime serialization format back into fields
+use serde::Serialize;
+use solana_program::pubkey::Pubkey;
+use std::fmt;
+
+use crate::constants::MAX_PERMITTED_DATA_INCREASE;
+
+/// Marker in the first byte of an account entry that is not a duplicate
+pub(crate) const NON_DUP_MARKER: u8 = 0xFF;
+
+/// One account entry as written by `serialize_account_solana_format`.
+/// Duplicate entries carry a copy of the account they point to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAccount {
+    pub is_dup: bool,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub rent_epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    /// The buffer ended while reading `field` at `offset`
+    UnexpectedEof { offset: usize, field: &'static str },
+    /// A duplicate entry points at itself or a later account
+    InvalidDuplicateIndex { account: usize, dup_index: u8 },
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::UnexpectedEof { offset, field } => {
+                write!(
+                    f,
+                    "unexpected end of buffer reading {field} at offset {offset}"
+                )
+            }
+            DeserializeError::InvalidDuplicateIndex { account, dup_index } => {
+                write!(
+                    f,
+                    "account {account} is a duplicate of account {dup_index}, which is not before it"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+/// Parse the accounts section of a serialized entrypoint buffer. Anything
+/// after the last account (instruction data, program id) is ignored.
+pub fn deserialize_account_buffer(bytes: &[u8]) -> Result<Vec<ParsedAccount>, DeserializeError> {
+    read_accounts(&mut Reader { bytes, offset: 0 })
+}
+
+/// A whole entrypoint buffer: the accounts and the epilogue after them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedInput {
+    pub accounts: Vec<ParsedAccount>,
+    pub instruction_data: Vec<u8>,
+    pub program_id: Pubkey,
+}
+
+/// Parse a complete entrypoint buffer, including the instruction data and
+/// program ID that follow the last account
+pub fn deserialize_input(bytes: &[u8]) -> Result<ParsedInput, DeserializeError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    let accounts = read_accounts(&mut reader)?;
+    let instruction_data_len = reader.read_u64("instruction_data_len")? as usize;
+    let instruction_data = reader
+        .read_bytes(instruction_data_len, "instruction_data")?
+        .to_vec();
+    let program_id = reader.read_pubkey("program_id")?;
+
+    Ok(ParsedInput {
+        accounts,
+        instruction_data,
+        program_id,
+    })
+}
+
+/// Where an account's fields sit in the buffer. Duplicate entries report
+/// the offsets of the account they point to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountOffsets {
+    pub key: usize,
+    pub lamports: usize,
+    pub data: usize,
+}
+
+/// Where every account and the epilogue fields sit in the buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputOffsets {
+    pub accounts: Vec<AccountOffsets>,
+    pub instruction_data: usize,
+    pub program_id: usize,
+}
+
+/// Parse a complete entrypoint buffer and report the offset of each field
+/// instead of its value
+pub fn deserialize_input_offsets(bytes: &[u8]) -> Result<InputOffsets, DeserializeError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    let accounts = read_entries(&mut reader)?
+        .into_iter()
+        .map(|(_, offsets)| offsets)
+        .collect();
+    let instruction_data_len = reader.read_u64("instruction_data_len")? as usize;
+    let instruction_data = reader.offset;
+    reader.read_bytes(instruction_data_len, "instruction_data")?;
+
+    Ok(InputOffsets {
+        accounts,
+        instruction_data,
+        program_id: reader.offset,
+    })
+}
+
+fn read_accounts(reader: &mut Reader<'_>) -> Result<Vec<ParsedAccount>, DeserializeError> {
+    Ok(read_entries(reader)?
+        .into_iter()
+        .map(|(account, _)| account)
+        .collect())
+}
+
+fn read_entries(
+    reader: &mut Reader<'_>,
+) -> Result<Vec<(ParsedAccount, AccountOffsets)>, DeserializeError> {
+    let num_accounts = reader.read_u64("num_accounts")?;
+
+    let mut accounts: Vec<(ParsedAccount, AccountOffsets)> = Vec::new();
+    for index in 0..num_accounts as usize {
+        let dup_marker = reader.read_u8("dup_marker")?;
+        if dup_marker != NON_DUP_MARKER {
+            reader.read_bytes(7, "dup_padding")?;
+            let (original, offsets) = accounts.get(dup_marker as usize).ok_or(
+                DeserializeError::InvalidDuplicateIndex {
+                    account: index,
+                    dup_index: dup_marker,
+                },
+            )?;
+            let duplicate = ParsedAccount {
+                is_dup: true,
+                ..original.clone()
+            };
+            let offsets = *offsets;
+            accounts.push((duplicate, offsets));
+            continue;
+        }
+
+        let is_signer = reader.read_u8("is_signer")? != 0;
+        let is_writable = reader.read_u8("is_writable")? != 0;
+        let executable = reader.read_u8("executable")? != 0;
+        reader.read_bytes(4, "original_data_len")?;
+        let key_offset = reader.offset;
+        let key = reader.read_pubkey("key")?;
+        let owner = reader.read_pubkey("owner")?;
+        let lamports_offset = reader.offset;
+        let lamports = reader.read_u64("lamports")?;
+        let data_len = reader.read_u64("data_len")? as usize;
+        let data_offset = reader.offset;
+        let data = reader.read_bytes(data_len, "data")?.to_vec();
+        reader.read_bytes(MAX_PERMITTED_DATA_INCREASE, "realloc_space")?;
+        reader.read_bytes((8 - data_len % 8) % 8, "alignment_padding")?;
+        let rent_epoch = reader.read_u64("rent_epoch")?;
+
+        let account = ParsedAccount {
+            is_dup: false,
+            is_signer,
+            is_writable,
+            executable,
+            key,
+            owner,
+            lamports,
+            data,
+            rent_epoch,
+        };
+        let offsets = AccountOffsets {
+            key: key_offset,
+            lamports: lamports_offset,
+            data: data_offset,
+        };
+        accounts.push((account, offsets));
+    }
+
+    Ok(accounts)
+}
+
+/// A broken structural invariant, at the byte offset where it shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "offset {}: {}", self.offset, self.message)
+    }
+}
+
+/// Most accounts a transaction can pass, and so the largest account count
+/// tried when looking for the count a buffer actually holds
+const MAX_ACCOUNTS: u64 = 256;
+
+/// Check an aligned entrypoint buffer for every broken invariant rather
+/// than stopping at the first: the account count matches the entries
+/// present, duplicates point at an earlier non-duplicate, each data_len
+/// leaves room for its data, realloc space and padding, the padding is
+/// zero, and the buffer ends right after the program ID. Checking stops
+/// early only once the remaining offsets can no longer be trusted.
+pub fn check_structure(bytes: &[u8]) -> Vec<Violation> {
+    let violations = check_with_count(bytes, None);
+    if violations.is_empty() {
+        return violations;
+    }
+
+    // A wrong count misreads everything after it. If another count makes
+    // the buffer check out, that is the only problem worth reporting.
+    let Ok(stated) = (Reader { bytes, offset: 0 }).read_u64("num_accounts") else {
+        return violations;
+    };
+    match (0..=MAX_ACCOUNTS)
+        .filter(|count| *count != stated)
+        .find(|count| check_with_count(bytes, Some(*count)).is_empty())
+    {
+        Some(count) => vec![Violation {
+            offset: 0,
+            message: format!("num_accounts is {stated} but the buffer holds {count} accounts"),
+        }],
+        None => violations,
+    }
+}
+
+/// Check `bytes`, walking `num_accounts` entries instead of the count the
+/// buffer states when given
+fn check_with_count(bytes: &[u8], num_accounts: Option<u64>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut reader = Reader { bytes, offset: 0 };
+    let checked = check_entries(&mut reader, num_accounts, &mut violations).and_then(|in_sync| {
+        if in_sync {
+            check_epilogue(&mut reader, &mut violations)
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(DeserializeError::UnexpectedEof { offset, field }) = checked {
+        violations.push(Violation {
+            offset,
+            message: format!("buffer ends while reading {field}"),
+        });
+    }
+    violations
+}
+
+/// Check every account entry. Returns false when an entry could not be
+/// read to its end, so the epilogue cannot be located.
+fn check_entries(
+    reader: &mut Reader<'_>,
+    num_accounts: Option<u64>,
+    violations: &mut Vec<Violation>,
+) -> Result<bool, DeserializeError> {
+    let stated = reader.read_u64("num_accounts")?;
+    let num_accounts = num_accounts.unwrap_or(stated) as usize;
+
+    let mut is_dup: Vec<bool> = Vec::new();
+    for index in 0..num_accounts {
+        match check_entry(reader, index, &is_dup, violations) {
+            Ok(Some(dup)) => is_dup.push(dup),
+            // data_len ran past the buffer, nothing after it can be located
+            Ok(None) => return Ok(false),
+            Err(DeserializeError::UnexpectedEof { offset, field }) => {
+                violations.push(Violation {
+                    offset,
+                    message: format!(
+                        "num_accounts is {num_accounts} but only {index} complete entries \
+                         are present (buffer ends while reading {field})"
+                    ),
+                });
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Check one entry. Returns whether it was a duplicate, or None when its
+/// data_len does not fit in the buffer.
+fn check_entry(
+    reader: &mut Reader<'_>,
+    index: usize,
+    is_dup: &[bool],
+    violations: &mut Vec<Violation>,
+) -> Result<Option<bool>, DeserializeError> {
+    let entry = reader.offset;
+    let dup_marker = reader.read_u8("dup_marker")?;
+    if dup_marker != NON_DUP_MARKER {
+        let target = dup_marker as usize;
+        let problem = match is_dup.get(target) {
+            None => Some("which is not before it"),
+            Some(true) => Some("which is itself a duplicate"),
+            Some(false) => None,
+        };
+        if let Some(problem) = problem {
+            violations.push(Violation {
+                offset: entry,
+                message: format!("account {index} is a duplicate of account {target}, {problem}"),
+            });
+        }
+        expect_zeros(reader, 7, index, "dup_padding", violations)?;
+        return Ok(Some(true));
+    }
+
+    for field in ["is_signer", "is_writable", "executable"] {
+        let offset = reader.offset;
+        let flag = reader.read_u8(field)?;
+        if flag > 1 {
+            violations.push(Violation {
+                offset,
+                message: format!("account {index} {field} is {flag}, not 0 or 1"),
+            });
+        }
+    }
+    reader.read_bytes(4, "original_data_len")?;
+    reader.read_pubkey("key")?;
+    reader.read_pubkey("owner")?;
+    reader.read_u64("lamports")?;
+
+    let data_len_offset = reader.offset;
+    let data_len = reader.read_u64("data_len")?;
+    let padding = ((8 - data_len % 8) % 8) as usize;
+    let needed = usize::try_from(data_len)
+        .ok()
+        .and_then(|len| len.checked_add(MAX_PERMITTED_DATA_INCREASE + padding + 8));
+    let remaining = reader.bytes.len() - reader.offset;
+    if needed.is_none_or(|needed| needed > remaining) {
+        violations.push(Violation {
+            offset: data_len_offset,
+            message: format!(
+                "account {index} data_len is {data_len}, but only {remaining} bytes remain \
+                 for its data, realloc space, padding and rent_epoch"
+            ),
+        });
+        return Ok(None);
+    }
+
+    reader.read_bytes(data_len as usize, "data")?;
+    expect_zeros(
+        reader,
+        MAX_PERMITTED_DATA_INCREASE,
+        index,
+        "realloc_space",
+        violations,
+    )?;
+    expect_zeros(reader, padding, index, "alignment_padding", violations)?;
+    reader.read_u64("rent_epoch")?;
+    Ok(Some(false))
+}
+
+fn check_epilogue(
+    reader: &mut Reader<'_>,
+    violations: &mut Vec<Violation>,
+) -> Result<(), DeserializeError> {
+    let instruction_data_len = reader.read_u64("instruction_data_len")? as usize;
+    reader.read_bytes(instruction_data_len, "instruction_data")?;
+    reader.read_pubkey("program_id")?;
+    let trailing = reader.bytes.len() - reader.offset;
+    if trailing > 0 {
+        violations.push(Violation {
+            offset: reader.offset,
+            message: format!("{trailing} bytes follow the program_id"),
+        });
+    }
+    Ok(())
+}
+
+/// Read `len` bytes of `field` and report the first one that is not zero
+fn expect_zeros(
+    reader: &mut Reader<'_>,
+    len: usize,
+    index: usize,
+    field: &'static str,
+    violations: &mut Vec<Violation>,
+) -> Result<(), DeserializeError> {
+    let start = reader.offset;
+    let bytes = reader.read_bytes(len, field)?;
+    if let Some(position) = bytes.iter().position(|b| *b != 0) {
+        violations.push(Violation {
+            offset: start + position,
+            message: format!(
+                "account {index} {field} byte {position} is {:#04x}, not zero",
+                bytes[position]
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Data bytes shown per account by `inspect_input`
+pub const INSPECT_DATA_PREFIX: usize = 32;
+
+/// A value read from the buffer and the absolute offset it starts at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Field<T> {
+    pub offset: usize,
+    pub value: T,
+}
+
+/// One account entry as far as it could be read. A duplicate entry has
+/// only `duplicate_of`; the fields of a truncated entry stop at the one
+/// that ran out of bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct InspectedAccount {
+    pub index: usize,
+    pub offset: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<Field<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_signer: Option<Field<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_writable: Option<Field<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<Field<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_data_len: Option<Field<u32>>,
+    /// Base58
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Field<String>>,
+    /// Base58
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Field<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lamports: Option<Field<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_len: Option<Field<u64>>,
+    /// Hex of the first `INSPECT_DATA_PREFIX` data bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Field<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_epoch: Option<Field<u64>>,
+}
+
+/// Where and why `inspect_input` stopped before the end of the buffer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InspectError {
+    pub offset: usize,
+    pub field: &'static str,
+    pub message: String,
+}
+
+/// Every field of an entrypoint buffer with its offset, for `inspect`.
+/// Unlike `deserialize_input` it never fails: a truncated or malformed
+/// buffer yields the fields read before the problem plus `error`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Inspection {
+    pub buffer_len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_accounts: Option<Field<u64>>,
+    pub accounts: Vec<InspectedAccount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_data_len: Option<Field<u64>>,
+    /// Hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_data: Option<Field<String>>,
+    /// Base58
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<Field<String>>,
+    /// Bytes left after the program ID, which a well-formed buffer has none of
+    pub trailing_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<InspectError>,
+}
+
+/// Read an entrypoint buffer field by field, recording each value and
+/// offset until the buffer ends or stops making sense
+pub fn inspect_input(bytes: &[u8]) -> Inspection {
+    let mut inspection = Inspection {
+        buffer_len: bytes.len(),
+        num_accounts: None,
+        accounts: Vec::new(),
+        instruction_data_len: None,
+        instruction_data: None,
+        program_id: None,
+        trailing_bytes: 0,
+        error: None,
+    };
+    let mut reader = Reader { bytes, offset: 0 };
+    if let Err(error) = inspect_fields(&mut reader, &mut inspection) {
+        let (offset, field) = match error {
+            DeserializeError::UnexpectedEof { offset, field } => (offset, field),
+            DeserializeError::InvalidDuplicateIndex { account, .. } => {
+                (inspection.accounts[account].offset, "dup_marker")
+            }
+        };
+        inspection.error = Some(InspectError {
+            offset,
+            field,
+            message: error.to_string(),
+        });
+    } else {
+        inspection.trailing_bytes = bytes.len() - reader.offset;
+    }
+    inspection
+}
+
+fn inspect_fields(
+    reader: &mut Reader<'_>,
+    inspection: &mut Inspection,
+) -> Result<(), DeserializeError> {
+    let num_accounts = field(reader, |r| r.read_u64("num_accounts"))?;
+    inspection.num_accounts = Some(num_accounts.clone());
+
+    for index in 0..num_accounts.value as usize {
+        inspection.accounts.push(InspectedAccount {
+            index,
+            offset: reader.offset,
+            ..InspectedAccount::default()
+        });
+        let account = inspection.accounts.last_mut().unwrap();
+
+        let dup_marker = field(reader, |r| r.read_u8("dup_marker"))?;
+        if dup_marker.value != NON_DUP_MARKER {
+            account.duplicate_of = Some(dup_marker.clone());
+            if dup_marker.value as usize >= index {
+                return Err(DeserializeError::InvalidDuplicateIndex {
+                    account: index,
+                    dup_index: dup_marker.value,
+                });
+            }
+            reader.read_bytes(7, "dup_padding")?;
+            continue;
+        }
+
+        account.is_signer = Some(field(reader, |r| r.read_u8("is_signer"))?);
+        account.is_writable = Some(field(reader, |r| r.read_u8("is_writable"))?);
+        account.executable = Some(field(reader, |r| r.read_u8("executable"))?);
+        account.original_data_len = Some(field(reader, |r| {
+            let bytes = r.read_bytes(4, "original_data_len")?;
+            Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+        })?);
+        account.key = Some(field(reader, |r| Ok(r.read_pubkey("key")?.to_string()))?);
+        account.owner = Some(field(reader, |r| Ok(r.read_pubkey("owner")?.to_string()))?);
+        account.lamports = Some(field(reader, |r| r.read_u64("lamports"))?);
+        let data_len = field(reader, |r| r.read_u64("data_len"))?;
+        account.data_len = Some(data_len.clone());
+        account.data = Some(field(reader, |r| {
+            let data = r.read_bytes(data_len.value as usize, "data")?;
+            Ok(to_hex(&data[..data.len().min(INSPECT_DATA_PREFIX)]))
+        })?);
+        reader.read_bytes(MAX_PERMITTED_DATA_INCREASE, "realloc_space")?;
+        reader.read_bytes((8 - data_len.value as usize % 8) % 8, "alignment_padding")?;
+        account.rent_epoch = Some(field(reader, |r| r.read_u64("rent_epoch"))?);
+    }
+
+    let instruction_data_len = field(reader, |r| r.read_u64("instruction_data_len"))?;
+    inspection.instruction_data_len = Some(instruction_data_len.clone());
+    inspection.instruction_data = Some(field(reader, |r| {
+        Ok(to_hex(r.read_bytes(
+            instruction_data_len.value as usize,
+            "instruction_data",
+        )?))
+    })?);
+    inspection.program_id = Some(field(reader, |r| {
+        Ok(r.read_pubkey("program_id")?.to_string())
+    })?);
+    Ok(())
+}
+
+/// Run `read` and pair its value with the offset it started at
+fn field<T>(
+    reader: &mut Reader<'_>,
+    read: impl FnOnce(&mut Reader<'_>) -> Result<T, DeserializeError>,
+) -> Result<Field<T>, DeserializeError> {
+    let offset = reader.offset;
+    let value = read(reader)?;
+    Ok(Field { offset, value })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Bounds-checked cursor over the input buffer
+pub(crate) struct Reader<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn read_bytes(
+        &mut self,
+        len: usize,
+        field: &'static str,
+    ) -> Result<&'a [u8], DeserializeError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(DeserializeError::UnexpectedEof {
+                offset: self.offset,
+                field,
+            })?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_u8(&mut self, field: &'static str) -> Result<u8, DeserializeError> {
+        Ok(self.read_bytes(1, field)?[0])
+    }
+
+    pub(crate) fn read_u64(&mut self, field: &'static str) -> Result<u64, DeserializeError> {
+        let bytes = self.read_bytes(8, field)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_pubkey(&mut self, field: &'static str) -> Result<Pubkey, DeserializeError> {
+        let bytes = self.read_bytes(32, field)?;
+        Ok(Pubkey::new_from_array(bytes.try_into().unwrap()))
+    }
+}
@@ -0,0 +1,397 @@
+// Compare two entrypoint buffers field by field rather than byte by byte
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::deserialize::{inspect_input, Field, InspectedAccount, Inspection};
+use crate::display::diff_ranges;
+use crate::error::{GenerateError, GenerateResult};
+
+/// One way two buffers differ, at its offset in the first buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "offset {}: {}", self.offset, self.message)
+    }
+}
+
+/// Parse `a` and `b` and list the fields whose values differ: account
+/// count, keys, owners, lamports, data length and contents, flags,
+/// rent_epoch, duplicate markers, instruction data and program ID. When
+/// every field sits at the same offset in both, bytes that differ outside
+/// the fields are reported as padding. If either buffer does not parse,
+/// only the first differing byte is reported.
+pub fn diff_buffers(a: &[u8], b: &[u8]) -> Vec<Difference> {
+    if a == b {
+        return Vec::new();
+    }
+
+    let (left, right) = (inspect_input(a), inspect_input(b));
+    let unparseable = [("first", &left), ("second", &right)]
+        .into_iter()
+        .find_map(|(which, inspection)| Some((which, inspection.error.as_ref()?)));
+    if let Some((which, error)) = unparseable {
+        return vec![first_byte_difference(
+            a,
+            b,
+            &format!(" (the {which} buffer does not parse: {})", error.message),
+        )];
+    }
+
+    let mut differences = Vec::new();
+    compare(
+        &mut differences,
+        "num_accounts",
+        &left.num_accounts,
+        &right.num_accounts,
+    );
+    for (x, y) in left.accounts.iter().zip(&right.accounts) {
+        compare_accounts(&mut differences, a, b, x, y);
+    }
+    for (extra, which) in [(&left, "first"), (&right, "second")] {
+        let shorter = left.accounts.len().min(right.accounts.len());
+        for account in extra.accounts.iter().skip(shorter) {
+            differences.push(Difference {
+                offset: account.offset,
+                message: format!("account {} is only in the {which} buffer", account.index),
+            });
+        }
+    }
+
+    compare(
+        &mut differences,
+        "instruction_data_len",
+        &left.instruction_data_len,
+        &right.instruction_data_len,
+    );
+    if let (Some(x), Some(y)) = (&left.instruction_data_len, &right.instruction_data_len) {
+        if x.value == y.value {
+            let start =
+                |inspection: &Inspection| inspection.instruction_data.as_ref().unwrap().offset;
+            compare_bytes(
+                &mut differences,
+                "instruction_data",
+                &a[start(&left)..][..x.value as usize],
+                &b[start(&right)..][..y.value as usize],
+                start(&left),
+            );
+        }
+    }
+    compare(
+        &mut differences,
+        "program_id",
+        &left.program_id,
+        &right.program_id,
+    );
+    if left.trailing_bytes != right.trailing_bytes {
+        differences.push(Difference {
+            offset: a.len() - left.trailing_bytes,
+            message: format!(
+                "trailing bytes after program_id: {} vs {}",
+                left.trailing_bytes, right.trailing_bytes
+            ),
+        });
+    }
+
+    let spans = field_spans(&left);
+    if spans == field_spans(&right) && a.len() == b.len() {
+        for range in diff_ranges(a, b) {
+            for padding in uncovered(range, &spans) {
+                differences.push(Difference {
+                    offset: padding.start,
+                    message: format!("padding bytes {}..{} differ", padding.start, padding.end),
+                });
+            }
+        }
+    }
+
+    if differences.is_empty() {
+        differences.push(first_byte_difference(a, b, ""));
+    }
+    differences
+}
+
+fn compare_accounts(
+    differences: &mut Vec<Difference>,
+    a: &[u8],
+    b: &[u8],
+    x: &InspectedAccount,
+    y: &InspectedAccount,
+) {
+    let index = x.index;
+    match (&x.duplicate_of, &y.duplicate_of) {
+        (None, None) => {}
+        (Some(_), Some(_)) => {
+            compare(
+                differences,
+                &format!("account {index} duplicate_of"),
+                &x.duplicate_of,
+                &y.duplicate_of,
+            );
+            return;
+        }
+        (dup, _) => {
+            let (dup, which, other) = if dup.is_some() {
+                (dup, "first", "second")
+            } else {
+                (&y.duplicate_of, "second", "first")
+            };
+            differences.push(Difference {
+                offset: x.offset,
+                message: format!(
+                    "account {index} is a duplicate of account {} in the {which} buffer \
+                     but not in the {other}",
+                    dup.as_ref().unwrap().value
+                ),
+            });
+            return;
+        }
+    }
+
+    let label = |field: &str| format!("account {index} {field}");
+    compare(differences, &label("key"), &x.key, &y.key);
+    compare(differences, &label("owner"), &x.owner, &y.owner);
+    compare(differences, &label("lamports"), &x.lamports, &y.lamports);
+    compare(differences, &label("is_signer"), &x.is_signer, &y.is_signer);
+    compare(
+        differences,
+        &label("is_writable"),
+        &x.is_writable,
+        &y.is_writable,
+    );
+    compare(
+        differences,
+        &label("executable"),
+        &x.executable,
+        &y.executable,
+    );
+    compare(
+        differences,
+        &label("original_data_len"),
+        &x.original_data_len,
+        &y.original_data_len,
+    );
+    compare(differences, &label("data_len"), &x.data_len, &y.data_len);
+    let (x_len, y_len) = (x.data_len.as_ref().unwrap(), y.data_len.as_ref().unwrap());
+    if x_len.value == y_len.value {
+        let (x_start, y_start) = (
+            x.data.as_ref().unwrap().offset,
+            y.data.as_ref().unwrap().offset,
+        );
+        compare_bytes(
+            differences,
+            &label("data"),
+            &a[x_start..][..x_len.value as usize],
+            &b[y_start..][..y_len.value as usize],
+            x_start,
+        );
+    }
+    compare(
+        differences,
+        &label("rent_epoch"),
+        &x.rent_epoch,
+        &y.rent_epoch,
+    );
+}
+
+/// Record `label: x vs y` when both fields were read and their values differ
+fn compare<T: PartialEq + fmt::Display>(
+    differences: &mut Vec<Difference>,
+    label: &str,
+    x: &Option<Field<T>>,
+    y: &Option<Field<T>>,
+) {
+    if let (Some(x), Some(y)) = (x, y) {
+        if x.value != y.value {
+            differences.push(Difference {
+                offset: x.offset,
+                message: format!("{label}: {} vs {}", x.value, y.value),
+            });
+        }
+    }
+}
+
+/// Record the first differing byte of two equally long fields
+fn compare_bytes(
+    differences: &mut Vec<Difference>,
+    label: &str,
+    x: &[u8],
+    y: &[u8],
+    offset: usize,
+) {
+    if let Some(i) = x.iter().zip(y).position(|(x, y)| x != y) {
+        let count = x.iter().zip(y).filter(|(x, y)| x != y).count();
+        differences.push(Difference {
+            offset: offset + i,
+            message: format!(
+                "{label} differs in {count} of {} bytes, first at byte {i}: {:#04x} vs {:#04x}",
+                x.len(),
+                x[i],
+                y[i]
+            ),
+        });
+    }
+}
+
+fn first_byte_difference(a: &[u8], b: &[u8], note: &str) -> Difference {
+    let offset = diff_ranges(a, b)[0].start;
+    let byte = |bytes: &[u8]| match bytes.get(offset) {
+        Some(byte) => format!("{byte:#04x}"),
+        None => "end of buffer".to_string(),
+    };
+    Difference {
+        offset,
+        message: format!("first differing byte: {} vs {}{note}", byte(a), byte(b)),
+    }
+}
+
+/// The byte ranges holding field values; everything else is padding
+fn field_spans(inspection: &Inspection) -> Vec<Range<usize>> {
+    fn span<T>(spans: &mut Vec<Range<usize>>, field: &Option<Field<T>>, len: usize) {
+        if let Some(field) = field {
+            spans.push(field.offset..field.offset + len);
+        }
+    }
+
+    let mut spans = Vec::new();
+    span(&mut spans, &inspection.num_accounts, 8);
+    for account in &inspection.accounts {
+        span(&mut spans, &account.duplicate_of, 1);
+        if account.duplicate_of.is_none() {
+            // The non-duplicate marker
+            spans.push(account.offset..account.offset + 1);
+        }
+        span(&mut spans, &account.is_signer, 1);
+        span(&mut spans, &account.is_writable, 1);
+        span(&mut spans, &account.executable, 1);
+        span(&mut spans, &account.original_data_len, 4);
+        span(&mut spans, &account.key, 32);
+        span(&mut spans, &account.owner, 32);
+        span(&mut spans, &account.lamports, 8);
+        span(&mut spans, &account.data_len, 8);
+        let data_len = account
+            .data_len
+            .as_ref()
+            .map_or(0, |len| len.value as usize);
+        span(&mut spans, &account.data, data_len);
+        span(&mut spans, &account.rent_epoch, 8);
+    }
+    span(&mut spans, &inspection.instruction_data_len, 8);
+    let instruction_data_len = inspection
+        .instruction_data_len
+        .as_ref()
+        .map_or(0, |len| len.value as usize);
+    span(
+        &mut spans,
+        &inspection.instruction_data,
+        instruction_data_len,
+    );
+    span(&mut spans, &inspection.program_id, 32);
+    spans
+}
+
+/// The parts of `range` outside every span
+fn uncovered(range: Range<usize>, spans: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut parts: Vec<Range<usize>> = Vec::new();
+    for offset in range {
+        if spans.iter().any(|span| span.contains(&offset)) {
+            continue;
+        }
+        match parts.last_mut() {
+            Some(part) if part.end == offset => part.end += 1,
+            _ => parts.push(offset..offset + 1),
+        }
+    }
+    parts
+}
+
+/// Offset of the first byte where `expected` and `actual` differ. When one
+/// buffer is a prefix of the other this is the length of the shorter one.
+pub fn first_mismatch(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(e, a)| e != a)
+        .or_else(|| (expected.len() != actual.len()).then(|| expected.len().min(actual.len())))
+}
+
+/// A file that is not the same in two directories compared by `diff_dirs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDifference {
+    /// File name, the same in both directories
+    pub name: String,
+    pub kind: FileDifferenceKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDifferenceKind {
+    /// Only the first directory has the file
+    OnlyInFirst,
+    /// Only the second directory has the file
+    OnlyInSecond,
+    /// Both have it; the contents first differ at this offset
+    Differs { offset: usize },
+}
+
+impl fmt::Display for FileDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FileDifferenceKind::OnlyInFirst => {
+                write!(f, "{}: only in the first directory", self.name)
+            }
+            FileDifferenceKind::OnlyInSecond => {
+                write!(f, "{}: only in the second directory", self.name)
+            }
+            FileDifferenceKind::Differs { offset } => {
+                write!(f, "{}: differs from offset {offset}", self.name)
+            }
+        }
+    }
+}
+
+/// Compare the files directly inside `a` and `b` byte for byte, sorted by
+/// name. Subdirectories are not descended into.
+pub fn diff_dirs(a: &Path, b: &Path) -> GenerateResult<Vec<FileDifference>> {
+    let (left, right) = (file_names(a)?, file_names(b)?);
+    let mut differences = Vec::new();
+    for name in left.union(&right) {
+        let kind = if !right.contains(name) {
+            FileDifferenceKind::OnlyInFirst
+        } else if !left.contains(name) {
+            FileDifferenceKind::OnlyInSecond
+        } else {
+            let read = |dir: &Path| {
+                let path = dir.join(name);
+                fs::read(&path).map_err(|e| GenerateError::new(&path, e))
+            };
+            match first_mismatch(&read(a)?, &read(b)?) {
+                Some(offset) => FileDifferenceKind::Differs { offset },
+                None => continue,
+            }
+        };
+        differences.push(FileDifference {
+            name: name.clone(),
+            kind,
+        });
+    }
+    Ok(differences)
+}
+
+/// Names of the regular files directly inside `dir`
+fn file_names(dir: &Path) -> GenerateResult<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    for entry in fs::read_dir(dir).map_err(|e| GenerateError::new(dir, e))? {
+        let entry = entry.map_err(|e| GenerateError::new(dir, e))?;
+        if entry.path().is_file() {
+            names.insert(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    Ok(names)
+}
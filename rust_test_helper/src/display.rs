@@ -0,0 +1,79 @@
+// Human-readable views of serialized buffers
+use std::fmt::Write as _;
+use std::ops::Range;
+
+/// Print `label` followed by an xxd-style dump of `data`, `width` bytes
+/// per line
+pub fn hexdump(label: &str, data: &[u8], width: usize) {
+    println!("{label} ({} bytes):", data.len());
+    print!("{}", format_hexdump(data, width));
+}
+
+/// xxd-style dump: offset, hex bytes in pairs, then the printable ASCII
+/// characters with `.` for everything else
+pub fn format_hexdump(data: &[u8], width: usize) -> String {
+    let width = width.max(1);
+    // Two hex digits per byte plus a space after every pair
+    let hex_width = width * 2 + width.div_ceil(2);
+    let mut out = String::new();
+
+    for (i, line) in data.chunks(width).enumerate() {
+        let mut hex = String::new();
+        for (j, byte) in line.iter().enumerate() {
+            let _ = write!(hex, "{byte:02x}");
+            if j % 2 == 1 {
+                hex.push(' ');
+            }
+        }
+        let ascii: String = line
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{:08x}: {hex:<hex_width$} {ascii}", i * width);
+    }
+    out
+}
+
+/// Byte ranges where `a` and `b` differ. Bytes past the end of the shorter
+/// buffer count as differing.
+pub fn diff_ranges(a: &[u8], b: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for offset in 0..a.len().max(b.len()) {
+        if a.get(offset) == b.get(offset) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end == offset => range.end += 1,
+            _ => ranges.push(offset..offset + 1),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_xxd_layout() {
+        let data = b"\x00\x01Hello, Solana!\xff";
+        assert_eq!(
+            format_hexdump(data, 16),
+            "00000000: 0001 4865 6c6c 6f2c 2053 6f6c 616e 6121  ..Hello, Solana!\n\
+             00000010: ff                                       .\n"
+        );
+    }
+
+    #[test]
+    fn diff_merges_adjacent_bytes() {
+        assert_eq!(diff_ranges(b"abcdef", b"abcdef"), []);
+        assert_eq!(diff_ranges(b"abcdef", b"aXYdeZ"), [1..3, 5..6]);
+        assert_eq!(diff_ranges(b"abc", b"abcde"), [Range { start: 3, end: 5 }]);
+    }
+}
@@ -0,0 +1,39 @@
+// Errors from writing generated fixtures to disk
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type GenerateResult<T> = Result<T, GenerateError>;
+
+/// An I/O failure while writing `path`
+#[derive(Debug)]
+pub struct GenerateError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl GenerateError {
+    pub fn new(path: &Path, source: io::Error) -> Self {
+        GenerateError {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to write {}: {}",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for GenerateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
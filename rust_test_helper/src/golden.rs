@@ -0,0 +1,180 @@
+// Compare the hand-rolled fixtures against golden buffers produced by the
+// runtime's own serializer
+use solana_program::pubkey::Pubkey;
+use std::path::Path;
+
+pub use crate::diff::first_mismatch;
+use crate::serialize_solana_format::*;
+use crate::verify::ExpectedAccount;
+
+/// Instruction data every golden scenario is serialized with
+pub const GOLDEN_INSTRUCTION_DATA: [u8; 3] = [0x01, 0x02, 0x03];
+
+/// A hand-rolled fixture and the `golden_<name>.bin` file it is checked against
+pub struct GoldenScenario {
+    pub name: &'static str,
+    /// The accounts both buffers are serialized from
+    pub accounts: fn(&Pubkey) -> Vec<ExpectedAccount>,
+}
+
+pub const GOLDEN_SCENARIOS: [GoldenScenario; 5] = [
+    GoldenScenario {
+        name: "single_account",
+        accounts: |_| single_account_scenario(),
+    },
+    GoldenScenario {
+        name: "multiple_accounts",
+        accounts: |_| multiple_accounts_scenario(),
+    },
+    GoldenScenario {
+        name: "accounts_with_duplicates",
+        accounts: |_| accounts_with_duplicates_scenario(),
+    },
+    GoldenScenario {
+        name: "alignment_sweep",
+        accounts: |_| alignment_sweep_scenario(),
+    },
+    GoldenScenario {
+        name: "full_entrypoint",
+        accounts: full_entrypoint_scenario,
+    },
+];
+
+impl GoldenScenario {
+    /// The hand-rolled buffer for this scenario
+    pub fn hand_rolled(&self) -> Vec<u8> {
+        build_from_accounts(
+            SerializationFormat::Aligned,
+            &(self.accounts)(&TEST_PROGRAM_ID),
+            &GOLDEN_INSTRUCTION_DATA,
+            &TEST_PROGRAM_ID,
+        )
+    }
+
+    /// `golden_<name>.bin`
+    pub fn file_name(&self) -> String {
+        format!("golden_{}.bin", self.name)
+    }
+
+    /// Read `golden_<name>.bin` from `test_data_dir`, if it has been generated
+    pub fn read_golden(&self, test_data_dir: &Path) -> Option<Vec<u8>> {
+        std::fs::read(test_data_dir.join(self.file_name())).ok()
+    }
+
+    /// The buffer the loader itself serializes for this scenario
+    #[cfg(feature = "golden")]
+    pub fn golden(&self) -> Vec<u8> {
+        let accounts = (self.accounts)(&TEST_PROGRAM_ID);
+        loader::serialize_parameters(&accounts, &GOLDEN_INSTRUCTION_DATA, &TEST_PROGRAM_ID)
+    }
+}
+
+/// Write `golden_<name>.bin` for every golden scenario into `test_data_dir`
+/// and return the files written
+#[cfg(feature = "golden")]
+pub fn write_golden_fixtures(
+    test_data_dir: &Path,
+) -> crate::error::GenerateResult<Vec<std::path::PathBuf>> {
+    GOLDEN_SCENARIOS
+        .iter()
+        .map(|scenario| {
+            let path = test_data_dir.join(scenario.file_name());
+            crate::stable::write_if_changed(&path, scenario.golden())?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// The loader's `serialize_parameters`, fed the same accounts as the
+/// hand-rolled serializer
+#[cfg(feature = "golden")]
+mod loader {
+    use loader_sdk::account::{AccountSharedData, WritableAccount};
+    use loader_sdk::pubkey::Pubkey as LoaderPubkey;
+    use loader_sdk::rent::Rent;
+    use loader_sdk::transaction_context::{
+        IndexOfAccount, InstructionAccount, TransactionAccount, TransactionContext,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    use crate::verify::ExpectedAccount;
+
+    /// The loader's SDK has its own `Pubkey`
+    fn loader_pubkey(key: &Pubkey) -> LoaderPubkey {
+        LoaderPubkey::new_from_array(key.to_bytes())
+    }
+
+    /// Build one `TransactionAccount` per distinct entry plus the program,
+    /// run a one-instruction transaction context and serialize it with the
+    /// account data copied in, as the aligned loader does
+    pub fn serialize_parameters(
+        accounts: &[ExpectedAccount],
+        instruction_data: &[u8],
+        program_id: &Pubkey,
+    ) -> Vec<u8> {
+        let mut transaction_accounts: Vec<TransactionAccount> = Vec::new();
+        let mut index_in_transaction: Vec<IndexOfAccount> = Vec::new();
+        for account in accounts {
+            let index = match account.dup_of {
+                Some(original) => index_in_transaction[original as usize],
+                None => {
+                    let mut shared = AccountSharedData::new(
+                        account.lamports,
+                        account.data.len(),
+                        &loader_pubkey(&account.owner),
+                    );
+                    shared.set_data_from_slice(&account.data);
+                    shared.set_executable(account.executable);
+                    shared.set_rent_epoch(account.rent_epoch);
+                    transaction_accounts.push((loader_pubkey(&account.key), shared));
+                    (transaction_accounts.len() - 1) as IndexOfAccount
+                }
+            };
+            index_in_transaction.push(index);
+        }
+
+        let mut program = AccountSharedData::new(1, 0, &loader_sdk::bpf_loader::id());
+        program.set_executable(true);
+        transaction_accounts.push((loader_pubkey(program_id), program));
+        let program_index = (transaction_accounts.len() - 1) as IndexOfAccount;
+
+        // Duplicates point at the first instruction account with their key
+        let instruction_accounts: Vec<InstructionAccount> = accounts
+            .iter()
+            .zip(&index_in_transaction)
+            .map(|(account, &index)| InstructionAccount {
+                index_in_transaction: index,
+                index_in_caller: index,
+                index_in_callee: index_in_transaction
+                    .iter()
+                    .position(|&other| other == index)
+                    .unwrap() as IndexOfAccount,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        let mut transaction_context =
+            TransactionContext::new(transaction_accounts, Some(Rent::default()), 1, 1);
+        transaction_context
+            .get_next_instruction_context()
+            .expect("no instruction context to configure")
+            .configure(&[program_index], &instruction_accounts, instruction_data);
+        transaction_context
+            .push()
+            .expect("failed to push the instruction");
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .expect("no current instruction");
+
+        let (memory, _regions, _account_lengths) =
+            solana_bpf_loader_program::serialization::serialize_parameters(
+                &transaction_context,
+                instruction_context,
+                true, // should_cap_ix_accounts
+                true, // copy_account_data
+            )
+            .expect("the loader failed to serialize the scenario");
+        memory.as_slice().to_vec()
+    }
+}
@@ -0,0 +1,252 @@
+// Annotated hex dumps of generated fixtures, one `<name>.hexdump.txt` per
+// `<name>.bin`
+use solana_program::pubkey::Pubkey;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::error::GenerateResult;
+use crate::serialize_solana_format::{write_from_accounts, FieldSink, SerializationFormat};
+use crate::stable::write_if_changed;
+use crate::verify::ExpectedAccount;
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Fields longer than this show only their first and last
+/// `EDGE_LINES` lines, so a 10 MiB account does not dump 10 MiB of text
+pub const MAX_DUMPED_FIELD_LEN: usize = 64 * 1024;
+
+const EDGE_LINES: usize = 4;
+
+/// One field as the serializer wrote it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpan {
+    pub offset: usize,
+    pub len: usize,
+    pub field: &'static str,
+    /// The account the field belongs to, `None` for the count and epilogue
+    pub entry: Option<String>,
+}
+
+impl FieldSpan {
+    /// `key account[0]`, or just the field name outside the accounts
+    pub fn label(&self) -> String {
+        match &self.entry {
+            Some(entry) => format!("{} {entry}", self.field),
+            None => self.field.to_string(),
+        }
+    }
+}
+
+/// Serialized bytes plus the span of every field in them
+#[derive(Debug, Default)]
+pub struct AnnotatedBuffer {
+    pub bytes: Vec<u8>,
+    pub spans: Vec<FieldSpan>,
+    entry: Option<String>,
+}
+
+impl FieldSink for AnnotatedBuffer {
+    fn field(&mut self, name: &'static str, bytes: &[u8]) {
+        self.spans.push(FieldSpan {
+            offset: self.bytes.len(),
+            len: bytes.len(),
+            field: name,
+            entry: self.entry.clone(),
+        });
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn set_entry(&mut self, entry: Option<String>) {
+        self.entry = entry;
+    }
+}
+
+/// Serialize like `build_from_accounts`, keeping the field spans
+pub fn annotate_from_accounts(
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> AnnotatedBuffer {
+    let mut annotated = AnnotatedBuffer::default();
+    write_from_accounts(
+        &mut annotated,
+        format,
+        accounts,
+        instruction_data,
+        program_id,
+    );
+    annotated
+}
+
+/// Render the dump. Every field starts on a new line labelled with its
+/// name; empty fields are left out. Repeated lines inside a field collapse
+/// into a single `*`, so the realloc space takes two lines. The middle of
+/// a field over `MAX_DUMPED_FIELD_LEN` is replaced by one `...` line
+/// giving how many bytes it left out.
+pub fn render_hexdump(title: &str, annotated: &AnnotatedBuffer) -> String {
+    let width = format!("{:x}", annotated.bytes.len()).len().max(4);
+    let mut out = format!("# {title} ({} bytes)\n", annotated.bytes.len());
+
+    for span in annotated.spans.iter().filter(|span| span.len > 0) {
+        let bytes = &annotated.bytes[span.offset..span.offset + span.len];
+        let lines = bytes.len().div_ceil(BYTES_PER_LINE);
+        let elided = (span.len > MAX_DUMPED_FIELD_LEN).then(|| EDGE_LINES..lines - EDGE_LINES);
+        let mut previous: Option<&[u8]> = None;
+        let mut collapsed = false;
+        for (i, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+            if let Some(elided) = elided.as_ref().filter(|elided| elided.contains(&i)) {
+                if i == elided.start {
+                    let skipped = (elided.end - elided.start) * BYTES_PER_LINE;
+                    let _ = writeln!(out, "... {skipped} bytes not shown");
+                    previous = None;
+                    collapsed = false;
+                }
+                continue;
+            }
+            if i > 0 && previous == Some(line) {
+                if !collapsed {
+                    out.push_str("*\n");
+                    collapsed = true;
+                }
+                continue;
+            }
+            collapsed = false;
+            previous = Some(line);
+
+            let hex: Vec<String> = line.iter().map(|b| format!("{b:02x}")).collect();
+            let offset = span.offset + i * BYTES_PER_LINE;
+            let _ = write!(out, "0x{offset:0width$x}  {}", hex.join(" "));
+            if i == 0 {
+                let _ = write!(
+                    out,
+                    "{:pad$}  {}",
+                    "",
+                    span.label(),
+                    pad = BYTES_PER_LINE * 3 - 1 - hex.join(" ").len()
+                );
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Read the labelled lines of a dump back as `(offset, label)`
+pub fn parse_hexdump(dump: &str) -> Vec<(usize, String)> {
+    dump.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, "  ");
+            let offset = parts.next()?.strip_prefix("0x")?;
+            let label = parts.nth(1)?.trim();
+            let offset = usize::from_str_radix(offset, 16).ok()?;
+            Some((offset, label.to_string()))
+        })
+        .collect()
+}
+
+/// `foo/bar.bin` -> `foo/bar.hexdump.txt`
+pub fn hexdump_path(file_path: &Path) -> PathBuf {
+    file_path.with_extension("hexdump.txt")
+}
+
+/// Write the dump for the fixture at `file_path`
+pub fn write_hexdump(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    let annotated = annotate_from_accounts(format, accounts, instruction_data, program_id);
+    let title = file_path
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+    let path = hexdump_path(file_path);
+    write_if_changed(&path, render_hexdump(&title, &annotated))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MAX_PERMITTED_DATA_LENGTH;
+    use crate::deserialize::deserialize_input_offsets;
+    use crate::serialize_solana_format::*;
+
+    #[test]
+    fn dump_round_trips_the_serializer_offsets() {
+        let accounts = accounts_with_duplicates_scenario();
+        let instruction_data = [0x01, 0x02, 0x03];
+        let annotated = annotate_from_accounts(
+            SerializationFormat::Aligned,
+            &accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        );
+        assert_eq!(
+            annotated.bytes,
+            build_accounts_with_duplicates_solana_format(&instruction_data, &TEST_PROGRAM_ID)
+        );
+
+        let parsed = parse_hexdump(&render_hexdump("duplicates", &annotated));
+        let expected: Vec<(usize, String)> = annotated
+            .spans
+            .iter()
+            .filter(|span| span.len > 0)
+            .map(|span| (span.offset, span.label()))
+            .collect();
+        assert_eq!(parsed, expected);
+
+        // The dump agrees with the parser on where each key and the
+        // epilogue sit
+        let offsets = deserialize_input_offsets(&annotated.bytes).unwrap();
+        for (i, account) in accounts.iter().enumerate() {
+            // Duplicates report the original's offsets, which are
+            // labelled under the original's index
+            if account.dup_of.is_some() {
+                continue;
+            }
+            let key = format!("key account[{i}]");
+            assert!(parsed.contains(&(offsets.accounts[i].key, key)));
+        }
+        assert!(parsed.contains(&(offsets.instruction_data, "instruction_data".to_string())));
+        assert!(parsed.contains(&(offsets.program_id, "program_id".to_string())));
+    }
+
+    #[test]
+    fn realloc_space_collapses() {
+        let annotated = annotate_from_accounts(
+            SerializationFormat::Aligned,
+            &single_account_scenario(),
+            &[],
+            &TEST_PROGRAM_ID,
+        );
+        let dump = render_hexdump("single", &annotated);
+        assert!(dump.contains("realloc_space account[0]\n*\n"));
+        assert!(dump.lines().count() < 40);
+    }
+
+    #[test]
+    fn long_fields_show_only_their_ends() {
+        let accounts = max_data_account_scenario(DEFAULT_SEED);
+        let annotated = annotate_from_accounts(
+            SerializationFormat::Aligned,
+            &accounts,
+            &[],
+            &TEST_PROGRAM_ID,
+        );
+        let dump = render_hexdump("max", &annotated);
+        assert!(dump.len() < 4096, "{} bytes of dump", dump.len());
+
+        let skipped = MAX_PERMITTED_DATA_LENGTH - 2 * EDGE_LINES * BYTES_PER_LINE;
+        assert!(dump.contains(&format!("\n... {skipped} bytes not shown\n")));
+        let last = MAX_PERMITTED_DATA_LENGTH - BYTES_PER_LINE;
+        let data = &accounts[0].data[last..];
+        let hex: Vec<String> = data.iter().map(|b| format!("{b:02x}")).collect();
+        assert!(dump.contains(&hex.join(" ")));
+
+        let parsed = parse_hexdump(&dump);
+        assert!(parsed.contains(&(96, "data account[0]".to_string())));
+    }
+}
@@ -0,0 +1,150 @@
+// test_data/index.json: every generated file, for the Zig harness to iterate
+use serde::{Deserialize, Serialize};
+use solana_program::hash::hash;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compress::{decompressed_path, read_compressed};
+use crate::error::{GenerateError, GenerateResult};
+use crate::manifest::read_manifest;
+use crate::serialize_solana_format::{is_invalid_fixture, SerializationFormat};
+use crate::stable::write_if_changed;
+
+/// File name of the index inside test_data
+pub const INDEX_FILE: &str = "index.json";
+
+/// Schema version written into the index
+pub const INDEX_VERSION: u32 = 1;
+
+/// Version of the generator that wrote the files
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Contents of `test_data/index.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Index {
+    /// Always `INDEX_VERSION`
+    pub version: u32,
+    /// Sorted by path
+    pub files: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// File name
+    pub name: String,
+    /// Path relative to the test_data directory, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the file
+    pub sha256: String,
+    /// Scenario that wrote the file, as listed by `list`, or the spec
+    /// scenario name
+    pub scenario: String,
+    /// Layout of a `.bin` fixture, null for the files written beside it
+    /// and for invalid fixtures, which have no manifest
+    pub format: Option<SerializationFormat>,
+    pub generator_version: String,
+    /// For a `.bin.zst`, what decompressing it gives; `size` and `sha256`
+    /// describe the compressed file. Absent for everything else.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<Compression>,
+}
+
+/// The raw fixture a compressed index entry decompresses to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compression {
+    /// Always `zstd`
+    pub algorithm: String,
+    pub uncompressed_size: u64,
+    /// Lowercase hex SHA-256 of the decompressed bytes
+    pub uncompressed_sha256: String,
+}
+
+impl IndexEntry {
+    /// Describe `path`, written by `scenario` into `test_data_dir`
+    pub fn new(test_data_dir: &Path, path: &Path, scenario: &str) -> GenerateResult<Self> {
+        let bytes = fs::read(path).map_err(|e| GenerateError::new(path, e))?;
+        let relative = path.strip_prefix(test_data_dir).unwrap_or(path);
+        let bin_path = decompressed_path(path);
+        let fixture = if path.extension().is_some_and(|ext| ext == "bin") {
+            Some(path)
+        } else {
+            bin_path.as_deref()
+        };
+        let format = match fixture {
+            Some(fixture) if !is_invalid_fixture(fixture) => Some(read_manifest(fixture)?.format),
+            _ => None,
+        };
+        let compression = match bin_path {
+            Some(_) => {
+                let raw = read_compressed(path)?;
+                Some(Compression {
+                    algorithm: "zstd".to_string(),
+                    uncompressed_size: raw.len() as u64,
+                    uncompressed_sha256: sha256_hex(&raw),
+                })
+            }
+            None => None,
+        };
+
+        Ok(IndexEntry {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path: relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            size: bytes.len() as u64,
+            sha256: sha256_hex(&bytes),
+            scenario: scenario.to_string(),
+            format,
+            generator_version: GENERATOR_VERSION.to_string(),
+            compression,
+        })
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hash(bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Record `written`, pairs of scenario name and file, in
+/// `test_data_dir/index.json` and return the index path. Entries of other
+/// scenarios already in the index are kept, so generating a few scenarios
+/// does not drop the rest.
+pub fn write_index(test_data_dir: &Path, written: &[(&str, PathBuf)]) -> GenerateResult<PathBuf> {
+    let index_path = test_data_dir.join(INDEX_FILE);
+    let mut files: Vec<IndexEntry> = fs::read_to_string(&index_path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Index>(&json).ok())
+        .map(|index| index.files)
+        .unwrap_or_default();
+    files.retain(|entry| {
+        !written
+            .iter()
+            .any(|(scenario, _)| *scenario == entry.scenario)
+    });
+
+    for (scenario, path) in written {
+        let entry = IndexEntry::new(test_data_dir, path, scenario)?;
+        files.retain(|existing| existing.path != entry.path);
+        files.push(entry);
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let index = Index {
+        version: INDEX_VERSION,
+        files,
+    };
+    let mut json = serde_json::to_string_pretty(&index).expect("Failed to serialize index");
+    json.push('\n');
+    write_if_changed(&index_path, json)?;
+    Ok(index_path)
+}
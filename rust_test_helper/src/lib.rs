@@ -0,0 +1,22 @@
+// Test data generators and parsers for the Zig SDK's Rust compatibility tests
+pub mod borsh_accounts;
+pub mod builder;
+pub mod compress;
+pub mod constants;
+pub mod deserialize;
+pub mod diff;
+pub mod display;
+pub mod error;
+pub mod golden;
+pub mod hexdump;
+pub mod index;
+pub mod manifest;
+pub mod negative_fixtures;
+pub mod offsets;
+pub mod output;
+pub mod reader;
+pub mod serialize_solana_format;
+pub mod spec;
+pub mod stable;
+pub mod verify;
+pub mod zig_codegen;
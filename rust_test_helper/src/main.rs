@@ -1,19 +1,389 @@
 // Rust helper to generate serialized AccountInfo data for Zig tests
+use clap::{Parser, Subcommand};
+use rust_test_helper::compress::{decompress_dir, decompress_fixture, DEFAULT_COMPRESS_THRESHOLD};
+use rust_test_helper::deserialize::inspect_input;
+use rust_test_helper::diff::{diff_buffers, diff_dirs};
+use rust_test_helper::display::hexdump;
+use rust_test_helper::error::GenerateError;
+use rust_test_helper::manifest::{write_test_data_manifest, MANIFEST_FILE};
+use rust_test_helper::output::{OutputFormat, OutputOptions};
+use rust_test_helper::serialize_solana_format::{
+    find_generator, generate_scenarios, FixtureGenerator, DEFAULT_SEED, FIXTURE_GENERATORS,
+    TEST_DATA_DIR,
+};
+use rust_test_helper::spec::{generate_from_spec, SpecFile};
+use rust_test_helper::stable::{take_write_log, WriteStatus};
+use rust_test_helper::verify::{verify_dir, verify_fixture};
+use rust_test_helper::zig_codegen;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+use std::path::{Path, PathBuf};
 
-mod serialize_solana_format;
+#[derive(Parser)]
+#[command(about = "Generate and inspect the Zig SDK's serialized test fixtures")]
+struct Cli {
+    /// Seed for the keys and bytes the generators pick at random
+    #[arg(long, global = true, default_value_t = DEFAULT_SEED)]
+    seed: u64,
+
+    /// Where fixtures are written, and where bare file names are looked up
+    #[arg(
+        long,
+        global = true,
+        env = "TEST_DATA_DIR",
+        value_name = "PATH",
+        default_value = TEST_DATA_DIR
+    )]
+    output_dir: PathBuf,
+
+    /// Defaults to `generate`
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write fixtures: every scenario, or only the named ones
+    Generate {
+        /// Scenario name from `list`, or the file stem of one of its
+        /// fixtures. May be repeated.
+        #[arg(long = "scenario", value_name = "NAME", alias = "name")]
+        scenarios: Vec<String>,
+
+        /// Write the scenarios described in a TOML or JSON spec file instead
+        #[arg(long, value_name = "FILE", conflicts_with = "scenarios")]
+        spec: Option<PathBuf>,
+
+        /// Comma-separated representations to write each fixture in. The
+        /// .bin and its side files are always written.
+        #[arg(
+            long = "format",
+            value_name = "FORMATS",
+            value_enum,
+            value_delimiter = ',',
+            default_value = "bin"
+        )]
+        formats: Vec<OutputFormat>,
+
+        /// Replace fixtures larger than --compress-threshold with zstd
+        /// compressed .bin.zst files
+        #[arg(long)]
+        compress: bool,
+
+        /// Size in bytes above which --compress compresses a fixture
+        #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_COMPRESS_THRESHOLD)]
+        compress_threshold: u64,
+    },
+    /// Print every scenario and the fixtures it writes
+    List,
+    /// Parse a fixture and print every field and its offset as JSON. A
+    /// truncated or malformed file reports where parsing stopped.
+    Inspect { file: PathBuf },
+    /// Hexdump a fixture
+    Dump { path: PathBuf },
+    /// Write the raw .bin next to a .bin.zst fixture, or next to every one
+    /// in a directory
+    Decompress {
+        /// Defaults to the output directory
+        path: Option<PathBuf>,
+    },
+    /// Check a fixture, or every fixture in a directory, for structural
+    /// problems and against its .expected.json manifest
+    Verify {
+        /// Defaults to the output directory
+        path: Option<PathBuf>,
+    },
+    /// Print the fields where two fixtures differ, or the first differing
+    /// byte when one does not parse. Given two directories, list the files
+    /// that differ between them.
+    Diff { path1: PathBuf, path2: PathBuf },
+    /// Print Rust's AccountInfo layout and a simulated entrypoint buffer
+    Layout {
+        /// Also hexdump the in-memory AccountInfo, which holds heap pointers
+        #[arg(long)]
+        debug_layout: bool,
+    },
+}
 
 fn main() {
-    // Generate test data files in Solana runtime format
-    serialize_solana_format::generate_solana_format_test_data();
-    serialize_solana_format::test_with_actual_account_info();
+    let cli = Cli::parse();
+    let dir = cli.output_dir.as_path();
+    match cli.command.unwrap_or(Command::Generate {
+        scenarios: vec![],
+        spec: None,
+        formats: vec![OutputFormat::Bin],
+        compress: false,
+        compress_threshold: DEFAULT_COMPRESS_THRESHOLD,
+    }) {
+        Command::Generate {
+            scenarios,
+            spec,
+            formats,
+            compress,
+            compress_threshold,
+        } => {
+            let options = OutputOptions {
+                formats,
+                compress_threshold: compress.then_some(compress_threshold),
+            };
+            generate(&scenarios, spec.as_deref(), &options, cli.seed, dir)
+        }
+        Command::List => list(),
+        Command::Inspect { file } => inspect(&file, dir),
+        Command::Dump { path } => {
+            let (path, bytes) = read_input(&path, dir);
+            hexdump(&path.display().to_string(), &bytes, 16);
+        }
+        Command::Decompress { path } => decompress(path.as_deref().unwrap_or(dir), dir),
+        Command::Verify { path } => verify(path.as_deref().unwrap_or(dir), dir),
+        Command::Diff { path1, path2 } => diff(&path1, &path2, dir),
+        Command::Layout { debug_layout } => print_layout_demo(debug_layout),
+    }
+}
+
+/// `generate`: run the named scenarios, all of them, or those in a spec
+/// file into `dir` as `options` asks, then refresh expected.zig and
+/// manifest.json there. Unknown names and invalid specs are reported before
+/// anything is written.
+fn generate(names: &[String], spec: Option<&Path>, options: &OutputOptions, seed: u64, dir: &Path) {
+    let result = if let Some(spec) = spec {
+        SpecFile::load(spec)
+            .map_err(Box::from)
+            .and_then(|spec| generate_from_spec(dir, &spec, options))
+    } else {
+        let mut generators: Vec<&FixtureGenerator> = Vec::new();
+        for name in names {
+            let Some(generator) = find_generator(name) else {
+                eprintln!(
+                    "error: unknown scenario `{name}`, run `list` to see the known scenarios"
+                );
+                std::process::exit(2);
+            };
+            if !generators.iter().any(|g| g.name == generator.name) {
+                generators.push(generator);
+            }
+        }
+        if names.is_empty() {
+            generators = FIXTURE_GENERATORS.iter().collect();
+        }
+        generate_scenarios(dir, &generators, seed, options).map_err(Box::from)
+    };
+    let mut files = match result {
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    // Both read the manifests the generators above just wrote
+    exit_on_errors(
+        [
+            zig_codegen::write_expected_zig(dir),
+            write_test_data_manifest(dir),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect(),
+    );
+    files.push(dir.join(zig_codegen::EXPECTED_ZIG_FILE));
+    files.push(dir.join(MANIFEST_FILE));
+
+    // Ground truth from the loader's own serializer
+    #[cfg(feature = "golden")]
+    match rust_test_helper::golden::write_golden_fixtures(dir) {
+        Ok(golden) => files.extend(golden),
+        Err(error) => exit_on_errors(vec![error]),
+    }
+
+    // Files whose bytes did not change were left alone
+    let log = take_write_log();
+    let mut updated = 0;
+    println!();
+    for file in &files {
+        let status = log
+            .iter()
+            .find(|(path, _)| path == file)
+            .map_or(WriteStatus::Unchanged, |(_, status)| *status);
+        if status == WriteStatus::Updated {
+            updated += 1;
+        }
+        println!("{:9}  {}", status.as_str(), file.display());
+    }
+    println!(
+        "\n✓ {} files in {}: {updated} updated, {} unchanged",
+        files.len(),
+        dir.display(),
+        files.len() - updated
+    );
+}
+
+/// `list`: one line per scenario with the files it writes
+fn list() {
+    let width = FIXTURE_GENERATORS
+        .iter()
+        .map(|generator| generator.name.len())
+        .max()
+        .unwrap_or(0);
+    for generator in FIXTURE_GENERATORS {
+        let files: Vec<String> = generator
+            .stems
+            .iter()
+            .map(|stem| format!("{stem}.bin"))
+            .collect();
+        println!("{:width$}  {}", generator.name, files.join(", "));
+    }
+}
+
+fn exit_on_errors(errors: Vec<GenerateError>) {
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("error: {error}");
+        }
+        std::process::exit(1);
+    }
+}
+
+/// Resolve a fixture path. A bare file name is looked up in `dir` when it
+/// does not exist relative to the current directory.
+fn resolve(path: &Path, dir: &Path) -> PathBuf {
+    if path.exists() {
+        path.to_path_buf()
+    } else {
+        dir.join(path)
+    }
+}
+
+fn read_input(path: &Path, dir: &Path) -> (PathBuf, Vec<u8>) {
+    let file_path = resolve(path, dir);
+    match std::fs::read(&file_path) {
+        Ok(bytes) => (file_path, bytes),
+        Err(error) => {
+            eprintln!("error: failed to read {}: {error}", file_path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `decompress [path]`: expand one .bin.zst fixture, or every one in a
+/// directory, so tests can read the raw bytes
+fn decompress(path: &Path, dir: &Path) {
+    let path = resolve(path, dir);
+    let result = if path.is_dir() {
+        decompress_dir(&path)
+    } else {
+        decompress_fixture(&path).map(|file| vec![file])
+    };
+    match result {
+        Ok(files) => {
+            for file in &files {
+                println!("Decompressed: {}", file.display());
+            }
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `inspect <file>`: print what the parser reads from a fixture as JSON.
+/// Exits with 1 after printing when the file could not be read to the end.
+fn inspect(path: &Path, dir: &Path) {
+    let (path, bytes) = read_input(path, dir);
+    let inspection = inspect_input(&bytes);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&inspection).expect("inspection serializes")
+    );
+    if let Some(error) = &inspection.error {
+        eprintln!("error: {}: {}", path.display(), error.message);
+        std::process::exit(1);
+    }
+}
+
+/// `verify [path]`: check one fixture, or every generated fixture in a
+/// directory, against its manifest
+fn verify(path: &Path, dir: &Path) {
+    let path = resolve(path, dir);
+    let results = if path.is_dir() {
+        verify_dir(&path).unwrap_or_else(|error| {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        })
+    } else {
+        vec![(path.clone(), verify_fixture(&path))]
+    };
+
+    let mut failed = false;
+    for (file_path, result) in results {
+        match result {
+            Ok(account_count) => {
+                println!("{}: OK ({account_count} accounts)", file_path.display())
+            }
+            Err(problems) => {
+                failed = true;
+                for problem in &problems {
+                    eprintln!("{}: {problem}", file_path.display());
+                }
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// `diff <path1> <path2>`: print each field that differs between two
+/// fixtures, or each file that differs between two directories. Exits with
+/// 1 when they differ, like diff(1).
+fn diff(path1: &Path, path2: &Path, dir: &Path) {
+    let (dir1, dir2) = (resolve(path1, dir), resolve(path2, dir));
+    if dir1.is_dir() && dir2.is_dir() {
+        return diff_directories(&dir1, &dir2);
+    }
+
+    let (path1, a) = read_input(path1, dir);
+    let (path2, b) = read_input(path2, dir);
+    let differences = diff_buffers(&a, &b);
+    if differences.is_empty() {
+        println!("{} and {} are identical", path1.display(), path2.display());
+        return;
+    }
 
+    println!("--- {} ({} bytes)", path1.display(), a.len());
+    println!("+++ {} ({} bytes)", path2.display(), b.len());
+    for difference in &differences {
+        println!("{difference}");
+    }
+    std::process::exit(1);
+}
+
+fn diff_directories(dir1: &Path, dir2: &Path) {
+    let differences = diff_dirs(dir1, dir2).unwrap_or_else(|error| {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    });
+    if differences.is_empty() {
+        println!("{} and {} are identical", dir1.display(), dir2.display());
+        return;
+    }
+
+    println!("--- {}", dir1.display());
+    println!("+++ {}", dir2.display());
+    for difference in &differences {
+        println!("{difference}");
+    }
+    std::process::exit(1);
+}
+
+/// Print the AccountInfo layout notes and a simulated entrypoint buffer
+fn print_layout_demo(debug_layout: bool) {
     // Also generate the other format for comparison
 
     println!("\n=== Original tests ===");
     // Create test account data
     let key1 = Pubkey::default(); // All zeros
-    let key2 = Pubkey::new_unique();
+    let key2 = Pubkey::new_from_array([2u8; 32]);
     let owner = Pubkey::default();
 
     let mut lamports1: u64 = 1000;
@@ -71,24 +441,9 @@ fn main() {
     println!("  executable: bool (1 byte)");
     println!("  padding: 5 bytes (for 8-byte alignment)");
 
-    // Serialize account data to bytes for comparison
-    println!("\n=== Serialized Account Data (hex) ===");
-
-    // Serialize account1
-    unsafe {
-        let account_bytes = std::slice::from_raw_parts(
-            &account1 as *const _ as *const u8,
-            std::mem::size_of::<AccountInfo>(),
-        );
-
-        println!("Account1 serialized ({} bytes):", account_bytes.len());
-        for (i, chunk) in account_bytes.chunks(16).enumerate() {
-            print!("{:04x}: ", i * 16);
-            for byte in chunk {
-                print!("{:02x} ", byte);
-            }
-            println!();
-        }
+    // The raw bytes hold heap pointers, so they differ on every run
+    if debug_layout {
+        print_account_info_bytes(&account1);
     }
 
     // Print actual field values for verification
@@ -121,35 +476,39 @@ fn main() {
     buffer.push(0x00); // Index 0
 
     // Print the buffer
-    println!("Total buffer size: {} bytes", buffer.len());
-    for (i, chunk) in buffer.chunks(32).enumerate() {
-        print!("{:04x}: ", i * 32);
-        for byte in chunk {
-            print!("{:02x} ", byte);
-        }
-        println!();
-    }
+    hexdump("Simulated entrypoint buffer", &buffer, 32);
 }
 
+/// Hexdump the in-memory bytes of an AccountInfo. Only printed with
+/// `--debug-layout` since the Rc and key pointers change between runs.
+fn print_account_info_bytes(account: &AccountInfo) {
+    println!("\n=== Serialized Account Data (hex) ===");
+    let account_bytes = unsafe {
+        std::slice::from_raw_parts(
+            account as *const _ as *const u8,
+            std::mem::size_of::<AccountInfo>(),
+        )
+    };
+    hexdump("Account1 serialized", account_bytes, 16);
+}
+
+/// Simplified account encoding for the simulated entrypoint dump. Values
+/// are written instead of pointers so the output is the same on every run:
+///
+/// ```text
+/// key (32) | lamports u64 | data_len u64 | data | owner (32)
+///   | is_signer | is_writable | executable
+/// ```
 fn serialize_account(account: &AccountInfo, buffer: &mut Vec<u8>) {
-    // This is a simplified serialization - actual format may differ
-    // Serialize key
     buffer.extend_from_slice(&account.key.to_bytes());
+    buffer.extend_from_slice(&account.lamports().to_le_bytes());
 
-    // Serialize lamports pointer (8 bytes)
-    let lamports_ptr = account.lamports.as_ptr() as usize;
-    buffer.extend_from_slice(&lamports_ptr.to_le_bytes());
-
-    // Serialize data pointer and length
     let data_ref = account.data.borrow();
-    let data_ptr = data_ref.as_ptr() as usize;
-    buffer.extend_from_slice(&data_ptr.to_le_bytes());
     buffer.extend_from_slice(&(data_ref.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(&data_ref);
 
-    // Serialize owner
     buffer.extend_from_slice(&account.owner.to_bytes());
 
-    // Serialize flags
     buffer.push(account.is_signer as u8);
     buffer.push(account.is_writable as u8);
     buffer.push(account.executable as u8);
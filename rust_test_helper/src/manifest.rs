@@ -0,0 +1,343 @@
+// JSON sidecar manifests describing what each generated fixture contains
+use serde::{Deserialize, Serialize};
+use solana_program::{hash::hash, pubkey::Pubkey};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::compress::read_fixture;
+use crate::error::{GenerateError, GenerateResult};
+use crate::serialize_solana_format::SerializationFormat;
+use crate::stable::write_if_changed;
+use crate::verify::ExpectedAccount;
+use crate::zig_codegen::load_manifests;
+
+/// Schema version written into every manifest
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// File name of the combined manifest inside test_data
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Contents of `<name>.expected.json`, written next to `<name>.bin`.
+/// Fields serialize in declaration order, so keep new fields at the end
+/// and bump `MANIFEST_VERSION` for anything that is not additive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestV1 {
+    /// Always `MANIFEST_VERSION`
+    pub version: u32,
+    pub account_count: usize,
+    pub accounts: Vec<AccountManifest>,
+    /// Lowercase hex, empty for no instruction data
+    pub instruction_data: String,
+    /// Base58
+    pub program_id: String,
+    /// Layout of the fixture, which decides where each field sits
+    pub format: SerializationFormat,
+    /// Set on fixtures that break a runtime limit on purpose, which a
+    /// parser is expected to reject. Left out of the JSON when false.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub negative: bool,
+}
+
+/// One account entry. Duplicates repeat the fields of the account they
+/// point to, the way the parser exposes them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountManifest {
+    /// Base58
+    pub key: String,
+    /// Base58
+    pub owner: String,
+    pub lamports: u64,
+    pub data_len: usize,
+    /// Lowercase hex SHA-256 of the account data
+    pub data_sha256: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    /// Index of the earlier account this entry duplicates, null otherwise
+    pub duplicate_of: Option<u8>,
+}
+
+impl ManifestV1 {
+    pub fn new(
+        format: SerializationFormat,
+        accounts: &[ExpectedAccount],
+        instruction_data: &[u8],
+        program_id: &Pubkey,
+    ) -> Self {
+        ManifestV1 {
+            version: MANIFEST_VERSION,
+            account_count: accounts.len(),
+            accounts: accounts.iter().map(AccountManifest::new).collect(),
+            instruction_data: to_hex(instruction_data),
+            program_id: program_id.to_string(),
+            format,
+            negative: false,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("Failed to serialize manifest");
+        json.push('\n');
+        json
+    }
+}
+
+impl AccountManifest {
+    pub fn new(account: &ExpectedAccount) -> Self {
+        AccountManifest {
+            key: account.key.to_string(),
+            owner: account.owner.to_string(),
+            lamports: account.lamports,
+            data_len: account.data.len(),
+            data_sha256: to_hex(hash(&account.data).as_ref()),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            duplicate_of: account.dup_of,
+        }
+    }
+}
+
+/// Contents of `test_data/manifest.json`: a summary of every generated
+/// fixture, so the Zig tests need no parser of their own to know what a
+/// file holds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Always `MANIFEST_VERSION`
+    pub version: u32,
+    /// Sorted by file name
+    pub files: Vec<FileManifest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileManifest {
+    pub filename: String,
+    pub byte_length: usize,
+    /// Lowercase hex SHA-256 of the whole file
+    pub sha256: String,
+    pub num_accounts: usize,
+    pub accounts: Vec<AccountSummary>,
+    /// Copied from the fixture's `.expected.json`
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub negative: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountSummary {
+    /// Base58
+    pub key: String,
+    pub lamports: u64,
+    pub data_len: usize,
+}
+
+impl Manifest {
+    /// Summarize every fixture in `test_data_dir` that has an
+    /// `.expected.json` manifest next to it, compressed or not
+    pub fn from_test_data(test_data_dir: &Path) -> GenerateResult<Self> {
+        let mut files = Vec::new();
+        for (name, manifest) in load_manifests(test_data_dir)? {
+            let filename = format!("{name}.bin");
+            let path = test_data_dir.join(&filename);
+            let bytes = read_fixture(&path)?;
+            files.push(FileManifest {
+                filename,
+                byte_length: bytes.len(),
+                sha256: to_hex(hash(&bytes).as_ref()),
+                num_accounts: manifest.account_count,
+                accounts: manifest
+                    .accounts
+                    .into_iter()
+                    .map(|account| AccountSummary {
+                        key: account.key,
+                        lamports: account.lamports,
+                        data_len: account.data_len,
+                    })
+                    .collect(),
+                negative: manifest.negative,
+            });
+        }
+        Ok(Manifest {
+            version: MANIFEST_VERSION,
+            files,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("Failed to serialize manifest");
+        json.push('\n');
+        json
+    }
+}
+
+/// Write `manifest.json` for the fixtures already in `test_data_dir`
+pub fn write_test_data_manifest(test_data_dir: &Path) -> GenerateResult<()> {
+    let manifest = Manifest::from_test_data(test_data_dir)?;
+    let path = test_data_dir.join(MANIFEST_FILE);
+    write_if_changed(&path, manifest.to_json())?;
+    println!(
+        "Generated: {} ({} fixtures)",
+        MANIFEST_FILE,
+        manifest.files.len()
+    );
+    Ok(())
+}
+
+/// `foo/bar.bin` -> `foo/bar.expected.json`
+pub fn manifest_path(file_path: &Path) -> PathBuf {
+    file_path.with_extension("expected.json")
+}
+
+/// Read the manifest of the fixture at `file_path`
+pub fn read_manifest(file_path: &Path) -> GenerateResult<ManifestV1> {
+    let path = manifest_path(file_path);
+    let json = fs::read_to_string(&path).map_err(|e| GenerateError::new(&path, e))?;
+    serde_json::from_str(&json).map_err(|e| GenerateError::new(&path, io::Error::from(e)))
+}
+
+/// Write the manifest for the fixture at `file_path`
+pub fn write_manifest(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    let manifest = ManifestV1::new(format, accounts, instruction_data, program_id);
+    write_if_changed(&manifest_path(file_path), manifest.to_json())?;
+    Ok(())
+}
+
+/// Write the manifest for a fixture at `file_path` that a parser should
+/// reject, marked `negative`
+pub fn write_negative_manifest(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    let manifest = ManifestV1 {
+        negative: true,
+        ..ManifestV1::new(format, accounts, instruction_data, program_id)
+    };
+    write_if_changed(&manifest_path(file_path), manifest.to_json())?;
+    Ok(())
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_serializes_in_schema_order() {
+        let original = ExpectedAccount::new(
+            Pubkey::new_from_array([1; 32]),
+            true,  // is_signer
+            false, // is_writable
+            500,
+            vec![],
+            Pubkey::default(),
+            false, // executable
+            u64::MAX,
+        );
+        let accounts = vec![
+            original.clone(),
+            ExpectedAccount::duplicate_of(&[original], 0),
+        ];
+        let manifest = ManifestV1::new(
+            SerializationFormat::Aligned,
+            &accounts,
+            &[0xAB, 0x01],
+            &Pubkey::default(),
+        );
+
+        let account_json = |duplicate_of: &str| {
+            format!(
+                r#"    {{
+      "key": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+      "owner": "11111111111111111111111111111111",
+      "lamports": 500,
+      "data_len": 0,
+      "data_sha256": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+      "is_signer": true,
+      "is_writable": false,
+      "executable": false,
+      "rent_epoch": 18446744073709551615,
+      "duplicate_of": {duplicate_of}
+    }}"#
+            )
+        };
+        let expected = format!(
+            r#"{{
+  "version": 1,
+  "account_count": 2,
+  "accounts": [
+{},
+{}
+  ],
+  "instruction_data": "ab01",
+  "program_id": "11111111111111111111111111111111",
+  "format": "aligned"
+}}
+"#,
+            account_json("null"),
+            account_json("0")
+        );
+        assert_eq!(manifest.to_json(), expected);
+
+        let parsed: ManifestV1 = serde_json::from_str(&manifest.to_json()).unwrap();
+        assert_eq!(parsed, manifest);
+    }
+
+    #[test]
+    fn only_negative_manifests_carry_the_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let positive = dir.path().join("positive.bin");
+        let negative = dir.path().join("negative.bin");
+        let args = (SerializationFormat::Aligned, &[], Pubkey::default());
+        write_manifest(&positive, args.0, args.1, &[], &args.2).unwrap();
+        write_negative_manifest(&negative, args.0, args.1, &[], &args.2).unwrap();
+
+        let json = fs::read_to_string(manifest_path(&positive)).unwrap();
+        assert!(!json.contains("negative"));
+        assert!(!read_manifest(&positive).unwrap().negative);
+
+        let json = fs::read_to_string(manifest_path(&negative)).unwrap();
+        assert!(json.ends_with("  \"negative\": true\n}\n"));
+        assert!(read_manifest(&negative).unwrap().negative);
+    }
+
+    #[test]
+    fn write_failure_names_the_manifest_path() {
+        let file_path = Path::new("../test_data/missing_dir/fixture.bin");
+        let error = write_manifest(
+            file_path,
+            SerializationFormat::Aligned,
+            &[],
+            &[],
+            &Pubkey::default(),
+        )
+        .unwrap_err();
+        assert_eq!(error.path, manifest_path(file_path));
+        assert_eq!(error.source.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn manifest_sits_next_to_the_fixture() {
+        assert_eq!(
+            manifest_path(Path::new("../test_data/sysvar_clock.bin")),
+            Path::new("../test_data/sysvar_clock.expected.json")
+        );
+    }
+}
@@ -0,0 +1,304 @@
+// Malformed entrypoint buffers under test_data/negative/, each broken in
+// exactly one way, with a manifest naming how a parser should fail on it
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::path::Path;
+
+use crate::builder::{AccountBuilder, EntrypointBufferBuilder};
+use crate::deserialize::check_structure;
+use crate::error::{GenerateError, GenerateResult};
+use crate::manifest::MANIFEST_VERSION;
+use crate::serialize_solana_format::{build_single_account_solana_format, TEST_PROGRAM_ID};
+use crate::stable::write_if_changed;
+
+/// Directory inside test_data holding the negative fixtures
+pub const NEGATIVE_DIR: &str = "negative";
+
+/// Every file the negative fixtures generator writes, relative to test_data.
+/// Keep in step with `NEGATIVE_FIXTURES`.
+pub const NEGATIVE_FIXTURE_FILES: &[&str] = &[
+    "negative/truncated_after_count.bin",
+    "negative/truncated_mid_key.bin",
+    "negative/data_len_overflow.bin",
+    "negative/duplicate_out_of_range.bin",
+    "negative/duplicate_forward.bin",
+    "negative/duplicate_first.bin",
+    "negative/flag_out_of_range.bin",
+    "negative/trailing_bytes.bin",
+    "negative/manifest.json",
+];
+
+/// How a parser that checks the buffer length should reject a fixture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The buffer ends before the entries the count promises
+    Truncated,
+    /// An account's data_len runs past the end of the buffer
+    DataLenOverflow,
+    /// A duplicate marker that does not name an earlier account
+    InvalidDuplicateIndex,
+    /// An is_signer, is_writable or executable byte other than 0 or 1
+    InvalidFlag,
+    /// Bytes after the program ID
+    TrailingBytes,
+}
+
+/// One malformed buffer and why it must not parse
+pub struct NegativeFixture {
+    /// File stem inside `NEGATIVE_DIR`
+    pub name: &'static str,
+    pub reason: FailureReason,
+    pub description: &'static str,
+    pub build: fn(&Pubkey) -> Vec<u8>,
+}
+
+pub const NEGATIVE_FIXTURES: &[NegativeFixture] = &[
+    NegativeFixture {
+        name: "truncated_after_count",
+        reason: FailureReason::Truncated,
+        description: "num_accounts is 1, then the buffer ends",
+        build: build_truncated_after_count,
+    },
+    NegativeFixture {
+        name: "truncated_mid_key",
+        reason: FailureReason::Truncated,
+        description: "the buffer ends 16 bytes into the first account's key",
+        build: build_truncated_mid_key,
+    },
+    NegativeFixture {
+        name: "data_len_overflow",
+        reason: FailureReason::DataLenOverflow,
+        description: "the only account's data_len is 1 MiB, past the end of the buffer",
+        build: build_data_len_overflow,
+    },
+    NegativeFixture {
+        name: "duplicate_out_of_range",
+        reason: FailureReason::InvalidDuplicateIndex,
+        description: "account 1 of 2 is a duplicate of account 2",
+        build: build_duplicate_out_of_range,
+    },
+    NegativeFixture {
+        name: "duplicate_forward",
+        reason: FailureReason::InvalidDuplicateIndex,
+        description: "account 1 of 3 is a duplicate of the later account 2",
+        build: build_duplicate_forward,
+    },
+    NegativeFixture {
+        name: "duplicate_first",
+        reason: FailureReason::InvalidDuplicateIndex,
+        description: "account 0 is a duplicate marker, with no account before it",
+        build: build_duplicate_first,
+    },
+    NegativeFixture {
+        name: "flag_out_of_range",
+        reason: FailureReason::InvalidFlag,
+        description: "the only account's is_signer and is_writable bytes are 2",
+        build: build_flag_out_of_range,
+    },
+    NegativeFixture {
+        name: "trailing_bytes",
+        reason: FailureReason::TrailingBytes,
+        description: "4 bytes of garbage follow the program_id",
+        build: build_trailing_bytes,
+    },
+];
+
+/// Contents of `test_data/negative/manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegativeManifest {
+    /// Always `MANIFEST_VERSION`
+    pub version: u32,
+    /// In `NEGATIVE_FIXTURES` order
+    pub fixtures: Vec<NegativeFixtureManifest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegativeFixtureManifest {
+    /// File name inside `NEGATIVE_DIR`
+    pub filename: String,
+    pub byte_length: usize,
+    pub reason: FailureReason,
+    pub description: String,
+}
+
+/// Offset of the first key byte in an aligned buffer: the account count,
+/// then the dup marker, three flags and original_data_len
+const FIRST_KEY_OFFSET: usize = 8 + 8;
+
+/// Offset of the first account's data_len in an aligned buffer
+const FIRST_DATA_LEN_OFFSET: usize = 8 + 80;
+
+fn build_truncated_after_count(program_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = build_single_account_solana_format(&[0x01], program_id);
+    buffer.truncate(8);
+    buffer
+}
+
+fn build_truncated_mid_key(program_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = build_single_account_solana_format(&[0x01], program_id);
+    buffer.truncate(FIRST_KEY_OFFSET + 16);
+    buffer
+}
+
+fn build_data_len_overflow(program_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = build_single_account_solana_format(&[0x01], program_id);
+    buffer[FIRST_DATA_LEN_OFFSET..][..8].copy_from_slice(&(1u64 << 20).to_le_bytes());
+    buffer
+}
+
+fn build_duplicate_out_of_range(program_id: &Pubkey) -> Vec<u8> {
+    let mut builder = EntrypointBufferBuilder::new();
+    builder
+        .add_account(AccountBuilder::new().lamports(1_000).data(vec![0xAA; 4]))
+        .add_duplicate(2);
+    builder.finish(program_id, &[0x01])
+}
+
+fn build_duplicate_forward(program_id: &Pubkey) -> Vec<u8> {
+    let mut builder = EntrypointBufferBuilder::new();
+    builder
+        .add_account(AccountBuilder::new().lamports(1_000).data(vec![0xAA; 4]))
+        .add_duplicate(2)
+        .add_account(AccountBuilder::new().lamports(2_000).data(vec![0xBB; 4]));
+    builder.finish(program_id, &[0x01])
+}
+
+fn build_duplicate_first(program_id: &Pubkey) -> Vec<u8> {
+    let mut builder = EntrypointBufferBuilder::new();
+    builder
+        .add_duplicate(0)
+        .add_account(AccountBuilder::new().lamports(1_000).data(vec![0xAA; 4]));
+    builder.finish(program_id, &[0x01])
+}
+
+fn build_flag_out_of_range(program_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = build_single_account_solana_format(&[0x01], program_id);
+    // is_signer and is_writable follow the dup marker
+    buffer[9] = 2;
+    buffer[10] = 2;
+    buffer
+}
+
+fn build_trailing_bytes(program_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = build_single_account_solana_format(&[0x01], program_id);
+    buffer.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    buffer
+}
+
+/// Write every negative fixture and `negative/manifest.json` under
+/// `test_data_dir`. Each buffer is checked to fail `check_structure`
+/// before it is written.
+pub fn generate_negative_fixtures(test_data_dir: &Path) -> GenerateResult<()> {
+    let dir = test_data_dir.join(NEGATIVE_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| GenerateError::new(&dir, e))?;
+
+    let mut fixtures = Vec::new();
+    for fixture in NEGATIVE_FIXTURES {
+        let buffer = (fixture.build)(&TEST_PROGRAM_ID);
+        assert!(
+            !check_structure(&buffer).is_empty(),
+            "negative fixture {} passes the structure check",
+            fixture.name
+        );
+
+        let filename = format!("{}.bin", fixture.name);
+        write_if_changed(&dir.join(&filename), &buffer)?;
+        println!(
+            "Generated: {NEGATIVE_DIR}/{filename} ({} bytes, {:?})",
+            buffer.len(),
+            fixture.reason
+        );
+        fixtures.push(NegativeFixtureManifest {
+            filename,
+            byte_length: buffer.len(),
+            reason: fixture.reason,
+            description: fixture.description.to_string(),
+        });
+    }
+
+    let manifest = NegativeManifest {
+        version: MANIFEST_VERSION,
+        fixtures,
+    };
+    let mut json = serde_json::to_string_pretty(&manifest).expect("Failed to serialize manifest");
+    json.push('\n');
+    write_if_changed(&dir.join("manifest.json"), json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::{deserialize_input, DeserializeError};
+    use crate::verify::{verify_dir, verify_fixture};
+
+    #[test]
+    fn files_list_every_fixture_and_the_manifest() {
+        let mut files: Vec<String> = NEGATIVE_FIXTURES
+            .iter()
+            .map(|fixture| format!("{NEGATIVE_DIR}/{}.bin", fixture.name))
+            .collect();
+        files.push(format!("{NEGATIVE_DIR}/manifest.json"));
+        assert_eq!(files, NEGATIVE_FIXTURE_FILES);
+    }
+
+    #[test]
+    fn verify_flags_every_negative_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        generate_negative_fixtures(dir.path()).unwrap();
+
+        let results = verify_dir(&dir.path().join(NEGATIVE_DIR)).unwrap();
+        assert_eq!(results.len(), NEGATIVE_FIXTURES.len());
+        for (path, result) in results {
+            assert!(result.is_err(), "{} verified", path.display());
+        }
+
+        let json = std::fs::read_to_string(dir.path().join("negative/manifest.json")).unwrap();
+        let manifest: NegativeManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest.fixtures.len(), NEGATIVE_FIXTURES.len());
+        for entry in &manifest.fixtures {
+            let path = dir.path().join(NEGATIVE_DIR).join(&entry.filename);
+            assert_eq!(
+                std::fs::metadata(&path).unwrap().len() as usize,
+                entry.byte_length
+            );
+            assert!(verify_fixture(&path).is_err());
+        }
+    }
+
+    #[test]
+    fn parser_errors_match_the_stated_reason() {
+        for fixture in NEGATIVE_FIXTURES {
+            let buffer = (fixture.build)(&TEST_PROGRAM_ID);
+            let result = deserialize_input(&buffer).map(|_| ());
+            match fixture.reason {
+                FailureReason::Truncated | FailureReason::DataLenOverflow => assert!(
+                    matches!(result, Err(DeserializeError::UnexpectedEof { .. })),
+                    "{}: {result:?}",
+                    fixture.name
+                ),
+                FailureReason::InvalidDuplicateIndex => assert!(
+                    matches!(result, Err(DeserializeError::InvalidDuplicateIndex { .. })),
+                    "{}: {result:?}",
+                    fixture.name
+                ),
+                // The parser reads flags as != 0 and stops at the program
+                // ID, so only the structure check sees these
+                FailureReason::InvalidFlag | FailureReason::TrailingBytes => {
+                    let expected = match fixture.reason {
+                        FailureReason::InvalidFlag => "not 0 or 1",
+                        _ => "follow the program_id",
+                    };
+                    let violations = check_structure(&buffer);
+                    assert!(!violations.is_empty(), "{}", fixture.name);
+                    assert!(
+                        violations.iter().all(|v| v.message.contains(expected)),
+                        "{}: {violations:?}",
+                        fixture.name
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Per-account field offsets of generated fixtures, one `<name>.offsets.json`
+// per `<name>.bin`, for checking zero-copy parsers that point into the buffer
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::path::{Path, PathBuf};
+
+use crate::error::GenerateResult;
+use crate::serialize_solana_format::{write_from_accounts, FieldSink, SerializationFormat};
+use crate::stable::write_if_changed;
+use crate::verify::ExpectedAccount;
+
+/// Where one account's fields start in the buffer. Duplicate entries
+/// report the offsets of the account they point to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountFieldOffsets {
+    pub key: usize,
+    pub owner: usize,
+    pub lamports: usize,
+    pub data: usize,
+    pub data_len: usize,
+    pub rent_epoch: usize,
+}
+
+/// Contents of `<name>.offsets.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OffsetsFile {
+    pub offsets: Vec<AccountFieldOffsets>,
+}
+
+/// Sink that records each account field's offset as the serializer writes
+/// it, without keeping the bytes
+#[derive(Debug, Default)]
+pub struct OffsetRecorder {
+    len: usize,
+    in_account: bool,
+    accounts: Vec<AccountFieldOffsets>,
+    /// `(entry, original)` for every duplicate entry
+    duplicates: Vec<(usize, u8)>,
+}
+
+impl FieldSink for OffsetRecorder {
+    fn field(&mut self, name: &'static str, bytes: &[u8]) {
+        let offset = self.len;
+        self.len += bytes.len();
+        if !self.in_account {
+            return;
+        }
+
+        let entry = self.accounts.len() - 1;
+        let account = &mut self.accounts[entry];
+        match name {
+            "key" => account.key = offset,
+            "owner" => account.owner = offset,
+            "lamports" => account.lamports = offset,
+            "data" => account.data = offset,
+            "data_len" => account.data_len = offset,
+            "rent_epoch" => account.rent_epoch = offset,
+            // 0xFF marks an account that is not a duplicate
+            "dup_marker" if bytes != [0xFF] => self.duplicates.push((entry, bytes[0])),
+            _ => {}
+        }
+    }
+
+    fn set_entry(&mut self, entry: Option<String>) {
+        self.in_account = entry.is_some();
+        if self.in_account {
+            self.accounts.push(AccountFieldOffsets::default());
+        }
+    }
+}
+
+impl OffsetRecorder {
+    /// The recorded offsets, one per account entry
+    pub fn finish(mut self) -> Vec<AccountFieldOffsets> {
+        // Duplicates always point backwards, so their originals are filled in
+        for (entry, original) in self.duplicates {
+            self.accounts[entry] = self.accounts[original as usize];
+        }
+        self.accounts
+    }
+}
+
+/// Serialize like `build_from_accounts`, returning the offsets instead of
+/// the bytes
+pub fn record_offsets(
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<AccountFieldOffsets> {
+    let mut recorder = OffsetRecorder::default();
+    write_from_accounts(
+        &mut recorder,
+        format,
+        accounts,
+        instruction_data,
+        program_id,
+    );
+    recorder.finish()
+}
+
+/// `foo/bar.bin` -> `foo/bar.offsets.json`
+pub fn offsets_path(file_path: &Path) -> PathBuf {
+    file_path.with_extension("offsets.json")
+}
+
+/// Write the offsets file for the fixture at `file_path`
+pub fn write_offsets(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    let offsets = OffsetsFile {
+        offsets: record_offsets(format, accounts, instruction_data, program_id),
+    };
+    let mut json = serde_json::to_string_pretty(&offsets).expect("Failed to serialize offsets");
+    json.push('\n');
+    let path = offsets_path(file_path);
+    write_if_changed(&path, json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::deserialize_input_offsets;
+    use crate::serialize_solana_format::*;
+
+    #[test]
+    fn recorded_offsets_match_the_parser() {
+        let accounts = accounts_with_duplicates_scenario();
+        let buffer = build_from_accounts(
+            SerializationFormat::Aligned,
+            &accounts,
+            &[0x01],
+            &TEST_PROGRAM_ID,
+        );
+        let recorded = record_offsets(
+            SerializationFormat::Aligned,
+            &accounts,
+            &[0x01],
+            &TEST_PROGRAM_ID,
+        );
+        let parsed = deserialize_input_offsets(&buffer).unwrap();
+
+        assert_eq!(recorded.len(), parsed.accounts.len());
+        for (recorded, parsed) in recorded.iter().zip(&parsed.accounts) {
+            assert_eq!(recorded.key, parsed.key);
+            assert_eq!(recorded.lamports, parsed.lamports);
+            assert_eq!(recorded.data, parsed.data);
+            assert_eq!(recorded.owner, recorded.key + 32);
+            assert_eq!(recorded.data_len, recorded.lamports + 8);
+        }
+        // Duplicates point at their original's fields
+        assert_eq!(recorded[2], recorded[0]);
+        assert_eq!(recorded[4], recorded[1]);
+    }
+}
@@ -0,0 +1,189 @@
+// Representations a fixture is written in: raw bytes, hex text and JSON
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compress::{compress_fixture, MAX_RAW_FIXTURE_SIZE};
+use crate::error::{GenerateError, GenerateResult};
+use crate::manifest::{read_manifest, ManifestV1};
+use crate::stable::write_if_changed;
+
+/// How `generate` writes each fixture besides the `.bin` and its side files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputOptions {
+    /// Representations to write, see `fan_out`
+    pub formats: Vec<OutputFormat>,
+    /// Replace `.bin` files larger than this many bytes with `.bin.zst`.
+    /// Fixtures over `MAX_RAW_FIXTURE_SIZE` are compressed regardless.
+    pub compress_threshold: Option<u64>,
+}
+
+impl Default for OutputOptions {
+    /// Only the `.bin`, uncompressed unless it is over
+    /// `MAX_RAW_FIXTURE_SIZE`
+    fn default() -> Self {
+        OutputOptions {
+            formats: vec![OutputFormat::Bin],
+            compress_threshold: None,
+        }
+    }
+}
+
+/// A representation selected with `generate --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// `<name>.bin`, the raw entrypoint buffer
+    Bin,
+    /// `<name>.hex`, lowercase hex on one line
+    Hex,
+    /// `<name>.json`, the bytes in base64 and the fixture's manifest
+    Json,
+}
+
+impl OutputFormat {
+    pub fn sink(self) -> Box<dyn OutputSink> {
+        match self {
+            OutputFormat::Bin => Box::new(BinSink),
+            OutputFormat::Hex => Box::new(HexSink),
+            OutputFormat::Json => Box::new(JsonSink),
+        }
+    }
+}
+
+/// Writes one representation of a fixture next to its `.bin`
+pub trait OutputSink {
+    /// Where the representation of the fixture at `bin_path` goes
+    fn path(&self, bin_path: &Path) -> PathBuf;
+
+    /// The file contents for the fixture at `bin_path`, which holds
+    /// `bytes` and is described by `manifest`
+    fn render(&self, bin_path: &Path, bytes: &[u8], manifest: &ManifestV1) -> Vec<u8>;
+
+    /// Write the representation and return its path
+    fn write(
+        &self,
+        bin_path: &Path,
+        bytes: &[u8],
+        manifest: &ManifestV1,
+    ) -> GenerateResult<PathBuf> {
+        let path = self.path(bin_path);
+        write_if_changed(&path, self.render(bin_path, bytes, manifest))?;
+        Ok(path)
+    }
+}
+
+pub struct BinSink;
+
+impl OutputSink for BinSink {
+    fn path(&self, bin_path: &Path) -> PathBuf {
+        bin_path.to_path_buf()
+    }
+
+    fn render(&self, _bin_path: &Path, bytes: &[u8], _manifest: &ManifestV1) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+pub struct HexSink;
+
+impl OutputSink for HexSink {
+    fn path(&self, bin_path: &Path) -> PathBuf {
+        bin_path.with_extension("hex")
+    }
+
+    fn render(&self, _bin_path: &Path, bytes: &[u8], _manifest: &ManifestV1) -> Vec<u8> {
+        let mut hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        hex.push('\n');
+        hex.into_bytes()
+    }
+}
+
+pub struct JsonSink;
+
+/// Contents of `<name>.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonFixture {
+    /// Name of the `.bin` the bytes came from
+    pub name: String,
+    pub byte_length: usize,
+    /// Standard base64 with padding
+    pub bytes: String,
+    pub manifest: ManifestV1,
+}
+
+impl JsonFixture {
+    pub fn decode(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        BASE64.decode(&self.bytes)
+    }
+}
+
+impl OutputSink for JsonSink {
+    fn path(&self, bin_path: &Path) -> PathBuf {
+        bin_path.with_extension("json")
+    }
+
+    fn render(&self, bin_path: &Path, bytes: &[u8], manifest: &ManifestV1) -> Vec<u8> {
+        let fixture = JsonFixture {
+            name: bin_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            byte_length: bytes.len(),
+            bytes: BASE64.encode(bytes),
+            manifest: manifest.clone(),
+        };
+        let mut json = serde_json::to_string_pretty(&fixture).expect("Failed to serialize fixture");
+        json.push('\n');
+        json.into_bytes()
+    }
+}
+
+/// Read the fixture at `bin_path` and its manifest once and hand them to
+/// the sink of every format in `formats`. Returns the files written other
+/// than the `.bin` itself: the generators always write that one, since the
+/// manifest, `verify` and index.json are keyed on it.
+pub fn fan_out(bin_path: &Path, formats: &[OutputFormat]) -> GenerateResult<Vec<PathBuf>> {
+    if formats.iter().all(|format| *format == OutputFormat::Bin) {
+        return Ok(Vec::new());
+    }
+
+    let bytes = fs::read(bin_path).map_err(|e| GenerateError::new(bin_path, e))?;
+    let manifest = read_manifest(bin_path)?;
+    let mut written = Vec::new();
+    for format in formats {
+        let path = format.sink().write(bin_path, &bytes, &manifest)?;
+        if path != bin_path && !written.contains(&path) {
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// Finish the fixture at `bin_path` once its generator has written it:
+/// fan it out to the other formats in `options`, then compress it if it is
+/// over the threshold or `MAX_RAW_FIXTURE_SIZE`. `files`, the files written for the scenario so far,
+/// gains the extra representations and has the `.bin` swapped for the
+/// `.bin.zst` when compressed.
+pub fn write_outputs(
+    bin_path: &Path,
+    options: &OutputOptions,
+    files: &mut Vec<PathBuf>,
+) -> GenerateResult<()> {
+    files.extend(fan_out(bin_path, &options.formats)?);
+
+    let threshold = options
+        .compress_threshold
+        .map_or(MAX_RAW_FIXTURE_SIZE, |threshold| {
+            threshold.min(MAX_RAW_FIXTURE_SIZE)
+        });
+    let compressed = compress_fixture(bin_path, threshold)?;
+    if let Some(compressed) = compressed {
+        for file in files.iter_mut().filter(|file| file.as_path() == bin_path) {
+            *file = compressed.clone();
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+// Walking the accounts of an entrypoint buffer one entry at a time, borrowing
+// from the buffer instead of copying every account out like deserialize does
+use solana_program::pubkey::Pubkey;
+
+use crate::constants::MAX_PERMITTED_DATA_INCREASE;
+use crate::deserialize::{DeserializeError, Reader, NON_DUP_MARKER};
+
+/// A non-duplicate account entry, its data borrowed from the buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedAccount<'a> {
+    /// Position of the entry among the accounts
+    pub index: usize,
+    /// Offset of the entry's dup marker
+    pub offset: usize,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: &'a [u8],
+    pub rent_epoch: u64,
+}
+
+/// A duplicate account entry, which holds nothing but the index it repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateEntry {
+    /// Position of the entry among the accounts
+    pub index: usize,
+    /// Offset of the entry's dup marker
+    pub offset: usize,
+    /// The index written in the entry, which may itself be a duplicate
+    pub dup_index: u8,
+    /// The non-duplicate account `dup_index` resolves to
+    pub original: usize,
+}
+
+/// One account entry of the buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountEntry<'a> {
+    Account(SerializedAccount<'a>),
+    Duplicate(DuplicateEntry),
+}
+
+/// Iterator over the account entries of an entrypoint buffer. It stops
+/// after the last account, leaving `position()` at the instruction data
+/// length, or after the first error.
+pub struct SerializedAccountReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    /// Accounts left to read, None until the count has been read
+    remaining: Option<u64>,
+    /// For each entry read so far, the non-duplicate account it resolves to
+    seen: Vec<usize>,
+}
+
+impl<'a> SerializedAccountReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            remaining: None,
+            seen: Vec::new(),
+        }
+    }
+
+    /// Offset of the next byte to read
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn read_entry(&mut self) -> Result<Option<AccountEntry<'a>>, DeserializeError> {
+        let mut reader = Reader {
+            bytes: self.buf,
+            offset: self.pos,
+        };
+        let remaining = match self.remaining {
+            Some(remaining) => remaining,
+            None => reader.read_u64("num_accounts")?,
+        };
+        self.pos = reader.offset;
+        self.remaining = Some(remaining);
+        if remaining == 0 {
+            return Ok(None);
+        }
+
+        let index = self.seen.len();
+        let offset = reader.offset;
+        let dup_marker = reader.read_u8("dup_marker")?;
+        let entry = if dup_marker != NON_DUP_MARKER {
+            let original = *self.seen.get(dup_marker as usize).ok_or(
+                DeserializeError::InvalidDuplicateIndex {
+                    account: index,
+                    dup_index: dup_marker,
+                },
+            )?;
+            reader.read_bytes(7, "dup_padding")?;
+            self.seen.push(original);
+            AccountEntry::Duplicate(DuplicateEntry {
+                index,
+                offset,
+                dup_index: dup_marker,
+                original,
+            })
+        } else {
+            let is_signer = reader.read_u8("is_signer")? != 0;
+            let is_writable = reader.read_u8("is_writable")? != 0;
+            let executable = reader.read_u8("executable")? != 0;
+            reader.read_bytes(4, "original_data_len")?;
+            let key = reader.read_pubkey("key")?;
+            let owner = reader.read_pubkey("owner")?;
+            let lamports = reader.read_u64("lamports")?;
+            let data_len = reader.read_u64("data_len")? as usize;
+            let data = reader.read_bytes(data_len, "data")?;
+            reader.read_bytes(MAX_PERMITTED_DATA_INCREASE, "realloc_space")?;
+            reader.read_bytes((8 - data_len % 8) % 8, "alignment_padding")?;
+            let rent_epoch = reader.read_u64("rent_epoch")?;
+            self.seen.push(index);
+            AccountEntry::Account(SerializedAccount {
+                index,
+                offset,
+                is_signer,
+                is_writable,
+                executable,
+                key,
+                owner,
+                lamports,
+                data,
+                rent_epoch,
+            })
+        };
+
+        self.pos = reader.offset;
+        self.remaining = Some(remaining - 1);
+        Ok(Some(entry))
+    }
+}
+
+impl<'a> Iterator for SerializedAccountReader<'a> {
+    type Item = Result<AccountEntry<'a>, DeserializeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.read_entry().transpose();
+        if let Some(Err(_)) = entry {
+            self.remaining = Some(0);
+        }
+        entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::{deserialize_input, deserialize_input_offsets};
+    use crate::serialize_solana_format::*;
+
+    #[test]
+    fn reader_agrees_with_deserialize() {
+        let buffer = build_accounts_with_duplicates_solana_format(&[0x01], &TEST_PROGRAM_ID);
+        let parsed = deserialize_input(&buffer).unwrap();
+        let offsets = deserialize_input_offsets(&buffer).unwrap();
+
+        let mut reader = SerializedAccountReader::new(&buffer);
+        let entries: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries.len(), parsed.accounts.len());
+        for (entry, expected) in entries.iter().zip(&parsed.accounts) {
+            let account = match entry {
+                AccountEntry::Account(account) => account,
+                AccountEntry::Duplicate(duplicate) => {
+                    assert!(expected.is_dup);
+                    match &entries[duplicate.original] {
+                        AccountEntry::Account(account) => account,
+                        AccountEntry::Duplicate(_) => panic!("resolved to a duplicate"),
+                    }
+                }
+            };
+            assert_eq!(account.key, expected.key);
+            assert_eq!(account.lamports, expected.lamports);
+            assert_eq!(account.data, expected.data);
+            assert_eq!(
+                account.data.as_ptr(),
+                buffer[offsets.accounts[account.index].data..].as_ptr()
+            );
+        }
+        assert_eq!(reader.position() + 8 + 1, offsets.program_id);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn reader_stops_at_an_invalid_duplicate() {
+        let buffer = build_invalid_early_duplicate(&TEST_PROGRAM_ID);
+        let mut reader = SerializedAccountReader::new(&buffer);
+        assert_eq!(
+            reader.next(),
+            Some(Err(DeserializeError::InvalidDuplicateIndex {
+                account: 0,
+                dup_index: 0
+            }))
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn reader_reports_a_truncated_entry() {
+        let buffer = build_single_account_solana_format(&[], &TEST_PROGRAM_ID);
+        let mut reader = SerializedAccountReader::new(&buffer[..8 + 20]);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(DeserializeError::UnexpectedEof { field: "key", .. }))
+        ));
+        assert!(reader.next().is_none());
+    }
+}
@@ -1,390 +1,3377 @@
 // Generate test data using actual Solana runtime serialization format
-use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_nonce::{
+    state::{DurableNonce, State as NonceState},
+    versions::Versions as NonceVersions,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    epoch_schedule::EpochSchedule,
+    hash::{hash, Hash},
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{self, stake_history::StakeHistoryEntry},
+};
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+use solana_stake_interface::{
+    self as stake,
+    stake_flags::StakeFlags,
+    state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+};
+use solana_vote_interface::{
+    self as vote,
+    state::{BlockTimestamp, VoteInit, VoteState, VoteStateVersions},
+};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::borsh_accounts::generate_borsh_counter_account;
+use crate::builder::{AccountBuilder, EntrypointBufferBuilder};
+use crate::constants::{
+    MAX_PERMITTED_DATA_INCREASE, MAX_PERMITTED_DATA_LENGTH, MAX_TX_ACCOUNT_LOCKS,
+};
+use crate::deserialize::{deserialize_input, DeserializeError};
+use crate::error::{GenerateError, GenerateResult};
+use crate::hexdump::{hexdump_path, write_hexdump};
+use crate::index::write_index;
+use crate::manifest::{manifest_path, write_manifest, write_negative_manifest};
+use crate::negative_fixtures::{generate_negative_fixtures, NEGATIVE_DIR, NEGATIVE_FIXTURE_FILES};
+use crate::offsets::{offsets_path, write_offsets};
+use crate::output::{write_outputs, OutputOptions};
+use crate::spec::write_default_scenarios;
+use crate::stable::{write_if_changed, write_stream_if_changed};
+use crate::verify::{verify_round_trip, ExpectedAccount};
+
+/// Program ID written at the end of every fixture, all 0x42 so it is easy
+/// to spot in a hex dump
+pub const TEST_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x42; 32]);
+
+/// Parameter layout a loader hands to the program entrypoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerializationFormat {
+    /// Current loaders: 8-byte aligned headers, realloc space after the data
+    Aligned,
+    /// bpf_loader_deprecated: packed fields, data inline with no realloc
+    /// space or padding
+    Deprecated,
+}
+
+/// Default output directory, the repository's test_data. Anchored to this
+/// crate rather than the working directory.
+pub const TEST_DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../test_data");
+
+/// Seed used when `--seed` is not given
+pub const DEFAULT_SEED: u64 = 42;
+
+/// Source of every "random" key and byte in the fixtures. ChaCha's output
+/// for a given seed is fixed, unlike `StdRng` or `Pubkey::new_unique`.
+pub type FixtureRng = ChaCha8Rng;
+
+/// A key drawn from `rng`, the same on every machine for the same seed
+pub fn deterministic_pubkey(rng: &mut FixtureRng) -> Pubkey {
+    Pubkey::new_from_array(rng.gen())
+}
+
+/// A named scenario and the `.bin` files its generator writes
+pub struct FixtureGenerator {
+    /// Scenario name used on the command line, e.g. `single_account`
+    pub name: &'static str,
+    /// File stems of every fixture the generator writes
+    pub stems: &'static [&'static str],
+    /// Files written besides the fixtures and their side files
+    pub extra_files: &'static [&'static str],
+    pub generate: fn(&Path, &mut FixtureRng) -> GenerateResult<()>,
+}
+
+impl FixtureGenerator {
+    /// Every file the generator writes into `test_data_dir`: each fixture
+    /// and its manifest, hexdump and offsets, then any extra files
+    pub fn files(&self, test_data_dir: &Path) -> Vec<PathBuf> {
+        self.stems
+            .iter()
+            .flat_map(|stem| {
+                let bin = test_data_dir.join(format!("{stem}.bin"));
+                [
+                    manifest_path(&bin),
+                    hexdump_path(&bin),
+                    offsets_path(&bin),
+                    bin,
+                ]
+            })
+            .chain(self.extra_files.iter().map(|file| test_data_dir.join(file)))
+            .collect()
+    }
+}
+
+/// Every generator, in the order `generate_solana_format_test_data` runs them
+pub const FIXTURE_GENERATORS: &[FixtureGenerator] = &[
+    FixtureGenerator {
+        name: "single_account",
+        stems: &["solana_single_account", "deprecated_single_account"],
+        extra_files: &[],
+        generate: generate_single_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "multiple_accounts",
+        stems: &["solana_multiple_accounts", "deprecated_multiple_accounts"],
+        extra_files: &[],
+        generate: generate_multiple_accounts_solana_format,
+    },
+    FixtureGenerator {
+        name: "empty_data_accounts",
+        stems: &["empty_data_accounts", "zero_vs_unallocated"],
+        extra_files: &[],
+        generate: generate_empty_data_accounts_solana_format,
+    },
+    FixtureGenerator {
+        name: "accounts_with_duplicates",
+        stems: &["solana_accounts_with_duplicates"],
+        extra_files: &[],
+        generate: generate_accounts_with_duplicates_solana_format,
+    },
+    FixtureGenerator {
+        name: "self_dup_account",
+        stems: &["self_dup_account"],
+        extra_files: &[],
+        generate: generate_self_dup_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "flags_matrix",
+        stems: &["flags_matrix"],
+        extra_files: &[],
+        generate: generate_flags_matrix_solana_format,
+    },
+    FixtureGenerator {
+        name: "invalid_early_duplicate",
+        stems: &[],
+        extra_files: &[INVALID_EARLY_DUPLICATE_FILE],
+        generate: generate_invalid_early_duplicate,
+    },
+    FixtureGenerator {
+        name: "negative",
+        stems: &[],
+        extra_files: NEGATIVE_FIXTURE_FILES,
+        generate: generate_negative,
+    },
+    FixtureGenerator {
+        name: "degenerate_inputs",
+        stems: &["solana_no_accounts", "solana_empty_instruction_data"],
+        extra_files: &[],
+        generate: generate_degenerate_inputs_solana_format,
+    },
+    FixtureGenerator {
+        name: "complex_iteration",
+        stems: &["solana_complex_iteration"],
+        extra_files: &[],
+        generate: generate_complex_iteration_solana_format,
+    },
+    FixtureGenerator {
+        name: "alignment_padding",
+        stems: &["solana_alignment_padding"],
+        extra_files: &[],
+        generate: generate_alignment_padding_solana_format,
+    },
+    FixtureGenerator {
+        name: "realloc_space",
+        stems: &["solana_realloc_space"],
+        extra_files: &[],
+        generate: generate_realloc_space_solana_format,
+    },
+    FixtureGenerator {
+        name: "full_entrypoint",
+        stems: &["solana_full_entrypoint"],
+        extra_files: &[],
+        generate: generate_full_entrypoint_solana_format,
+    },
+    FixtureGenerator {
+        name: "alignment_sweep",
+        stems: &["solana_alignment_sweep"],
+        extra_files: &[],
+        generate: generate_alignment_sweep_solana_format,
+    },
+    FixtureGenerator {
+        name: "max_accounts",
+        stems: &["solana_max_accounts", "solana_max_accounts_overflow"],
+        extra_files: &[],
+        generate: generate_max_accounts_solana_format,
+    },
+    FixtureGenerator {
+        name: "max_lamports",
+        stems: &["solana_max_lamports", "solana_zero_lamports"],
+        extra_files: &[],
+        generate: generate_max_lamports_solana_format,
+    },
+    FixtureGenerator {
+        name: "edge_values",
+        stems: &["edge_values"],
+        extra_files: &[],
+        generate: generate_edge_values_solana_format,
+    },
+    FixtureGenerator {
+        name: "permission_mismatch",
+        stems: &["permission_mismatch"],
+        extra_files: &[PERMISSION_REQUIREMENTS_FILE],
+        generate: generate_permission_mismatch_solana_format,
+    },
+    FixtureGenerator {
+        name: "max_data_account",
+        stems: &["solana_max_data_account"],
+        extra_files: &[],
+        generate: generate_max_data_account,
+    },
+    FixtureGenerator {
+        name: "clock_sysvar",
+        stems: &["sysvar_clock"],
+        extra_files: &[],
+        generate: generate_clock_sysvar_solana_format,
+    },
+    FixtureGenerator {
+        name: "rent_sysvar",
+        stems: &["sysvar_rent", "sysvar_rent_mainnet"],
+        extra_files: &[],
+        generate: generate_rent_sysvar_solana_format,
+    },
+    FixtureGenerator {
+        name: "epoch_schedule_sysvar",
+        stems: &["sysvar_epoch_schedule"],
+        extra_files: &[],
+        generate: generate_epoch_schedule_sysvar_solana_format,
+    },
+    FixtureGenerator {
+        name: "stake_history_sysvar",
+        stems: &["sysvar_stake_history"],
+        extra_files: &[],
+        generate: generate_stake_history_sysvar_solana_format,
+    },
+    FixtureGenerator {
+        name: "slot_hashes_sysvar",
+        stems: &["sysvar_slot_hashes"],
+        extra_files: &[],
+        generate: generate_slot_hashes_sysvar_solana_format,
+    },
+    FixtureGenerator {
+        name: "nonce_account",
+        stems: &["nonce_account", "nonce_account_uninitialized"],
+        extra_files: &[],
+        generate: generate_nonce_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "stake_account",
+        stems: &[
+            "stake_account_uninitialized",
+            "stake_account_initialized",
+            "stake_account_delegated",
+            "stake_account_rewards_pool",
+        ],
+        extra_files: &[],
+        generate: generate_stake_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "vote_account",
+        stems: &["vote_account"],
+        extra_files: &[],
+        generate: generate_vote_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "bpf_loader_upgradeable",
+        stems: &["bpf_loader_upgradeable"],
+        extra_files: &[],
+        generate: generate_bpf_loader_upgradeable_solana_format,
+    },
+    FixtureGenerator {
+        name: "borsh_counter_account",
+        stems: &["borsh_counter_account"],
+        extra_files: &[],
+        generate: generate_borsh_counter_account,
+    },
+    FixtureGenerator {
+        name: "pda_accounts",
+        stems: &["pda_accounts"],
+        extra_files: &[PDA_EXPECTED_FILE],
+        generate: generate_pda_accounts_solana_format,
+    },
+    FixtureGenerator {
+        name: "spl_token_mint",
+        stems: &["spl_token_mint", "spl_token_mint_with_freeze_authority"],
+        extra_files: &[],
+        generate: generate_spl_token_mint_solana_format,
+    },
+    FixtureGenerator {
+        name: "spl_token_account",
+        stems: &[
+            "spl_token_account",
+            "spl_token_account_frozen",
+            "spl_token_account_native",
+        ],
+        extra_files: &[],
+        generate: generate_spl_token_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "ata_account",
+        stems: &["ata_account"],
+        extra_files: &[],
+        generate: generate_associated_token_account_solana_format,
+    },
+    FixtureGenerator {
+        name: "instruction_data",
+        stems: &[
+            "solana_instruction_data_empty",
+            "solana_instruction_data_1_byte",
+            "solana_instruction_data_1kb",
+        ],
+        extra_files: &[],
+        generate: generate_instruction_data_solana_format,
+    },
+    FixtureGenerator {
+        name: "program_id",
+        stems: &["solana_program_id"],
+        extra_files: &[],
+        generate: generate_program_id_solana_format,
+    },
+    FixtureGenerator {
+        name: "actual_accountinfo",
+        stems: &["solana_actual_accountinfo"],
+        extra_files: &[],
+        generate: test_with_actual_account_info,
+    },
+];
+
+/// Create the test data directory if it is missing
+fn ensure_test_data_dir(test_data_dir: &Path) -> GenerateResult<()> {
+    std::fs::create_dir_all(test_data_dir).map_err(|e| GenerateError::new(test_data_dir, e))
+}
 
 /// This simulates how Solana runtime serializes accounts for BPF programs
 /// Based on solana/programs/bpf_loader/src/serialization.rs
-pub fn generate_solana_format_test_data() {
-    let test_data_dir = Path::new("../test_data");
-    if !test_data_dir.exists() {
-        std::fs::create_dir_all(test_data_dir).expect("Failed to create test_data directory");
+///
+/// Returns every file written.
+pub fn generate_solana_format_test_data(
+    test_data_dir: &Path,
+    seed: u64,
+) -> GenerateResult<Vec<PathBuf>> {
+    let generators: Vec<&FixtureGenerator> = FIXTURE_GENERATORS.iter().collect();
+    generate_scenarios(test_data_dir, &generators, seed, &OutputOptions::default())
+}
+
+/// Look up a scenario by name, or by the stem of any file it writes
+pub fn find_generator(name: &str) -> Option<&'static FixtureGenerator> {
+    FIXTURE_GENERATORS
+        .iter()
+        .find(|generator| generator.name == name || generator.stems.contains(&name))
+}
+
+/// Run the named scenarios into `test_data_dir`, creating it if needed,
+/// write and compress each fixture as `options` asks, record what they
+/// wrote in index.json, and return every file written including the
+/// index. Stops at the first failure. Each one gets its own generator
+/// seeded with `seed`, so a scenario writes the same bytes whether it runs
+/// alone or with the others.
+pub fn generate_scenarios(
+    test_data_dir: &Path,
+    generators: &[&FixtureGenerator],
+    seed: u64,
+    options: &OutputOptions,
+) -> GenerateResult<Vec<PathBuf>> {
+    ensure_test_data_dir(test_data_dir)?;
+    let mut written: Vec<(&str, PathBuf)> = Vec::new();
+    for generator in generators {
+        (generator.generate)(test_data_dir, &mut FixtureRng::seed_from_u64(seed))?;
+        let mut files = generator.files(test_data_dir);
+        for stem in generator.stems {
+            write_outputs(
+                &test_data_dir.join(format!("{stem}.bin")),
+                options,
+                &mut files,
+            )?;
+        }
+        written.extend(files.into_iter().map(|file| (generator.name, file)));
     }
+    let index = write_index(test_data_dir, &written)?;
 
-    // Generate different test cases
-    generate_single_account_solana_format(&test_data_dir);
-    generate_multiple_accounts_solana_format(&test_data_dir);
-    generate_empty_data_accounts_solana_format(&test_data_dir);
-    generate_accounts_with_duplicates_solana_format(&test_data_dir);
-    generate_complex_iteration_solana_format(&test_data_dir);
+    let mut files: Vec<PathBuf> = written.into_iter().map(|(_, file)| file).collect();
+    files.push(index);
+    Ok(files)
+}
 
-    println!("\n✓ All Solana format test data files generated in test_data/");
+/// Write one fixture file unless it already holds `buffer`
+fn write_fixture(file_path: &Path, buffer: &[u8]) -> GenerateResult<()> {
+    write_if_changed(file_path, buffer)?;
+    Ok(())
 }
 
-fn generate_single_account_solana_format(test_data_dir: &Path) {
-    let mut buffer = Vec::new();
+/// Check the fixture at `file_path` parses back to `accounts`, then write
+/// its manifest, hexdump and offsets next to it
+fn write_sidecars(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    verify_round_trip(file_path, accounts);
+    write_manifest(file_path, format, accounts, instruction_data, program_id)?;
+    write_annotations(file_path, format, accounts, instruction_data, program_id)
+}
 
-    // Create account data
-    let key = Pubkey::default();
-    let mut lamports = 1000u64;
-    let mut data = vec![0xAA; 10];
-    let owner = Pubkey::default();
+/// Write the hexdump and offsets of the fixture at `file_path`
+fn write_annotations(
+    file_path: &Path,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    write_hexdump(file_path, format, accounts, instruction_data, program_id)?;
+    write_offsets(file_path, format, accounts, instruction_data, program_id)
+}
 
-    // Number of accounts
-    buffer.push(1u8);
+/// Write a fixture built from `accounts` and every sidecar that goes with
+/// it, see `write_sidecars`
+fn write_fixture_with_sidecars(
+    file_path: &Path,
+    buffer: &[u8],
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> GenerateResult<()> {
+    write_fixture(file_path, buffer)?;
+    write_sidecars(file_path, format, accounts, instruction_data, program_id)
+}
 
-    // Serialize account following Solana's format
-    serialize_account_solana_format(
-        &mut buffer,
-        &key,
+pub fn single_account_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::default(),
         true, // is_signer
         true, // is_writable
-        &mut lamports,
-        &mut data,
-        &owner,
+        1000,
+        vec![0xAA; 10],
+        Pubkey::default(),
         false, // executable
-        true,  // is_non_dup
-        0,     // dup_index (unused for non-dup)
-    );
+        0,     // rent_epoch
+    )]
+}
+
+pub fn build_single_account_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_single_account(SerializationFormat::Aligned, instruction_data, program_id)
+}
+
+pub fn build_single_account(
+    format: SerializationFormat,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        format,
+        &single_account_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `solana_single_account.bin` and `deprecated_single_account.bin`, from
+/// scenarios.toml
+fn generate_single_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(
+        test_data_dir,
+        &["solana_single_account", "deprecated_single_account"],
+    )
+}
+
+pub fn multiple_accounts_scenario() -> Vec<ExpectedAccount> {
+    vec![
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![0xAA; 5],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+        ExpectedAccount::new(
+            key_with_first_byte(1),
+            false, // is_signer
+            true,  // is_writable
+            2000,
+            vec![0xBB; 10],
+            Pubkey::default(),
+            false, // executable
+            361,   // rent_epoch
+        ),
+        ExpectedAccount::new(
+            key_with_first_byte(2),
+            false, // is_signer
+            false, // is_writable
+            3000,
+            vec![0xCC; 15],
+            Pubkey::default(),
+            true,     // executable
+            u64::MAX, // rent_epoch: rent exempt
+        ),
+    ]
+}
+
+pub fn build_multiple_accounts_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_multiple_accounts(SerializationFormat::Aligned, instruction_data, program_id)
+}
+
+pub fn build_multiple_accounts(
+    format: SerializationFormat,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        format,
+        &multiple_accounts_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `solana_multiple_accounts.bin` and `deprecated_multiple_accounts.bin`,
+/// from scenarios.toml
+fn generate_multiple_accounts_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(
+        test_data_dir,
+        &["solana_multiple_accounts", "deprecated_multiple_accounts"],
+    )
+}
+
+pub fn empty_data_accounts_scenario() -> Vec<ExpectedAccount> {
+    vec![
+        // Account 1: empty data
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+        // Account 2: with data
+        ExpectedAccount::new(
+            key_with_first_byte(1),
+            false, // is_signer
+            false, // is_writable
+            2000,
+            vec![0xFF; 4],
+            Pubkey::default(),
+            true, // executable
+            0,    // rent_epoch
+        ),
+    ]
+}
+
+pub fn build_empty_data_accounts_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &empty_data_accounts_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// An account that does not exist yet, as a transaction creating it passes
+/// it, and one that exists with zero-length data. Both have a data length
+/// of 0; only the owner and lamports tell them apart.
+pub fn zero_vs_unallocated_scenario() -> Vec<ExpectedAccount> {
+    vec![
+        // Unallocated: system-owned, no lamports
+        ExpectedAccount::new(
+            Pubkey::new_from_array([0x21; 32]),
+            true, // is_signer
+            true, // is_writable
+            0,
+            vec![],
+            system_program::ID,
+            false, // executable
+            0,     // rent_epoch
+        ),
+        // Explicitly empty: program-owned, rent-exempt for 0 bytes
+        ExpectedAccount::new(
+            Pubkey::new_from_array([0x22; 32]),
+            false, // is_signer
+            true,  // is_writable
+            890_880,
+            vec![],
+            TEST_PROGRAM_ID,
+            false, // executable
+            0,     // rent_epoch
+        ),
+    ]
+}
+
+pub fn build_zero_vs_unallocated_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &zero_vs_unallocated_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `empty_data_accounts.bin` and `zero_vs_unallocated.bin`, from
+/// scenarios.toml
+fn generate_empty_data_accounts_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(
+        test_data_dir,
+        &["empty_data_accounts", "zero_vs_unallocated"],
+    )
+}
+
+pub fn accounts_with_duplicates_scenario() -> Vec<ExpectedAccount> {
+    let mut accounts = vec![
+        // Account 0: Original
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![0xAA; 8],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+        // Account 1: Original
+        ExpectedAccount::new(
+            key_with_first_byte(1),
+            false, // is_signer
+            true,  // is_writable
+            2000,
+            vec![0xBB; 12],
+            Pubkey::default(),
+            true, // executable
+            0,    // rent_epoch
+        ),
+    ];
+    // Account 2: Duplicate of account 0
+    accounts.push(ExpectedAccount::duplicate_of(&accounts, 0));
+    // Account 3: Original
+    accounts.push(ExpectedAccount::new(
+        key_with_first_byte(3),
+        true,  // is_signer
+        false, // is_writable
+        3000,
+        vec![0xCC; 6],
+        Pubkey::default(),
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    ));
+    // Account 4: Duplicate of account 1
+    accounts.push(ExpectedAccount::duplicate_of(&accounts, 1));
+    accounts
+}
+
+pub fn build_accounts_with_duplicates_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &accounts_with_duplicates_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `solana_accounts_with_duplicates.bin`, from scenarios.toml
+fn generate_accounts_with_duplicates_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(test_data_dir, &["solana_accounts_with_duplicates"])
+}
+
+/// One original followed by two duplicates of it, so the same index is
+/// referenced more than once
+pub fn self_dup_account_scenario() -> Vec<ExpectedAccount> {
+    let mut accounts = vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x2D; 32]),
+        true, // is_signer
+        true, // is_writable
+        5000,
+        vec![0xDD; 8],
+        TEST_PROGRAM_ID,
+        false, // executable
+        0,     // rent_epoch
+    )];
+    accounts.push(ExpectedAccount::duplicate_of(&accounts, 0));
+    accounts.push(ExpectedAccount::duplicate_of(&accounts, 0));
+    accounts
+}
+
+pub fn build_self_dup_account_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &self_dup_account_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `self_dup_account.bin`, from scenarios.toml
+fn generate_self_dup_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(test_data_dir, &["self_dup_account"])
+}
+
+/// Flag bits of a `flags_matrix` account, encoded in its first key byte
+pub const FLAG_IS_SIGNER: u8 = 1 << 0;
+pub const FLAG_IS_WRITABLE: u8 = 1 << 1;
+pub const FLAG_EXECUTABLE: u8 = 1 << 2;
+
+/// One account for each of the 8 combinations of is_signer, is_writable and
+/// executable. Account `i` has `i` as its first key byte, with the flags in
+/// its bits, and `i + 1` bytes of `0xF0 + i` data so every length differs.
+pub fn flags_matrix_scenario() -> Vec<ExpectedAccount> {
+    (0..8u8)
+        .map(|i| {
+            ExpectedAccount::new(
+                key_with_first_byte(i),
+                i & FLAG_IS_SIGNER != 0,
+                i & FLAG_IS_WRITABLE != 0,
+                1000 + i as u64,
+                vec![0xF0 + i; i as usize + 1],
+                TEST_PROGRAM_ID,
+                i & FLAG_EXECUTABLE != 0,
+                0, // rent_epoch
+            )
+        })
+        .collect()
+}
 
-    let file_path = test_data_dir.join("solana_single_account.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&buffer).expect("Failed to write data");
+/// `flags_matrix.bin`, from scenarios.toml
+fn generate_flags_matrix_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(test_data_dir, &["flags_matrix"])
+}
+
+/// Prefix of fixtures that are malformed on purpose. Each is written as an
+/// extra file on its own, without a manifest, hexdump or offsets, since no
+/// parse of it succeeds.
+pub const INVALID_FIXTURE_PREFIX: &str = "invalid_";
+
+/// Whether `path` names a fixture that is malformed on purpose: one with
+/// `INVALID_FIXTURE_PREFIX`, or any file under `NEGATIVE_DIR`
+pub fn is_invalid_fixture(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with(INVALID_FIXTURE_PREFIX))
+        || path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|dir| dir == NEGATIVE_DIR)
+}
+
+/// A duplicate marker at position 0, where no account precedes it
+pub const INVALID_EARLY_DUPLICATE_FILE: &str = "invalid_early_duplicate.bin";
+
+/// One account whose entry is a duplicate marker of 0x00, then empty
+/// instruction data and `program_id`
+pub fn build_invalid_early_duplicate(program_id: &Pubkey) -> Vec<u8> {
+    let mut builder = EntrypointBufferBuilder::new();
+    builder.add_duplicate(0);
+    builder.finish(program_id, &[])
+}
+
+fn generate_invalid_early_duplicate(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let buffer = build_invalid_early_duplicate(&TEST_PROGRAM_ID);
+    assert_eq!(
+        deserialize_input(&buffer).map(|_| ()),
+        Err(DeserializeError::InvalidDuplicateIndex {
+            account: 0,
+            dup_index: 0,
+        }),
+        "{INVALID_EARLY_DUPLICATE_FILE} must not parse"
+    );
 
+    write_fixture(&test_data_dir.join(INVALID_EARLY_DUPLICATE_FILE), &buffer)?;
     println!(
-        "Generated: solana_single_account.bin ({} bytes)",
+        "Generated: {} ({} bytes, invalid)",
+        INVALID_EARLY_DUPLICATE_FILE,
         buffer.len()
     );
+    Ok(())
 }
 
-fn generate_multiple_accounts_solana_format(test_data_dir: &Path) {
-    let mut buffer = Vec::new();
-
-    // Number of accounts
-    buffer.push(3u8);
+fn generate_negative(test_data_dir: &Path, _rng: &mut FixtureRng) -> GenerateResult<()> {
+    generate_negative_fixtures(test_data_dir)
+}
 
-    // Account 1
-    let key1 = Pubkey::default();
-    let mut lamports1 = 1000u64;
-    let mut data1 = vec![0xAA; 5];
-    let owner1 = Pubkey::default();
+/// Instruction data of `solana_no_accounts.bin`: 48 bytes counting up
+/// from 0x00
+pub fn no_accounts_instruction_data() -> Vec<u8> {
+    (0..48).collect()
+}
 
-    serialize_account_solana_format(
-        &mut buffer,
-        &key1,
+/// A single signing, writable account with 4 bytes of data, invoked with
+/// no instruction data
+pub fn empty_instruction_data_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x31; 32]),
         true, // is_signer
         true, // is_writable
-        &mut lamports1,
-        &mut data1,
-        &owner1,
+        5000,
+        vec![0x31; 4],
+        TEST_PROGRAM_ID,
         false, // executable
-        true,  // is_non_dup
-        0,
+        0,     // rent_epoch
+    )]
+}
+
+/// `solana_no_accounts.bin`, an account count of 0 with instruction data,
+/// and `solana_empty_instruction_data.bin`, an account with none, from
+/// scenarios.toml
+fn generate_degenerate_inputs_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    write_default_scenarios(
+        test_data_dir,
+        &["solana_no_accounts", "solana_empty_instruction_data"],
+    )
+}
+
+pub fn complex_iteration_scenario() -> Vec<ExpectedAccount> {
+    let mut accounts = Vec::new();
+
+    // Generate accounts with various patterns
+    for i in 0..10u8 {
+        let account = if i == 4 {
+            // Duplicate of account 1
+            ExpectedAccount::duplicate_of(&accounts, 1)
+        } else if i == 7 {
+            // Duplicate of account 2
+            ExpectedAccount::duplicate_of(&accounts, 2)
+        } else {
+            // Original account
+            let data_len = ((i % 4) + 1) * 3;
+            ExpectedAccount::new(
+                key_with_first_byte(i),
+                i % 2 == 0, // is_signer
+                i % 3 != 0, // is_writable
+                (i as u64 + 1) * 500,
+                vec![0xA0 + i; data_len as usize],
+                Pubkey::default(),
+                i % 5 == 0,     // executable
+                i as u64 * 100, // rent_epoch
+            )
+        };
+        accounts.push(account);
+    }
+
+    accounts
+}
+
+pub fn build_complex_iteration_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &complex_iteration_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_complex_iteration_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: Vec<u8> = (0..10).collect();
+    let buffer = build_complex_iteration_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_complex_iteration.bin");
+    let expected_accounts = complex_iteration_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: solana_complex_iteration.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
     );
 
-    // Account 2
-    let mut key2_bytes = [0u8; 32];
-    key2_bytes[0] = 1;
-    let key2 = Pubkey::new_from_array(key2_bytes);
-    let mut lamports2 = 2000u64;
-    let mut data2 = vec![0xBB; 10];
-    let owner2 = Pubkey::default();
+    Ok(())
+}
 
-    serialize_account_solana_format(
-        &mut buffer,
-        &key2,
+pub fn alignment_padding_scenario() -> Vec<ExpectedAccount> {
+    vec![
+        // Account 1: 5 bytes of data, followed by 3 bytes of padding
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![0xAA; 5],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+        // Account 2: already aligned data, no padding
+        ExpectedAccount::new(
+            key_with_first_byte(1),
+            false, // is_signer
+            true,  // is_writable
+            2000,
+            vec![0xBB; 8],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+    ]
+}
+
+pub fn build_alignment_padding_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &alignment_padding_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_alignment_padding_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = [0xAA; 5];
+    let buffer = build_alignment_padding_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_alignment_padding.bin");
+    let expected_accounts = alignment_padding_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: solana_alignment_padding.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// The same account before and after a realloc from 8 to 18 bytes. Growing
+/// account 0 in place must fit inside its reserved realloc space.
+pub fn realloc_space_scenario() -> Vec<ExpectedAccount> {
+    vec![
+        // Account 0: 8 bytes of data before the realloc
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![0xAA; 8],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+        // Account 1: 18 bytes of data after the realloc
+        ExpectedAccount::new(
+            Pubkey::default(),
+            true, // is_signer
+            true, // is_writable
+            1000,
+            vec![0xAA; 18],
+            Pubkey::default(),
+            false, // executable
+            0,     // rent_epoch
+        ),
+    ]
+}
+
+pub fn build_realloc_space_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &realloc_space_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_realloc_space_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = 18u64.to_le_bytes();
+    let buffer = build_realloc_space_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_realloc_space.bin");
+    let expected_accounts = realloc_space_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: solana_realloc_space.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// A complete entrypoint input: accounts followed by the instruction data
+/// and program ID, as handed to a program's entrypoint
+pub fn full_entrypoint_scenario(program_id: &Pubkey) -> Vec<ExpectedAccount> {
+    vec![
+        // Account 0: payer
+        ExpectedAccount::new(
+            key_with_first_byte(1),
+            true, // is_signer
+            true, // is_writable
+            5_000_000,
+            vec![],
+            Pubkey::default(),
+            false,    // executable
+            u64::MAX, // rent_epoch
+        ),
+        // Account 1: program-owned state account
+        ExpectedAccount::new(
+            key_with_first_byte(2),
+            false, // is_signer
+            true,  // is_writable
+            1_000_000,
+            vec![0xDD; 13],
+            *program_id,
+            false,    // executable
+            u64::MAX, // rent_epoch
+        ),
+        // Account 2: the program itself
+        ExpectedAccount::new(
+            *program_id,
+            false, // is_signer
+            false, // is_writable
+            1,
+            vec![0x7F, 0x45, 0x4C, 0x46],
+            Pubkey::default(),
+            true,     // executable
+            u64::MAX, // rent_epoch
+        ),
+    ]
+}
+
+pub fn build_full_entrypoint_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &full_entrypoint_scenario(program_id),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_full_entrypoint_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    // Instruction 2 with a u64 amount
+    let mut instruction_data = vec![0x02];
+    instruction_data.extend_from_slice(&1_000u64.to_le_bytes());
+    let buffer = build_full_entrypoint_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_full_entrypoint.bin");
+    let expected_accounts = full_entrypoint_scenario(&TEST_PROGRAM_ID);
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: solana_full_entrypoint.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// One account per data length from 0 to 16 bytes, covering every amount of
+/// alignment padding. Account `i` has `i` bytes of data filled with `i`.
+pub fn alignment_sweep_scenario() -> Vec<ExpectedAccount> {
+    (0..=16u8)
+        .map(|i| {
+            ExpectedAccount::new(
+                key_with_first_byte(i),
+                false, // is_signer
+                true,  // is_writable
+                (i as u64 + 1) * 100,
+                vec![i; i as usize],
+                Pubkey::default(),
+                false, // executable
+                0,     // rent_epoch
+            )
+        })
+        .collect()
+}
+
+pub fn build_alignment_sweep_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &alignment_sweep_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_alignment_sweep_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_alignment_sweep_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_alignment_sweep.bin");
+    let expected_accounts = alignment_sweep_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: solana_alignment_sweep.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Data lengths the max accounts scenario cycles through: empty, either
+/// side of one 8-byte word, and a full KiB
+pub const MAX_ACCOUNTS_DATA_LENS: [usize; 6] = [0, 1, 7, 8, 9, 1024];
+
+/// `(position, original)` of each duplicate in the max accounts scenario
+pub const MAX_ACCOUNTS_DUPLICATES: [(usize, u8); 6] =
+    [(10, 0), (21, 3), (32, 17), (43, 42), (54, 5), (63, 62)];
+
+/// A distinct account of the max accounts scenarios. Account `i` has `i` as
+/// a little-endian u16 in the first two key bytes and data of the `i`th
+/// length in `MAX_ACCOUNTS_DATA_LENS` filled with its low byte; every third
+/// account signs and odd ones are writable.
+fn max_accounts_entry(i: usize) -> ExpectedAccount {
+    let mut key_bytes = [0u8; 32];
+    key_bytes[0..2].copy_from_slice(&(i as u16).to_le_bytes());
+    ExpectedAccount::new(
+        Pubkey::new_from_array(key_bytes),
+        i.is_multiple_of(3), // is_signer
+        i % 2 == 1,          // is_writable
+        1_000 + i as u64,
+        vec![i as u8; MAX_ACCOUNTS_DATA_LENS[i % MAX_ACCOUNTS_DATA_LENS.len()]],
+        TEST_PROGRAM_ID,
+        false, // executable
+        0,     // rent_epoch
+    )
+}
+
+/// `MAX_TX_ACCOUNT_LOCKS` accounts, the most a transaction can reference:
+/// distinct accounts from `max_accounts_entry`, except at the positions in
+/// `MAX_ACCOUNTS_DUPLICATES`
+pub fn max_accounts_scenario() -> Vec<ExpectedAccount> {
+    let mut accounts: Vec<ExpectedAccount> = Vec::with_capacity(MAX_TX_ACCOUNT_LOCKS);
+    for i in 0..MAX_TX_ACCOUNT_LOCKS {
+        let account = match MAX_ACCOUNTS_DUPLICATES.iter().find(|(at, _)| *at == i) {
+            Some(&(_, original)) => ExpectedAccount::duplicate_of(&accounts, original),
+            None => max_accounts_entry(i),
+        };
+        accounts.push(account);
+    }
+    accounts
+}
+
+/// One account past the limit: the max accounts scenario plus another
+/// distinct account
+pub fn max_accounts_overflow_scenario() -> Vec<ExpectedAccount> {
+    let mut accounts = max_accounts_scenario();
+    accounts.push(max_accounts_entry(MAX_TX_ACCOUNT_LOCKS));
+    accounts
+}
+
+/// Instruction data of the max accounts scenarios: the account limit as a
+/// little-endian u64
+pub const MAX_ACCOUNTS_INSTRUCTION_DATA: [u8; 8] = (MAX_TX_ACCOUNT_LOCKS as u64).to_le_bytes();
+
+pub fn build_max_accounts_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &max_accounts_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+pub fn build_max_accounts_overflow_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &max_accounts_overflow_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// Exactly `MAX_TX_ACCOUNT_LOCKS` accounts, and a negative fixture with one
+/// more that a parser sized for the limit should refuse
+fn generate_max_accounts_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = MAX_ACCOUNTS_INSTRUCTION_DATA;
+    let variants = [
+        (
+            "solana_max_accounts.bin",
+            max_accounts_scenario(),
+            build_max_accounts_solana_format(&instruction_data, &TEST_PROGRAM_ID),
+        ),
+        (
+            "solana_max_accounts_overflow.bin",
+            max_accounts_overflow_scenario(),
+            build_max_accounts_overflow_solana_format(&instruction_data, &TEST_PROGRAM_ID),
+        ),
+    ];
+
+    for (file_name, expected_accounts, buffer) in variants {
+        let file_path = test_data_dir.join(file_name);
+        if expected_accounts.len() > MAX_TX_ACCOUNT_LOCKS {
+            // Over the limit, so the manifest says how a parser should fail
+            write_fixture(&file_path, &buffer)?;
+            verify_round_trip(&file_path, &expected_accounts);
+            write_negative_manifest(
+                &file_path,
+                SerializationFormat::Aligned,
+                &expected_accounts,
+                &instruction_data,
+                &TEST_PROGRAM_ID,
+            )?;
+            write_annotations(
+                &file_path,
+                SerializationFormat::Aligned,
+                &expected_accounts,
+                &instruction_data,
+                &TEST_PROGRAM_ID,
+            )?;
+        } else {
+            write_fixture_with_sidecars(
+                &file_path,
+                &buffer,
+                SerializationFormat::Aligned,
+                &expected_accounts,
+                &instruction_data,
+                &TEST_PROGRAM_ID,
+            )?;
+        }
+
+        println!(
+            "Generated: {} ({} accounts, {} bytes, instruction data {})",
+            file_name,
+            expected_accounts.len(),
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Offset of the first account's lamports: the account count, then the
+/// marker, flags, original data length, key and owner
+pub const FIRST_ACCOUNT_LAMPORTS_OFFSET: usize = 8 + 8 + 32 + 32;
+
+/// A single writable account with no data holding `lamports`, owned by the
+/// system program the way a closed account is
+pub fn lamports_edge_scenario(lamports: u64) -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x1A; 32]),
         false, // is_signer
         true,  // is_writable
-        &mut lamports2,
-        &mut data2,
-        &owner2,
+        lamports,
+        vec![],
+        system_program::ID,
         false, // executable
-        true,  // is_non_dup
-        0,
-    );
+        0,     // rent_epoch
+    )]
+}
 
-    // Account 3
-    let mut key3_bytes = [0u8; 32];
-    key3_bytes[0] = 2;
-    let key3 = Pubkey::new_from_array(key3_bytes);
-    let mut lamports3 = 3000u64;
-    let mut data3 = vec![0xCC; 15];
-    let owner3 = Pubkey::default();
-
-    serialize_account_solana_format(
-        &mut buffer,
-        &key3,
-        false, // is_signer
-        false, // is_writable
-        &mut lamports3,
-        &mut data3,
-        &owner3,
-        true, // executable
-        true, // is_non_dup
-        0,
+pub fn build_lamports_edge_solana_format(
+    lamports: u64,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &lamports_edge_scenario(lamports),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// An account holding `u64::MAX` lamports, and a closed one holding none
+fn generate_max_lamports_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        ("solana_max_lamports.bin", u64::MAX),
+        ("solana_zero_lamports.bin", 0),
+    ];
+
+    for (file_name, lamports) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer =
+            build_lamports_edge_solana_format(lamports, &instruction_data, &TEST_PROGRAM_ID);
+        assert_eq!(
+            buffer[FIRST_ACCOUNT_LAMPORTS_OFFSET..][..8],
+            lamports.to_le_bytes(),
+            "{file_name}: lamports are not where the parser reads them"
+        );
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = lamports_edge_scenario(lamports);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Lamports whose bytes all differ, so a reader with the wrong byte order
+/// gets a different number
+pub const EDGE_VALUES_LAMPORTS: u64 = 0x0102_0304_0506_0708;
+
+/// Three accounts holding 0, `u64::MAX` and `EDGE_VALUES_LAMPORTS`
+/// lamports, with rent epochs of `u64::MAX`, one with only the top and
+/// bottom bits set, and `EDGE_VALUES_LAMPORTS` byte-reversed. Their data
+/// continues one increasing sequence from 0x01, 5, 8 and 3 bytes long, so
+/// a read from the wrong field or account shows up as the wrong bytes.
+pub fn edge_values_scenario() -> Vec<ExpectedAccount> {
+    let lamports = [0, u64::MAX, EDGE_VALUES_LAMPORTS];
+    let rent_epochs = [
+        u64::MAX,
+        0x8000_0000_0000_0001,
+        EDGE_VALUES_LAMPORTS.swap_bytes(),
+    ];
+    let data_lens = [5u8, 8, 3];
+
+    let mut next_byte = 1u8;
+    (0..3)
+        .map(|i| {
+            let data: Vec<u8> = (next_byte..next_byte + data_lens[i]).collect();
+            next_byte += data_lens[i];
+            ExpectedAccount::new(
+                Pubkey::new_from_array([0xE0 + i as u8; 32]),
+                i == 1, // is_signer
+                true,   // is_writable
+                lamports[i],
+                data,
+                TEST_PROGRAM_ID,
+                false, // executable
+                rent_epochs[i],
+            )
+        })
+        .collect()
+}
+
+/// `edge_values.bin`. Written here rather than in scenarios.toml, which
+/// cannot spell `u64::MAX` lamports.
+fn generate_edge_values_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = [0xED];
+    let expected_accounts = edge_values_scenario();
+    let buffer = build_from_accounts(
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
     );
 
-    let file_path = test_data_dir.join("solana_multiple_accounts.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&buffer).expect("Failed to write data");
+    let file_path = test_data_dir.join("edge_values.bin");
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
 
     println!(
-        "Generated: solana_multiple_accounts.bin ({} bytes)",
-        buffer.len()
+        "Generated: edge_values.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
     );
+
+    Ok(())
 }
 
-fn generate_empty_data_accounts_solana_format(test_data_dir: &Path) {
-    let mut buffer = Vec::new();
+/// What the instruction in `permission_mismatch.bin` needs of each account
+/// it misuses
+pub const PERMISSION_REQUIREMENTS_FILE: &str = "permission_requirements.json";
 
-    // Number of accounts
-    buffer.push(2u8);
+/// One account the runtime passed with weaker permissions than the
+/// instruction declares, as written to `PERMISSION_REQUIREMENTS_FILE`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionRequirement {
+    pub account_index: usize,
+    /// The flag as serialized in the buffer
+    pub is_writable: bool,
+    /// What the instruction declares for the account
+    pub expects_writable: bool,
+    /// Name of the ProgramError a writability guard returns
+    pub expected_error: String,
+}
 
-    // Account 1: empty data
-    let key1 = Pubkey::default();
-    let mut lamports1 = 1000u64;
-    let mut data1 = vec![]; // Empty data
-    let owner1 = Pubkey::default();
+/// Instruction data of `permission_mismatch.bin`: a request to write into
+/// account 0
+pub const PERMISSION_MISMATCH_INSTRUCTION: [u8; 2] = [0x01, 0x2A];
 
-    serialize_account_solana_format(
-        &mut buffer,
-        &key1,
+/// A program-owned account that signed but was passed read-only, to an
+/// instruction that writes to it
+pub fn permission_mismatch_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x9E; 32]),
         true,  // is_signer
-        true,  // is_writable
-        &mut lamports1,
-        &mut data1,
-        &owner1,
+        false, // is_writable
+        1_000_000,
+        vec![0; 8],
+        TEST_PROGRAM_ID,
         false, // executable
-        true,  // is_non_dup
-        0,
+        0,     // rent_epoch
+    )]
+}
+
+pub fn permission_requirements() -> Vec<PermissionRequirement> {
+    vec![PermissionRequirement {
+        account_index: 0,
+        is_writable: false,
+        expects_writable: true,
+        expected_error: "AccountNotWritable".to_string(),
+    }]
+}
+
+fn generate_permission_mismatch_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = PERMISSION_MISMATCH_INSTRUCTION;
+    let expected_accounts = permission_mismatch_scenario();
+    let buffer = build_from_accounts(
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
     );
 
-    // Account 2: with data
-    let mut key2_bytes = [0u8; 32];
-    key2_bytes[0] = 1;
-    let key2 = Pubkey::new_from_array(key2_bytes);
-    let mut lamports2 = 2000u64;
-    let mut data2 = vec![0xFF; 4]; // Small data buffer
-    let owner2 = Pubkey::default();
+    let file_path = test_data_dir.join("permission_mismatch.bin");
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
 
-    serialize_account_solana_format(
-        &mut buffer,
-        &key2,
-        false, // is_signer
-        false, // is_writable
-        &mut lamports2,
-        &mut data2,
-        &owner2,
-        true, // executable
-        true, // is_non_dup
-        0,
+    let expected_path = test_data_dir.join(PERMISSION_REQUIREMENTS_FILE);
+    let json = serde_json::to_string_pretty(&permission_requirements())
+        .expect("permission requirements serialize");
+    write_if_changed(&expected_path, json + "\n")?;
+
+    println!(
+        "Generated: permission_mismatch.bin ({} bytes, instruction data {}) and {}",
+        buffer.len(),
+        describe_instruction_data(&instruction_data),
+        PERMISSION_REQUIREMENTS_FILE
     );
 
-    let file_path = test_data_dir.join("empty_data_accounts.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&buffer).expect("Failed to write data");
+    Ok(())
+}
+
+/// Offset of the first account's data length, right after its lamports
+pub const FIRST_ACCOUNT_DATA_LEN_OFFSET: usize = FIRST_ACCOUNT_LAMPORTS_OFFSET + 8;
+
+/// `len` bytes of xorshift64 output from `seed`, eight little-endian
+/// bytes per step. A zero seed, which xorshift cannot leave, is taken as 1.
+pub fn xorshift_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.max(1);
+    let mut bytes = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        bytes.extend_from_slice(&state.to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// Length of the xorshift block the maximum data account repeats (64 KiB)
+pub const MAX_DATA_PATTERN_LEN: usize = 64 * 1024;
+
+/// A single writable account holding `MAX_PERMITTED_DATA_LENGTH` bytes of
+/// `xorshift_bytes(seed)`, so a reader that copies, truncates or shifts
+/// the data gets a different hash. The block repeats every
+/// `MAX_DATA_PATTERN_LEN` bytes so the fixture compresses; a shifted read
+/// still picks up realloc padding at one end.
+pub fn max_data_account_scenario(seed: u64) -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x1E; 32]),
+        false, // is_signer
+        true,  // is_writable
+        1_000_000_000,
+        xorshift_bytes(seed, MAX_DATA_PATTERN_LEN)
+            .repeat(MAX_PERMITTED_DATA_LENGTH / MAX_DATA_PATTERN_LEN),
+        TEST_PROGRAM_ID,
+        false, // executable
+        0,     // rent_epoch
+    )]
+}
+
+/// A `FieldSink` streaming into an `io::Write`. It keeps the first error
+/// and skips every write after it, for `finish` to report.
+pub struct WriterSink<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> WriterSink<W> {
+    pub fn new(writer: W) -> Self {
+        WriterSink {
+            writer,
+            error: None,
+        }
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        self.error.map_or(Ok(()), Err)
+    }
+}
+
+impl<W: Write> FieldSink for WriterSink<W> {
+    fn field(&mut self, _name: &'static str, bytes: &[u8]) {
+        if self.error.is_none() {
+            self.error = self.writer.write_all(bytes).err();
+        }
+    }
+}
+
+/// `solana_max_data_account.bin`, a 10 MiB account of seeded pseudo-random
+/// data. The scenario holds the data once and the file is written through
+/// a `WriterSink`, without a second copy of the whole buffer; the
+/// manifest, hexdump and offsets each serialize the scenario again. Being
+/// over `MAX_RAW_FIXTURE_SIZE`, it is committed as `.bin.zst`, and the
+/// manifest's `data_sha256` lets the Zig side check the decompressed data.
+fn generate_max_data_account(test_data_dir: &Path, rng: &mut FixtureRng) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let expected_accounts = max_data_account_scenario(rng.gen());
+
+    let file_path = test_data_dir.join("solana_max_data_account.bin");
+    write_stream_if_changed(&file_path, |writer| {
+        let mut sink = WriterSink::new(writer);
+        write_from_accounts(
+            &mut sink,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        );
+        sink.finish()
+    })?;
+
+    write_sidecars(
+        &file_path,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    let written = std::fs::metadata(&file_path)
+        .map_err(|e| GenerateError::new(&file_path, e))?
+        .len();
+    println!(
+        "Generated: solana_max_data_account.bin ({written} bytes, instruction data {})",
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Encode a Clock the way the runtime stores it in the sysvar account:
+/// five 8-byte little-endian fields in declaration order (40 bytes)
+fn encode_clock(clock: &Clock) -> Vec<u8> {
+    let mut data = Vec::with_capacity(40);
+    data.extend_from_slice(&clock.slot.to_le_bytes());
+    data.extend_from_slice(&clock.epoch_start_timestamp.to_le_bytes());
+    data.extend_from_slice(&clock.epoch.to_le_bytes());
+    data.extend_from_slice(&clock.leader_schedule_epoch.to_le_bytes());
+    data.extend_from_slice(&clock.unix_timestamp.to_le_bytes());
+    data
+}
+
+/// A single read-only Clock sysvar account
+pub fn clock_sysvar_scenario() -> Vec<ExpectedAccount> {
+    let clock = Clock {
+        slot: 100,
+        epoch_start_timestamp: 1_700_000_000,
+        epoch: 5,
+        leader_schedule_epoch: 6,
+        unix_timestamp: 1_700_000_500,
+    };
+    vec![sysvar_account(
+        sysvar::clock::ID,
+        1_169_280,
+        encode_clock(&clock),
+    )]
+}
+
+pub fn build_clock_sysvar_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &clock_sysvar_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_clock_sysvar_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_clock_sysvar_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("sysvar_clock.bin");
+    let expected_accounts = clock_sysvar_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: sysvar_clock.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Encode a Rent the way the runtime stores it in the sysvar account:
+/// u64 lamports_per_byte_year, f64 exemption_threshold and u8 burn_percent,
+/// little-endian with no padding (17 bytes)
+fn encode_rent(rent: &Rent) -> Vec<u8> {
+    let mut data = Vec::with_capacity(17);
+    data.extend_from_slice(&rent.lamports_per_byte_year.to_le_bytes());
+    data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+    data.push(rent.burn_percent);
+    data
+}
+
+/// A single read-only Rent sysvar account holding `rent`
+pub fn rent_sysvar_scenario(rent: &Rent) -> Vec<ExpectedAccount> {
+    vec![sysvar_account(
+        sysvar::rent::ID,
+        1_009_200,
+        encode_rent(rent),
+    )]
+}
+
+pub fn build_rent_sysvar_solana_format(
+    rent: &Rent,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &rent_sysvar_scenario(rent),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// A non-default Rent in `sysvar_rent.bin` and the mainnet values
+/// (exemption_threshold 2.0) in `sysvar_rent_mainnet.bin`
+fn generate_rent_sysvar_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        (
+            "sysvar_rent.bin",
+            Rent {
+                lamports_per_byte_year: 1_000,
+                exemption_threshold: 1.5,
+                burn_percent: 25,
+            },
+        ),
+        ("sysvar_rent_mainnet.bin", Rent::default()),
+    ];
+
+    for (file_name, rent) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_rent_sysvar_solana_format(&rent, &instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = rent_sysvar_scenario(&rent);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Encode an EpochSchedule the way the runtime stores it in the sysvar
+/// account, little-endian with no padding (33 bytes):
+///
+/// | offset | size | field                       |
+/// |--------|------|-----------------------------|
+/// | 0      | 8    | slots_per_epoch             |
+/// | 8      | 8    | leader_schedule_slot_offset |
+/// | 16     | 1    | warmup (0 or 1)             |
+/// | 17     | 8    | first_normal_epoch          |
+/// | 25     | 8    | first_normal_slot           |
+fn encode_epoch_schedule(epoch_schedule: &EpochSchedule) -> Vec<u8> {
+    let mut data = Vec::with_capacity(33);
+    data.extend_from_slice(&epoch_schedule.slots_per_epoch.to_le_bytes());
+    data.extend_from_slice(&epoch_schedule.leader_schedule_slot_offset.to_le_bytes());
+    data.push(epoch_schedule.warmup as u8);
+    data.extend_from_slice(&epoch_schedule.first_normal_epoch.to_le_bytes());
+    data.extend_from_slice(&epoch_schedule.first_normal_slot.to_le_bytes());
+    data
+}
+
+/// A single read-only EpochSchedule sysvar account with the mainnet schedule
+pub fn epoch_schedule_sysvar_scenario() -> Vec<ExpectedAccount> {
+    let epoch_schedule = EpochSchedule {
+        slots_per_epoch: 432_000,
+        leader_schedule_slot_offset: 432_000,
+        warmup: true,
+        first_normal_epoch: 14,
+        first_normal_slot: 524_256,
+    };
+    vec![sysvar_account(
+        sysvar::epoch_schedule::ID,
+        1_120_560,
+        encode_epoch_schedule(&epoch_schedule),
+    )]
+}
+
+pub fn build_epoch_schedule_sysvar_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &epoch_schedule_sysvar_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_epoch_schedule_sysvar_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_epoch_schedule_sysvar_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("sysvar_epoch_schedule.bin");
+    let expected_accounts = epoch_schedule_sysvar_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: sysvar_epoch_schedule.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Encode StakeHistory entries the way the runtime stores them in the
+/// sysvar account: a u64 entry count followed by one 32-byte record per
+/// entry (u64 epoch, effective, activating, deactivating), little-endian.
+/// The runtime keeps the newest epoch first.
+fn encode_stake_history(entries: &[(u64, StakeHistoryEntry)]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + entries.len() * 32);
+    data.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+    for (epoch, entry) in entries {
+        data.extend_from_slice(&epoch.to_le_bytes());
+        data.extend_from_slice(&entry.effective.to_le_bytes());
+        data.extend_from_slice(&entry.activating.to_le_bytes());
+        data.extend_from_slice(&entry.deactivating.to_le_bytes());
+    }
+    data
+}
+
+/// A single read-only StakeHistory sysvar account with three entries
+pub fn stake_history_sysvar_scenario() -> Vec<ExpectedAccount> {
+    let entries = [
+        (
+            12,
+            StakeHistoryEntry {
+                effective: 3_000_000,
+                activating: 250_000,
+                deactivating: 0,
+            },
+        ),
+        (
+            11,
+            StakeHistoryEntry {
+                effective: 2_500_000,
+                activating: 500_000,
+                deactivating: 100_000,
+            },
+        ),
+        (
+            10,
+            StakeHistoryEntry {
+                effective: 2_000_000,
+                activating: 0,
+                deactivating: 50_000,
+            },
+        ),
+    ];
+    vec![sysvar_account(
+        sysvar::stake_history::ID,
+        1_614_720,
+        encode_stake_history(&entries),
+    )]
+}
+
+pub fn build_stake_history_sysvar_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &stake_history_sysvar_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_stake_history_sysvar_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_stake_history_sysvar_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("sysvar_stake_history.bin");
+    let expected_accounts = stake_history_sysvar_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: sysvar_stake_history.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Encode SlotHashes the way the runtime stores them in the sysvar account:
+/// a u64 entry count followed by (u64 slot, 32-byte hash) pairs, newest
+/// slot first. Each hash is SHA-256 of the slot's little-endian bytes so
+/// the Zig side can recompute it.
+fn encode_slot_hashes(slots: &[u64]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + slots.len() * 40);
+    data.extend_from_slice(&(slots.len() as u64).to_le_bytes());
+    for slot in slots {
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(hash(&slot.to_le_bytes()).as_ref());
+    }
+    data
+}
+
+/// A single read-only SlotHashes sysvar account with five entries
+pub fn slot_hashes_sysvar_scenario() -> Vec<ExpectedAccount> {
+    let slots = [1_004, 1_003, 1_002, 1_001, 1_000];
+    vec![sysvar_account(
+        sysvar::slot_hashes::ID,
+        2_338_560,
+        encode_slot_hashes(&slots),
+    )]
+}
+
+pub fn build_slot_hashes_sysvar_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &slot_hashes_sysvar_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_slot_hashes_sysvar_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_slot_hashes_sysvar_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("sysvar_slot_hashes.bin");
+    let expected_accounts = slot_hashes_sysvar_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: sysvar_slot_hashes.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Bincode-encode a nonce account's state the way the system program stores
+/// it: u32 Versions tag (1 = Current), u32 State tag (0 = Uninitialized,
+/// 1 = Initialized), then for Initialized the authority (32), durable nonce
+/// (32) and lamports_per_signature (u64). The account always holds
+/// `NonceState::size()` (80) bytes, zero-filled past the encoded state.
+fn encode_nonce_state(state: &NonceState) -> Vec<u8> {
+    let mut data = vec![0u8; NonceState::size()];
+    bincode::serialize_into(&mut data[..], &NonceVersions::new(state.clone()))
+        .expect("Failed to encode nonce state");
+    data
+}
+
+/// A single writable nonce account, owned by the system program and holding
+/// the rent-exempt minimum for its 80 bytes
+pub fn nonce_account_scenario(state: &NonceState) -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x4E; 32]),
+        false, // is_signer
+        true,  // is_writable
+        Rent::default().minimum_balance(NonceState::size()),
+        encode_nonce_state(state),
+        system_program::ID,
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    )]
+}
+
+pub fn build_nonce_account_solana_format(
+    state: &NonceState,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &nonce_account_scenario(state),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// The nonce state written to `nonce_account.bin`: authority all 0xA1,
+/// durable nonce derived from a blockhash of all 0x5A, 5000 lamports per
+/// signature
+pub fn initialized_nonce_state() -> NonceState {
+    NonceState::new_initialized(
+        &Pubkey::new_from_array([0xA1; 32]),
+        DurableNonce::from_blockhash(&Hash::new_from_array([0x5A; 32])),
+        5_000,
+    )
+}
+
+/// An initialized nonce account in `nonce_account.bin` and an
+/// uninitialized one in `nonce_account_uninitialized.bin`
+fn generate_nonce_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        ("nonce_account.bin", initialized_nonce_state()),
+        ("nonce_account_uninitialized.bin", NonceState::Uninitialized),
+    ];
+
+    for (file_name, state) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_nonce_account_solana_format(&state, &instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = nonce_account_scenario(&state);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Bincode-encode a stake state, padded to the 200 bytes every stake
+/// account holds whatever its state
+fn encode_stake_state(state: &StakeStateV2) -> Vec<u8> {
+    let mut data = vec![0u8; StakeStateV2::size_of()];
+    bincode::serialize_into(&mut data[..], state).expect("Failed to encode stake state");
+    data
+}
+
+/// A single writable stake account owned by the Stake program, holding the
+/// rent-exempt minimum for its 200 bytes plus whatever is delegated
+pub fn stake_account_scenario(state: &StakeStateV2) -> Vec<ExpectedAccount> {
+    let staked = state.stake().map_or(0, |stake| stake.delegation.stake);
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x5E; 32]),
+        false, // is_signer
+        true,  // is_writable
+        Rent::default().minimum_balance(StakeStateV2::size_of()) + staked,
+        encode_stake_state(state),
+        stake::program::ID,
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    )]
+}
+
+pub fn build_stake_account_solana_format(
+    state: &StakeStateV2,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &stake_account_scenario(state),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// Meta of the initialized and delegated fixtures: staker all 0x5A,
+/// withdrawer all 0x5B, no lockup
+pub fn stake_meta() -> Meta {
+    Meta {
+        rent_exempt_reserve: Rent::default().minimum_balance(StakeStateV2::size_of()),
+        authorized: Authorized {
+            staker: Pubkey::new_from_array([0x5A; 32]),
+            withdrawer: Pubkey::new_from_array([0x5B; 32]),
+        },
+        lockup: Lockup::default(),
+    }
+}
+
+/// Stake of the delegated fixture: 5 SOL delegated to a vote account of
+/// all 0x70 since epoch 100, still active
+pub fn delegated_stake() -> Stake {
+    Stake {
+        delegation: Delegation::new(
+            &Pubkey::new_from_array([0x70; 32]),
+            5_000_000_000,
+            100, // activation_epoch
+        ),
+        credits_observed: 12_345,
+    }
+}
+
+/// One stake account per `StakeStateV2` variant:
+/// `stake_account_{uninitialized,initialized,delegated,rewards_pool}.bin`
+fn generate_stake_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        (
+            "stake_account_uninitialized.bin",
+            StakeStateV2::Uninitialized,
+        ),
+        (
+            "stake_account_initialized.bin",
+            StakeStateV2::Initialized(stake_meta()),
+        ),
+        (
+            "stake_account_delegated.bin",
+            StakeStateV2::Stake(stake_meta(), delegated_stake(), StakeFlags::empty()),
+        ),
+        ("stake_account_rewards_pool.bin", StakeStateV2::RewardsPool),
+    ];
+
+    for (file_name, state) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_stake_account_solana_format(&state, &instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = stake_account_scenario(&state);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Vote account of the delegated stake fixture, all 0x70
+pub const VOTE_ACCOUNT_KEY: Pubkey = Pubkey::new_from_array([0x70; 32]);
+
+/// Validator identity (`node_pubkey`) of the vote account, all 0x71
+pub const VOTE_NODE_PUBKEY: Pubkey = Pubkey::new_from_array([0x71; 32]);
+
+/// Authorized voter of the vote account, all 0x72
+pub const VOTE_AUTHORIZED_VOTER: Pubkey = Pubkey::new_from_array([0x72; 32]);
+
+/// Authorized withdrawer of the vote account, all 0x73
+pub const VOTE_AUTHORIZED_WITHDRAWER: Pubkey = Pubkey::new_from_array([0x73; 32]);
+
+/// Vote state of the fixture: 10% commission, voter authorized since
+/// epoch 100, votes for slots 1000 to 1009 each landing one slot later,
+/// and credits earned over epochs 98 to 100
+pub fn vote_state() -> VoteState {
+    let clock = Clock {
+        slot: 1000,
+        epoch: 100,
+        ..Clock::default()
+    };
+    let mut state = VoteState::new(
+        &VoteInit {
+            node_pubkey: VOTE_NODE_PUBKEY,
+            authorized_voter: VOTE_AUTHORIZED_VOTER,
+            authorized_withdrawer: VOTE_AUTHORIZED_WITHDRAWER,
+            commission: 10,
+        },
+        &clock,
+    );
+    for slot in 1000..1010 {
+        state.process_next_vote_slot(slot, clock.epoch, slot + 1);
+    }
+    // (epoch, credits, prev_credits)
+    state.epoch_credits = vec![(98, 1_000, 0), (99, 2_500, 1_000), (100, 2_800, 2_500)];
+    state.last_timestamp = BlockTimestamp {
+        slot: 1009,
+        timestamp: 1_700_000_000,
+    };
+    state
+}
+
+/// Bincode-encode a vote state as `VoteStateVersions::Current`, padded to
+/// the size a vote account is created with
+fn encode_vote_state(state: &VoteState) -> Vec<u8> {
+    let mut data = vec![0u8; VoteState::size_of()];
+    VoteState::serialize(&VoteStateVersions::new_current(state.clone()), &mut data)
+        .expect("Failed to encode vote state");
+    data
+}
+
+/// A single writable, rent-exempt vote account owned by the Vote program
+pub fn vote_account_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        VOTE_ACCOUNT_KEY,
+        false, // is_signer
+        true,  // is_writable
+        Rent::default().minimum_balance(VoteState::size_of()),
+        encode_vote_state(&vote_state()),
+        vote::program::ID,
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    )]
+}
+
+pub fn build_vote_account_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &vote_account_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_vote_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_vote_account_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("vote_account.bin");
+    let expected_accounts = vote_account_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: vote_account.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Program account of the upgradeable loader fixture, all 0xB1
+pub const UPGRADEABLE_PROGRAM_KEY: Pubkey = Pubkey::new_from_array([0xB1; 32]);
+
+/// Buffer account of the upgradeable loader fixture, all 0xB2
+pub const UPGRADEABLE_BUFFER_KEY: Pubkey = Pubkey::new_from_array([0xB2; 32]);
+
+/// Upgrade authority of the programdata and buffer accounts, all 0xB3
+pub const UPGRADE_AUTHORITY: Pubkey = Pubkey::new_from_array([0xB3; 32]);
+
+/// Slot the fixture's program was last deployed at
+pub const PROGRAMDATA_SLOT: u64 = 123_456;
+
+/// Stand-in for the deployed ELF: the ELF magic followed by 60 counting
+/// bytes, enough to tell where the metadata ends and the program begins
+pub fn program_elf() -> Vec<u8> {
+    let mut elf = b"\x7fELF".to_vec();
+    elf.extend(0..60u8);
+    elf
+}
+
+/// The programdata address of `program`: the PDA of `[program]` under the
+/// upgradeable loader
+pub fn programdata_address(program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Bincode-encode a loader state, followed by `program` for the buffer
+/// and programdata accounts that hold one
+fn encode_upgradeable_loader_state(state: &UpgradeableLoaderState, program: &[u8]) -> Vec<u8> {
+    let mut data = bincode::serialize(state).expect("Failed to encode loader state");
+    data.extend_from_slice(program);
+    data
+}
+
+/// The three accounts of a deployed upgradeable program: the executable
+/// program account pointing at its programdata, the programdata account
+/// holding the ELF, and a buffer with the same ELF staged for an upgrade.
+/// All are owned by the upgradeable loader and rent exempt.
+pub fn bpf_loader_upgradeable_scenario() -> Vec<ExpectedAccount> {
+    let elf = program_elf();
+    let programdata = programdata_address(&UPGRADEABLE_PROGRAM_KEY);
+    let account = |key, is_writable, data: Vec<u8>, executable| {
+        ExpectedAccount::new(
+            key,
+            false, // is_signer
+            is_writable,
+            Rent::default().minimum_balance(data.len()),
+            data,
+            bpf_loader_upgradeable::ID,
+            executable,
+            u64::MAX, // rent_epoch: rent exempt
+        )
+    };
+
+    vec![
+        account(
+            UPGRADEABLE_PROGRAM_KEY,
+            false,
+            encode_upgradeable_loader_state(
+                &UpgradeableLoaderState::Program {
+                    programdata_address: programdata,
+                },
+                &[],
+            ),
+            true,
+        ),
+        account(
+            programdata,
+            true,
+            encode_upgradeable_loader_state(
+                &UpgradeableLoaderState::ProgramData {
+                    slot: PROGRAMDATA_SLOT,
+                    upgrade_authority_address: Some(UPGRADE_AUTHORITY),
+                },
+                &elf,
+            ),
+            false,
+        ),
+        account(
+            UPGRADEABLE_BUFFER_KEY,
+            true,
+            encode_upgradeable_loader_state(
+                &UpgradeableLoaderState::Buffer {
+                    authority_address: Some(UPGRADE_AUTHORITY),
+                },
+                &elf,
+            ),
+            false,
+        ),
+    ]
+}
+
+pub fn build_bpf_loader_upgradeable_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &bpf_loader_upgradeable_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_bpf_loader_upgradeable_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_bpf_loader_upgradeable_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("bpf_loader_upgradeable.bin");
+    let expected_accounts = bpf_loader_upgradeable_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: bpf_loader_upgradeable.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// Expected derivation of every `pda_accounts.bin` account, for the Zig
+/// test to check its own `findProgramAddress` against
+pub const PDA_EXPECTED_FILE: &str = "pda_expected.json";
+
+/// Seeds and program of one derived address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdaCase {
+    pub seeds: Vec<Vec<u8>>,
+    pub program_id: Pubkey,
+}
+
+/// One entry of `pda_expected.json`: seeds as hex, keys in base58
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PdaExpected {
+    pub seeds: Vec<String>,
+    pub program_id: String,
+    pub address: String,
+    pub bump: u8,
+}
+
+/// Seed combinations worth checking: a single string, a string and a key,
+/// mixed types including an integer, no seeds at all, and a seed at the
+/// 32-byte maximum, under the test program and SPL Token
+pub fn pda_cases() -> Vec<PdaCase> {
+    vec![
+        PdaCase {
+            seeds: vec![b"vault".to_vec()],
+            program_id: TEST_PROGRAM_ID,
+        },
+        PdaCase {
+            seeds: vec![b"metadata".to_vec(), vec![0x3D; 32]],
+            program_id: TEST_PROGRAM_ID,
+        },
+        PdaCase {
+            seeds: vec![
+                b"escrow".to_vec(),
+                vec![0xE1; 32],
+                42u64.to_le_bytes().to_vec(),
+            ],
+            program_id: TEST_PROGRAM_ID,
+        },
+        PdaCase {
+            seeds: vec![],
+            program_id: TEST_PROGRAM_ID,
+        },
+        PdaCase {
+            seeds: vec![vec![0xFF; 32], b"a".to_vec()],
+            program_id: SPL_TOKEN_PROGRAM_ID,
+        },
+    ]
+}
+
+/// Account data of a PDA fixture account: the bump, then the seeds
+///
+/// ```text
+/// bump u8 | seed count u8 | (seed len u8 | seed bytes)*
+/// ```
+fn encode_pda_seeds(seeds: &[Vec<u8>], bump: u8) -> Vec<u8> {
+    let mut data = vec![bump, seeds.len() as u8];
+    for seed in seeds {
+        data.push(seed.len() as u8);
+        data.extend_from_slice(seed);
+    }
+    data
+}
+
+/// One read-only account per case, keyed by the derived address and owned
+/// by the program it was derived under
+pub fn pda_accounts_scenario() -> Vec<ExpectedAccount> {
+    pda_cases()
+        .iter()
+        .map(|case| {
+            let seeds: Vec<&[u8]> = case.seeds.iter().map(Vec::as_slice).collect();
+            let (address, bump) = Pubkey::find_program_address(&seeds, &case.program_id);
+            ExpectedAccount::new(
+                address,
+                false, // is_signer
+                false, // is_writable
+                0,
+                encode_pda_seeds(&case.seeds, bump),
+                case.program_id,
+                false, // executable
+                0,     // rent_epoch
+            )
+        })
+        .collect()
+}
+
+pub fn build_pda_accounts_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &pda_accounts_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// The derivation of every case, as written to `pda_expected.json`
+pub fn pda_expected() -> Vec<PdaExpected> {
+    pda_cases()
+        .iter()
+        .map(|case| {
+            let seeds: Vec<&[u8]> = case.seeds.iter().map(Vec::as_slice).collect();
+            let (address, bump) = Pubkey::find_program_address(&seeds, &case.program_id);
+            PdaExpected {
+                seeds: case
+                    .seeds
+                    .iter()
+                    .map(|seed| seed.iter().map(|b| format!("{b:02x}")).collect())
+                    .collect(),
+                program_id: case.program_id.to_string(),
+                address: address.to_string(),
+                bump,
+            }
+        })
+        .collect()
+}
+
+fn generate_pda_accounts_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data: [u8; 0] = [];
+    let buffer = build_pda_accounts_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("pda_accounts.bin");
+    let expected_accounts = pda_accounts_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    let expected_path = test_data_dir.join(PDA_EXPECTED_FILE);
+    let json = serde_json::to_string_pretty(&pda_expected()).expect("PDA cases serialize");
+    write_if_changed(&expected_path, json + "\n")?;
+
+    println!(
+        "Generated: pda_accounts.bin ({} bytes, instruction data {}) and {}",
+        buffer.len(),
+        describe_instruction_data(&instruction_data),
+        PDA_EXPECTED_FILE
+    );
+
+    Ok(())
+}
+
+/// The SPL Token program, owner of every mint and token account
+pub const SPL_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Packed size of an SPL Token `Mint`
+pub const MINT_LEN: usize = 82;
+
+/// The fields of spl_token::state::Mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mint {
+    pub mint_authority: Option<Pubkey>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+/// Pack a mint the way spl_token's `Mint::pack` does:
+///
+/// ```text
+/// mint_authority (4 + 32) | supply u64 | decimals u8 | is_initialized u8
+///   | freeze_authority (4 + 32)
+/// ```
+fn encode_mint(mint: &Mint) -> Vec<u8> {
+    let mut data = Vec::with_capacity(MINT_LEN);
+    encode_coption(&mut data, mint.mint_authority.map(|key| key.to_bytes()));
+    data.extend_from_slice(&mint.supply.to_le_bytes());
+    data.push(mint.decimals);
+    data.push(mint.is_initialized as u8);
+    encode_coption(&mut data, mint.freeze_authority.map(|key| key.to_bytes()));
+    assert_eq!(data.len(), MINT_LEN);
+    data
+}
+
+/// Append an SPL `COption`: a u32 tag, 0 for None and 1 for Some, then
+/// the value, or zeros of the same size for None
+fn encode_coption<const N: usize>(data: &mut Vec<u8>, value: Option<[u8; N]>) {
+    data.extend_from_slice(&(value.is_some() as u32).to_le_bytes());
+    data.extend_from_slice(&value.unwrap_or([0; N]));
+}
+
+/// A single read-only, rent-exempt mint owned by the Token program
+pub fn spl_token_mint_scenario(mint: &Mint) -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x3D; 32]),
+        false, // is_signer
+        false, // is_writable
+        Rent::default().minimum_balance(MINT_LEN),
+        encode_mint(mint),
+        SPL_TOKEN_PROGRAM_ID,
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    )]
+}
+
+pub fn build_spl_token_mint_solana_format(
+    mint: &Mint,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &spl_token_mint_scenario(mint),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// The mint written to `spl_token_mint.bin`: 1,000 whole tokens of 6
+/// decimals, mint authority all 0xA1 and no freeze authority
+pub fn spl_token_mint() -> Mint {
+    Mint {
+        mint_authority: Some(Pubkey::new_from_array([0xA1; 32])),
+        supply: 1_000_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: None,
+    }
+}
+
+/// `spl_token_mint.bin` and `spl_token_mint_with_freeze_authority.bin`,
+/// the same mint with a freeze authority of all 0xF2
+fn generate_spl_token_mint_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        ("spl_token_mint.bin", spl_token_mint()),
+        (
+            "spl_token_mint_with_freeze_authority.bin",
+            Mint {
+                freeze_authority: Some(Pubkey::new_from_array([0xF2; 32])),
+                ..spl_token_mint()
+            },
+        ),
+    ];
+
+    for (file_name, mint) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_spl_token_mint_solana_format(&mint, &instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = spl_token_mint_scenario(&mint);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Packed size of an SPL Token `Account`
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Mint of wrapped SOL, the mint of every native token account
+pub const NATIVE_MINT_ID: Pubkey =
+    Pubkey::from_str_const("So11111111111111111111111111111111111111112");
+
+/// spl_token::state::AccountState
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccountState {
+    Uninitialized = 0,
+    Initialized = 1,
+    Frozen = 2,
+}
+
+/// The fields of spl_token::state::Account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub delegate: Option<Pubkey>,
+    pub state: AccountState,
+    /// Rent-exempt reserve of a wrapped SOL account, None otherwise
+    pub is_native: Option<u64>,
+    pub delegated_amount: u64,
+    pub close_authority: Option<Pubkey>,
+}
+
+/// Pack a token account the way spl_token's `Account::pack` does:
+///
+/// ```text
+/// mint (32) | owner (32) | amount u64 | delegate (4 + 32) | state u8
+///   | is_native (4 + 8) | delegated_amount u64 | close_authority (4 + 32)
+/// ```
+fn encode_token_account(account: &TokenAccount) -> Vec<u8> {
+    let mut data = Vec::with_capacity(TOKEN_ACCOUNT_LEN);
+    data.extend_from_slice(&account.mint.to_bytes());
+    data.extend_from_slice(&account.owner.to_bytes());
+    data.extend_from_slice(&account.amount.to_le_bytes());
+    encode_coption(&mut data, account.delegate.map(|key| key.to_bytes()));
+    data.push(account.state as u8);
+    encode_coption(&mut data, account.is_native.map(u64::to_le_bytes));
+    data.extend_from_slice(&account.delegated_amount.to_le_bytes());
+    encode_coption(&mut data, account.close_authority.map(|key| key.to_bytes()));
+    assert_eq!(data.len(), TOKEN_ACCOUNT_LEN);
+    data
+}
+
+/// A single writable token account owned by the Token program. Native
+/// accounts hold their rent-exempt reserve plus the wrapped amount.
+pub fn spl_token_account_scenario(account: &TokenAccount) -> Vec<ExpectedAccount> {
+    let rent_exempt_reserve = Rent::default().minimum_balance(TOKEN_ACCOUNT_LEN);
+    let lamports = match account.is_native {
+        Some(reserve) => reserve + account.amount,
+        None => rent_exempt_reserve,
+    };
+    vec![ExpectedAccount::new(
+        Pubkey::new_from_array([0x7C; 32]),
+        false, // is_signer
+        true,  // is_writable
+        lamports,
+        encode_token_account(account),
+        SPL_TOKEN_PROGRAM_ID,
+        false,    // executable
+        u64::MAX, // rent_epoch: rent exempt
+    )]
+}
+
+pub fn build_spl_token_account_solana_format(
+    account: &TokenAccount,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &spl_token_account_scenario(account),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// The account written to `spl_token_account.bin`: 500 tokens of the
+/// `spl_token_mint.bin` mint, owned by a wallet of all 0xB0
+pub fn spl_token_account() -> TokenAccount {
+    TokenAccount {
+        mint: spl_token_mint_scenario(&spl_token_mint())[0].key,
+        owner: Pubkey::new_from_array([0xB0; 32]),
+        amount: 500_000_000, // 500 tokens at 6 decimals
+        delegate: None,
+        state: AccountState::Initialized,
+        is_native: None,
+        delegated_amount: 0,
+        close_authority: None,
+    }
+}
+
+/// `spl_token_account.bin`, the same account frozen in
+/// `spl_token_account_frozen.bin`, and a wrapped SOL account holding 1 SOL
+/// in `spl_token_account_native.bin`
+fn generate_spl_token_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let variants = [
+        ("spl_token_account.bin", spl_token_account()),
+        (
+            "spl_token_account_frozen.bin",
+            TokenAccount {
+                state: AccountState::Frozen,
+                ..spl_token_account()
+            },
+        ),
+        (
+            "spl_token_account_native.bin",
+            TokenAccount {
+                mint: NATIVE_MINT_ID,
+                amount: 1_000_000_000,
+                is_native: Some(Rent::default().minimum_balance(TOKEN_ACCOUNT_LEN)),
+                ..spl_token_account()
+            },
+        ),
+    ];
+
+    for (file_name, account) in variants {
+        let instruction_data: [u8; 0] = [];
+        let buffer =
+            build_spl_token_account_solana_format(&account, &instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = spl_token_account_scenario(&account);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// The Associated Token Account program, whose PDAs are the canonical
+/// token account of each wallet and mint
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Derive the associated token account of `wallet` for `mint` and its bump
+/// seed, from the seeds `[wallet, token program, mint]`
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            wallet.as_ref(),
+            SPL_TOKEN_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
+
+/// `account` stored at the associated token address of its owner and mint
+pub fn associated_token_account_scenario(account: &TokenAccount) -> Vec<ExpectedAccount> {
+    let (address, _bump) = associated_token_address(&account.owner, &account.mint);
+    let mut accounts = spl_token_account_scenario(account);
+    accounts[0].key = address;
+    accounts
+}
+
+pub fn build_associated_token_account_solana_format(
+    account: &TokenAccount,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &associated_token_account_scenario(account),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// `ata_account.bin`: the `spl_token_account.bin` account at the address
+/// the ATA program derives for its wallet and mint
+fn generate_associated_token_account_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let file_name = "ata_account.bin";
+    let account = spl_token_account();
+    let instruction_data: [u8; 0] = [];
+    let buffer =
+        build_associated_token_account_solana_format(&account, &instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join(file_name);
+    let expected_accounts = associated_token_account_scenario(&account);
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
+
+    println!(
+        "Generated: {} ({} bytes, instruction data {})",
+        file_name,
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
+    );
+
+    Ok(())
+}
+
+/// A single account followed by the given instruction data
+pub fn instruction_data_scenario(program_id: &Pubkey) -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::default(),
+        true, // is_signer
+        true, // is_writable
+        1000,
+        vec![0xAA; 8],
+        *program_id,
+        false, // executable
+        0,     // rent_epoch
+    )]
+}
+
+pub fn build_instruction_data_solana_format(
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &instruction_data_scenario(program_id),
+        instruction_data,
+        program_id,
+    )
+}
+
+/// Empty, 1-byte and 1 KiB instruction data payloads. The 1 KiB payload is
+/// byte `i` = `i % 256`.
+fn generate_instruction_data_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let payloads: [(&str, Vec<u8>); 3] = [
+        ("solana_instruction_data_empty.bin", vec![]),
+        ("solana_instruction_data_1_byte.bin", vec![0x2A]),
+        (
+            "solana_instruction_data_1kb.bin",
+            (0..1024).map(|i| i as u8).collect(),
+        ),
+    ];
+
+    for (file_name, instruction_data) in payloads {
+        let buffer = build_instruction_data_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+        let file_path = test_data_dir.join(file_name);
+        let expected_accounts = instruction_data_scenario(&TEST_PROGRAM_ID);
+        write_fixture_with_sidecars(
+            &file_path,
+            &buffer,
+            SerializationFormat::Aligned,
+            &expected_accounts,
+            &instruction_data,
+            &TEST_PROGRAM_ID,
+        )?;
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&instruction_data)
+        );
+    }
+
+    Ok(())
+}
+
+/// Only here to check program_id extraction: one account with 7 bytes of
+/// data, so the epilogue sits behind a padded account, and 3 bytes of
+/// instruction data, so the program ID itself is not 8-byte aligned
+pub fn program_id_scenario() -> Vec<ExpectedAccount> {
+    vec![ExpectedAccount::new(
+        Pubkey::default(),
+        false, // is_signer
+        false, // is_writable
+        1000,
+        vec![0xEE; 7],
+        Pubkey::default(),
+        false, // executable
+        0,     // rent_epoch
+    )]
+}
+
+pub fn build_program_id_solana_format(instruction_data: &[u8], program_id: &Pubkey) -> Vec<u8> {
+    build_from_accounts(
+        SerializationFormat::Aligned,
+        &program_id_scenario(),
+        instruction_data,
+        program_id,
+    )
+}
+
+fn generate_program_id_solana_format(
+    test_data_dir: &Path,
+    _rng: &mut FixtureRng,
+) -> GenerateResult<()> {
+    let instruction_data = [0x01, 0x02, 0x03];
+    let buffer = build_program_id_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+    let file_path = test_data_dir.join("solana_program_id.bin");
+    let expected_accounts = program_id_scenario();
+    write_fixture_with_sidecars(
+        &file_path,
+        &buffer,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &instruction_data,
+        &TEST_PROGRAM_ID,
+    )?;
 
     println!(
-        "Generated: empty_data_accounts.bin ({} bytes)",
-        buffer.len()
+        "Generated: solana_program_id.bin ({} bytes, instruction data {})",
+        buffer.len(),
+        describe_instruction_data(&instruction_data)
     );
+
+    Ok(())
 }
 
-fn generate_accounts_with_duplicates_solana_format(test_data_dir: &Path) {
-    let mut buffer = Vec::new();
+/// Serialize `accounts` into a complete entrypoint buffer in `format`,
+/// followed by the instruction data and program ID
+pub fn build_from_accounts(
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) -> Vec<u8> {
+    let mut builder = EntrypointBufferBuilder::with_format(format);
+    for account in accounts {
+        match account.dup_of {
+            Some(index) => builder.add_duplicate(index),
+            None => builder.add_account(&AccountBuilder::from(account)),
+        };
+    }
+    builder.finish(program_id, instruction_data)
+}
 
-    // Number of accounts (including duplicates)
-    buffer.push(5u8);
+/// Destination for serialized fields. A `Vec<u8>` only collects the bytes;
+/// other sinks can also record which field each byte belongs to.
+pub trait FieldSink {
+    /// Append the bytes of the field called `name`
+    fn field(&mut self, name: &'static str, bytes: &[u8]);
 
-    // Store account data for duplicates
-    let key1 = Pubkey::default();
-    let mut lamports1 = 1000u64;
-    let mut data1 = vec![0xAA; 8];
-    let owner1 = Pubkey::default();
+    /// Name the entry the following fields belong to, e.g. `account[1]`,
+    /// or `None` for the top-level fields around the accounts
+    fn set_entry(&mut self, _entry: Option<String>) {}
+}
 
-    // Account 0: Original
-    serialize_account_solana_format(
-        &mut buffer,
-        &key1,
-        true, // is_signer
-        true, // is_writable
-        &mut lamports1,
-        &mut data1,
-        &owner1,
-        false, // executable
-        true,  // is_non_dup
-        0,
-    );
+impl FieldSink for Vec<u8> {
+    fn field(&mut self, _name: &'static str, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
 
-    // Account 1: Original
-    let mut key2_bytes = [0u8; 32];
-    key2_bytes[0] = 1;
-    let key2 = Pubkey::new_from_array(key2_bytes);
-    let mut lamports2 = 2000u64;
-    let mut data2 = vec![0xBB; 12];
-    let owner2 = Pubkey::default();
+/// `build_from_accounts`, writing into any `FieldSink`
+pub fn write_from_accounts<S: FieldSink>(
+    buffer: &mut S,
+    format: SerializationFormat,
+    accounts: &[ExpectedAccount],
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) {
+    // Number of accounts
+    write_account_count(buffer, accounts.len());
 
-    serialize_account_solana_format(
-        &mut buffer,
-        &key2,
-        false, // is_signer
-        true,  // is_writable
-        &mut lamports2,
-        &mut data2,
-        &owner2,
-        true, // executable
-        true, // is_non_dup
-        0,
-    );
+    for (i, account) in accounts.iter().enumerate() {
+        buffer.set_entry(Some(format!("account[{i}]")));
+        match (account.dup_of, format) {
+            (Some(index), SerializationFormat::Aligned) => {
+                serialize_duplicate_account(buffer, index)
+            }
+            // The deprecated format has no padding after the index
+            (Some(index), SerializationFormat::Deprecated) => buffer.field("dup_marker", &[index]),
+            (None, _) => AccountBuilder::from(account).build_into_format(format, buffer),
+        }
+    }
 
-    // Account 2: Duplicate of account 0
-    buffer.push(0x00); // Duplicate marker pointing to index 0
+    buffer.set_entry(None);
+    append_instruction_epilogue(buffer, instruction_data, program_id);
+}
 
-    // Account 3: Original
-    let mut key3_bytes = [0u8; 32];
-    key3_bytes[0] = 3;
-    let key3 = Pubkey::new_from_array(key3_bytes);
-    let mut lamports3 = 3000u64;
-    let mut data3 = vec![0xCC; 6];
-    let owner3 = Pubkey::default();
-
-    serialize_account_solana_format(
-        &mut buffer,
-        &key3,
-        true,  // is_signer
+/// A key that is all zeros except for the first byte
+fn key_with_first_byte(first_byte: u8) -> Pubkey {
+    let mut key_bytes = [0u8; 32];
+    key_bytes[0] = first_byte;
+    Pubkey::new_from_array(key_bytes)
+}
+
+/// A read-only account owned by the sysvar program
+fn sysvar_account(key: Pubkey, lamports: u64, data: Vec<u8>) -> ExpectedAccount {
+    ExpectedAccount::new(
+        key,
+        false, // is_signer
         false, // is_writable
-        &mut lamports3,
-        &mut data3,
-        &owner3,
+        lamports,
+        data,
+        sysvar::ID,
         false, // executable
-        true,  // is_non_dup
-        0,
-    );
+        0,     // rent_epoch
+    )
+}
 
-    // Account 4: Duplicate of account 1
-    buffer.push(0x01); // Duplicate marker pointing to index 1
+/// Describe instruction data for the generation summary: the length and the
+/// bytes themselves, truncated after the first 16
+pub fn describe_instruction_data(instruction_data: &[u8]) -> String {
+    match instruction_data.len() {
+        0 => "empty".to_string(),
+        len if len <= 16 => format!("len {len} {instruction_data:02x?}"),
+        len => format!("len {len} {:02x?}...", &instruction_data[..16]),
+    }
+}
 
-    let file_path = test_data_dir.join("solana_accounts_with_duplicates.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&buffer).expect("Failed to write data");
+/// Write the number of accounts as a u64 little-endian, like the runtime does
+pub fn write_account_count<S: FieldSink>(buffer: &mut S, num_accounts: usize) {
+    buffer.field("num_accounts", &(num_accounts as u64).to_le_bytes());
+}
 
-    println!(
-        "Generated: solana_accounts_with_duplicates.bin ({} bytes)",
-        buffer.len()
+/// Append the instruction data length (u64 little-endian), the instruction
+/// data and the program ID that follow the last account
+pub fn append_instruction_epilogue<S: FieldSink>(
+    buffer: &mut S,
+    instruction_data: &[u8],
+    program_id: &Pubkey,
+) {
+    buffer.field(
+        "instruction_data_len",
+        &(instruction_data.len() as u64).to_le_bytes(),
     );
+    buffer.field("instruction_data", instruction_data);
+    buffer.field("program_id", &program_id.to_bytes());
 }
 
-fn generate_complex_iteration_solana_format(test_data_dir: &Path) {
-    let mut buffer = Vec::new();
-
-    // Number of accounts
-    buffer.push(10u8);
-
-    // Generate accounts with various patterns
-    for i in 0..10u8 {
-        if i == 4 {
-            // Duplicate of account 1
-            buffer.push(0x01);
-        } else if i == 7 {
-            // Duplicate of account 2
-            buffer.push(0x02);
-        } else {
-            // Original account
-            let mut key_bytes = [0u8; 32];
-            key_bytes[0] = i;
-            let key = Pubkey::new_from_array(key_bytes);
-            let mut lamports = (i as u64 + 1) * 500;
-            let data_len = ((i % 4) + 1) * 3;
-            let mut data = vec![0xA0 + i; data_len as usize];
-            let owner = Pubkey::default();
-
-            serialize_account_solana_format(
-                &mut buffer,
-                &key,
-                i % 2 == 0, // is_signer
-                i % 3 != 0, // is_writable
-                &mut lamports,
-                &mut data,
-                &owner,
-                i % 5 == 0, // executable
-                true,       // is_non_dup
-                0,
-            );
-        }
-    }
-
-    let file_path = test_data_dir.join("solana_complex_iteration.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&buffer).expect("Failed to write data");
+/// Reserve MAX_PERMITTED_DATA_INCREASE zeroed bytes after an account's data
+fn write_realloc_space<S: FieldSink>(buffer: &mut S) {
+    buffer.field("realloc_space", &[0; MAX_PERMITTED_DATA_INCREASE]);
+}
 
-    println!(
-        "Generated: solana_complex_iteration.bin ({} bytes)",
-        buffer.len()
-    );
+/// Pad the buffer with `(8 - (data_len % 8)) % 8` zero bytes after an
+/// account's data so the following entry starts on an 8-byte boundary
+fn align_to_8_bytes<S: FieldSink>(buffer: &mut S, data_len: usize) {
+    let padding = (8 - (data_len % 8)) % 8;
+    buffer.field("alignment_padding", &[0; 7][..padding]);
 }
 
 /// Serialize account in the exact format used by Solana runtime
 /// Based on solana/programs/bpf_loader/src/serialization.rs
-fn serialize_account_solana_format(
-    buffer: &mut Vec<u8>,
+#[allow(clippy::too_many_arguments)]
+pub fn serialize_account_solana_format<S: FieldSink>(
+    buffer: &mut S,
     key: &Pubkey,
     is_signer: bool,
     is_writable: bool,
     lamports: &mut u64,
-    data: &mut Vec<u8>,
+    data: &[u8],
     owner: &Pubkey,
     executable: bool,
-    is_non_dup: bool,
-    dup_index: u8,
+    rent_epoch: u64,
 ) {
-    if is_non_dup {
-        // Non-duplicate marker
-        buffer.push(0xFF);
+    // Serialize as packed struct matching what Solana runtime creates
+    // This is the 88-byte structure we're targeting
+
+    // duplicate_index doubles as the non-duplicate marker (always 0xFF)
+    buffer.field("dup_marker", &[0xFF]);
+
+    // Flags
+    buffer.field("is_signer", &[is_signer as u8]);
+    buffer.field("is_writable", &[is_writable as u8]);
+    buffer.field("executable", &[executable as u8]);
+
+    // original_data_len: the loader writes zeros, and the program's
+    // entrypoint stores data_len here on entry
+    buffer.field("original_data_len", &[0; 4]);
 
-        // Serialize as packed struct matching what Solana runtime creates
-        // This is the 88-byte structure we're targeting
+    // key (32 bytes)
+    buffer.field("key", &key.to_bytes());
 
-        // duplicate_index (always 0xFF for non-dup)
-        buffer.push(0xFF);
+    // owner (32 bytes)
+    buffer.field("owner", &owner.to_bytes());
 
-        // Flags
-        buffer.push(is_signer as u8);
-        buffer.push(is_writable as u8);
-        buffer.push(executable as u8);
+    // lamports (8 bytes, little-endian)
+    buffer.field("lamports", &lamports.to_le_bytes());
 
-        // original_data_len (4 bytes, little-endian)
-        let original_len = data.len() as u32;
-        buffer.extend_from_slice(&original_len.to_le_bytes());
+    // data_len (8 bytes, little-endian)
+    let data_len = data.len() as u64;
+    buffer.field("data_len", &data_len.to_le_bytes());
 
-        // key (32 bytes)
-        buffer.extend_from_slice(&key.to_bytes());
+    // Actual data bytes
+    buffer.field("data", data);
 
-        // owner (32 bytes)
-        buffer.extend_from_slice(&owner.to_bytes());
+    // Reserved realloc space
+    write_realloc_space(buffer);
 
-        // lamports (8 bytes, little-endian)
-        buffer.extend_from_slice(&lamports.to_le_bytes());
+    // Pad the data so the next account header starts 8-byte aligned
+    align_to_8_bytes(buffer, data.len());
 
-        // data_len (8 bytes, little-endian)
-        let data_len = data.len() as u64;
-        buffer.extend_from_slice(&data_len.to_le_bytes());
+    // rent_epoch (8 bytes, little-endian)
+    buffer.field("rent_epoch", &rent_epoch.to_le_bytes());
+}
 
-        // Actual data bytes
-        buffer.extend_from_slice(data);
-    } else {
-        // For duplicates, just the index
-        buffer.push(dup_index);
+/// Serialize one non-duplicate account entry in the given format
+#[allow(clippy::too_many_arguments)]
+pub fn serialize_account<S: FieldSink>(
+    format: SerializationFormat,
+    buffer: &mut S,
+    key: &Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: &mut u64,
+    data: &[u8],
+    owner: &Pubkey,
+    executable: bool,
+    rent_epoch: u64,
+) {
+    match format {
+        SerializationFormat::Aligned => serialize_account_solana_format(
+            buffer,
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            rent_epoch,
+        ),
+        SerializationFormat::Deprecated => serialize_account_deprecated_format(
+            buffer,
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            rent_epoch,
+        ),
     }
 }
 
+/// Serialize account in the unaligned format bpf_loader_deprecated uses
+/// Based on serialize_parameters_unaligned in
+/// solana/programs/bpf_loader/src/serialization.rs
+///
+/// Layout, with no padding anywhere (92 bytes plus the data):
+/// 0xFF marker, is_signer, is_writable, key (32), lamports (8),
+/// data_len (8), data, owner (32), executable, rent_epoch (8)
+#[allow(clippy::too_many_arguments)]
+pub fn serialize_account_deprecated_format<S: FieldSink>(
+    buffer: &mut S,
+    key: &Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: &mut u64,
+    data: &[u8],
+    owner: &Pubkey,
+    executable: bool,
+    rent_epoch: u64,
+) {
+    // Non-duplicate marker
+    buffer.field("dup_marker", &[0xFF]);
+
+    buffer.field("is_signer", &[is_signer as u8]);
+    buffer.field("is_writable", &[is_writable as u8]);
+    buffer.field("key", &key.to_bytes());
+    buffer.field("lamports", &lamports.to_le_bytes());
+    buffer.field("data_len", &(data.len() as u64).to_le_bytes());
+
+    // Data is copied inline, the account cannot grow in this format
+    buffer.field("data", data);
+
+    buffer.field("owner", &owner.to_bytes());
+    buffer.field("executable", &[executable as u8]);
+    buffer.field("rent_epoch", &rent_epoch.to_le_bytes());
+}
+
+/// Serialize a duplicate account entry: the index of the original account
+/// followed by 7 bytes of padding, keeping the next entry 8-byte aligned
+pub fn serialize_duplicate_account<S: FieldSink>(buffer: &mut S, dup_index: u8) {
+    buffer.field("dup_marker", &[dup_index]);
+    buffer.field("dup_padding", &[0u8; 7]);
+}
+
 /// Create a test that mimics actual Solana runtime behavior
-pub fn test_with_actual_account_info() {
+pub fn test_with_actual_account_info(
+    test_data_dir: &Path,
+    rng: &mut FixtureRng,
+) -> GenerateResult<()> {
     println!("\n=== Testing with actual AccountInfo structures ===");
 
     // Create AccountInfo instances like a real Solana program would
     let key1 = Pubkey::default();
-    let key2 = Pubkey::new_unique();
+    let key2 = deterministic_pubkey(rng);
     let owner = Pubkey::default();
 
     let mut lamports1 = 1000u64;
@@ -434,67 +3421,930 @@ pub fn test_with_actual_account_info() {
     println!("  executable: {}", account2.executable);
 
     // Create a buffer simulating what the runtime would pass to a program
-    let mut runtime_buffer = Vec::new();
-
-    // Number of accounts
-    runtime_buffer.push(2u8);
-
-    // Serialize first account
-    serialize_account_info_as_runtime(&account1, &mut runtime_buffer, true);
-
-    // Serialize second account
-    serialize_account_info_as_runtime(&account2, &mut runtime_buffer, true);
+    let mut builder = EntrypointBufferBuilder::new();
+    builder
+        .add_account(&AccountBuilder::from(&account1))
+        .add_account(&AccountBuilder::from(&account2));
+    let runtime_buffer = builder.finish(&TEST_PROGRAM_ID, &[0x01]);
 
     // Save to file
-    let test_data_dir = Path::new("../test_data");
     let file_path = test_data_dir.join("solana_actual_accountinfo.bin");
-    let mut file = File::create(&file_path).expect("Failed to create file");
-    file.write_all(&runtime_buffer)
-        .expect("Failed to write data");
+    write_fixture(&file_path, &runtime_buffer)?;
+
+    let expected_accounts: Vec<ExpectedAccount> = [&account1, &account2]
+        .into_iter()
+        .map(|account| {
+            ExpectedAccount::new(
+                *account.key,
+                account.is_signer,
+                account.is_writable,
+                account.lamports(),
+                account.data.borrow().to_vec(),
+                *account.owner,
+                account.executable,
+                account.rent_epoch,
+            )
+        })
+        .collect();
+    write_sidecars(
+        &file_path,
+        SerializationFormat::Aligned,
+        &expected_accounts,
+        &[0x01],
+        &TEST_PROGRAM_ID,
+    )?;
 
     println!(
         "\nGenerated: solana_actual_accountinfo.bin ({} bytes)",
         runtime_buffer.len()
     );
+
+    Ok(())
 }
 
-/// Serialize an AccountInfo as the runtime would
-fn serialize_account_info_as_runtime(
-    account: &AccountInfo,
-    buffer: &mut Vec<u8>,
-    is_non_dup: bool,
-) {
-    if is_non_dup {
-        // Non-duplicate marker
-        buffer.push(0xFF);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deserialize::deserialize_account_buffer;
+
+    /// Size of a serialized non-duplicate account header
+    const ACCOUNT_HEADER_LEN: usize = 88;
+
+    fn read_account_count(buffer: &[u8]) -> u64 {
+        u64::from_le_bytes(buffer[..8].try_into().unwrap())
+    }
+
+    fn read_u64(buffer: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(buffer[offset..offset + 8].try_into().unwrap())
+    }
+
+    /// Check the instruction data and program ID epilogue starting at
+    /// `offset` runs to the end of the buffer
+    fn assert_instruction_epilogue(buffer: &[u8], offset: usize) {
+        let data_len = read_u64(buffer, offset) as usize;
+        let program_id = offset + 8 + data_len;
+        assert_eq!(&buffer[program_id..], TEST_PROGRAM_ID.as_ref());
+    }
+
+    /// Walk the serialized accounts and collect the rent_epoch of every
+    /// non-duplicate account, in order
+    fn read_rent_epochs(buffer: &[u8]) -> Vec<u64> {
+        let mut rent_epochs = Vec::new();
+        let mut offset = 8;
+        for _ in 0..read_account_count(buffer) {
+            if buffer[offset] != 0xFF {
+                offset += 8;
+                continue;
+            }
+            let data_len = read_u64(buffer, offset + ACCOUNT_HEADER_LEN - 8) as usize;
+            let padding = (8 - data_len % 8) % 8;
+            let rent_epoch_offset =
+                offset + ACCOUNT_HEADER_LEN + data_len + MAX_PERMITTED_DATA_INCREASE + padding;
+            rent_epochs.push(read_u64(buffer, rent_epoch_offset));
+            offset = rent_epoch_offset + 8;
+        }
+        assert_instruction_epilogue(buffer, offset);
+        rent_epochs
+    }
+
+    #[test]
+    fn account_count_is_u64_le() {
+        let cases: [(&str, Vec<u8>, u64); 7] = [
+            (
+                "single_account",
+                build_single_account_solana_format(&[], &TEST_PROGRAM_ID),
+                1,
+            ),
+            (
+                "multiple_accounts",
+                build_multiple_accounts_solana_format(&[], &TEST_PROGRAM_ID),
+                3,
+            ),
+            (
+                "empty_data_accounts",
+                build_empty_data_accounts_solana_format(&[], &TEST_PROGRAM_ID),
+                2,
+            ),
+            (
+                "zero_vs_unallocated",
+                build_zero_vs_unallocated_solana_format(&[], &TEST_PROGRAM_ID),
+                2,
+            ),
+            (
+                "accounts_with_duplicates",
+                build_accounts_with_duplicates_solana_format(&[], &TEST_PROGRAM_ID),
+                5,
+            ),
+            (
+                "self_dup_account",
+                build_self_dup_account_solana_format(&[], &TEST_PROGRAM_ID),
+                3,
+            ),
+            (
+                "complex_iteration",
+                build_complex_iteration_solana_format(&[], &TEST_PROGRAM_ID),
+                10,
+            ),
+        ];
+
+        for (name, buffer, expected) in cases {
+            assert_eq!(read_account_count(&buffer), expected, "{name}");
+            // First entry after the count must be an account marker
+            assert_eq!(buffer[8], 0xFF, "{name}");
+        }
+    }
+
+    #[test]
+    fn align_to_8_bytes_pads_to_boundary() {
+        for data_len in 0..=16 {
+            let mut buffer = vec![0u8; data_len];
+            align_to_8_bytes(&mut buffer, data_len);
+            assert_eq!(buffer.len() % 8, 0, "data_len {data_len}");
+            assert!(buffer.len() - data_len < 8, "data_len {data_len}");
+        }
+    }
+
+    #[test]
+    fn five_byte_data_gets_three_padding_bytes() {
+        let instruction_data = [0xAA; 5];
+        let buffer = build_alignment_padding_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+        let data_start = 8 + ACCOUNT_HEADER_LEN;
+        let padding_start = data_start + 5 + MAX_PERMITTED_DATA_INCREASE;
+        assert_eq!(&buffer[data_start..data_start + 5], &[0xAA; 5]);
+        assert_eq!(&buffer[padding_start..padding_start + 3], &[0u8; 3]);
+
+        // The second account header starts right after the padding and rent_epoch
+        let next_account = padding_start + 3 + 8;
+        assert_eq!(next_account % 8, 0);
+        assert_eq!(buffer[next_account], 0xFF);
+        assert_eq!(buffer[next_account + 8], 1); // key[0]
+        assert_instruction_epilogue(
+            &buffer,
+            next_account + ACCOUNT_HEADER_LEN + 8 + MAX_PERMITTED_DATA_INCREASE + 8,
+        );
+    }
+
+    #[test]
+    fn realloc_space_fits_growth_from_8_to_18_bytes() {
+        let instruction_data = 18u64.to_le_bytes();
+        let buffer = build_realloc_space_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+        // Account 0: 8 bytes of data followed by the zeroed reserve
+        let data_start = 8 + ACCOUNT_HEADER_LEN;
+        let data_len = u64::from_le_bytes(buffer[data_start - 8..data_start].try_into().unwrap());
+        assert_eq!(data_len, 8);
+        let reserve = &buffer[data_start + 8..data_start + 8 + MAX_PERMITTED_DATA_INCREASE];
+        assert!(reserve.iter().all(|&b| b == 0));
+
+        // Growing to 18 bytes stays inside account 0's region
+        let next_account = data_start + 8 + MAX_PERMITTED_DATA_INCREASE + 8;
+        assert!(data_start + 18 <= next_account);
+        assert_eq!(buffer[next_account], 0xFF);
+
+        // Account 1 is the grown account
+        let data_start = next_account + ACCOUNT_HEADER_LEN;
+        let data_len = u64::from_le_bytes(buffer[data_start - 8..data_start].try_into().unwrap());
+        assert_eq!(data_len, 18);
+        assert_eq!(&buffer[data_start..data_start + 18], &[0xAA; 18]);
+    }
+
+    #[test]
+    fn rent_epoch_follows_padding_for_every_account() {
+        assert_eq!(
+            read_rent_epochs(&build_multiple_accounts_solana_format(
+                &[],
+                &TEST_PROGRAM_ID
+            )),
+            [0, 361, u64::MAX]
+        );
+        assert_eq!(
+            read_rent_epochs(&build_accounts_with_duplicates_solana_format(
+                &[],
+                &TEST_PROGRAM_ID
+            )),
+            [0, 0, u64::MAX]
+        );
 
-        // duplicate_index
-        buffer.push(0xFF);
+        let expected: Vec<u64> = (0..10u64)
+            .filter(|i| *i != 4 && *i != 7)
+            .map(|i| i * 100)
+            .collect();
+        assert_eq!(
+            read_rent_epochs(&build_complex_iteration_solana_format(
+                &[],
+                &TEST_PROGRAM_ID
+            )),
+            expected
+        );
+    }
+
+    #[test]
+    fn realloc_region_is_zeroed_and_next_header_follows() {
+        let instruction_data = [0x01, 0x02, 0x03];
+        let buffer = build_multiple_accounts_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+        let mut offset = 8;
+        for i in 0..read_account_count(&buffer) {
+            assert_eq!(buffer[offset], 0xFF, "account {i}");
+            let data_len = read_u64(&buffer, offset + ACCOUNT_HEADER_LEN - 8) as usize;
+            let data_offset = offset + ACCOUNT_HEADER_LEN;
+
+            let region = &buffer
+                [data_offset + data_len..data_offset + data_len + MAX_PERMITTED_DATA_INCREASE];
+            assert!(region.iter().all(|&b| b == 0), "account {i}");
+
+            // Next header starts after the reserve, the alignment pad and rent_epoch
+            let padding = (8 - data_len % 8) % 8;
+            offset = data_offset + data_len + MAX_PERMITTED_DATA_INCREASE + padding + 8;
+            assert_eq!(offset % 8, 0, "account {i}");
+        }
+        assert_instruction_epilogue(&buffer, offset);
+    }
+
+    #[test]
+    fn full_entrypoint_ends_with_instruction_data_and_program_id() {
+        // Instruction 2 with a u64 amount
+        let mut instruction_data = vec![0x02];
+        instruction_data.extend_from_slice(&1_000u64.to_le_bytes());
+        let buffer = build_full_entrypoint_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+        assert_eq!(read_rent_epochs(&buffer), [u64::MAX; 3]);
+
+        let program_id_offset = buffer.len() - 32;
+        assert_eq!(&buffer[program_id_offset..], TEST_PROGRAM_ID.as_ref());
+
+        let data_offset = program_id_offset - 9;
+        assert_eq!(read_u64(&buffer, data_offset - 8), 9);
+        assert_eq!(buffer[data_offset], 0x02);
+        assert_eq!(read_u64(&buffer, data_offset + 1), 1_000);
+    }
+
+    #[test]
+    fn max_accounts_reads_back_64_accounts_with_duplicates() {
+        let buffer =
+            build_max_accounts_solana_format(&MAX_ACCOUNTS_INSTRUCTION_DATA, &TEST_PROGRAM_ID);
+        assert_eq!(read_account_count(&buffer), 64);
+
+        let parsed = deserialize_account_buffer(&buffer).unwrap();
+        assert_eq!(parsed.len(), MAX_TX_ACCOUNT_LOCKS);
+        for (i, account) in parsed.iter().enumerate() {
+            let original = match MAX_ACCOUNTS_DUPLICATES.iter().find(|(at, _)| *at == i) {
+                Some(&(_, original)) => original as usize,
+                None => i,
+            };
+            assert_eq!(account.is_dup, original != i, "account {i}");
+            assert_eq!(account.key.as_ref()[0..2], (original as u16).to_le_bytes());
+            assert_eq!(account.is_signer, original.is_multiple_of(3), "account {i}");
+            assert_eq!(account.is_writable, original % 2 == 1, "account {i}");
+            assert_eq!(
+                account.data,
+                vec![original as u8; MAX_ACCOUNTS_DATA_LENS[original % 6]]
+            );
+        }
+
+        let lens: std::collections::BTreeSet<usize> =
+            parsed.iter().map(|account| account.data.len()).collect();
+        assert_eq!(lens.into_iter().collect::<Vec<_>>(), MAX_ACCOUNTS_DATA_LENS);
+    }
+
+    #[test]
+    fn max_accounts_overflow_has_one_account_past_the_limit() {
+        let buffer = build_max_accounts_overflow_solana_format(
+            &MAX_ACCOUNTS_INSTRUCTION_DATA,
+            &TEST_PROGRAM_ID,
+        );
+        assert_eq!(read_account_count(&buffer), 65);
+
+        let parsed = deserialize_account_buffer(&buffer).unwrap();
+        assert_eq!(parsed.len(), MAX_TX_ACCOUNT_LOCKS + 1);
+        let last = &parsed[MAX_TX_ACCOUNT_LOCKS];
+        assert!(!last.is_dup);
+        assert_eq!(last.key.as_ref()[0..2], 64u16.to_le_bytes());
+    }
+
+    #[test]
+    fn lamports_edges_are_little_endian_at_the_first_account() {
+        let max = build_lamports_edge_solana_format(u64::MAX, &[], &TEST_PROGRAM_ID);
+        assert_eq!(
+            max[FIRST_ACCOUNT_LAMPORTS_OFFSET..][..8],
+            [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(
+            deserialize_account_buffer(&max).unwrap()[0].lamports,
+            u64::MAX
+        );
+
+        let zero = build_lamports_edge_solana_format(0, &[], &TEST_PROGRAM_ID);
+        assert_eq!(zero[FIRST_ACCOUNT_LAMPORTS_OFFSET..][..8], [0; 8]);
+        assert_eq!(deserialize_account_buffer(&zero).unwrap()[0].lamports, 0);
+    }
+
+    #[test]
+    fn self_dup_account_points_both_duplicates_at_account_0() {
+        let buffer = build_self_dup_account_solana_format(&[], &TEST_PROGRAM_ID);
+        let original_len = ACCOUNT_HEADER_LEN + 8 + MAX_PERMITTED_DATA_INCREASE + 8; // data, rent_epoch
+        let first_dup = 8 + original_len;
+        assert_eq!(buffer[first_dup..][..8], [0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(buffer[first_dup + 8..][..8], [0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let accounts = deserialize_account_buffer(&buffer).unwrap();
+        assert_eq!(accounts.len(), 3);
+        assert!(!accounts[0].is_dup);
+        for duplicate in &accounts[1..] {
+            assert!(duplicate.is_dup);
+            assert_eq!(duplicate.key, accounts[0].key);
+            assert_eq!(duplicate.data, [0xDD; 8]);
+        }
+    }
+
+    #[test]
+    fn degenerate_inputs_write_a_zero_count_and_empty_instruction_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(DEFAULT_SEED);
+        generate_degenerate_inputs_solana_format(dir.path(), &mut rng).unwrap();
+
+        let no_accounts = dir.path().join("solana_no_accounts.bin");
+        let bytes = std::fs::read(&no_accounts).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 48 + 32);
+        assert_eq!(read_account_count(&bytes), 0);
+        let parsed = deserialize_input(&bytes).unwrap();
+        assert!(parsed.accounts.is_empty());
+        assert_eq!(parsed.instruction_data, no_accounts_instruction_data());
+        let manifest = crate::manifest::read_manifest(&no_accounts).unwrap();
+        assert_eq!(manifest.account_count, 0);
+        assert!(manifest.accounts.is_empty());
+
+        let empty_data = dir.path().join("solana_empty_instruction_data.bin");
+        let bytes = std::fs::read(&empty_data).unwrap();
+        let parsed = deserialize_input(&bytes).unwrap();
+        assert_eq!(parsed.accounts.len(), 1);
+        assert!(parsed.instruction_data.is_empty());
+        assert_eq!(
+            crate::manifest::read_manifest(&empty_data)
+                .unwrap()
+                .instruction_data,
+            ""
+        );
+    }
+
+    #[test]
+    fn flags_matrix_covers_every_flag_combination_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(DEFAULT_SEED);
+        generate_flags_matrix_solana_format(dir.path(), &mut rng).unwrap();
+
+        let bytes = std::fs::read(dir.path().join("flags_matrix.bin")).unwrap();
+        let parsed = deserialize_input(&bytes).unwrap();
+        assert_eq!(parsed.accounts.len(), 8);
+        let mut seen = [false; 8];
+        for account in &parsed.accounts {
+            let combination = account.key.to_bytes()[0];
+            assert_eq!(account.is_signer, combination & FLAG_IS_SIGNER != 0);
+            assert_eq!(account.is_writable, combination & FLAG_IS_WRITABLE != 0);
+            assert_eq!(account.executable, combination & FLAG_EXECUTABLE != 0);
+            assert_eq!(account.data.len(), combination as usize + 1);
+            assert!(!std::mem::replace(&mut seen[combination as usize], true));
+        }
+    }
+
+    #[test]
+    fn edge_values_are_little_endian_in_every_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(DEFAULT_SEED);
+        generate_edge_values_solana_format(dir.path(), &mut rng).unwrap();
+
+        let path = dir.path().join("edge_values.bin");
+        let bytes = std::fs::read(&path).unwrap();
+        // Each entry is the header, data, realloc space, padding and rent_epoch
+        let second = FIRST_ACCOUNT_LAMPORTS_OFFSET
+            + ACCOUNT_HEADER_LEN
+            + (5 + MAX_PERMITTED_DATA_INCREASE + 3)
+            + 8;
+        assert_eq!(bytes[second..][..8], [0xFF; 8]);
+        let third = second + ACCOUNT_HEADER_LEN + (8 + MAX_PERMITTED_DATA_INCREASE) + 8;
+        assert_eq!(bytes[third..][..8], [8, 7, 6, 5, 4, 3, 2, 1]);
+
+        let parsed = deserialize_input(&bytes).unwrap();
+        let data: Vec<u8> = parsed
+            .accounts
+            .iter()
+            .flat_map(|a| a.data.clone())
+            .collect();
+        assert_eq!(data, (1..=16).collect::<Vec<u8>>());
+        assert_eq!(parsed.accounts[0].rent_epoch, u64::MAX);
+
+        let manifest = crate::manifest::read_manifest(&path).unwrap();
+        let lamports: Vec<u64> = manifest.accounts.iter().map(|a| a.lamports).collect();
+        assert_eq!(lamports, [0, u64::MAX, EDGE_VALUES_LAMPORTS]);
+        let rent_epochs: Vec<u64> = manifest.accounts.iter().map(|a| a.rent_epoch).collect();
+        assert_eq!(
+            rent_epochs,
+            [u64::MAX, 0x8000_0000_0000_0001, 0x0807_0605_0403_0201]
+        );
+    }
+
+    #[test]
+    fn permission_requirements_contradict_the_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(DEFAULT_SEED);
+        generate_permission_mismatch_solana_format(dir.path(), &mut rng).unwrap();
+
+        let bytes = std::fs::read(dir.path().join("permission_mismatch.bin")).unwrap();
+        let parsed = deserialize_input(&bytes).unwrap();
+        let json = std::fs::read_to_string(dir.path().join(PERMISSION_REQUIREMENTS_FILE)).unwrap();
+        let expected: Vec<PermissionRequirement> = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected, permission_requirements());
+        for requirement in &expected {
+            let account = &parsed.accounts[requirement.account_index];
+            assert_eq!(account.is_writable, requirement.is_writable);
+            assert_ne!(account.is_writable, requirement.expects_writable);
+        }
+        assert_eq!(parsed.instruction_data, PERMISSION_MISMATCH_INSTRUCTION);
+    }
+
+    #[test]
+    fn max_data_account_writes_the_same_bytes_as_build_from_accounts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = FixtureRng::seed_from_u64(DEFAULT_SEED);
+        generate_max_data_account(dir.path(), &mut rng).unwrap();
+
+        let path = dir.path().join("solana_max_data_account.bin");
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(
+            bytes[FIRST_ACCOUNT_DATA_LEN_OFFSET..][..8],
+            [0x00, 0x00, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        let accounts = max_data_account_scenario(FixtureRng::seed_from_u64(DEFAULT_SEED).gen());
+        assert_eq!(
+            bytes,
+            build_from_accounts(
+                SerializationFormat::Aligned,
+                &accounts,
+                &[],
+                &TEST_PROGRAM_ID
+            )
+        );
+
+        let data = &bytes[FIRST_ACCOUNT_DATA_LEN_OFFSET + 8..][..MAX_PERMITTED_DATA_LENGTH];
+        let manifest = crate::manifest::read_manifest(&path).unwrap();
+        assert_eq!(manifest.accounts[0].data_len, MAX_PERMITTED_DATA_LENGTH);
+        assert_eq!(
+            manifest.accounts[0].data_sha256,
+            hash(data)
+                .to_bytes()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn xorshift_bytes_depend_on_the_seed() {
+        assert_eq!(xorshift_bytes(1, 8), 0x4082_2041u64.to_le_bytes());
+        assert_eq!(xorshift_bytes(0, 8), xorshift_bytes(1, 8));
+        assert_ne!(xorshift_bytes(2, 64), xorshift_bytes(3, 64));
+        assert_eq!(xorshift_bytes(7, 13), xorshift_bytes(7, 16)[..13]);
+    }
+
+    #[test]
+    fn alignment_sweep_headers_are_8_byte_aligned() {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_alignment_sweep_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+        assert_eq!(read_account_count(&buffer), 17);
+
+        let mut offset = 8;
+        for i in 0..=16usize {
+            assert_eq!(offset % 8, 0, "data_len {i}");
+            assert_eq!(buffer[offset], 0xFF, "data_len {i}");
+            assert_eq!(buffer[offset + 8], i as u8, "data_len {i}"); // key[0]
+
+            let data_len = read_u64(&buffer, offset + ACCOUNT_HEADER_LEN - 8) as usize;
+            assert_eq!(data_len, i);
+            let data_offset = offset + ACCOUNT_HEADER_LEN;
+            assert!(buffer[data_offset..data_offset + i]
+                .iter()
+                .all(|&b| b == i as u8));
+
+            let rent_epoch_offset =
+                data_offset + MAX_PERMITTED_DATA_INCREASE + i.next_multiple_of(8);
+            assert_eq!(rent_epoch_offset % 8, 0, "data_len {i}");
+            offset = rent_epoch_offset + 8;
+        }
+        assert_instruction_epilogue(&buffer, offset);
+    }
+
+    #[test]
+    fn clock_sysvar_data_is_40_byte_clock() {
+        let instruction_data: [u8; 0] = [];
+        let buffer = build_clock_sysvar_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], sysvar::clock::ID.as_ref()); // key
+        assert_eq!(&buffer[48..80], sysvar::ID.as_ref()); // owner
+        assert_eq!(read_u64(&buffer, data_offset - 16), 1_169_280); // lamports
+        assert_eq!(read_u64(&buffer, data_offset - 8), 40); // data_len
+
+        let fields: Vec<u64> = (0..5)
+            .map(|i| read_u64(&buffer, data_offset + i * 8))
+            .collect();
+        assert_eq!(fields, [100, 1_700_000_000, 5, 6, 1_700_000_500]);
+    }
+
+    #[test]
+    fn instruction_data_is_length_prefixed_after_accounts() {
+        for len in [0usize, 1, 1024] {
+            let instruction_data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let buffer = build_instruction_data_solana_format(&instruction_data, &TEST_PROGRAM_ID);
+
+            let epilogue = 8 + ACCOUNT_HEADER_LEN + 8 + MAX_PERMITTED_DATA_INCREASE + 8;
+            assert_eq!(read_u64(&buffer, epilogue), len as u64);
+            assert_eq!(
+                &buffer[epilogue + 8..epilogue + 8 + len],
+                &instruction_data[..]
+            );
+            assert_eq!(buffer.len(), epilogue + 8 + len + 32);
+        }
+    }
+
+    #[test]
+    fn rent_sysvar_data_is_17_bytes() {
+        let buffer = build_rent_sysvar_solana_format(&Rent::default(), &[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], sysvar::rent::ID.as_ref()); // key
+        assert_eq!(&buffer[48..80], sysvar::ID.as_ref()); // owner
+        assert_eq!(read_u64(&buffer, data_offset - 8), 17); // data_len
+
+        assert_eq!(read_u64(&buffer, data_offset), 3480);
+        assert_eq!(f64::from_bits(read_u64(&buffer, data_offset + 8)), 2.0);
+        assert_eq!(buffer[data_offset + 16], 50);
+    }
+
+    #[test]
+    fn program_id_is_the_last_32_bytes() {
+        let program_id = deterministic_pubkey(&mut FixtureRng::seed_from_u64(DEFAULT_SEED));
+        let buffer = build_program_id_solana_format(&[0x01, 0x02, 0x03], &program_id);
+
+        let epilogue = 8 + ACCOUNT_HEADER_LEN + 8 + MAX_PERMITTED_DATA_INCREASE + 8;
+        assert_eq!(read_u64(&buffer, epilogue), 3);
+        assert_eq!(&buffer[epilogue + 8 + 3..], program_id.as_ref());
+    }
+
+    #[test]
+    fn account_after_duplicate_starts_8_byte_aligned() {
+        let buffer = build_accounts_with_duplicates_solana_format(&[], &TEST_PROGRAM_ID);
+
+        // Accounts 0 and 1 have 8 and 12 bytes of data
+        let account_2 = 8
+            + 2 * (ACCOUNT_HEADER_LEN + MAX_PERMITTED_DATA_INCREASE + 8)
+            + 8
+            + 12usize.next_multiple_of(8);
+        assert_eq!(&buffer[account_2..account_2 + 8], &[0, 0, 0, 0, 0, 0, 0, 0]);
+
+        // Account 3 follows the 8-byte duplicate entry
+        let account_3 = account_2 + 8;
+        assert_eq!(account_3 % 8, 0);
+        assert_eq!(buffer[account_3], 0xFF);
+        assert_eq!(buffer[account_3 + 8], 3); // key[0]
+
+        // Account 4 duplicates account 1 and is followed by the epilogue
+        let account_4 = account_3 + ACCOUNT_HEADER_LEN + MAX_PERMITTED_DATA_INCREASE + 8 + 8;
+        assert_eq!(account_4 % 8, 0);
+        assert_eq!(&buffer[account_4..account_4 + 8], &[1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_instruction_epilogue(&buffer, account_4 + 8);
+    }
+
+    #[test]
+    fn epoch_schedule_sysvar_data_is_33_bytes() {
+        let buffer = build_epoch_schedule_sysvar_solana_format(&[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], sysvar::epoch_schedule::ID.as_ref()); // key
+        assert_eq!(read_u64(&buffer, data_offset - 8), 33); // data_len
+
+        assert_eq!(read_u64(&buffer, data_offset), 432_000);
+        assert_eq!(read_u64(&buffer, data_offset + 8), 432_000);
+        assert_eq!(buffer[data_offset + 16], 1);
+        assert_eq!(read_u64(&buffer, data_offset + 17), 14);
+        assert_eq!(read_u64(&buffer, data_offset + 25), 524_256);
+    }
+
+    #[test]
+    fn stake_history_sysvar_data_is_count_then_entries() {
+        let buffer = build_stake_history_sysvar_solana_format(&[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], sysvar::stake_history::ID.as_ref()); // key
+        assert_eq!(read_u64(&buffer, data_offset - 8), 8 + 3 * 32); // data_len
+        assert_eq!(read_u64(&buffer, data_offset), 3);
+
+        let epochs: Vec<u64> = (0..3)
+            .map(|i| read_u64(&buffer, data_offset + 8 + i * 32))
+            .collect();
+        assert_eq!(epochs, [12, 11, 10]);
+        assert_eq!(read_u64(&buffer, data_offset + 8 + 32 + 24), 100_000); // deactivating
+    }
+
+    #[test]
+    fn deprecated_format_is_smaller_than_aligned() {
+        for (aligned, deprecated) in [
+            (
+                build_single_account(SerializationFormat::Aligned, &[0x01], &TEST_PROGRAM_ID),
+                build_single_account(SerializationFormat::Deprecated, &[0x01], &TEST_PROGRAM_ID),
+            ),
+            (
+                build_multiple_accounts(SerializationFormat::Aligned, &[1, 2, 3], &TEST_PROGRAM_ID),
+                build_multiple_accounts(
+                    SerializationFormat::Deprecated,
+                    &[1, 2, 3],
+                    &TEST_PROGRAM_ID,
+                ),
+            ),
+        ] {
+            assert!(deprecated.len() < aligned.len());
+        }
+    }
+
+    #[test]
+    fn deprecated_buffers_match_hand_computed_lengths() {
+        // 92 bytes of account fields plus the data, per account
+        let single =
+            build_single_account(SerializationFormat::Deprecated, &[0x01], &TEST_PROGRAM_ID);
+        assert_eq!(single.len(), 8 + (92 + 10) + (8 + 1 + 32));
+        assert_eq!(read_account_count(&single), 1);
+        assert_instruction_epilogue(&single, 8 + 92 + 10);
+
+        let multiple = build_multiple_accounts(
+            SerializationFormat::Deprecated,
+            &[1, 2, 3],
+            &TEST_PROGRAM_ID,
+        );
+        assert_eq!(
+            multiple.len(),
+            8 + (92 + 5) + (92 + 10) + (92 + 15) + (8 + 3 + 32)
+        );
+        assert_instruction_epilogue(&multiple, 8 + 92 * 3 + 30);
+
+        // The last account: executable flag and rent_epoch close the entry
+        let rent_epoch_offset = 8 + 92 * 3 + 30 - 8;
+        assert_eq!(read_u64(&multiple, rent_epoch_offset), u64::MAX);
+        assert_eq!(multiple[rent_epoch_offset - 1], 1);
+    }
+
+    #[test]
+    fn slot_hashes_sysvar_hashes_are_sha256_of_the_slot() {
+        let buffer = build_slot_hashes_sysvar_solana_format(&[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], sysvar::slot_hashes::ID.as_ref()); // key
+        assert_eq!(read_u64(&buffer, data_offset - 8), 8 + 5 * 40); // data_len
+        assert_eq!(read_u64(&buffer, data_offset), 5);
+
+        let first_entry = data_offset + 8;
+        assert_eq!(read_u64(&buffer, first_entry), 1_004);
+        assert_eq!(
+            &buffer[first_entry + 8..first_entry + 40],
+            hash(&1_004u64.to_le_bytes()).as_ref()
+        );
+    }
 
-        // Flags
-        buffer.push(account.is_signer as u8);
-        buffer.push(account.is_writable as u8);
-        buffer.push(account.executable as u8);
+    #[test]
+    fn nonce_account_data_decodes_back_to_the_state() {
+        for state in [initialized_nonce_state(), NonceState::Uninitialized] {
+            let buffer = build_nonce_account_solana_format(&state, &[], &TEST_PROGRAM_ID);
+            let data_offset = 8 + ACCOUNT_HEADER_LEN;
 
-        // original_data_len
-        let data_len = account.data.borrow().len() as u32;
-        buffer.extend_from_slice(&data_len.to_le_bytes());
+            assert_eq!(&buffer[48..80], system_program::ID.as_ref()); // owner
+            assert_eq!(read_u64(&buffer, 80), 1_447_680); // lamports
+            assert_eq!(read_u64(&buffer, data_offset - 8), 80); // data_len
 
-        // key
-        buffer.extend_from_slice(&account.key.to_bytes());
+            let data = &buffer[data_offset..data_offset + 80];
+            let decoded: NonceVersions = bincode::deserialize(data).unwrap();
+            assert_eq!(decoded.state(), &state);
+        }
+    }
+
+    #[test]
+    fn stake_account_data_decodes_back_to_each_state() {
+        let reserve = 2_282_880; // rent-exempt minimum for 200 bytes
+        for (state, lamports) in [
+            (StakeStateV2::Uninitialized, reserve),
+            (StakeStateV2::Initialized(stake_meta()), reserve),
+            (
+                StakeStateV2::Stake(stake_meta(), delegated_stake(), StakeFlags::empty()),
+                reserve + 5_000_000_000,
+            ),
+            (StakeStateV2::RewardsPool, reserve),
+        ] {
+            let buffer = build_stake_account_solana_format(&state, &[], &TEST_PROGRAM_ID);
+            let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+            assert_eq!(&buffer[48..80], stake::program::ID.as_ref()); // owner
+            assert_eq!(read_u64(&buffer, 80), lamports);
+            assert_eq!(read_u64(&buffer, data_offset - 8), 200); // data_len
+
+            let data = &buffer[data_offset..data_offset + 200];
+            let decoded: StakeStateV2 = bincode::deserialize(data).unwrap();
+            assert_eq!(decoded, state);
+        }
+    }
+
+    #[test]
+    fn vote_account_data_decodes_back_to_the_vote_state() {
+        let buffer = build_vote_account_solana_format(&[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[16..48], VOTE_ACCOUNT_KEY.as_ref());
+        assert_eq!(&buffer[48..80], vote::program::ID.as_ref()); // owner
+        assert_eq!(read_u64(&buffer, 80), 27_074_400); // rent-exempt for 3762 bytes
+        assert_eq!(read_u64(&buffer, data_offset - 8), 3762); // data_len
+
+        let data = &buffer[data_offset..data_offset + 3762];
+        let decoded = VoteState::deserialize(data).unwrap();
+        assert_eq!(decoded, vote_state());
+        assert_eq!(decoded.node_pubkey, VOTE_NODE_PUBKEY);
+        assert_eq!(
+            decoded.get_authorized_voter(100),
+            Some(VOTE_AUTHORIZED_VOTER)
+        );
+        assert_eq!(decoded.authorized_withdrawer, VOTE_AUTHORIZED_WITHDRAWER);
+        assert_eq!(decoded.commission, 10);
+        assert_eq!(decoded.votes.len(), 10);
+        assert_eq!(decoded.last_voted_slot(), Some(1009));
+        assert_eq!(decoded.credits(), 2_800);
+    }
+
+    #[test]
+    fn upgradeable_program_account_points_at_its_programdata() {
+        let buffer = build_bpf_loader_upgradeable_solana_format(&[], &TEST_PROGRAM_ID);
+        let accounts = deserialize_account_buffer(&buffer).unwrap();
+        assert_eq!(accounts.len(), 3);
+        for account in &accounts {
+            assert_eq!(account.owner, bpf_loader_upgradeable::ID);
+        }
+
+        let [program, programdata, upgrade_buffer] = &accounts[..] else {
+            unreachable!()
+        };
+        assert!(program.executable);
+        assert_eq!(
+            program.data.len(),
+            UpgradeableLoaderState::size_of_program()
+        );
+        assert_eq!(
+            bincode::deserialize::<UpgradeableLoaderState>(&program.data).unwrap(),
+            UpgradeableLoaderState::Program {
+                programdata_address: programdata.key
+            }
+        );
+        assert_eq!(programdata.key, programdata_address(&program.key));
+
+        let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+        assert_eq!(
+            bincode::deserialize::<UpgradeableLoaderState>(&programdata.data).unwrap(),
+            UpgradeableLoaderState::ProgramData {
+                slot: PROGRAMDATA_SLOT,
+                upgrade_authority_address: Some(UPGRADE_AUTHORITY),
+            }
+        );
+        assert_eq!(programdata.data[metadata_len..], program_elf());
+
+        let metadata_len = UpgradeableLoaderState::size_of_buffer_metadata();
+        assert_eq!(
+            bincode::deserialize::<UpgradeableLoaderState>(&upgrade_buffer.data).unwrap(),
+            UpgradeableLoaderState::Buffer {
+                authority_address: Some(UPGRADE_AUTHORITY),
+            }
+        );
+        assert_eq!(upgrade_buffer.data[metadata_len..], program_elf());
+    }
+
+    #[test]
+    fn pda_accounts_carry_their_seeds_and_bump() {
+        let buffer = build_pda_accounts_solana_format(&[], &TEST_PROGRAM_ID);
+        let accounts = deserialize_account_buffer(&buffer).unwrap();
+        let expected = pda_expected();
+        assert_eq!(accounts.len(), 5);
+
+        for ((account, case), expected) in accounts.iter().zip(pda_cases()).zip(&expected) {
+            assert_eq!(account.owner, case.program_id);
+            assert_eq!(account.key.to_string(), expected.address);
+
+            // bump | count | (len | seed)*
+            let bump = account.data[0];
+            assert_eq!(bump, expected.bump);
+            assert_eq!(account.data[1] as usize, case.seeds.len());
+            let mut rest = &account.data[2..];
+            let mut seeds: Vec<&[u8]> = Vec::new();
+            for _ in 0..case.seeds.len() {
+                let (seed, tail) = rest[1..].split_at(rest[0] as usize);
+                seeds.push(seed);
+                rest = tail;
+            }
+            assert!(rest.is_empty());
+
+            seeds.push(std::slice::from_ref(&bump));
+            assert_eq!(
+                Pubkey::create_program_address(&seeds, &case.program_id),
+                Ok(account.key)
+            );
+        }
+    }
 
-        // owner
-        buffer.extend_from_slice(&account.owner.to_bytes());
+    #[test]
+    fn spl_token_mint_packs_to_82_bytes() {
+        let freeze_authority = Pubkey::new_from_array([0xF2; 32]);
+        for freeze in [None, Some(freeze_authority)] {
+            let mint = Mint {
+                freeze_authority: freeze,
+                ..spl_token_mint()
+            };
+            let buffer = build_spl_token_mint_solana_format(&mint, &[], &TEST_PROGRAM_ID);
+            let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+            assert_eq!(&buffer[48..80], SPL_TOKEN_PROGRAM_ID.as_ref()); // owner
+            assert_eq!(read_u64(&buffer, 80), 1_461_600); // lamports
+            assert_eq!(read_u64(&buffer, data_offset - 8), 82); // data_len
+
+            let data = &buffer[data_offset..data_offset + MINT_LEN];
+            assert_eq!(data[0..4], [1, 0, 0, 0]);
+            assert_eq!(&data[4..36], &[0xA1; 32]);
+            assert_eq!(read_u64(data, 36), 1_000_000_000);
+            assert_eq!(data[44], 6); // decimals
+            assert_eq!(data[45], 1); // is_initialized
+            match freeze {
+                Some(key) => {
+                    assert_eq!(data[46..50], [1, 0, 0, 0]);
+                    assert_eq!(&data[50..82], key.as_ref());
+                }
+                None => assert_eq!(data[46..82], [0; 36]),
+            }
+        }
+    }
+
+    #[test]
+    fn spl_token_account_packs_to_165_bytes() {
+        let native = TokenAccount {
+            mint: NATIVE_MINT_ID,
+            amount: 1_000_000_000,
+            is_native: Some(2_039_280),
+            state: AccountState::Frozen,
+            ..spl_token_account()
+        };
+        let buffer = build_spl_token_account_solana_format(&native, &[], &TEST_PROGRAM_ID);
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+
+        assert_eq!(&buffer[48..80], SPL_TOKEN_PROGRAM_ID.as_ref()); // owner
+        assert_eq!(read_u64(&buffer, 80), 2_039_280 + 1_000_000_000); // lamports
+        assert_eq!(read_u64(&buffer, data_offset - 8), 165); // data_len
+
+        let data = &buffer[data_offset..data_offset + TOKEN_ACCOUNT_LEN];
+        assert_eq!(&data[0..32], NATIVE_MINT_ID.as_ref());
+        assert_eq!(&data[32..64], &[0xB0; 32]);
+        assert_eq!(read_u64(data, 64), 1_000_000_000); // amount
+        assert_eq!(data[72..108], [0; 36]); // delegate: None
+        assert_eq!(data[108], 2); // state: Frozen
+        assert_eq!(data[109..113], [1, 0, 0, 0]); // is_native: Some
+        assert_eq!(read_u64(data, 113), 2_039_280);
+        assert_eq!(read_u64(data, 121), 0); // delegated_amount
+        assert_eq!(data[129..165], [0; 36]); // close_authority: None
+    }
 
-        // lamports
-        let lamports_val = **account.lamports.borrow();
-        buffer.extend_from_slice(&lamports_val.to_le_bytes());
+    #[test]
+    fn ata_account_key_is_the_derived_associated_token_address() {
+        let account = spl_token_account();
+        assert_eq!(account.owner, Pubkey::new_from_array([0xB0; 32]));
+        assert_eq!(account.mint, Pubkey::new_from_array([0x3D; 32]));
 
-        // data_len
-        let data_len_64 = account.data.borrow().len() as u64;
-        buffer.extend_from_slice(&data_len_64.to_le_bytes());
+        // Ground truth for the Zig PDA derivation of this wallet/mint pair
+        let (address, bump) = associated_token_address(&account.owner, &account.mint);
+        assert_eq!(
+            address,
+            Pubkey::from_str_const("73LzPHYFSKBKWUZbzeDg3CGMwWfsLbc9QFjnXE4bAqLP")
+        );
+        assert_eq!(bump, 254);
+        let recreated = Pubkey::create_program_address(
+            &[
+                account.owner.as_ref(),
+                SPL_TOKEN_PROGRAM_ID.as_ref(),
+                account.mint.as_ref(),
+                &[bump],
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        );
+        assert_eq!(recreated, Ok(address));
 
-        // Actual data
-        buffer.extend_from_slice(&account.data.borrow());
+        let buffer = build_associated_token_account_solana_format(&account, &[], &TEST_PROGRAM_ID);
+        assert_eq!(&buffer[16..48], address.as_ref()); // key
+        assert_eq!(&buffer[48..80], SPL_TOKEN_PROGRAM_ID.as_ref()); // owner
+        let data_offset = 8 + ACCOUNT_HEADER_LEN;
+        assert_eq!(
+            &buffer[data_offset..data_offset + 32],
+            account.mint.as_ref()
+        );
+        assert_eq!(
+            &buffer[data_offset + 32..data_offset + 64],
+            account.owner.as_ref()
+        );
     }
 }
@@ -0,0 +1,544 @@
+// Fixtures described by a TOML or JSON spec file instead of Rust code
+use serde::Deserialize;
+use solana_program::pubkey::Pubkey;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{GenerateError, GenerateResult};
+use crate::hexdump::{hexdump_path, write_hexdump};
+use crate::index::write_index;
+use crate::manifest::{manifest_path, write_manifest};
+use crate::offsets::{offsets_path, write_offsets};
+use crate::output::{write_outputs, OutputOptions};
+use crate::serialize_solana_format::{
+    build_from_accounts, describe_instruction_data, SerializationFormat, TEST_PROGRAM_ID,
+};
+use crate::stable::write_if_changed;
+use crate::verify::{verify_round_trip, ExpectedAccount};
+
+/// The spec behind the fixtures that used to be hand-written generators,
+/// compiled into the binary
+pub const DEFAULT_SPEC: &str = include_str!("../scenarios.toml");
+
+/// A whole spec file: `[[scenario]]` tables in TOML, a `scenario` array in
+/// JSON
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecFile {
+    #[serde(rename = "scenario", default)]
+    pub scenarios: Vec<ScenarioSpec>,
+}
+
+/// One fixture, written to `<name>.bin`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioSpec {
+    pub name: String,
+    #[serde(default = "aligned")]
+    pub format: SerializationFormat,
+    /// Base58, defaults to `TEST_PROGRAM_ID`
+    pub program_id: Option<String>,
+    /// Hex
+    #[serde(default)]
+    pub instruction_data: String,
+    #[serde(default)]
+    pub accounts: Vec<AccountSpec>,
+}
+
+fn aligned() -> SerializationFormat {
+    SerializationFormat::Aligned
+}
+
+/// One account entry. A duplicate sets only `duplicate_of`; every other
+/// entry needs a `key`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountSpec {
+    /// Base58
+    pub key: Option<String>,
+    /// Base58, defaults to all zeroes
+    pub owner: Option<String>,
+    pub lamports: Option<u64>,
+    pub data: Option<DataSpec>,
+    pub is_signer: Option<bool>,
+    pub is_writable: Option<bool>,
+    pub executable: Option<bool>,
+    pub rent_epoch: Option<u64>,
+    /// Shorthand for a `rent_epoch` of `u64::MAX`, which TOML integers
+    /// cannot hold
+    pub rent_exempt: Option<bool>,
+    /// Index of an earlier account this entry repeats
+    pub duplicate_of: Option<usize>,
+}
+
+/// Account data as a hex string, or `len` copies of the byte `fill`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum DataSpec {
+    Hex(String),
+    Fill(Fill),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fill {
+    pub fill: u8,
+    pub len: usize,
+}
+
+/// A spec entry with every field checked and decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub format: SerializationFormat,
+    pub program_id: Pubkey,
+    pub instruction_data: Vec<u8>,
+    pub accounts: Vec<ExpectedAccount>,
+}
+
+/// Why a spec could not be loaded
+#[derive(Debug)]
+pub enum SpecError {
+    Read {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Not valid TOML or JSON, or a field the spec does not know
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    /// Well-formed, but a value is unusable
+    Invalid {
+        scenario: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::Read { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            SpecError::Parse { path, message } => {
+                write!(f, "{}: {}", path.display(), message.trim_end())
+            }
+            SpecError::Invalid { scenario, message } => {
+                write!(f, "scenario `{scenario}`: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+impl SpecFile {
+    /// Read a spec, as JSON when the file ends in `.json` and TOML otherwise
+    pub fn load(path: &Path) -> Result<SpecFile, SpecError> {
+        let text = std::fs::read_to_string(path).map_err(|source| SpecError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let parsed = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&text).map_err(|e| e.to_string())
+        };
+        parsed.map_err(|message| SpecError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// The embedded `DEFAULT_SPEC`
+    pub fn default_spec() -> SpecFile {
+        toml::from_str(DEFAULT_SPEC).expect("scenarios.toml is a valid spec")
+    }
+
+    /// Check and decode every scenario. Names must be unique, since each
+    /// one is a file name.
+    pub fn resolve(&self) -> Result<Vec<Scenario>, SpecError> {
+        let mut scenarios: Vec<Scenario> = Vec::new();
+        for spec in &self.scenarios {
+            if scenarios.iter().any(|s| s.name == spec.name) {
+                return Err(invalid(&spec.name, "name is used by an earlier scenario"));
+            }
+            scenarios.push(spec.resolve()?);
+        }
+        Ok(scenarios)
+    }
+}
+
+impl ScenarioSpec {
+    pub fn resolve(&self) -> Result<Scenario, SpecError> {
+        let name = &self.name;
+        if name.is_empty() || name.contains(['/', '\\']) {
+            return Err(invalid(name, "name must be a plain file stem"));
+        }
+        let program_id = match &self.program_id {
+            Some(key) => parse_key(key).map_err(|e| invalid(name, format!("program_id: {e}")))?,
+            None => TEST_PROGRAM_ID,
+        };
+        let instruction_data = parse_hex(&self.instruction_data)
+            .map_err(|e| invalid(name, format!("instruction_data: {e}")))?;
+
+        let mut accounts: Vec<ExpectedAccount> = Vec::with_capacity(self.accounts.len());
+        for (index, spec) in self.accounts.iter().enumerate() {
+            let account = spec
+                .resolve(&accounts)
+                .map_err(|e| invalid(name, format!("account {index}: {e}")))?;
+            accounts.push(account);
+        }
+
+        Ok(Scenario {
+            name: name.clone(),
+            format: self.format,
+            program_id,
+            instruction_data,
+            accounts,
+        })
+    }
+}
+
+impl AccountSpec {
+    /// Decode this entry, given the accounts before it
+    fn resolve(&self, earlier: &[ExpectedAccount]) -> Result<ExpectedAccount, String> {
+        if let Some(index) = self.duplicate_of {
+            let AccountSpec {
+                key: None,
+                owner: None,
+                lamports: None,
+                data: None,
+                is_signer: None,
+                is_writable: None,
+                executable: None,
+                rent_epoch: None,
+                rent_exempt: None,
+                duplicate_of: _,
+            } = self
+            else {
+                return Err("a duplicate entry takes only `duplicate_of`".to_string());
+            };
+            if index >= earlier.len() {
+                return Err(format!(
+                    "duplicate_of {index} is out of range, only {} accounts come before it",
+                    earlier.len()
+                ));
+            }
+            // The runtime always points a duplicate at the first occurrence
+            if let Some(original) = earlier[index].dup_of {
+                return Err(format!(
+                    "duplicate_of {index} is itself a duplicate, use {original}"
+                ));
+            }
+            return Ok(ExpectedAccount::duplicate_of(earlier, index as u8));
+        }
+
+        let key = self.key.as_deref().ok_or("missing `key`")?;
+        let key = parse_key(key).map_err(|e| format!("key: {e}"))?;
+        let owner = match &self.owner {
+            Some(owner) => parse_key(owner).map_err(|e| format!("owner: {e}"))?,
+            None => Pubkey::default(),
+        };
+        let data = match &self.data {
+            Some(DataSpec::Hex(hex)) => parse_hex(hex).map_err(|e| format!("data: {e}"))?,
+            Some(DataSpec::Fill(Fill { fill, len })) => vec![*fill; *len],
+            None => vec![],
+        };
+        let rent_epoch = match (self.rent_epoch, self.rent_exempt) {
+            (Some(_), Some(true)) => {
+                return Err("set `rent_epoch` or `rent_exempt`, not both".to_string())
+            }
+            (_, Some(true)) => u64::MAX,
+            (rent_epoch, _) => rent_epoch.unwrap_or(0),
+        };
+
+        Ok(ExpectedAccount::new(
+            key,
+            self.is_signer.unwrap_or(false),
+            self.is_writable.unwrap_or(false),
+            self.lamports.unwrap_or(0),
+            data,
+            owner,
+            self.executable.unwrap_or(false),
+            rent_epoch,
+        ))
+    }
+}
+
+impl Scenario {
+    /// The serialized entrypoint input
+    pub fn build(&self) -> Vec<u8> {
+        build_from_accounts(
+            self.format,
+            &self.accounts,
+            &self.instruction_data,
+            &self.program_id,
+        )
+    }
+
+    /// Write `<name>.bin` and its manifest, hexdump and offsets into
+    /// `test_data_dir`, returning the paths written
+    pub fn write(&self, test_data_dir: &Path) -> GenerateResult<Vec<PathBuf>> {
+        let file_name = format!("{}.bin", self.name);
+        let file_path = test_data_dir.join(&file_name);
+        let buffer = self.build();
+        write_if_changed(&file_path, &buffer)?;
+        // Only the current layout can be parsed back
+        if self.format == SerializationFormat::Aligned {
+            verify_round_trip(&file_path, &self.accounts);
+        }
+        for write in [write_manifest, write_hexdump, write_offsets] {
+            write(
+                &file_path,
+                self.format,
+                &self.accounts,
+                &self.instruction_data,
+                &self.program_id,
+            )?;
+        }
+
+        println!(
+            "Generated: {} ({} bytes, instruction data {})",
+            file_name,
+            buffer.len(),
+            describe_instruction_data(&self.instruction_data)
+        );
+
+        Ok(vec![
+            manifest_path(&file_path),
+            hexdump_path(&file_path),
+            offsets_path(&file_path),
+            file_path,
+        ])
+    }
+}
+
+/// Write the scenarios named in `names`, in that order. A missing name is
+/// a bug in the caller, so it panics.
+pub fn write_default_scenarios(test_data_dir: &Path, names: &[&str]) -> GenerateResult<()> {
+    let scenarios = SpecFile::default_spec()
+        .resolve()
+        .expect("scenarios.toml resolves");
+    for name in names {
+        let scenario = scenarios
+            .iter()
+            .find(|scenario| scenario.name == *name)
+            .unwrap_or_else(|| panic!("scenarios.toml has no `{name}`"));
+        scenario.write(test_data_dir)?;
+    }
+    Ok(())
+}
+
+/// Write every scenario in `spec` into `test_data_dir`, creating it if
+/// needed, writing and compressing each fixture as `options` asks, and
+/// record them in index.json. Nothing is written unless the
+/// whole spec resolves.
+pub fn generate_from_spec(
+    test_data_dir: &Path,
+    spec: &SpecFile,
+    options: &OutputOptions,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let scenarios = spec.resolve()?;
+    std::fs::create_dir_all(test_data_dir).map_err(|e| GenerateError::new(test_data_dir, e))?;
+    let mut written: Vec<(&str, PathBuf)> = Vec::new();
+    for scenario in &scenarios {
+        let name = scenario.name.as_str();
+        let mut files = scenario.write(test_data_dir)?;
+        write_outputs(
+            &test_data_dir.join(format!("{name}.bin")),
+            options,
+            &mut files,
+        )?;
+        written.extend(files.into_iter().map(|file| (name, file)));
+    }
+    let index = write_index(test_data_dir, &written)?;
+
+    let mut files: Vec<PathBuf> = written.into_iter().map(|(_, file)| file).collect();
+    files.push(index);
+    Ok(files)
+}
+
+fn invalid(scenario: &str, message: impl Into<String>) -> SpecError {
+    SpecError::Invalid {
+        scenario: scenario.to_string(),
+        message: message.into(),
+    }
+}
+
+fn parse_key(key: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(key).map_err(|e| format!("`{key}` is not a base58 public key ({e})"))
+}
+
+/// Decode hex, allowing an optional `0x` prefix
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("`{hex}` has an odd number of hex digits"));
+    }
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("`{hex}` is not valid hex"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize_solana_format::{
+        accounts_with_duplicates_scenario, empty_data_accounts_scenario,
+        empty_instruction_data_scenario, flags_matrix_scenario, multiple_accounts_scenario,
+        no_accounts_instruction_data, self_dup_account_scenario, single_account_scenario,
+        zero_vs_unallocated_scenario,
+    };
+
+    fn resolve_toml(spec: &str) -> Result<Vec<Scenario>, String> {
+        let spec: SpecFile = toml::from_str(spec).map_err(|e| e.to_string())?;
+        spec.resolve().map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn default_spec_matches_the_rust_scenarios() {
+        let scenarios = SpecFile::default_spec().resolve().unwrap();
+        let accounts = |name: &str| {
+            &scenarios
+                .iter()
+                .find(|scenario| scenario.name == name)
+                .unwrap()
+                .accounts
+        };
+        assert_eq!(
+            accounts("solana_single_account"),
+            &single_account_scenario()
+        );
+        assert_eq!(
+            accounts("deprecated_single_account"),
+            &single_account_scenario()
+        );
+        assert_eq!(
+            accounts("solana_multiple_accounts"),
+            &multiple_accounts_scenario()
+        );
+        assert_eq!(
+            accounts("deprecated_multiple_accounts"),
+            &multiple_accounts_scenario()
+        );
+        assert_eq!(
+            accounts("empty_data_accounts"),
+            &empty_data_accounts_scenario()
+        );
+        assert_eq!(
+            accounts("zero_vs_unallocated"),
+            &zero_vs_unallocated_scenario()
+        );
+        assert_eq!(
+            accounts("solana_accounts_with_duplicates"),
+            &accounts_with_duplicates_scenario()
+        );
+        assert_eq!(accounts("self_dup_account"), &self_dup_account_scenario());
+        assert_eq!(accounts("flags_matrix"), &flags_matrix_scenario());
+        assert!(accounts("solana_no_accounts").is_empty());
+        assert_eq!(
+            accounts("solana_empty_instruction_data"),
+            &empty_instruction_data_scenario()
+        );
+
+        let instruction_data = |name: &str| {
+            &scenarios
+                .iter()
+                .find(|scenario| scenario.name == name)
+                .unwrap()
+                .instruction_data
+        };
+        assert_eq!(
+            instruction_data("solana_no_accounts"),
+            &no_accounts_instruction_data()
+        );
+        assert!(instruction_data("solana_empty_instruction_data").is_empty());
+    }
+
+    #[test]
+    fn json_and_toml_specs_agree() {
+        let toml_spec = r#"
+            [[scenario]]
+            name = "one"
+            instruction_data = "0xdead"
+            [[scenario.accounts]]
+            key = "11111111111111111111111111111111"
+            data = "0102"
+            [[scenario.accounts]]
+            duplicate_of = 0
+        "#;
+        let json_spec = r#"{"scenario": [{
+            "name": "one",
+            "instruction_data": "dead",
+            "accounts": [
+                {"key": "11111111111111111111111111111111", "data": "0102"},
+                {"duplicate_of": 0}
+            ]
+        }]}"#;
+        let from_json: SpecFile = serde_json::from_str(json_spec).unwrap();
+        let scenarios = resolve_toml(toml_spec).unwrap();
+        assert_eq!(scenarios, from_json.resolve().unwrap());
+        assert_eq!(scenarios[0].instruction_data, [0xDE, 0xAD]);
+        assert_eq!(scenarios[0].accounts[1].dup_of, Some(0));
+    }
+
+    #[test]
+    fn errors_name_the_scenario_account_and_field() {
+        let unknown_field = resolve_toml(
+            "[[scenario]]\nname = \"a\"\n[[scenario.accounts]]\nkey = \"11111111111111111111111111111111\"\nlamport = 1\n",
+        )
+        .unwrap_err();
+        assert!(
+            unknown_field.contains("unknown field `lamport`"),
+            "{unknown_field}"
+        );
+
+        let bad_hex = resolve_toml(
+            "[[scenario]]\nname = \"a\"\n[[scenario.accounts]]\nkey = \"11111111111111111111111111111111\"\ndata = \"0g\"\n",
+        )
+        .unwrap_err();
+        assert_eq!(
+            bad_hex,
+            "scenario `a`: account 0: data: `0g` is not valid hex"
+        );
+
+        let odd_hex =
+            resolve_toml("[[scenario]]\nname = \"a\"\ninstruction_data = \"abc\"\n").unwrap_err();
+        assert_eq!(
+            odd_hex,
+            "scenario `a`: instruction_data: `abc` has an odd number of hex digits"
+        );
+
+        let dup_out_of_range =
+            resolve_toml("[[scenario]]\nname = \"a\"\n[[scenario.accounts]]\nduplicate_of = 0\n")
+                .unwrap_err();
+        assert_eq!(
+            dup_out_of_range,
+            "scenario `a`: account 0: duplicate_of 0 is out of range, only 0 accounts come before it"
+        );
+
+        let dup_with_fields = resolve_toml(
+            "[[scenario]]\nname = \"a\"\n[[scenario.accounts]]\nkey = \"11111111111111111111111111111111\"\n[[scenario.accounts]]\nduplicate_of = 0\nlamports = 5\n",
+        )
+        .unwrap_err();
+        assert!(dup_with_fields.contains("account 1: a duplicate entry takes only"));
+
+        let repeated_name =
+            resolve_toml("[[scenario]]\nname = \"a\"\n[[scenario]]\nname = \"a\"\n").unwrap_err();
+        assert_eq!(
+            repeated_name,
+            "scenario `a`: name is used by an earlier scenario"
+        );
+    }
+}
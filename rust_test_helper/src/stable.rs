@@ -0,0 +1,158 @@
+// Writing generated files only when their bytes change, so a rerun with the
+// same seed leaves test_data, its mtimes and Zig's build cache alone
+use solana_program::hash::{Hash, Hasher};
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{GenerateError, GenerateResult};
+
+/// What `write_if_changed` did to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStatus {
+    /// Created, or replaced because its bytes differed
+    Updated,
+    /// Already held the same bytes and was not touched
+    Unchanged,
+}
+
+impl WriteStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WriteStatus::Updated => "updated",
+            WriteStatus::Unchanged => "unchanged",
+        }
+    }
+}
+
+thread_local! {
+    /// Every `write_if_changed` on this thread since the last `take_write_log`
+    static WRITE_LOG: RefCell<Vec<(PathBuf, WriteStatus)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// `.<name>.tmp` next to `path`, so the rename stays on one file system
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+    path.with_file_name(format!(".{name}.tmp"))
+}
+
+/// Write `bytes` to a temporary file and rename it over `path` only when
+/// `path` does not already hold the same bytes. The outcome is returned and
+/// recorded for `take_write_log`. Errors name `path`, not the temporary file.
+pub fn write_if_changed(path: &Path, bytes: impl AsRef<[u8]>) -> GenerateResult<WriteStatus> {
+    write_stream_if_changed(path, |writer| writer.write_all(bytes.as_ref()))
+}
+
+/// `write_if_changed` for files too large to build in memory first: `write`
+/// streams the contents through a buffered writer into the temporary file
+pub fn write_stream_if_changed(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> GenerateResult<WriteStatus> {
+    let temp = temp_path(path);
+    let written = File::create(&temp).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(GenerateError::new(path, e));
+    }
+
+    let new_hash = hash_file(&temp).map_err(|e| GenerateError::new(path, e))?;
+    let unchanged = hash_file(path).is_ok_and(|existing| existing == new_hash);
+    let status = if unchanged {
+        fs::remove_file(&temp).map_err(|e| GenerateError::new(path, e))?;
+        WriteStatus::Unchanged
+    } else {
+        fs::rename(&temp, path).map_err(|e| GenerateError::new(path, e))?;
+        WriteStatus::Updated
+    };
+
+    WRITE_LOG.with(|log| log.borrow_mut().push((path.to_path_buf(), status)));
+    Ok(status)
+}
+
+/// SHA-256 of the file at `path`, read in chunks
+fn hash_file(path: &Path) -> io::Result<Hash> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Hasher::default();
+    let mut chunk = [0u8; 64 * 1024];
+    loop {
+        let len = reader.read(&mut chunk)?;
+        if len == 0 {
+            return Ok(hasher.result());
+        }
+        hasher.hash(&chunk[..len]);
+    }
+}
+
+/// The files `write_if_changed` wrote on this thread since the last call,
+/// in order, with the last status of each. Clears the log.
+pub fn take_write_log() -> Vec<(PathBuf, WriteStatus)> {
+    let log = WRITE_LOG.with(|log| std::mem::take(&mut *log.borrow_mut()));
+    let mut latest: Vec<(PathBuf, WriteStatus)> = Vec::new();
+    for (path, status) in log {
+        latest.retain(|(existing, _)| *existing != path);
+        latest.push((path, status));
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_bytes_leave_the_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.bin");
+        take_write_log();
+
+        assert_eq!(
+            write_if_changed(&path, [1, 2, 3]).unwrap(),
+            WriteStatus::Updated
+        );
+        assert_eq!(
+            write_if_changed(&path, [1, 2, 3]).unwrap(),
+            WriteStatus::Unchanged
+        );
+        assert_eq!(write_if_changed(&path, [4]).unwrap(), WriteStatus::Updated);
+        assert_eq!(fs::read(&path).unwrap(), [4]);
+        assert!(!temp_path(&path).exists());
+
+        assert_eq!(take_write_log(), vec![(path, WriteStatus::Updated)]);
+        assert!(take_write_log().is_empty());
+    }
+
+    #[test]
+    fn streamed_writes_compare_against_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.bin");
+        let stream = |writer: &mut dyn Write| {
+            for chunk in 0..4u8 {
+                writer.write_all(&[chunk; 100_000])?;
+            }
+            Ok(())
+        };
+
+        assert_eq!(
+            write_stream_if_changed(&path, stream).unwrap(),
+            WriteStatus::Updated
+        );
+        assert_eq!(fs::metadata(&path).unwrap().len(), 400_000);
+        assert_eq!(
+            write_stream_if_changed(&path, stream).unwrap(),
+            WriteStatus::Unchanged
+        );
+
+        let failed = write_stream_if_changed(&path, |_| Err(io::Error::other("disk full")));
+        assert!(failed.unwrap_err().to_string().contains("fixture.bin"));
+        assert_eq!(fs::metadata(&path).unwrap().len(), 400_000);
+        assert!(!temp_path(&path).exists());
+    }
+}
@@ -0,0 +1,234 @@
+// Read generated fixtures back and check them against the generator's inputs
+use solana_program::pubkey::Pubkey;
+use std::path::{Path, PathBuf};
+
+use crate::compress::{decompressed_path, read_fixture};
+use crate::deserialize::{
+    check_structure, deserialize_account_buffer, deserialize_input, Violation,
+};
+use crate::manifest::{manifest_path, AccountManifest, ManifestV1};
+use crate::serialize_solana_format::{SerializationFormat, INVALID_FIXTURE_PREFIX};
+
+/// One account entry as a generator serializes it. Duplicate entries carry
+/// a copy of the account they point to, like the parser returns them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAccount {
+    /// Index of the earlier account this entry duplicates
+    pub dup_of: Option<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub rent_epoch: u64,
+}
+
+impl ExpectedAccount {
+    /// A non-duplicate account, arguments in `AccountInfo::new` order
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        lamports: u64,
+        data: Vec<u8>,
+        owner: Pubkey,
+        executable: bool,
+        rent_epoch: u64,
+    ) -> Self {
+        ExpectedAccount {
+            dup_of: None,
+            is_signer,
+            is_writable,
+            executable,
+            key,
+            owner,
+            lamports,
+            data,
+            rent_epoch,
+        }
+    }
+
+    /// A duplicate entry pointing at `accounts[index]`
+    pub fn duplicate_of(accounts: &[ExpectedAccount], index: u8) -> Self {
+        ExpectedAccount {
+            dup_of: Some(index),
+            ..accounts[index as usize].clone()
+        }
+    }
+}
+
+/// Parse the fixture at `file_path` and panic with the file name, account
+/// index and field on the first value that does not match `expected_accounts`
+pub fn verify_round_trip(file_path: &Path, expected_accounts: &[ExpectedAccount]) {
+    let name = file_path.display();
+    let bytes = std::fs::read(file_path).unwrap_or_else(|e| panic!("{name}: {e}"));
+    let parsed = deserialize_account_buffer(&bytes).unwrap_or_else(|e| panic!("{name}: {e}"));
+
+    assert_eq!(
+        parsed.len(),
+        expected_accounts.len(),
+        "{name}: account count does not round-trip"
+    );
+
+    for (i, (parsed, expected)) in parsed.iter().zip(expected_accounts).enumerate() {
+        let fields = [
+            ("is_dup", parsed.is_dup == expected.dup_of.is_some()),
+            ("is_signer", parsed.is_signer == expected.is_signer),
+            ("is_writable", parsed.is_writable == expected.is_writable),
+            ("executable", parsed.executable == expected.executable),
+            ("key", parsed.key == expected.key),
+            ("owner", parsed.owner == expected.owner),
+            ("lamports", parsed.lamports == expected.lamports),
+            ("data", parsed.data == expected.data),
+            ("rent_epoch", parsed.rent_epoch == expected.rent_epoch),
+        ];
+        for (field, matches) in fields {
+            assert!(matches, "{name}: account {i} {field} does not round-trip");
+        }
+    }
+}
+
+/// Account count of a fixture that matches its manifest, or every mismatch
+pub type VerifyResult = Result<usize, Vec<String>>;
+
+/// Check the structural invariants of the fixture at `file_path` (see
+/// `check_structure`), then compare it with the `.expected.json` manifest
+/// next to it when there is one. A `.bin.zst` is checked as the fixture it
+/// decompresses to. Returns the number of accounts, or every problem found.
+pub fn verify_fixture(file_path: &Path) -> VerifyResult {
+    let bin_path = decompressed_path(file_path);
+    let file_path = bin_path.as_deref().unwrap_or(file_path);
+    let manifest = if manifest_path(file_path).exists() {
+        Some(read_manifest(file_path).map_err(|e| vec![e])?)
+    } else {
+        None
+    };
+    if let Some(manifest) = &manifest {
+        if manifest.format != SerializationFormat::Aligned {
+            return Err(vec![format!(
+                "{:?} format fixtures cannot be parsed",
+                manifest.format
+            )]);
+        }
+    }
+
+    let bytes = read_fixture(file_path).map_err(|e| vec![e.to_string()])?;
+    let violations = check_structure(&bytes);
+    if !violations.is_empty() {
+        return Err(violations.iter().map(Violation::to_string).collect());
+    }
+    let parsed = deserialize_input(&bytes).map_err(|e| vec![e.to_string()])?;
+    let Some(manifest) = manifest else {
+        return Ok(parsed.accounts.len());
+    };
+
+    let mut problems = Vec::new();
+    let mut check = |what: String, matches: bool| {
+        if !matches {
+            problems.push(format!("{what} does not match the manifest"));
+        }
+    };
+    check(
+        "account count".to_string(),
+        parsed.accounts.len() == manifest.account_count,
+    );
+    for (i, (parsed, expected)) in parsed.accounts.iter().zip(&manifest.accounts).enumerate() {
+        let mut actual = AccountManifest::new(&ExpectedAccount::new(
+            parsed.key,
+            parsed.is_signer,
+            parsed.is_writable,
+            parsed.lamports,
+            parsed.data.clone(),
+            parsed.owner,
+            parsed.executable,
+            parsed.rent_epoch,
+        ));
+        // The parser knows an entry is a duplicate but not of which index
+        actual.duplicate_of = expected.duplicate_of;
+        check(
+            format!("account {i}"),
+            actual == *expected && parsed.is_dup == expected.duplicate_of.is_some(),
+        );
+    }
+    let instruction_data: String = parsed
+        .instruction_data
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    check(
+        "instruction data".to_string(),
+        instruction_data == manifest.instruction_data,
+    );
+    check(
+        "program id".to_string(),
+        parsed.program_id.to_string() == manifest.program_id,
+    );
+
+    if problems.is_empty() {
+        Ok(parsed.accounts.len())
+    } else {
+        Err(problems)
+    }
+}
+
+/// `verify_fixture` for every generated aligned fixture in `dir`, sorted by
+/// name. Golden files, deprecated layouts and `invalid_` fixtures have
+/// nothing to compare with the parser, so they are left out. The fixtures
+/// in test_data/negative/ are not, so verifying that directory flags each
+/// of them. A `.bin.zst` is checked when its `.bin` has not been
+/// decompressed next to it.
+pub fn verify_dir(dir: &Path) -> std::io::Result<Vec<(PathBuf, VerifyResult)>> {
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    fixtures.retain(|path| match decompressed_path(path) {
+        Some(bin_path) => !bin_path.exists(),
+        None => true,
+    });
+    fixtures.retain(|path| {
+        let bin_path = decompressed_path(path).unwrap_or_else(|| path.clone());
+        bin_path.extension().is_some_and(|ext| ext == "bin")
+            && !path.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with("golden_") || name.starts_with(INVALID_FIXTURE_PREFIX)
+            })
+            && read_manifest(&bin_path).map_or(true, |m| m.format == SerializationFormat::Aligned)
+    });
+    fixtures.sort();
+
+    Ok(fixtures
+        .into_iter()
+        .map(|path| {
+            let result = verify_fixture(&path);
+            (path, result)
+        })
+        .collect())
+}
+
+fn read_manifest(file_path: &Path) -> Result<ManifestV1, String> {
+    let path = manifest_path(file_path);
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    serde_json::from_str(&json).map_err(|e| format!("invalid {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize_solana_format::*;
+
+    #[test]
+    #[should_panic(expected = "account 2 lamports does not round-trip")]
+    fn mismatch_names_the_account_and_field() {
+        let mut accounts = multiple_accounts_scenario();
+        accounts[2].lamports += 1;
+
+        let file_path = std::env::temp_dir().join("verify_round_trip_mismatch.bin");
+        let buffer = build_multiple_accounts(SerializationFormat::Aligned, &[], &TEST_PROGRAM_ID);
+        std::fs::write(&file_path, buffer).unwrap();
+        verify_round_trip(&file_path, &accounts);
+    }
+}
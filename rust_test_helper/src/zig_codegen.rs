@@ -0,0 +1,280 @@
+// Emit test_data/expected.zig, the fixture manifests as Zig constants
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::constants::MAX_PERMITTED_DATA_INCREASE;
+use crate::deserialize::{AccountOffsets, InputOffsets};
+use crate::error::{GenerateError, GenerateResult};
+use crate::manifest::ManifestV1;
+use crate::serialize_solana_format::SerializationFormat;
+use crate::stable::write_if_changed;
+
+/// File name of the generated Zig source inside test_data
+pub const EXPECTED_ZIG_FILE: &str = "expected.zig";
+
+const MANIFEST_SUFFIX: &str = ".expected.json";
+
+/// Zig keywords that cannot be used as bare identifiers
+const ZIG_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+];
+
+const HEADER: &str = "\
+// Generated by rust_test_helper (src/zig_codegen.rs) from the .expected.json
+// manifests. Do not edit, rerun `cargo run` in rust_test_helper instead.
+
+pub const Account = struct {
+    key_offset: usize,
+    lamports_offset: usize,
+    data_offset: usize,
+    lamports: u64,
+    rent_epoch: u64,
+    data_len: usize,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    /// Index of the account this entry duplicates. The offsets are the
+    /// original account's.
+    duplicate_of: ?u8,
+};
+
+pub const Fixture = struct {
+    file_name: []const u8,
+    account_count: usize,
+    accounts: []const Account,
+    instruction_data_offset: usize,
+    program_id_offset: usize,
+    /// Size of the whole input, which a parse should consume exactly
+    byte_length: usize,
+    /// The input breaks a runtime limit on purpose and should be rejected
+    negative: bool,
+};
+";
+
+/// Every `<name>.expected.json` in `test_data_dir` as `(name, manifest)`,
+/// sorted by name
+pub fn load_manifests(test_data_dir: &Path) -> GenerateResult<Vec<(String, ManifestV1)>> {
+    let entries = fs::read_dir(test_data_dir).map_err(|e| GenerateError::new(test_data_dir, e))?;
+
+    let mut manifests = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| GenerateError::new(test_data_dir, e))?
+            .path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(MANIFEST_SUFFIX))
+        else {
+            continue;
+        };
+        let json = fs::read_to_string(&path).map_err(|e| GenerateError::new(&path, e))?;
+        let manifest: ManifestV1 = serde_json::from_str(&json)
+            .map_err(|e| GenerateError::new(&path, io::Error::from(e)))?;
+        manifests.push((name.to_string(), manifest));
+    }
+    manifests.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(manifests)
+}
+
+/// Where each field of the fixture described by `manifest` sits, worked
+/// out from the data lengths and format alone
+pub fn layout_offsets(manifest: &ManifestV1) -> InputOffsets {
+    // The account count comes first
+    let mut offset = 8;
+    let mut accounts: Vec<AccountOffsets> = Vec::with_capacity(manifest.accounts.len());
+
+    for account in &manifest.accounts {
+        if let Some(index) = account.duplicate_of {
+            accounts.push(accounts[index as usize]);
+            offset += match manifest.format {
+                SerializationFormat::Aligned => 8,
+                SerializationFormat::Deprecated => 1,
+            };
+            continue;
+        }
+
+        let data_len = account.data_len;
+        match manifest.format {
+            SerializationFormat::Aligned => {
+                // marker, signer, writable, executable, original_data_len
+                let key = offset + 8;
+                let lamports = key + 64;
+                let data = lamports + 16;
+                accounts.push(AccountOffsets {
+                    key,
+                    lamports,
+                    data,
+                });
+                let padding = (8 - data_len % 8) % 8;
+                offset = data + data_len + MAX_PERMITTED_DATA_INCREASE + padding + 8;
+            }
+            SerializationFormat::Deprecated => {
+                // marker, signer, writable
+                let key = offset + 3;
+                let lamports = key + 32;
+                let data = lamports + 16;
+                accounts.push(AccountOffsets {
+                    key,
+                    lamports,
+                    data,
+                });
+                // owner, executable, rent_epoch
+                offset = data + data_len + 32 + 1 + 8;
+            }
+        }
+    }
+
+    let instruction_data = offset + 8;
+    InputOffsets {
+        accounts,
+        instruction_data,
+        program_id: instruction_data + manifest.instruction_data.len() / 2,
+    }
+}
+
+/// `name` as a Zig identifier, quoted with `@"..."` when it is not a
+/// valid bare identifier
+pub fn zig_identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let bare = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !ZIG_KEYWORDS.contains(&name);
+    if bare {
+        return name.to_string();
+    }
+    format!("@\"{}\"", name.escape_default())
+}
+
+/// Render expected.zig for `manifests`. The output depends only on its
+/// input, so regenerating unchanged fixtures leaves the file untouched.
+pub fn render_expected_zig(manifests: &[(String, ManifestV1)]) -> String {
+    let mut out = String::from(HEADER);
+
+    for (name, manifest) in manifests {
+        let offsets = layout_offsets(manifest);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "pub const {} = Fixture{{", zig_identifier(name));
+        let _ = writeln!(out, "    .file_name = \"{}.bin\",", name.escape_default());
+        let _ = writeln!(out, "    .account_count = {},", manifest.account_count);
+        let _ = writeln!(out, "    .accounts = &.{{");
+        for (account, account_offsets) in manifest.accounts.iter().zip(&offsets.accounts) {
+            let duplicate_of = account
+                .duplicate_of
+                .map_or("null".to_string(), |index| index.to_string());
+            let _ = writeln!(out, "        .{{");
+            let _ = writeln!(out, "            .key_offset = {},", account_offsets.key);
+            let _ = writeln!(
+                out,
+                "            .lamports_offset = {},",
+                account_offsets.lamports
+            );
+            let _ = writeln!(out, "            .data_offset = {},", account_offsets.data);
+            let _ = writeln!(out, "            .lamports = {},", account.lamports);
+            let _ = writeln!(out, "            .rent_epoch = {},", account.rent_epoch);
+            let _ = writeln!(out, "            .data_len = {},", account.data_len);
+            let _ = writeln!(out, "            .is_signer = {},", account.is_signer);
+            let _ = writeln!(out, "            .is_writable = {},", account.is_writable);
+            let _ = writeln!(out, "            .executable = {},", account.executable);
+            let _ = writeln!(out, "            .duplicate_of = {duplicate_of},");
+            let _ = writeln!(out, "        }},");
+        }
+        let _ = writeln!(out, "    }},");
+        let _ = writeln!(
+            out,
+            "    .instruction_data_offset = {},",
+            offsets.instruction_data
+        );
+        let _ = writeln!(out, "    .program_id_offset = {},", offsets.program_id);
+        let _ = writeln!(out, "    .byte_length = {},", offsets.program_id + 32);
+        let _ = writeln!(out, "    .negative = {},", manifest.negative);
+        let _ = writeln!(out, "}};");
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "pub const all = [_]*const Fixture{{");
+    for (name, _) in manifests {
+        let _ = writeln!(out, "    &{},", zig_identifier(name));
+    }
+    let _ = writeln!(out, "}};");
+    out
+}
+
+/// Regenerate `test_data/expected.zig` from the manifests next to the fixtures
+pub fn write_expected_zig(test_data_dir: &Path) -> GenerateResult<()> {
+    let manifests = load_manifests(test_data_dir)?;
+    let path = test_data_dir.join(EXPECTED_ZIG_FILE);
+    write_if_changed(&path, render_expected_zig(&manifests))?;
+    println!(
+        "Generated: {} ({} fixtures)",
+        EXPECTED_ZIG_FILE,
+        manifests.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_are_escaped_only_when_needed() {
+        assert_eq!(zig_identifier("sysvar_clock"), "sysvar_clock");
+        assert_eq!(zig_identifier("1kb"), "@\"1kb\"");
+        assert_eq!(zig_identifier("test"), "@\"test\"");
+        assert_eq!(zig_identifier("nonce-account"), "@\"nonce-account\"");
+        assert_eq!(zig_identifier("quote\"d"), "@\"quote\\\"d\"");
+    }
+}
@@ -0,0 +1,111 @@
+// Scenario registry lookups and the command line built on them
+use rust_test_helper::serialize_solana_format::{find_generator, FIXTURE_GENERATORS};
+use std::collections::HashSet;
+use std::process::Command;
+
+fn helper(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .args(args)
+        .output()
+        .expect("failed to run rust_test_helper")
+}
+
+#[test]
+fn scenarios_are_found_by_name_or_file_stem() {
+    let by_name = find_generator("rent_sysvar").unwrap();
+    assert_eq!(by_name.stems, ["sysvar_rent", "sysvar_rent_mainnet"]);
+    let by_stem = find_generator("sysvar_rent_mainnet").unwrap();
+    assert_eq!(by_stem.name, "rent_sysvar");
+    assert!(find_generator("no_such_scenario").is_none());
+}
+
+#[test]
+fn scenario_names_and_stems_are_unique() {
+    let mut names = HashSet::new();
+    let mut stems = HashSet::new();
+    for generator in FIXTURE_GENERATORS {
+        assert!(names.insert(generator.name), "{} twice", generator.name);
+        for stem in generator.stems {
+            assert!(stems.insert(*stem), "{stem} written twice");
+        }
+    }
+}
+
+#[test]
+fn unknown_scenario_exits_non_zero_without_generating() {
+    let output = helper(&["generate", "--scenario", "no_such_scenario"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown scenario `no_such_scenario`") && stderr.contains("`list`"),
+        "{stderr}"
+    );
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn list_prints_every_scenario_and_its_files() {
+    let output = helper(&["list"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), FIXTURE_GENERATORS.len());
+    assert!(stdout
+        .lines()
+        .any(|line| line.starts_with("single_account ")
+            && line.ends_with("solana_single_account.bin, deprecated_single_account.bin")));
+}
+
+#[test]
+fn generate_spec_writes_each_scenario_and_rejects_bad_specs() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("scenarios.toml");
+    std::fs::write(
+        &spec,
+        r#"
+[[scenario]]
+name = "from_spec"
+instruction_data = "0102"
+
+[[scenario.accounts]]
+key = "11111111111111111111111111111111"
+is_writable = true
+lamports = 7
+data = { fill = 0x5A, len = 3 }
+"#,
+    )
+    .unwrap();
+    let out = dir.path().join("out");
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("--output-dir")
+        .arg(&out)
+        .arg("generate")
+        .arg("--spec")
+        .arg(&spec)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    for extension in ["bin", "expected.json", "hexdump.txt", "offsets.json"] {
+        assert!(out.join(format!("from_spec.{extension}")).exists());
+    }
+
+    std::fs::write(
+        &spec,
+        "[[scenario]]\nname = \"bad\"\n[[scenario.accounts]]\nduplicate_of = 3\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("--output-dir")
+        .arg(dir.path().join("never"))
+        .arg("generate")
+        .arg("--spec")
+        .arg(&spec)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("error: scenario `bad`: account 0: duplicate_of 3"),
+        "{stderr}"
+    );
+    assert!(!dir.path().join("never").exists());
+}
@@ -0,0 +1,175 @@
+// --compress writes large fixtures as .bin.zst, and decompress restores them
+use rust_test_helper::compress::{
+    compressed_path, decompress_dir, decompress_fixture, read_fixture, DEFAULT_COMPRESS_THRESHOLD,
+    MAX_RAW_FIXTURE_SIZE,
+};
+use rust_test_helper::index::{Index, INDEX_FILE};
+use rust_test_helper::output::{OutputFormat, OutputOptions};
+use rust_test_helper::serialize_solana_format::*;
+use solana_program::hash::hash;
+use std::path::Path;
+use std::process::Command;
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hash(bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn compressing(threshold: u64) -> OutputOptions {
+    OutputOptions {
+        formats: vec![OutputFormat::Bin],
+        compress_threshold: Some(threshold),
+    }
+}
+
+fn generate(dir: &Path, names: &[&str], options: &OutputOptions) -> Vec<std::path::PathBuf> {
+    let generators: Vec<&FixtureGenerator> = names
+        .iter()
+        .map(|name| find_generator(name).unwrap())
+        .collect();
+    generate_scenarios(dir, &generators, DEFAULT_SEED, options).unwrap()
+}
+
+#[test]
+fn compress_then_decompress_reproduces_the_original_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let written = generate(
+        dir.path(),
+        &["max_accounts", "clock_sysvar"],
+        &compressing(DEFAULT_COMPRESS_THRESHOLD),
+    );
+
+    // 610208 bytes: over the threshold, so only the compressed copy is kept
+    let bin = dir.path().join("solana_max_accounts.bin");
+    let zst = compressed_path(&bin);
+    let original =
+        build_max_accounts_solana_format(&MAX_ACCOUNTS_INSTRUCTION_DATA, &TEST_PROGRAM_ID);
+    assert!(!bin.exists());
+    assert!(written.contains(&zst) && !written.contains(&bin));
+    assert!(std::fs::metadata(&zst).unwrap().len() < original.len() as u64 / 10);
+    assert_eq!(read_fixture(&bin).unwrap(), original);
+
+    // 40 bytes of Clock: left alone
+    let clock = dir.path().join("sysvar_clock.bin");
+    assert!(clock.exists());
+    assert!(!compressed_path(&clock).exists());
+
+    let overflow = dir.path().join("solana_max_accounts_overflow.bin");
+    assert_eq!(
+        decompress_dir(dir.path()).unwrap(),
+        vec![bin.clone(), overflow]
+    );
+    assert_eq!(std::fs::read(&bin).unwrap(), original);
+    assert!(zst.exists(), "decompressing keeps the .zst");
+}
+
+#[test]
+fn fixtures_over_the_raw_limit_are_compressed_without_the_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    let written = generate(dir.path(), &["max_data_account"], &OutputOptions::default());
+
+    let bin = dir.path().join("solana_max_data_account.bin");
+    let zst = compressed_path(&bin);
+    assert!(!bin.exists());
+    assert!(written.contains(&zst));
+    assert!(std::fs::metadata(&zst).unwrap().len() < MAX_RAW_FIXTURE_SIZE);
+    assert!(read_fixture(&bin).unwrap().len() as u64 > MAX_RAW_FIXTURE_SIZE);
+
+    let hexdump = std::fs::metadata(dir.path().join("solana_max_data_account.hexdump.txt"));
+    assert!(hexdump.unwrap().len() < 4096);
+}
+
+#[test]
+fn index_records_both_sizes_and_the_raw_hash() {
+    let dir = tempfile::tempdir().unwrap();
+    generate(
+        dir.path(),
+        &["alignment_sweep"],
+        &compressing(DEFAULT_COMPRESS_THRESHOLD),
+    );
+    let index: Index =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join(INDEX_FILE)).unwrap())
+            .unwrap();
+
+    let entry = index
+        .files
+        .iter()
+        .find(|entry| entry.path == "solana_alignment_sweep.bin.zst")
+        .unwrap();
+    let zst = std::fs::read(dir.path().join(&entry.path)).unwrap();
+    let raw = build_alignment_sweep_solana_format(&[], &TEST_PROGRAM_ID);
+    assert_eq!(entry.size, zst.len() as u64);
+    assert_eq!(entry.format, Some(SerializationFormat::Aligned));
+    let compression = entry.compression.as_ref().unwrap();
+    assert_eq!(compression.algorithm, "zstd");
+    assert_eq!(compression.uncompressed_size, raw.len() as u64);
+    assert_eq!(compression.uncompressed_sha256, sha256_hex(&raw));
+
+    assert!(index
+        .files
+        .iter()
+        .filter(|entry| entry.path != "solana_alignment_sweep.bin.zst")
+        .all(|entry| entry.compression.is_none()));
+}
+
+#[test]
+fn threshold_is_configurable_and_plain_runs_drop_stale_copies() {
+    let dir = tempfile::tempdir().unwrap();
+    let clock = dir.path().join("sysvar_clock.bin");
+
+    generate(dir.path(), &["clock_sysvar"], &compressing(0));
+    assert!(!clock.exists());
+    let zst = compressed_path(&clock);
+    assert_eq!(
+        decompress_fixture(&zst).unwrap(),
+        clock,
+        "decompress names the .bin it wrote"
+    );
+
+    generate(dir.path(), &["clock_sysvar"], &OutputOptions::default());
+    assert!(clock.exists());
+    assert!(!zst.exists());
+}
+
+#[test]
+fn cli_compresses_verifies_and_decompresses() {
+    let dir = tempfile::tempdir().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+            .arg("--output-dir")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&[
+        "generate",
+        "--scenario",
+        "max_lamports",
+        "--compress",
+        "--compress-threshold",
+        "100",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let bin = dir.path().join("solana_max_lamports.bin");
+    assert!(!bin.exists());
+
+    let output = run(&["verify"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("solana_max_lamports.bin.zst: OK (1 accounts)"));
+
+    let output = run(&["decompress"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        std::fs::read(&bin).unwrap(),
+        build_lamports_edge_solana_format(u64::MAX, &[], &TEST_PROGRAM_ID)
+    );
+
+    let output = run(&["decompress", "solana_max_lamports.expected.json"]);
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,197 @@
+// Serialize accounts, parse them back and compare against the inputs
+use rust_test_helper::builder::AccountBuilder;
+use rust_test_helper::deserialize::{
+    deserialize_account_buffer, deserialize_input, DeserializeError, ParsedAccount,
+};
+use rust_test_helper::serialize_solana_format::*;
+use solana_program::pubkey::Pubkey;
+
+fn key(first_byte: u8) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[0] = first_byte;
+    Pubkey::new_from_array(bytes)
+}
+
+fn account(
+    key: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    lamports: u64,
+    data: Vec<u8>,
+    rent_epoch: u64,
+) -> ParsedAccount {
+    ParsedAccount {
+        is_dup: false,
+        is_signer,
+        is_writable,
+        executable,
+        key,
+        owner: Pubkey::default(),
+        lamports,
+        data,
+        rent_epoch,
+    }
+}
+
+fn duplicate_of(original: &ParsedAccount) -> ParsedAccount {
+    ParsedAccount {
+        is_dup: true,
+        ..original.clone()
+    }
+}
+
+/// Serialize `accounts`, writing duplicates as the index of their original
+fn serialize(accounts: &[ParsedAccount]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    write_account_count(&mut buffer, accounts.len());
+    for account in accounts {
+        if account.is_dup {
+            let dup_index = accounts.iter().position(|a| a.key == account.key).unwrap();
+            serialize_duplicate_account(&mut buffer, dup_index as u8);
+            continue;
+        }
+        AccountBuilder::new()
+            .key(account.key)
+            .lamports(account.lamports)
+            .data(account.data.clone())
+            .owner(account.owner)
+            .signer(account.is_signer)
+            .writable(account.is_writable)
+            .executable(account.executable)
+            .rent_epoch(account.rent_epoch)
+            .build_into(&mut buffer);
+    }
+    append_instruction_epilogue(&mut buffer, &[1, 2, 3], &TEST_PROGRAM_ID);
+    buffer
+}
+
+#[test]
+fn round_trip_preserves_every_field() {
+    let a = account(key(0), true, true, false, 1_000, vec![0xAA; 5], 0);
+    let b = account(key(1), false, true, true, 2_000, vec![], u64::MAX);
+    let c = account(key(2), true, false, false, u64::MAX, vec![0xCC; 17], 361);
+    let inputs = vec![a.clone(), b.clone(), duplicate_of(&a), c, duplicate_of(&b)];
+
+    let parsed = deserialize_account_buffer(&serialize(&inputs)).unwrap();
+    assert_eq!(parsed, inputs);
+}
+
+#[test]
+fn generated_fixtures_match_their_inputs() {
+    let parsed =
+        deserialize_account_buffer(&build_single_account_solana_format(&[], &TEST_PROGRAM_ID))
+            .unwrap();
+    assert_eq!(
+        parsed,
+        [account(key(0), true, true, false, 1000, vec![0xAA; 10], 0)]
+    );
+
+    let parsed = deserialize_account_buffer(&build_multiple_accounts_solana_format(
+        &[],
+        &TEST_PROGRAM_ID,
+    ))
+    .unwrap();
+    assert_eq!(
+        parsed,
+        [
+            account(key(0), true, true, false, 1000, vec![0xAA; 5], 0),
+            account(key(1), false, true, false, 2000, vec![0xBB; 10], 361),
+            account(key(2), false, false, true, 3000, vec![0xCC; 15], u64::MAX),
+        ]
+    );
+
+    let parsed = deserialize_account_buffer(&build_accounts_with_duplicates_solana_format(
+        &[],
+        &TEST_PROGRAM_ID,
+    ))
+    .unwrap();
+    let a = account(key(0), true, true, false, 1000, vec![0xAA; 8], 0);
+    let b = account(key(1), false, true, true, 2000, vec![0xBB; 12], 0);
+    let c = account(key(3), true, false, false, 3000, vec![0xCC; 6], u64::MAX);
+    assert_eq!(
+        parsed,
+        [a.clone(), b.clone(), duplicate_of(&a), c, duplicate_of(&b)]
+    );
+
+    let parsed =
+        deserialize_account_buffer(&build_alignment_sweep_solana_format(&[], &TEST_PROGRAM_ID))
+            .unwrap();
+    assert_eq!(parsed.len(), 17);
+    for (i, parsed) in parsed.iter().enumerate() {
+        assert_eq!(parsed.key, key(i as u8));
+        assert_eq!(parsed.data, vec![i as u8; i]);
+    }
+
+    for (name, buffer, expected_accounts) in [
+        (
+            "empty_data_accounts",
+            build_empty_data_accounts_solana_format(&[], &TEST_PROGRAM_ID),
+            2,
+        ),
+        (
+            "complex_iteration",
+            build_complex_iteration_solana_format(&[], &TEST_PROGRAM_ID),
+            10,
+        ),
+        (
+            "alignment_padding",
+            build_alignment_padding_solana_format(&[], &TEST_PROGRAM_ID),
+            2,
+        ),
+        (
+            "realloc_space",
+            build_realloc_space_solana_format(&[], &TEST_PROGRAM_ID),
+            2,
+        ),
+        (
+            "full_entrypoint",
+            build_full_entrypoint_solana_format(&[], &TEST_PROGRAM_ID),
+            3,
+        ),
+    ] {
+        let parsed = deserialize_account_buffer(&buffer).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(parsed.len(), expected_accounts, "{name}");
+    }
+}
+
+#[test]
+fn truncated_buffer_reports_the_missing_field() {
+    let buffer = build_single_account_solana_format(&[], &TEST_PROGRAM_ID);
+    assert_eq!(
+        deserialize_account_buffer(&buffer[..8 + 30]),
+        Err(DeserializeError::UnexpectedEof {
+            offset: 8 + 8,
+            field: "key",
+        })
+    );
+}
+
+#[test]
+fn forward_duplicate_is_rejected() {
+    let mut buffer = Vec::new();
+    write_account_count(&mut buffer, 1);
+    buffer.extend_from_slice(&[0x00, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        deserialize_account_buffer(&buffer),
+        Err(DeserializeError::InvalidDuplicateIndex {
+            account: 0,
+            dup_index: 0,
+        })
+    );
+}
+
+#[test]
+fn early_duplicate_fixture_is_rejected() {
+    let path = std::path::Path::new("../test_data").join(INVALID_EARLY_DUPLICATE_FILE);
+    let buffer = std::fs::read(&path).expect("test_data missing, run `cargo run` first");
+    assert_eq!(buffer, build_invalid_early_duplicate(&TEST_PROGRAM_ID));
+    assert_eq!(buffer[8], 0x00);
+
+    let expected = DeserializeError::InvalidDuplicateIndex {
+        account: 0,
+        dup_index: 0,
+    };
+    assert_eq!(deserialize_account_buffer(&buffer), Err(expected.clone()));
+    assert_eq!(deserialize_input(&buffer).map(|_| ()), Err(expected));
+}
@@ -0,0 +1,106 @@
+// Generating twice with the same seed must give byte-identical output
+use rust_test_helper::manifest::write_test_data_manifest;
+use rust_test_helper::serialize_solana_format::{generate_solana_format_test_data, DEFAULT_SEED};
+use rust_test_helper::stable::{take_write_log, WriteStatus};
+use rust_test_helper::zig_codegen::write_expected_zig;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Generate everything into `dir` and return each file's contents by name
+fn generate(dir: &Path, seed: u64) -> BTreeMap<String, Vec<u8>> {
+    generate_solana_format_test_data(dir, seed).unwrap();
+    write_expected_zig(dir).unwrap();
+    write_test_data_manifest(dir).unwrap();
+
+    let mut files = BTreeMap::new();
+    read_files(dir, "", &mut files);
+    files
+}
+
+/// Add every file under `dir` to `files`, keyed by its path below the top
+fn read_files(dir: &Path, prefix: &str, files: &mut BTreeMap<String, Vec<u8>>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = format!("{prefix}{}", path.file_name().unwrap().to_string_lossy());
+        if path.is_dir() {
+            read_files(&path, &format!("{name}/"), files);
+        } else {
+            files.insert(name, std::fs::read(&path).unwrap());
+        }
+    }
+}
+
+#[test]
+fn same_seed_gives_identical_files() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    let a = generate(first.path(), DEFAULT_SEED);
+    let b = generate(second.path(), DEFAULT_SEED);
+
+    assert!(a.contains_key("solana_actual_accountinfo.bin"));
+    assert_eq!(a.keys().collect::<Vec<_>>(), b.keys().collect::<Vec<_>>());
+    for (name, bytes) in &a {
+        assert!(bytes == &b[name], "{name} differs between runs");
+    }
+}
+
+#[test]
+fn different_seed_changes_the_random_keys() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    let a = generate(first.path(), DEFAULT_SEED);
+    let b = generate(second.path(), DEFAULT_SEED + 1);
+
+    let fixture = "solana_actual_accountinfo.bin";
+    assert_ne!(a[fixture], b[fixture]);
+    // Fixtures without random keys do not depend on the seed
+    assert_eq!(
+        a["solana_single_account.bin"],
+        b["solana_single_account.bin"]
+    );
+}
+
+#[test]
+fn rerun_with_the_same_seed_updates_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    take_write_log();
+    let first = generate(dir.path(), DEFAULT_SEED);
+    let written = take_write_log();
+    assert!(written
+        .iter()
+        .all(|(_, status)| *status == WriteStatus::Updated));
+
+    let mtimes: BTreeMap<String, std::time::SystemTime> = first
+        .keys()
+        .map(|name| {
+            let modified = std::fs::metadata(dir.path().join(name))
+                .unwrap()
+                .modified()
+                .unwrap();
+            (name.clone(), modified)
+        })
+        .collect();
+
+    let second = generate(dir.path(), DEFAULT_SEED);
+    let rewritten = take_write_log();
+    assert_eq!(rewritten.len(), written.len());
+    // A fixture over MAX_RAW_FIXTURE_SIZE is written out raw on every run
+    // and then replaced by its .bin.zst, so only files still there count
+    let updated: Vec<_> = rewritten
+        .iter()
+        .filter(|(path, status)| *status == WriteStatus::Updated && path.exists())
+        .collect();
+    assert!(
+        updated.is_empty(),
+        "rewritten on the second run: {updated:?}"
+    );
+
+    assert_eq!(first, second);
+    for (name, modified) in &mtimes {
+        let now = std::fs::metadata(dir.path().join(name))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(*modified, now, "{name} was touched");
+    }
+}
@@ -0,0 +1,179 @@
+// Field-by-field comparison of two entrypoint buffers
+use rust_test_helper::diff::{diff_buffers, diff_dirs, Difference, FileDifferenceKind};
+use rust_test_helper::serialize_solana_format::*;
+use std::process::Command;
+
+fn duplicates() -> Vec<u8> {
+    build_accounts_with_duplicates_solana_format(&[0, 1], &TEST_PROGRAM_ID)
+}
+
+fn messages(differences: &[Difference]) -> Vec<String> {
+    differences.iter().map(Difference::to_string).collect()
+}
+
+#[test]
+fn identical_buffers_have_no_differences() {
+    assert_eq!(diff_buffers(&duplicates(), &duplicates()), []);
+}
+
+#[test]
+fn lamports_change_is_reported_as_a_field() {
+    let a = duplicates();
+    let mut b = a.clone();
+    b[80..88].copy_from_slice(&2000u64.to_le_bytes());
+
+    assert_eq!(
+        messages(&diff_buffers(&a, &b)),
+        ["offset 80: account 0 lamports: 1000 vs 2000"]
+    );
+}
+
+#[test]
+fn data_flags_and_epilogue_changes_are_each_reported() {
+    let a = duplicates();
+    let mut b = a.clone();
+    b[9] = 0; // account 0 is_signer
+    b[96 + 3] = 0x00; // account 0 data byte 3
+    let program_id = b.len() - 32;
+    b[program_id..].copy_from_slice(&[0x43; 32]);
+
+    assert_eq!(
+        messages(&diff_buffers(&a, &b)),
+        [
+            "offset 9: account 0 is_signer: 1 vs 0".to_string(),
+            "offset 99: account 0 data differs in 1 of 8 bytes, first at byte 3: 0xaa vs 0x00"
+                .to_string(),
+            format!(
+                "offset {program_id}: program_id: {} vs {}",
+                TEST_PROGRAM_ID,
+                solana_program::pubkey::Pubkey::new_from_array([0x43; 32])
+            ),
+        ]
+    );
+}
+
+#[test]
+fn padding_change_is_reported_when_fields_match() {
+    let a = duplicates();
+    let mut b = a.clone();
+    // Inside account 0's realloc space
+    b[96 + 8 + 100] = 1;
+
+    assert_eq!(
+        messages(&diff_buffers(&a, &b)),
+        ["offset 204: padding bytes 204..205 differ"]
+    );
+}
+
+#[test]
+fn structural_change_reports_the_differing_fields() {
+    // Different data lengths shift every later offset
+    let a = build_multiple_accounts(SerializationFormat::Aligned, &[1, 2, 3], &TEST_PROGRAM_ID);
+    let b = build_from_accounts(
+        SerializationFormat::Aligned,
+        &multiple_accounts_scenario()[..2],
+        &[1, 2, 3],
+        &TEST_PROGRAM_ID,
+    );
+
+    let messages = messages(&diff_buffers(&a, &b));
+    assert_eq!(messages[0], "offset 0: num_accounts: 3 vs 2");
+    assert!(messages
+        .iter()
+        .any(|m| m.ends_with("account 2 is only in the first buffer")));
+    assert!(!messages.iter().any(|m| m.contains("padding")));
+}
+
+#[test]
+fn unparseable_buffer_falls_back_to_the_first_differing_byte() {
+    let a = duplicates();
+    let b = &a[..200];
+    let mut c = a[..200].to_vec();
+    c[150] ^= 0xFF;
+
+    assert_eq!(
+        messages(&diff_buffers(&a, b)),
+        [
+            "offset 200: first differing byte: 0x00 vs end of buffer (the second buffer does not \
+          parse: unexpected end of buffer reading realloc_space at offset 104)"
+        ]
+    );
+    assert!(messages(&diff_buffers(b, &c))[0].starts_with("offset 150: first differing byte"));
+}
+
+#[test]
+fn diff_exits_non_zero_only_when_files_differ() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.bin");
+    let b = dir.path().join("b.bin");
+    std::fs::write(&a, duplicates()).unwrap();
+    let mut changed = duplicates();
+    changed[80] = 0xFF;
+    std::fs::write(&b, &changed).unwrap();
+
+    let diff = |x: &std::path::Path, y: &std::path::Path| {
+        Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+            .arg("diff")
+            .arg(x)
+            .arg(y)
+            .output()
+            .unwrap()
+    };
+
+    let output = diff(&a, &a);
+    assert!(output.status.success(), "{output:?}");
+
+    let output = diff(&a, &b);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("offset 80: account 0 lamports: 1000 vs 1023"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn directories_report_missing_and_differing_files() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    for (dir, bytes) in [(&first, [1, 2, 3]), (&second, [1, 2, 4])] {
+        std::fs::write(dir.path().join("same.bin"), [9]).unwrap();
+        std::fs::write(dir.path().join("changed.bin"), bytes).unwrap();
+    }
+    std::fs::write(first.path().join("old.bin"), [0]).unwrap();
+    std::fs::write(second.path().join("new.bin"), [0]).unwrap();
+
+    let differences = diff_dirs(first.path(), second.path()).unwrap();
+    let lines: Vec<String> = differences.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "changed.bin: differs from offset 2",
+            "new.bin: only in the second directory",
+            "old.bin: only in the first directory",
+        ]
+    );
+    assert_eq!(
+        differences[0].kind,
+        FileDifferenceKind::Differs { offset: 2 }
+    );
+    assert_eq!(diff_dirs(first.path(), first.path()).unwrap(), []);
+}
+
+#[test]
+fn diff_subcommand_compares_directories() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    std::fs::write(first.path().join("a.bin"), [1]).unwrap();
+    std::fs::write(second.path().join("a.bin"), [2]).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("diff")
+        .arg(first.path())
+        .arg(second.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("a.bin: differs from offset 0"), "{stdout}");
+}
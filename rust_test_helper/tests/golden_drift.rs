@@ -0,0 +1,44 @@
+// Diff the hand-rolled fixtures against the runtime's golden buffers
+use rust_test_helper::golden::{first_mismatch, GOLDEN_SCENARIOS};
+use std::path::Path;
+
+#[test]
+fn first_mismatch_reports_the_offset() {
+    assert_eq!(first_mismatch(&[1, 2, 3], &[1, 2, 3]), None);
+    assert_eq!(first_mismatch(&[1, 2, 3], &[1, 9, 3]), Some(1));
+    assert_eq!(first_mismatch(&[1, 2, 3], &[1, 2]), Some(2));
+    assert_eq!(first_mismatch(&[], &[0]), Some(0));
+}
+
+/// The golden files are committed, so every scenario is checked. With the
+/// `golden` feature the loader serializes each one again, and the file on
+/// disk must still match it.
+#[test]
+fn hand_rolled_fixtures_match_golden_buffers() {
+    let test_data_dir = Path::new("../test_data");
+    for scenario in &GOLDEN_SCENARIOS {
+        let golden = scenario.read_golden(test_data_dir).unwrap_or_else(|| {
+            panic!(
+                "{} is missing, run `cargo run --features golden`",
+                scenario.file_name()
+            )
+        });
+        #[cfg(feature = "golden")]
+        assert!(
+            scenario.golden() == golden,
+            "{} is stale, run `cargo run --features golden`",
+            scenario.file_name()
+        );
+
+        let hand_rolled = scenario.hand_rolled();
+        if let Some(offset) = first_mismatch(&golden, &hand_rolled) {
+            panic!(
+                "{}: hand-rolled buffer drifts from the runtime at offset {offset} \
+                 (golden {} bytes, hand-rolled {} bytes)",
+                scenario.name,
+                golden.len(),
+                hand_rolled.len()
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// test_data/index.json lists every generated file with its size and hash
+use rust_test_helper::index::{Index, GENERATOR_VERSION, INDEX_FILE, INDEX_VERSION};
+use rust_test_helper::output::OutputOptions;
+use rust_test_helper::serialize_solana_format::{
+    find_generator, generate_scenarios, generate_solana_format_test_data, is_invalid_fixture,
+    SerializationFormat, DEFAULT_SEED, FIXTURE_GENERATORS, INVALID_EARLY_DUPLICATE_FILE,
+};
+use solana_program::hash::hash;
+use std::path::Path;
+
+fn read_index(dir: &Path) -> Index {
+    serde_json::from_str(&std::fs::read_to_string(dir.join(INDEX_FILE)).unwrap()).unwrap()
+}
+
+#[test]
+fn index_entries_match_the_files_on_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let written = generate_solana_format_test_data(dir.path(), DEFAULT_SEED).unwrap();
+    let index = read_index(dir.path());
+
+    assert_eq!(index.version, INDEX_VERSION);
+    // Everything written except the index itself
+    assert_eq!(index.files.len(), written.len() - 1);
+    for entry in &index.files {
+        let path = dir.path().join(&entry.path);
+        let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("{}: {e}", entry.path));
+        assert_eq!(entry.size, bytes.len() as u64, "{}", entry.path);
+        let sha256: String = hash(&bytes)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(entry.sha256, sha256, "{}", entry.path);
+        assert_eq!(entry.generator_version, GENERATOR_VERSION);
+        assert!(
+            FIXTURE_GENERATORS.iter().any(|g| g.name == entry.scenario),
+            "{}",
+            entry.scenario
+        );
+        assert_eq!(
+            entry.format.is_some(),
+            (entry.name.ends_with(".bin") || entry.name.ends_with(".bin.zst"))
+                && !is_invalid_fixture(Path::new(&entry.path))
+        );
+    }
+
+    let fixture = |name: &str| index.files.iter().find(|e| e.name == name).unwrap();
+    assert_eq!(
+        fixture("solana_single_account.bin").scenario,
+        "single_account"
+    );
+    assert_eq!(
+        fixture("deprecated_single_account.bin").format,
+        Some(SerializationFormat::Deprecated)
+    );
+    assert_eq!(fixture("pda_expected.json").scenario, "pda_accounts");
+    assert_eq!(fixture(INVALID_EARLY_DUPLICATE_FILE).format, None);
+    assert_eq!(
+        fixture("duplicate_forward.bin").path,
+        "negative/duplicate_forward.bin"
+    );
+    assert_eq!(fixture("duplicate_forward.bin").format, None);
+}
+
+#[test]
+fn generating_one_scenario_keeps_the_others_in_the_index() {
+    let dir = tempfile::tempdir().unwrap();
+    generate_solana_format_test_data(dir.path(), DEFAULT_SEED).unwrap();
+    let before = read_index(dir.path());
+
+    let clock = find_generator("clock_sysvar").unwrap();
+    generate_scenarios(
+        dir.path(),
+        &[clock],
+        DEFAULT_SEED,
+        &OutputOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(read_index(dir.path()), before);
+}
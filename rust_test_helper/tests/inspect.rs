@@ -0,0 +1,137 @@
+// The inspect subcommand on generated and truncated fixtures
+use rust_test_helper::deserialize::{inspect_input, Field};
+use rust_test_helper::serialize_solana_format::*;
+use std::process::Command;
+
+fn duplicates() -> Vec<u8> {
+    build_accounts_with_duplicates_solana_format(&[0, 1], &TEST_PROGRAM_ID)
+}
+
+#[test]
+fn every_field_of_a_valid_fixture_is_reported_with_its_offset() {
+    let buffer = duplicates();
+    let inspection = inspect_input(&buffer);
+
+    assert_eq!(inspection.error, None);
+    assert_eq!(inspection.buffer_len, buffer.len());
+    assert_eq!(inspection.trailing_bytes, 0);
+    assert_eq!(
+        inspection.num_accounts,
+        Some(Field {
+            offset: 0,
+            value: 5
+        })
+    );
+    assert_eq!(inspection.accounts.len(), 5);
+
+    let first = &inspection.accounts[0];
+    assert_eq!(first.offset, 8);
+    assert_eq!(first.duplicate_of, None);
+    assert_eq!(first.is_signer.as_ref().unwrap().value, 1);
+    assert_eq!(first.key.as_ref().unwrap().offset, 16);
+    assert_eq!(
+        first.key.as_ref().unwrap().value,
+        "11111111111111111111111111111111"
+    );
+    assert_eq!(first.owner.as_ref().unwrap().offset, 48);
+    assert_eq!(
+        first.lamports,
+        Some(Field {
+            offset: 80,
+            value: 1000
+        })
+    );
+    assert_eq!(first.data_len.as_ref().unwrap().value, 8);
+    assert_eq!(
+        first.data,
+        Some(Field {
+            offset: 96,
+            value: "aa".repeat(8)
+        })
+    );
+
+    // The third entry is a duplicate of the first and has no fields of its own
+    let duplicate = &inspection.accounts[2];
+    assert_eq!(duplicate.duplicate_of.as_ref().unwrap().value, 0);
+    assert_eq!(duplicate.key, None);
+
+    let instruction_data = inspection.instruction_data.as_ref().unwrap();
+    assert_eq!(instruction_data.value, "0001");
+    let program_id = inspection.program_id.as_ref().unwrap();
+    assert_eq!(program_id.offset, buffer.len() - 32);
+    assert_eq!(program_id.value, TEST_PROGRAM_ID.to_string());
+}
+
+#[test]
+fn data_is_cut_to_its_first_32_bytes() {
+    let buffer = build_alignment_sweep_solana_format(&[], &TEST_PROGRAM_ID);
+    let inspection = inspect_input(&buffer);
+
+    assert_eq!(inspection.error, None);
+    for account in &inspection.accounts {
+        let data_len = account.data_len.as_ref().unwrap().value as usize;
+        let shown = account.data.as_ref().unwrap().value.len() / 2;
+        assert_eq!(shown, data_len.min(32));
+    }
+}
+
+#[test]
+fn truncated_fixture_reports_where_parsing_stopped() {
+    let buffer = duplicates();
+    // Cut inside the second account's owner
+    let truncated = &buffer[..inspect_input(&buffer).accounts[1].offset + 50];
+    let inspection = inspect_input(truncated);
+
+    let error = inspection.error.as_ref().unwrap();
+    assert_eq!(error.field, "owner");
+    assert_eq!(error.offset, inspection.accounts[1].offset + 40);
+    assert_eq!(
+        error.message,
+        format!(
+            "unexpected end of buffer reading owner at offset {}",
+            error.offset
+        )
+    );
+
+    // Everything before the cut is still reported
+    assert_eq!(inspection.accounts.len(), 2);
+    assert!(inspection.accounts[0].rent_epoch.is_some());
+    assert!(inspection.accounts[1].key.is_some());
+    assert_eq!(inspection.accounts[1].owner, None);
+    assert_eq!(inspection.program_id, None);
+
+    assert!(inspect_input(&[]).error.is_some());
+}
+
+#[test]
+fn inspect_prints_json_and_fails_on_truncated_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let buffer = duplicates();
+    let whole = dir.path().join("whole.bin");
+    let truncated = dir.path().join("truncated.bin");
+    std::fs::write(&whole, &buffer).unwrap();
+    std::fs::write(&truncated, &buffer[..100]).unwrap();
+
+    let inspect = |path: &std::path::Path| {
+        Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+            .arg("inspect")
+            .arg(path)
+            .output()
+            .unwrap()
+    };
+
+    let output = inspect(&whole);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["num_accounts"]["value"], 5);
+    assert_eq!(json["accounts"][4]["duplicate_of"]["value"], 1);
+    assert!(json.get("error").is_none());
+
+    let output = inspect(&truncated);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["error"]["field"], "data");
+    assert_eq!(json["error"]["offset"], 96);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
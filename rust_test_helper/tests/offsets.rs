@@ -0,0 +1,59 @@
+// Check the recorded .offsets.json files against the fixtures they describe
+use rust_test_helper::manifest::ManifestV1;
+use rust_test_helper::offsets::{offsets_path, OffsetsFile};
+use rust_test_helper::serialize_solana_format::is_invalid_fixture;
+
+const TEST_DATA: &str = "../test_data";
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+#[test]
+fn lamports_sit_at_the_recorded_offset_in_every_fixture() {
+    let mut checked = 0;
+    for entry in std::fs::read_dir(TEST_DATA).expect("test_data missing, run `cargo run` first") {
+        let bin_path = entry.unwrap().path();
+        let name = bin_path.file_stem().unwrap().to_string_lossy().into_owned();
+        // Golden files come from the real loader, not from the generators,
+        // and invalid fixtures have no offsets to record
+        if bin_path.extension().is_none_or(|ext| ext != "bin")
+            || name.starts_with("golden_")
+            || is_invalid_fixture(&bin_path)
+        {
+            continue;
+        }
+
+        let bytes = std::fs::read(&bin_path).unwrap();
+        let offsets: OffsetsFile = serde_json::from_str(
+            &std::fs::read_to_string(offsets_path(&bin_path))
+                .unwrap_or_else(|e| panic!("{name}: no offsets file: {e}")),
+        )
+        .unwrap();
+        let manifest: ManifestV1 = serde_json::from_str(
+            &std::fs::read_to_string(bin_path.with_extension("expected.json")).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(offsets.offsets.len(), manifest.account_count, "{name}");
+        for (i, (offsets, account)) in offsets.offsets.iter().zip(&manifest.accounts).enumerate() {
+            assert_eq!(
+                read_u64(&bytes, offsets.lamports),
+                account.lamports,
+                "{name}: account {i} lamports"
+            );
+            assert_eq!(
+                read_u64(&bytes, offsets.data_len),
+                account.data_len as u64,
+                "{name}: account {i} data_len"
+            );
+            assert_eq!(
+                read_u64(&bytes, offsets.rent_epoch),
+                account.rent_epoch,
+                "{name}: account {i} rent_epoch"
+            );
+        }
+        checked += 1;
+    }
+    assert!(checked > 0, "no fixtures found in {TEST_DATA}");
+}
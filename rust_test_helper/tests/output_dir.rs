@@ -0,0 +1,92 @@
+// Generating into a directory other than test_data
+use rust_test_helper::serialize_solana_format::{
+    find_generator, generate_solana_format_test_data, DEFAULT_SEED,
+};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Every file under `dir`, including those in subdirectories
+fn files_in(dir: &Path) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(files_in(&path));
+        } else {
+            files.insert(path);
+        }
+    }
+    files
+}
+
+#[test]
+fn generate_reports_exactly_the_files_it_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let written = generate_solana_format_test_data(dir.path(), DEFAULT_SEED).unwrap();
+
+    let written: BTreeSet<PathBuf> = written.into_iter().collect();
+    assert_eq!(files_in(dir.path()), written);
+    for stem in ["solana_single_account", "spl_token_mint", "sysvar_clock"] {
+        for extension in ["bin", "expected.json", "hexdump.txt", "offsets.json"] {
+            let path = dir.path().join(format!("{stem}.{extension}"));
+            assert!(written.contains(&path), "{} not written", path.display());
+        }
+    }
+}
+
+#[test]
+fn output_dir_flag_creates_the_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let output_dir = dir.path().join("nested").join("fixtures");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .args(["generate", "--scenario", "single_account"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    let mut expected: BTreeSet<PathBuf> = find_generator("single_account")
+        .unwrap()
+        .files(&output_dir)
+        .into_iter()
+        .collect();
+    expected.insert(output_dir.join("expected.zig"));
+    expected.insert(output_dir.join("manifest.json"));
+    expected.insert(output_dir.join("index.json"));
+    assert_eq!(files_in(&output_dir), expected);
+}
+
+#[test]
+fn output_dir_falls_back_to_the_environment() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .env("TEST_DATA_DIR", dir.path())
+        .args(["generate", "--scenario", "clock_sysvar"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.path().join("sysvar_clock.bin").exists());
+}
+
+#[test]
+fn uncreatable_output_dir_is_an_error_not_a_panic() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("not_a_directory");
+    std::fs::write(&file, b"").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("--output-dir")
+        .arg(file.join("fixtures"))
+        .arg("generate")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("error: "), "{stderr}");
+    assert!(stderr.contains("not_a_directory"), "{stderr}");
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
@@ -0,0 +1,116 @@
+// Writing each fixture as .bin, .hex and .json in one pass
+use rust_test_helper::index::{Index, INDEX_FILE};
+use rust_test_helper::manifest::read_manifest;
+use rust_test_helper::output::{JsonFixture, OutputFormat, OutputOptions};
+use rust_test_helper::serialize_solana_format::{find_generator, generate_scenarios, DEFAULT_SEED};
+use std::path::Path;
+use std::process::Command;
+
+fn all_formats() -> OutputOptions {
+    OutputOptions {
+        formats: vec![OutputFormat::Bin, OutputFormat::Hex, OutputFormat::Json],
+        compress_threshold: None,
+    }
+}
+
+fn decode_hex(text: &str) -> Vec<u8> {
+    let digits = text.strip_suffix('\n').expect("hex ends with a newline");
+    assert!(!digits.contains(char::is_whitespace));
+    assert_eq!(digits, digits.to_lowercase());
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// The three representations of `stem` in `dir`, decoded back to bytes
+fn decode_all(dir: &Path, stem: &str) -> [Vec<u8>; 3] {
+    let bin_path = dir.join(format!("{stem}.bin"));
+    let bin = std::fs::read(&bin_path).unwrap();
+    let hex = decode_hex(&std::fs::read_to_string(dir.join(format!("{stem}.hex"))).unwrap());
+
+    let json = std::fs::read_to_string(dir.join(format!("{stem}.json"))).unwrap();
+    let json: JsonFixture = serde_json::from_str(&json).unwrap();
+    assert_eq!(json.name, format!("{stem}.bin"));
+    assert_eq!(json.byte_length, bin.len());
+    assert_eq!(json.manifest, read_manifest(&bin_path).unwrap());
+
+    [bin, hex, json.decode().unwrap()]
+}
+
+#[test]
+fn every_format_decodes_to_the_same_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let generator = find_generator("nonce_account").unwrap();
+    let written =
+        generate_scenarios(dir.path(), &[generator], DEFAULT_SEED, &all_formats()).unwrap();
+
+    for stem in generator.stems {
+        let [bin, hex, json] = decode_all(dir.path(), stem);
+        assert_eq!(hex, bin, "{stem}");
+        assert_eq!(json, bin, "{stem}");
+        for extension in ["hex", "json"] {
+            assert!(written.contains(&dir.path().join(format!("{stem}.{extension}"))));
+        }
+    }
+
+    // The extra files are indexed under their scenario like the rest
+    let index: Index =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join(INDEX_FILE)).unwrap())
+            .unwrap();
+    let hex = index
+        .files
+        .iter()
+        .find(|entry| entry.path == "nonce_account.hex")
+        .unwrap();
+    assert_eq!(hex.scenario, "nonce_account");
+    assert_eq!(hex.format, None);
+}
+
+#[test]
+fn only_the_bin_is_written_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let generator = find_generator("clock_sysvar").unwrap();
+    generate_scenarios(
+        dir.path(),
+        &[generator],
+        DEFAULT_SEED,
+        &OutputOptions::default(),
+    )
+    .unwrap();
+
+    assert!(dir.path().join("sysvar_clock.bin").exists());
+    assert!(!dir.path().join("sysvar_clock.hex").exists());
+    assert!(!dir.path().join("sysvar_clock.json").exists());
+}
+
+#[test]
+fn format_flag_takes_a_comma_list() {
+    let dir = tempfile::tempdir().unwrap();
+    let generate = |formats: &str| {
+        Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+            .arg("--output-dir")
+            .arg(dir.path())
+            .args([
+                "generate",
+                "--scenario",
+                "single_account",
+                "--format",
+                formats,
+            ])
+            .output()
+            .unwrap()
+    };
+
+    let output = generate("hex,json");
+    assert!(output.status.success(), "{output:?}");
+    for stem in ["solana_single_account", "deprecated_single_account"] {
+        let [bin, hex, json] = decode_all(dir.path(), stem);
+        assert_eq!(hex, bin);
+        assert_eq!(json, bin);
+    }
+
+    let output = generate("bin,yaml");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("yaml"));
+}
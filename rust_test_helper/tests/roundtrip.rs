@@ -0,0 +1,91 @@
+// Feed every generated fixture to solana_program's own entrypoint
+// deserializer and check it recovers what the helper wrote
+use rust_test_helper::deserialize::{deserialize_input, ParsedInput};
+use rust_test_helper::serialize_solana_format::{is_invalid_fixture, TEST_PROGRAM_ID};
+use solana_program::entrypoint::deserialize;
+use std::path::Path;
+
+/// Fixtures in the aligned format: everything except the deprecated loader,
+/// golden files and invalid fixtures
+fn aligned_fixtures() -> Vec<(String, Vec<u8>)> {
+    let mut fixtures: Vec<(String, Vec<u8>)> = std::fs::read_dir(Path::new("../test_data"))
+        .expect("test_data missing, run `cargo run` first")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bin"))
+        .filter(|path| !is_invalid_fixture(path))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, std::fs::read(&path).unwrap())
+        })
+        .filter(|(name, _)| !name.starts_with("deprecated_") && !name.starts_with("golden_"))
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+/// Copy `bytes` into a leaked, 8-byte aligned buffer the way the loader
+/// hands the input region to a program
+fn leak_aligned(bytes: &[u8]) -> *mut u8 {
+    let words = vec![0u64; bytes.len().div_ceil(8)].into_boxed_slice();
+    let ptr = Box::leak(words).as_mut_ptr() as *mut u8;
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+    ptr
+}
+
+fn check_fixture(name: &str, bytes: &[u8]) {
+    let expected: ParsedInput =
+        deserialize_input(bytes).unwrap_or_else(|e| panic!("{name}: helper parse failed: {e}"));
+    let (program_id, accounts, instruction_data) = unsafe { deserialize(leak_aligned(bytes)) };
+
+    assert_eq!(*program_id, TEST_PROGRAM_ID, "{name}: program_id");
+    assert_eq!(*program_id, expected.program_id, "{name}: program_id");
+    assert_eq!(
+        instruction_data, expected.instruction_data,
+        "{name}: instruction_data"
+    );
+    assert_eq!(
+        accounts.len(),
+        expected.accounts.len(),
+        "{name}: account count"
+    );
+
+    for (i, (actual, expected)) in accounts.iter().zip(&expected.accounts).enumerate() {
+        assert_eq!(*actual.key, expected.key, "{name}: account {i} key");
+        assert_eq!(*actual.owner, expected.owner, "{name}: account {i} owner");
+        assert_eq!(
+            actual.lamports(),
+            expected.lamports,
+            "{name}: account {i} lamports"
+        );
+        assert_eq!(
+            *actual.data.borrow(),
+            &expected.data[..],
+            "{name}: account {i} data"
+        );
+        assert_eq!(
+            actual.is_signer, expected.is_signer,
+            "{name}: account {i} is_signer"
+        );
+        assert_eq!(
+            actual.is_writable, expected.is_writable,
+            "{name}: account {i} is_writable"
+        );
+        assert_eq!(
+            actual.executable, expected.executable,
+            "{name}: account {i} executable"
+        );
+        assert_eq!(
+            actual.rent_epoch, expected.rent_epoch,
+            "{name}: account {i} rent_epoch"
+        );
+    }
+}
+
+#[test]
+fn entrypoint_deserialize_recovers_every_fixture() {
+    let fixtures = aligned_fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in test_data");
+    for (name, bytes) in &fixtures {
+        check_fixture(name, bytes);
+    }
+}
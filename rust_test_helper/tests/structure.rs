@@ -0,0 +1,195 @@
+// Structural checks on deliberately corrupted entrypoint buffers
+use rust_test_helper::constants::MAX_PERMITTED_DATA_INCREASE;
+use rust_test_helper::deserialize::{check_structure, Violation};
+use rust_test_helper::serialize_solana_format::*;
+use std::process::Command;
+
+/// Offset of account 1 in the multiple_accounts buffer: the count, then
+/// account 0's 88-byte header, 5 data bytes, realloc space, 3 bytes of
+/// padding and rent_epoch
+const ACCOUNT_1: usize = 8 + 88 + 5 + MAX_PERMITTED_DATA_INCREASE + 3 + 8;
+
+fn multiple_accounts() -> Vec<u8> {
+    build_multiple_accounts(SerializationFormat::Aligned, &[1, 2, 3], &TEST_PROGRAM_ID)
+}
+
+fn messages(violations: &[Violation]) -> Vec<String> {
+    violations.iter().map(Violation::to_string).collect()
+}
+
+#[test]
+fn generated_buffers_have_no_violations() {
+    for buffer in [
+        multiple_accounts(),
+        build_accounts_with_duplicates_solana_format(&[0, 1], &TEST_PROGRAM_ID),
+        build_alignment_sweep_solana_format(&[], &TEST_PROGRAM_ID),
+    ] {
+        assert_eq!(check_structure(&buffer), []);
+    }
+}
+
+#[test]
+fn account_count_that_does_not_match_the_entries() {
+    for stated in [2u8, 4, 200] {
+        let mut buffer = multiple_accounts();
+        buffer[0] = stated;
+        assert_eq!(
+            messages(&check_structure(&buffer)),
+            [format!(
+                "offset 0: num_accounts is {stated} but the buffer holds 3 accounts"
+            )]
+        );
+    }
+}
+
+#[test]
+fn account_count_past_the_end_of_the_buffer() {
+    let buffer = multiple_accounts();
+    let truncated = &buffer[..ACCOUNT_1 + 20];
+    assert_eq!(
+        messages(&check_structure(truncated)),
+        [format!(
+            "offset {}: num_accounts is 3 but only 1 complete entries are present \
+             (buffer ends while reading key)",
+            ACCOUNT_1 + 8
+        )]
+    );
+}
+
+#[test]
+fn every_violation_is_reported_with_its_offset() {
+    let mut buffer = multiple_accounts();
+    let padding = 8 + 88 + 5 + MAX_PERMITTED_DATA_INCREASE;
+    buffer[padding + 1] = 0x01; // account 0 alignment padding
+    buffer[8 + 88 + 5 + 100] = 0x02; // account 0 realloc space
+    buffer[ACCOUNT_1 + 2] = 7; // account 1 is_writable
+    buffer.push(0); // trailing byte
+
+    assert_eq!(
+        messages(&check_structure(&buffer)),
+        [
+            format!(
+                "offset {}: account 0 realloc_space byte 100 is 0x02, not zero",
+                8 + 88 + 5 + 100
+            ),
+            format!(
+                "offset {}: account 0 alignment_padding byte 1 is 0x01, not zero",
+                padding + 1
+            ),
+            format!(
+                "offset {}: account 1 is_writable is 7, not 0 or 1",
+                ACCOUNT_1 + 2
+            ),
+            format!("offset {}: 1 bytes follow the program_id", buffer.len() - 1),
+        ]
+    );
+}
+
+#[test]
+fn duplicates_must_point_at_an_earlier_original() {
+    let mut buffer = build_accounts_with_duplicates_solana_format(&[], &TEST_PROGRAM_ID);
+    let violations = check_structure(&buffer);
+    assert_eq!(violations, []);
+
+    // Account 2 is a duplicate of account 0; point it forward instead
+    let accounts = rust_test_helper::deserialize::deserialize_input_offsets(&buffer).unwrap();
+    let dup_entry = accounts.accounts[1].data + 12 + MAX_PERMITTED_DATA_INCREASE + 4 + 8;
+    assert_eq!(buffer[dup_entry], 0);
+    buffer[dup_entry] = 3;
+    buffer[dup_entry + 3] = 0xEE;
+    assert_eq!(
+        messages(&check_structure(&buffer)),
+        [
+            format!(
+                "offset {dup_entry}: account 2 is a duplicate of account 3, which is not before it"
+            ),
+            format!(
+                "offset {}: account 2 dup_padding byte 2 is 0xee, not zero",
+                dup_entry + 3
+            ),
+        ]
+    );
+
+    // A duplicate of a duplicate
+    buffer[dup_entry] = 0;
+    buffer[dup_entry + 3] = 0;
+    let last_dup = buffer.len() - 8 - 32 - 8;
+    assert_eq!(buffer[last_dup], 1);
+    buffer[last_dup] = 2;
+    assert_eq!(
+        messages(&check_structure(&buffer)),
+        [format!(
+            "offset {last_dup}: account 4 is a duplicate of account 2, which is itself a duplicate"
+        )]
+    );
+}
+
+#[test]
+fn data_len_past_the_end_of_the_buffer() {
+    let mut buffer = multiple_accounts();
+    let data_len = ACCOUNT_1 + 8 + 32 + 32 + 8;
+    buffer[data_len..data_len + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let violations = check_structure(&buffer);
+    assert_eq!(violations.len(), 1, "{violations:?}");
+    assert_eq!(violations[0].offset, data_len);
+    assert!(violations[0]
+        .message
+        .starts_with("account 1 data_len is 18446744073709551615, but only"));
+}
+
+#[test]
+fn truncated_buffer_reports_where_it_ends() {
+    let buffer = multiple_accounts();
+    let truncated = &buffer[..buffer.len() - 4];
+    assert_eq!(
+        messages(&check_structure(truncated)),
+        [format!(
+            "offset {}: buffer ends while reading program_id",
+            buffer.len() - 32
+        )]
+    );
+}
+
+#[test]
+fn verify_reports_each_problem_with_the_file_name_and_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("corrupt.bin");
+    let mut buffer = multiple_accounts();
+    buffer[ACCOUNT_1 + 1] = 2; // account 1 is_signer
+    buffer.extend_from_slice(&[0, 0]);
+    std::fs::write(&file, &buffer).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .arg("verify")
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert_eq!(lines.len(), 2, "{stderr}");
+    for line in lines {
+        assert!(
+            line.starts_with(&format!("{}: offset ", file.display())),
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn verify_flags_every_committed_negative_fixture() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_test_helper"))
+        .args(["verify", "../test_data/negative"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty(), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for fixture in rust_test_helper::negative_fixtures::NEGATIVE_FIXTURES {
+        assert!(
+            stderr.contains(&format!("{}.bin: ", fixture.name)),
+            "{} not flagged:\n{stderr}",
+            fixture.name
+        );
+    }
+}
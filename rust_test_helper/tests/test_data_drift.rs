@@ -0,0 +1,40 @@
+// Regenerating every fixture must reproduce the committed test_data, so the
+// Zig tests never run against data a generator change has made stale
+use rust_test_helper::diff::{diff_dirs, FileDifferenceKind};
+use rust_test_helper::manifest::write_test_data_manifest;
+use rust_test_helper::negative_fixtures::NEGATIVE_DIR;
+use rust_test_helper::serialize_solana_format::{generate_solana_format_test_data, DEFAULT_SEED};
+use rust_test_helper::zig_codegen::write_expected_zig;
+use std::path::Path;
+
+#[test]
+fn committed_test_data_is_up_to_date() {
+    let generated = tempfile::tempdir().unwrap();
+    generate_solana_format_test_data(generated.path(), DEFAULT_SEED).unwrap();
+    write_expected_zig(generated.path()).unwrap();
+    write_test_data_manifest(generated.path()).unwrap();
+
+    // Files only in test_data, like golden buffers, are not generated here.
+    // diff_dirs does not descend, so the negative fixtures get their own pass.
+    let mut stale: Vec<String> = Vec::new();
+    for subdir in ["", NEGATIVE_DIR] {
+        stale.extend(
+            diff_dirs(
+                &Path::new("../test_data").join(subdir),
+                &generated.path().join(subdir),
+            )
+            .unwrap()
+            .into_iter()
+            .filter(|difference| difference.kind != FileDifferenceKind::OnlyInFirst)
+            .map(|difference| match subdir {
+                "" => difference.to_string(),
+                _ => format!("{subdir}/{difference}"),
+            }),
+        );
+    }
+    assert!(
+        stale.is_empty(),
+        "test_data is stale, run `cargo run` in rust_test_helper to regenerate it:\n{}",
+        stale.join("\n")
+    );
+}
@@ -0,0 +1,40 @@
+// Check test_data/manifest.json against the fixtures it summarizes
+use rust_test_helper::compress::read_fixture;
+use rust_test_helper::manifest::{Manifest, MANIFEST_FILE};
+use solana_program::hash::hash;
+use std::path::Path;
+
+const TEST_DATA: &str = "../test_data";
+
+#[test]
+fn manifest_json_is_fresh() {
+    let manifest = Manifest::from_test_data(Path::new(TEST_DATA)).unwrap();
+    let on_disk = std::fs::read_to_string(Path::new(TEST_DATA).join(MANIFEST_FILE))
+        .expect("manifest.json missing, run `cargo run` first");
+    assert_eq!(
+        on_disk,
+        manifest.to_json(),
+        "{MANIFEST_FILE} is stale, run `cargo run`"
+    );
+}
+
+#[test]
+fn manifest_json_matches_the_bytes_on_disk() {
+    let on_disk = std::fs::read_to_string(Path::new(TEST_DATA).join(MANIFEST_FILE)).unwrap();
+    let manifest: Manifest = serde_json::from_str(&on_disk).unwrap();
+    assert!(!manifest.files.is_empty());
+
+    for file in &manifest.files {
+        // Fixtures too big to commit raw are read from their .bin.zst
+        let bytes = read_fixture(&Path::new(TEST_DATA).join(&file.filename)).unwrap();
+        let name = &file.filename;
+        assert_eq!(file.byte_length, bytes.len(), "{name}");
+        let sha256: String = hash(&bytes)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(file.sha256, sha256, "{name}");
+        assert_eq!(file.num_accounts, file.accounts.len(), "{name}");
+    }
+}
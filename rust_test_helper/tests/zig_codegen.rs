@@ -0,0 +1,98 @@
+// Check test_data/expected.zig against the fixtures it describes
+use rust_test_helper::compress::{decompressed_path, read_fixture};
+use rust_test_helper::deserialize::deserialize_input_offsets;
+use rust_test_helper::serialize_solana_format::{is_invalid_fixture, SerializationFormat};
+use rust_test_helper::zig_codegen::{
+    layout_offsets, load_manifests, render_expected_zig, zig_identifier, EXPECTED_ZIG_FILE,
+};
+use std::path::Path;
+
+const TEST_DATA: &str = "../test_data";
+
+/// Names of the generated .bin fixtures, without the extension. Golden
+/// files come from the real loader, not from the generators, and invalid
+/// fixtures have no manifest to describe them. A compressed fixture is
+/// named after the .bin it decompresses to.
+fn generated_fixtures() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(TEST_DATA)
+        .expect("test_data missing, run `cargo run` first")
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| match path.extension() {
+            Some(ext) if ext == "bin" => Some(path),
+            _ => decompressed_path(&path),
+        })
+        .filter(|path| !is_invalid_fixture(path))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with("golden_"))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn expected_zig_has_one_fixture_per_bin() {
+    let manifests = load_manifests(Path::new(TEST_DATA)).unwrap();
+    let rendered = render_expected_zig(&manifests);
+
+    let fixtures = generated_fixtures();
+    assert_eq!(rendered.matches(" = Fixture{").count(), fixtures.len());
+    for name in &fixtures {
+        assert!(
+            rendered.contains(&format!("pub const {} = Fixture{{", zig_identifier(name))),
+            "{name}: no struct in {EXPECTED_ZIG_FILE}"
+        );
+    }
+
+    let on_disk = std::fs::read_to_string(Path::new(TEST_DATA).join(EXPECTED_ZIG_FILE)).unwrap();
+    assert_eq!(
+        on_disk, rendered,
+        "{EXPECTED_ZIG_FILE} is stale, run `cargo run`"
+    );
+}
+
+#[test]
+fn stated_offsets_match_the_parser() {
+    for (name, manifest) in load_manifests(Path::new(TEST_DATA)).unwrap() {
+        // The parser only reads the aligned layout
+        if manifest.format != SerializationFormat::Aligned {
+            continue;
+        }
+        let bytes = read_fixture(&Path::new(TEST_DATA).join(format!("{name}.bin"))).unwrap();
+        let parsed = deserialize_input_offsets(&bytes).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(layout_offsets(&manifest), parsed, "{name}");
+    }
+}
+
+#[test]
+fn byte_length_is_the_size_of_the_file() {
+    let manifests = load_manifests(Path::new(TEST_DATA)).unwrap();
+    let rendered = render_expected_zig(&manifests);
+    for (name, manifest) in &manifests {
+        let bytes = read_fixture(&Path::new(TEST_DATA).join(format!("{name}.bin"))).unwrap();
+        assert_eq!(
+            layout_offsets(manifest).program_id + 32,
+            bytes.len(),
+            "{name}"
+        );
+        assert!(rendered.contains(&format!(
+            "    .byte_length = {},\n    .negative = {},\n}};",
+            bytes.len(),
+            manifest.negative
+        )));
+    }
+}
+
+#[test]
+fn edge_values_keep_every_bit_of_their_u64s() {
+    let manifests = load_manifests(Path::new(TEST_DATA)).unwrap();
+    let rendered = render_expected_zig(&manifests);
+    let start = rendered.find("pub const edge_values = Fixture{").unwrap();
+    let fixture = &rendered[start..start + rendered[start..].find("\n};").unwrap()];
+
+    for lamports in [0, u64::MAX, 0x0102_0304_0506_0708] {
+        assert!(fixture.contains(&format!(".lamports = {lamports},")));
+    }
+    for rent_epoch in [u64::MAX, 0x8000_0000_0000_0001, 0x0807_0605_0403_0201] {
+        assert!(fixture.contains(&format!(".rent_epoch = {rent_epoch},")));
+    }
+}
@@ -3,6 +3,7 @@ const pubkey = @import("../pubkey/pubkey.zig");
 const Pubkey = pubkey.Pubkey;
 const syscalls = @import("../syscalls.zig");
 const ProgramError = @import("../program_error.zig").ProgramError;
+const math = @import("../math.zig");
 
 /// Maximum number of bytes a program may add to an account during a single instruction
 pub const MAX_PERMITTED_DATA_INCREASE: usize = 1_024 * 10;
@@ -64,12 +65,25 @@ pub const AccountInfo = struct {
     /// When non-null, this is used instead of data_ptr for key/owner access
     raw_ptr: ?*const RawAccountInfo = null,
 
-    /// Create AccountInfo from a pointer to AccountData
+    /// The account's duplicate marker byte, reused as its borrow state once
+    /// parsed (see `BorrowState`). Copies of an AccountInfo, like those
+    /// handed out for duplicate entries, share it.
+    borrow_state: *u8,
+
+    /// Index of the account this entry duplicates, or `NON_DUP_MARKER`.
+    /// Captured when the entry is parsed, since the marker byte it came
+    /// from changes with every borrow.
+    duplicate_of: u8 = NON_DUP_MARKER,
+
+    /// Create AccountInfo from a pointer to AccountData. The duplicate
+    /// marker is read now, so `ptr` must not be borrowed from yet.
     pub fn fromDataPtr(ptr: *align(8) AccountData, data_buffer: [*]u8) AccountInfo {
         return .{
             .data_ptr = ptr,
             .data_buffer = data_buffer,
             .raw_ptr = null,
+            .borrow_state = &ptr.duplicate_index,
+            .duplicate_of = ptr.duplicate_index,
         };
     }
 
@@ -79,6 +93,8 @@ pub const AccountInfo = struct {
             .data_ptr = ptr,
             .data_buffer = data_buffer,
             .raw_ptr = raw,
+            .borrow_state = &ptr.duplicate_index,
+            .duplicate_of = ptr.duplicate_index,
         };
     }
 
@@ -179,8 +195,15 @@ pub const AccountInfo = struct {
         return self.data_ptr.original_data_len;
     }
 
-    /// Reallocate account data
-    pub fn realloc(self: *AccountInfo, new_len: usize, zero_init: bool) !void {
+    /// Resize account data in place, the way the runtime allows: the new
+    /// length may be at most the original length plus
+    /// MAX_PERMITTED_DATA_INCREASE, which is the realloc space reserved after
+    /// the data in the input buffer. The loader leaves the original length
+    /// zero; `entrypoint.deserialize` stores it on entry. The new length is written back to the
+    /// serialized data_len field so the runtime picks it up on return.
+    /// With `zero_init` every byte between the old and new length is zeroed,
+    /// so growing after a shrink never exposes stale data.
+    pub fn realloc(self: *const AccountInfo, new_len: usize, zero_init: bool) !void {
         if (!self.isWritable()) {
             return error.AccountNotWritable;
         }
@@ -191,23 +214,18 @@ pub const AccountInfo = struct {
             return error.AccountNotWritable;
         }
 
-        const old_len = self.data_ptr.data_len;
-
-        // Check maximum increase
-        if (new_len > old_len) {
-            const increase = new_len - old_len;
-            if (increase > MAX_PERMITTED_DATA_INCREASE) {
-                return error.ExceedsMaxDataIncrease;
-            }
+        const old_len: usize = @intCast(self.data_ptr.data_len);
+        const max_len = @as(usize, self.data_ptr.original_data_len) + MAX_PERMITTED_DATA_INCREASE;
+        if (new_len > max_len) {
+            return ProgramError.InvalidRealloc;
         }
 
-        // Update length
+        // Update the serialized length
         self.data_ptr.data_len = new_len;
 
         // Zero new memory if requested
         if (zero_init and new_len > old_len) {
-            const new_data = self.data_buffer[old_len..new_len];
-            @memset(new_data, 0);
+            @memset(self.data_buffer[old_len..new_len], 0);
         }
     }
 
@@ -234,6 +252,13 @@ pub const AccountInfo = struct {
         return self.owner().equals(program_id);
     }
 
+    /// Check if this account does not exist yet: system-owned with no
+    /// lamports and no data. A funded account with zero-length data is
+    /// not unallocated, even though its `dataLen()` is also 0.
+    pub inline fn isUnallocated(self: *const AccountInfo) bool {
+        return self.dataLen() == 0 and self.getLamports() == 0 and self.isOwnedBy(&pubkey.SYSTEM_PROGRAM_ID);
+    }
+
     /// Verify this account is a signer
     pub inline fn verifySigner(self: *const AccountInfo) !void {
         if (!self.isSigner()) {
@@ -248,21 +273,86 @@ pub const AccountInfo = struct {
         }
     }
 
+    /// Options for `transferLamports`
+    pub const TransferLamportsOptions = struct {
+        /// Allow debiting an account the program does not own, for
+        /// system-level moves
+        skip_owner_check: bool = false,
+    };
+
     /// Transfer lamports from this account to another
-    pub fn transferLamports(self: *AccountInfo, to: *AccountInfo, amount: u64) !void {
+    ///
+    /// The source must be owned by `program_id` unless the owner check is
+    /// skipped. Fails without changing either balance on insufficient funds
+    /// or overflow.
+    pub fn transferLamports(
+        self: *AccountInfo,
+        to: *AccountInfo,
+        amount: u64,
+        program_id: *const Pubkey,
+        options: TransferLamportsOptions,
+    ) !void {
         if (!self.isWritable() or !to.isWritable()) {
             return error.AccountNotWritable;
         }
+        if (!options.skip_owner_check and !self.isOwnedBy(program_id)) {
+            return ProgramError.ExternalAccountLamportSpend;
+        }
 
         const from_lamports = try self.getLamportsMut();
         const to_lamports = try to.getLamportsMut();
 
-        if (from_lamports.* < amount) {
-            return error.InsufficientFunds;
+        const new_from = math.checkedSub(from_lamports.*, amount) catch return ProgramError.InsufficientFunds;
+        // Moving lamports within the same account is a no-op
+        if (from_lamports == to_lamports) {
+            return;
+        }
+
+        const new_to = try math.checkedAdd(to_lamports.*, amount);
+        from_lamports.* = new_from;
+        to_lamports.* = new_to;
+    }
+
+    /// Close this account: move all of its lamports to `destination`, zero
+    /// and drop its data, and hand it to the System Program, so the runtime
+    /// deletes it at the end of the transaction
+    ///
+    /// The account must be owned by `program_id`, the executing program.
+    /// Fails with `AccountBorrowFailed` while any borrow of either account is
+    /// out, like Rust's `try_borrow_mut_lamports`. Accounts viewed through a
+    /// `RawAccountInfo` (`fromDataPtrWithRaw`) cannot be resized or
+    /// reassigned, so closing them fails with `Immutable`. Fails without
+    /// changing either account when a check fails or the destination balance
+    /// would overflow.
+    pub fn close(self: *const AccountInfo, destination: *const AccountInfo, program_id: *const Pubkey) !void {
+        if (!self.isWritable() or !destination.isWritable()) {
+            return error.AccountNotWritable;
+        }
+        if (self.raw_ptr != null) {
+            return ProgramError.Immutable; // realloc and assign need AccountData
+        }
+        if (self.borrow_state.* != NON_DUP_MARKER or destination.borrow_state.* != NON_DUP_MARKER) {
+            return ProgramError.AccountBorrowFailed;
+        }
+        if (!self.isOwnedBy(program_id)) {
+            return ProgramError.ExternalAccountLamportSpend;
+        }
+        // Closing into itself would keep the lamports and the account alive
+        if (self.keyEquals(destination.key())) {
+            return ProgramError.InvalidArgument;
         }
 
-        from_lamports.* -= amount;
-        to_lamports.* += amount;
+        var source = self.*;
+        var target = destination.*;
+        const from_lamports = try source.getLamportsMut();
+        const to_lamports = try target.getLamportsMut();
+        const new_to = try math.checkedAdd(to_lamports.*, from_lamports.*);
+
+        @memset(self.data_buffer[0..@intCast(self.dataLen())], 0);
+        try self.realloc(0, false);
+        to_lamports.* = new_to;
+        from_lamports.* = 0;
+        try source.assign(&pubkey.SYSTEM_PROGRAM_ID);
     }
 
     /// Zero-copy cast data to a type
@@ -299,14 +389,101 @@ pub const AccountInfo = struct {
         return self.dataMutAs(T);
     }
 
-    /// Get duplicate index (0xFF if not a duplicate)
+    /// Borrow the lamports for reading. Fails with `AccountBorrowFailed`
+    /// while they are mutably borrowed, through this AccountInfo or any
+    /// copy of it, or when `BorrowState.MAX_SHARED` borrows are already out.
+    pub fn tryBorrowLamports(self: *const AccountInfo) ProgramError!Ref(*const u64) {
+        const release = try BorrowState.borrowShared(self.borrow_state, BorrowState.LAMPORTS);
+        const lamports: *const u64 = if (self.raw_ptr) |raw| @alignCast(raw.lamports) else &self.data_ptr.lamports;
+        return .{ .value = lamports, .state = self.borrow_state, .release = release };
+    }
+
+    /// Borrow the lamports for writing. The account must be writable, and
+    /// fails with `AccountBorrowFailed` while any other lamports borrow is out.
+    pub fn tryBorrowMutLamports(self: *AccountInfo) !Ref(*u64) {
+        if (!self.isWritable()) {
+            return error.AccountNotWritable;
+        }
+        const release = try BorrowState.borrowMut(self.borrow_state, BorrowState.LAMPORTS);
+        return .{ .value = self.getLamportsMut() catch unreachable, .state = self.borrow_state, .release = release };
+    }
+
+    /// Borrow the data for reading, like `tryBorrowLamports`
+    pub fn tryBorrowData(self: *const AccountInfo) ProgramError!Ref([]const u8) {
+        const release = try BorrowState.borrowShared(self.borrow_state, BorrowState.DATA);
+        return .{ .value = self.getData(), .state = self.borrow_state, .release = release };
+    }
+
+    /// Borrow the data for writing, like `tryBorrowMutLamports`
+    pub fn tryBorrowMutData(self: *AccountInfo) !Ref([]u8) {
+        if (!self.isWritable()) {
+            return error.AccountNotWritable;
+        }
+        const release = try BorrowState.borrowMut(self.borrow_state, BorrowState.DATA);
+        return .{ .value = self.getDataMut() catch unreachable, .state = self.borrow_state, .release = release };
+    }
+
+    /// Get duplicate index (0xFF if not a duplicate), as parsed. Borrows
+    /// do not change it.
     pub inline fn duplicateIndex(self: *const AccountInfo) u8 {
-        return self.data_ptr.duplicate_index;
+        return self.duplicate_of;
     }
 
     /// Check if this is a duplicate account
     pub inline fn isDuplicate(self: *const AccountInfo) bool {
-        return self.data_ptr.duplicate_index != NON_DUP_MARKER;
+        return self.duplicate_of != NON_DUP_MARKER;
+    }
+};
+
+/// A borrow of an account's lamports or data, like Rust's `Ref` and
+/// `RefMut`. `deinit` gives it back.
+pub fn Ref(comptime T: type) type {
+    return struct {
+        value: T,
+        state: *u8,
+        /// What the borrow took from `state`
+        release: u8,
+
+        pub fn deinit(self: @This()) void {
+            self.state.* += self.release;
+        }
+    };
+}
+
+/// The borrow state byte. The runtime sets an account's duplicate marker to
+/// `NON_DUP_MARKER`, which here means nothing is borrowed; borrows clear
+/// bits and count down, and releasing them restores the marker.
+///
+/// ```text
+/// bit 7      lamports are not mutably borrowed
+/// bits 6..4  shared lamports borrows left, MAX_SHARED when none are out
+/// bit 3      data is not mutably borrowed
+/// bits 2..0  shared data borrows left
+/// ```
+pub const BorrowState = struct {
+    /// Most shared borrows of the lamports, or of the data, at once
+    pub const MAX_SHARED = 7;
+
+    /// Where lamports or data keep their half of the state
+    const Field = struct { mut_bit: u8, shared_one: u8, shared_mask: u8 };
+
+    const LAMPORTS = Field{ .mut_bit = 0b1000_0000, .shared_one = 0b0001_0000, .shared_mask = 0b0111_0000 };
+    const DATA = Field{ .mut_bit = 0b0000_1000, .shared_one = 0b0000_0001, .shared_mask = 0b0000_0111 };
+
+    fn borrowShared(state: *u8, comptime field: Field) ProgramError!u8 {
+        if (state.* & field.mut_bit == 0 or state.* & field.shared_mask == 0) {
+            return ProgramError.AccountBorrowFailed;
+        }
+        state.* -= field.shared_one;
+        return field.shared_one;
+    }
+
+    fn borrowMut(state: *u8, comptime field: Field) ProgramError!u8 {
+        if (state.* & field.mut_bit == 0 or state.* & field.shared_mask != field.shared_mask) {
+            return ProgramError.AccountBorrowFailed;
+        }
+        state.* -= field.mut_bit;
+        return field.mut_bit;
     }
 };
 
@@ -329,7 +506,7 @@ pub const AccountIterator = struct {
     pub fn init(input: [*]const u8, num_accounts: usize, accounts_buffer: []AccountInfo, aligned_buffer: []AccountData) AccountIterator {
         return .{
             .input = input,
-            .offset = 1, // Skip the account count byte
+            .offset = 8, // Skip the u64 account count
             .remaining = num_accounts,
             .accounts = accounts_buffer,
             .current_index = 0,
@@ -337,45 +514,51 @@ pub const AccountIterator = struct {
         };
     }
 
-    /// Get next account
+    /// Get next account. Returns null once every account has been read, on
+    /// a duplicate marker that does not point at an earlier account, and
+    /// when the buffers have no room for another account; `remaining` then
+    /// counts the accounts left unread.
     pub fn next(self: *AccountIterator) ?AccountInfo {
         if (self.remaining == 0) return null;
+        if (self.current_index >= self.accounts.len or self.current_index >= self.aligned_buffer.len) {
+            return null; // More accounts than the buffers hold
+        }
 
-        // Read duplicate marker
+        // Read duplicate marker (the first byte of AccountData)
         const dup_marker = self.input[self.offset];
-        self.offset += 1;
 
         if (dup_marker != NON_DUP_MARKER) {
-            // This is a duplicate, return the original
-            const dup_index = dup_marker;
-            self.remaining -= 1;
-            if (dup_index < self.current_index) {
-                return self.accounts[dup_index];
+            if (dup_marker >= self.current_index) {
+                return null; // Invalid duplicate index
             }
-            return null; // Invalid duplicate index
+            // This is a duplicate, return the original (index + 7 bytes of padding).
+            // It still takes a slot so later markers index the same positions.
+            self.offset += 8;
+            var account = self.accounts[dup_marker];
+            account.duplicate_of = dup_marker;
+            self.accounts[self.current_index] = account;
+            self.current_index += 1;
+            self.remaining -= 1;
+            return account;
         }
 
         // Parse new account - copy to aligned memory
         const account_data_ptr = @as(*align(1) const AccountData, @ptrCast(self.input + self.offset));
         self.offset += @sizeOf(AccountData);
-
-        // Copy to aligned buffer
-        if (self.current_index < self.aligned_buffer.len) {
-            self.aligned_buffer[self.current_index] = account_data_ptr.*;
-        }
+        self.aligned_buffer[self.current_index] = account_data_ptr.*;
+        // The loader leaves original_data_len zero, see `entrypoint.deserialize`
+        self.aligned_buffer[self.current_index].original_data_len = @intCast(account_data_ptr.data_len);
 
         // Get data buffer pointer (follows AccountData)
         const data_buffer = @as([*]u8, @ptrCast(@constCast(self.input + self.offset)));
-        self.offset += account_data_ptr.data_len;
+        // Skip data, the reserved realloc space, the alignment padding and rent_epoch
+        const data_len: usize = @intCast(account_data_ptr.data_len);
+        self.offset += std.mem.alignForward(usize, data_len, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64);
 
-        // Create AccountInfo using aligned data
+        // Create AccountInfo using aligned data, and store it for potential duplicates
         const account = AccountInfo.fromDataPtr(&self.aligned_buffer[self.current_index], data_buffer);
-
-        // Store for potential duplicates
-        if (self.current_index < self.accounts.len) {
-            self.accounts[self.current_index] = account;
-            self.current_index += 1;
-        }
+        self.accounts[self.current_index] = account;
+        self.current_index += 1;
 
         self.remaining -= 1;
         return account;
@@ -404,8 +587,8 @@ pub const ParsedAccounts = struct {
 
 /// Parse accounts from entrypoint input
 pub fn parseAccounts(input: [*]const u8, allocator: std.mem.Allocator) !ParsedAccounts {
-    // First byte is the number of accounts
-    const num_accounts = input[0];
+    // The input starts with the number of accounts as a u64 little-endian
+    const num_accounts: usize = @intCast(std.mem.readInt(u64, input[0..8], .little));
 
     // Allocate space for accounts and aligned data
     const accounts = try allocator.alloc(AccountInfo, num_accounts);
@@ -573,14 +756,114 @@ test "AccountInfo lamports transfer" {
     defer allocator.destroy(to_info.data_ptr);
 
     // Transfer 250 lamports
-    try from_info.transferLamports(&to_info, 250);
+    try from_info.transferLamports(&to_info, 250, &owner_id, .{});
 
     try testing.expectEqual(@as(u64, 750), from_info.getLamports());
     try testing.expectEqual(@as(u64, 750), to_info.getLamports());
 
     // Test insufficient funds
-    const result = from_info.transferLamports(&to_info, 1000);
-    try testing.expectError(error.InsufficientFunds, result);
+    const result = from_info.transferLamports(&to_info, 1000, &owner_id, .{});
+    try testing.expectError(ProgramError.InsufficientFunds, result);
+
+    // Overflow on the destination leaves both balances untouched
+    to_info.data_ptr.lamports = std.math.maxInt(u64);
+    try testing.expectError(ProgramError.ArithmeticOverflow, from_info.transferLamports(&to_info, 1, &owner_id, .{}));
+    try testing.expectEqual(@as(u64, 750), from_info.getLamports());
+    try testing.expectEqual(std.math.maxInt(u64), to_info.getLamports());
+    to_info.data_ptr.lamports = 750;
+
+    // The source must be owned by the calling program...
+    const other_program = Pubkey.newUnique();
+    try testing.expectError(
+        ProgramError.ExternalAccountLamportSpend,
+        from_info.transferLamports(&to_info, 100, &other_program, .{}),
+    );
+    try testing.expectEqual(@as(u64, 750), from_info.getLamports());
+
+    // ...unless the owner check is skipped
+    try from_info.transferLamports(&to_info, 100, &other_program, .{ .skip_owner_check = true });
+    try testing.expectEqual(@as(u64, 650), from_info.getLamports());
+    try testing.expectEqual(@as(u64, 850), to_info.getLamports());
+}
+
+test "AccountInfo close" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const program_id = Pubkey.newUnique();
+    const account_id = Pubkey.newUnique();
+    const destination_id = Pubkey.newUnique();
+
+    var account_data = [_]u8{0xAB} ** 16;
+    var destination_data: [0]u8 = undefined;
+
+    const account = try createTestAccountInfo(
+        allocator,
+        &account_id,
+        &program_id,
+        2_039_280,
+        &account_data,
+        false, // is_signer
+        true, // is_writable
+        false, // is_executable
+    );
+    defer allocator.destroy(account.data_ptr);
+
+    const destination = try createTestAccountInfo(
+        allocator,
+        &destination_id,
+        &pubkey.SYSTEM_PROGRAM_ID,
+        1_000,
+        &destination_data,
+        true, // is_signer
+        true, // is_writable
+        false, // is_executable
+    );
+    defer allocator.destroy(destination.data_ptr);
+
+    // Only the owning program may close the account
+    const other_program = Pubkey.newUnique();
+    try testing.expectError(ProgramError.ExternalAccountLamportSpend, account.close(&destination, &other_program));
+    try testing.expectError(ProgramError.InvalidArgument, account.close(&account, &program_id));
+
+    // No borrow of either account may be out
+    {
+        const data = try account.tryBorrowData();
+        defer data.deinit();
+        try testing.expectError(ProgramError.AccountBorrowFailed, account.close(&destination, &program_id));
+    }
+    {
+        const lamports = try destination.tryBorrowLamports();
+        defer lamports.deinit();
+        try testing.expectError(ProgramError.AccountBorrowFailed, account.close(&destination, &program_id));
+    }
+
+    // A RawAccountInfo view has no AccountData to resize and reassign
+    const raw = RawAccountInfo{
+        .id = account.key(),
+        .lamports = &account.data_ptr.lamports,
+        .data_len = account.dataLen(),
+        .data = account.data_buffer,
+        .owner_id = account.owner(),
+        .rent_epoch = 0,
+        .is_signer = 0,
+        .is_writable = 1,
+        .is_executable = 0,
+    };
+    const raw_view = AccountInfo.fromDataPtrWithRaw(account.data_ptr, account.data_buffer, &raw);
+    try testing.expectError(ProgramError.Immutable, raw_view.close(&destination, &program_id));
+
+    try testing.expectEqual(@as(u64, 2_039_280), account.getLamports());
+    try testing.expectEqual(@as(u64, 16), account.dataLen());
+
+    try account.close(&destination, &program_id);
+
+    try testing.expectEqual(@as(u64, 0), account.getLamports());
+    try testing.expectEqual(@as(u64, 1_000 + 2_039_280), destination.getLamports());
+    try testing.expectEqual(@as(u64, 0), account.dataLen());
+    try testing.expect(account.isOwnedBy(&pubkey.SYSTEM_PROGRAM_ID));
+    try testing.expect(account.isUnallocated());
+    try testing.expectEqualSlices(u8, &([_]u8{0} ** 16), &account_data);
 }
 
 test "AccountData size and alignment" {
@@ -620,17 +903,14 @@ test "AccountIterator basic iteration" {
     const testing = std.testing;
 
     // Create mock serialized input with proper alignment
-    var buffer: [1024]u8 align(@alignOf(AccountData)) = undefined;
+    var buffer: [3 * 11 * 1024]u8 align(@alignOf(AccountData)) = undefined;
     var offset: usize = 0;
 
-    // Number of accounts
-    buffer[offset] = 3;
-    offset += 1;
+    // Number of accounts (u64)
+    std.mem.writeInt(u64, buffer[offset..][0..8], 3, .little);
+    offset += 8;
 
     // First account (non-duplicate)
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
-
     const account1_data = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 1,
@@ -646,12 +926,9 @@ test "AccountIterator basic iteration" {
     offset += @sizeOf(AccountData);
     // Account data
     @memset(buffer[offset..][0..10], 0xAA);
-    offset += 10;
+    offset += std.mem.alignForward(usize, 10, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Second account (non-duplicate)
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
-
     const account2_data = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 0,
@@ -667,11 +944,11 @@ test "AccountIterator basic iteration" {
     offset += @sizeOf(AccountData);
     // Account data
     @memset(buffer[offset..][0..20], 0xBB);
-    offset += 20;
+    offset += std.mem.alignForward(usize, 20, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Third account (duplicate of first)
     buffer[offset] = 0; // Duplicate marker
-    offset += 1;
+    offset += 8; // index + 7 bytes of padding
 
     // Allocate space for tracking accounts
     var accounts_buffer: [10]AccountInfo = undefined;
@@ -714,19 +991,16 @@ test "AccountIterator skip functionality" {
     const testing = std.testing;
 
     // Create mock serialized input with 5 accounts
-    var buffer: [1024]u8 align(@alignOf(AccountData)) = undefined;
+    var buffer: [5 * 11 * 1024]u8 align(@alignOf(AccountData)) = undefined;
     var offset: usize = 0;
 
-    // Number of accounts
-    buffer[offset] = 5;
-    offset += 1;
+    // Number of accounts (u64)
+    std.mem.writeInt(u64, buffer[offset..][0..8], 5, .little);
+    offset += 8;
 
     // Add 5 non-duplicate accounts
     var i: usize = 0;
     while (i < 5) : (i += 1) {
-        buffer[offset] = NON_DUP_MARKER;
-        offset += 1;
-
         const account_data = AccountData{
             .duplicate_index = NON_DUP_MARKER,
             .is_signer = if (i == 0) 1 else 0,
@@ -741,7 +1015,7 @@ test "AccountIterator skip functionality" {
         @memcpy(buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_data));
         offset += @sizeOf(AccountData);
         @memset(buffer[offset..][0..5], @intCast(i));
-        offset += 5;
+        offset += std.mem.alignForward(usize, 5, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
     }
 
     var accounts_buffer: [10]AccountInfo = undefined;
@@ -774,17 +1048,14 @@ test "parseAccounts function" {
     const allocator = testing.allocator;
 
     // Create mock serialized input
-    var buffer: [512]u8 align(@alignOf(AccountData)) = undefined;
+    var buffer: [2 * 11 * 1024]u8 align(@alignOf(AccountData)) = undefined;
     var offset: usize = 0;
 
-    // Number of accounts
-    buffer[offset] = 2;
-    offset += 1;
+    // Number of accounts (u64)
+    std.mem.writeInt(u64, buffer[offset..][0..8], 2, .little);
+    offset += 8;
 
     // First account
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
-
     const account1_data = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 1,
@@ -799,12 +1070,9 @@ test "parseAccounts function" {
     @memcpy(buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account1_data));
     offset += @sizeOf(AccountData);
     @memset(buffer[offset..][0..8], 0xFF);
-    offset += 8;
+    offset += std.mem.alignForward(usize, 8, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Second account
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
-
     const account2_data = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 0,
@@ -848,7 +1116,7 @@ test "AccountInfo realloc functionality" {
     const owner_id = pubkey.SYSTEM_PROGRAM_ID;
     var data_buffer: [1024]u8 = undefined;
 
-    var info = try createTestAccountInfo(
+    const info = try createTestAccountInfo(
         allocator,
         &id,
         &owner_id,
@@ -880,9 +1148,11 @@ test "AccountInfo realloc functionality" {
     try info.realloc(50, false);
     try testing.expectEqual(@as(u64, 50), info.dataLen());
 
-    // Test max increase limit
-    const result = info.realloc(50 + MAX_PERMITTED_DATA_INCREASE + 1, false);
-    try testing.expectError(error.ExceedsMaxDataIncrease, result);
+    // The cap is measured from the original length, not the current one
+    try info.realloc(100 + MAX_PERMITTED_DATA_INCREASE, false);
+    try testing.expectEqual(@as(u64, 100 + MAX_PERMITTED_DATA_INCREASE), info.dataLen());
+    const result = info.realloc(100 + MAX_PERMITTED_DATA_INCREASE + 1, false);
+    try testing.expectError(ProgramError.InvalidRealloc, result);
 
     // Test non-writable account
     info.data_ptr.is_writable = 0;
@@ -890,6 +1160,37 @@ test "AccountInfo realloc functionality" {
     try testing.expectError(error.AccountNotWritable, result2);
 }
 
+test "AccountInfo realloc does not resurrect data after shrinking" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const id = Pubkey.ZEROES;
+    const owner_id = pubkey.SYSTEM_PROGRAM_ID;
+    var data_buffer: [64]u8 = undefined;
+    @memset(&data_buffer, 0xAB);
+
+    const info = try createTestAccountInfo(
+        allocator,
+        &id,
+        &owner_id,
+        1000,
+        data_buffer[0..32],
+        false,
+        true, // is_writable
+        false,
+    );
+    defer allocator.destroy(info.data_ptr);
+
+    try info.realloc(8, true);
+    try testing.expectEqual(@as(u64, 8), info.data_ptr.data_len);
+
+    try info.realloc(48, true);
+    try testing.expectEqual(@as(u64, 48), info.data_ptr.data_len);
+    const data = info.getData();
+    for (data[0..8]) |byte| try testing.expectEqual(@as(u8, 0xAB), byte);
+    for (data[8..48]) |byte| try testing.expectEqual(@as(u8, 0), byte);
+}
+
 test "AccountInfo duplicate detection" {
     const testing = std.testing;
     const allocator = testing.allocator;
@@ -1060,16 +1361,14 @@ test "AccountInfo complex duplicate scenario" {
     const testing = std.testing;
 
     // Create serialized input with multiple duplicates
-    var buffer: [2048]u8 align(@alignOf(AccountData)) = undefined;
+    var buffer: [5 * 11 * 1024]u8 align(@alignOf(AccountData)) = undefined;
     var offset: usize = 0;
 
     // 5 accounts: [A, B, dup(A), C, dup(B)]
-    buffer[offset] = 5;
-    offset += 1;
+    std.mem.writeInt(u64, buffer[offset..][0..8], 5, .little);
+    offset += 8;
 
     // Account A (index 0)
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
     const account_a = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 1,
@@ -1084,11 +1383,9 @@ test "AccountInfo complex duplicate scenario" {
     @memcpy(buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_a));
     offset += @sizeOf(AccountData);
     @memset(buffer[offset..][0..4], 0xAA);
-    offset += 4;
+    offset += std.mem.alignForward(usize, 4, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Account B (index 1)
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
     const account_b = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 0,
@@ -1103,15 +1400,13 @@ test "AccountInfo complex duplicate scenario" {
     @memcpy(buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_b));
     offset += @sizeOf(AccountData);
     @memset(buffer[offset..][0..4], 0xBB);
-    offset += 4;
+    offset += std.mem.alignForward(usize, 4, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Duplicate of A (index 2)
     buffer[offset] = 0; // duplicate marker pointing to index 0
-    offset += 1;
+    offset += 8; // index + 7 bytes of padding
 
     // Account C (index 3)
-    buffer[offset] = NON_DUP_MARKER;
-    offset += 1;
     const account_c = AccountData{
         .duplicate_index = NON_DUP_MARKER,
         .is_signer = 1,
@@ -1126,11 +1421,11 @@ test "AccountInfo complex duplicate scenario" {
     @memcpy(buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_c));
     offset += @sizeOf(AccountData);
     @memset(buffer[offset..][0..4], 0xCC);
-    offset += 4;
+    offset += std.mem.alignForward(usize, 4, 8) + MAX_PERMITTED_DATA_INCREASE + @sizeOf(u64); // + rent_epoch
 
     // Duplicate of B (index 4)
     buffer[offset] = 1; // duplicate marker pointing to index 1
-    offset += 1;
+    offset += 8; // index + 7 bytes of padding
 
     var accounts_buffer: [10]AccountInfo = undefined;
     var aligned_buffer: [10]AccountData = undefined;
@@ -1157,6 +1452,61 @@ test "AccountInfo complex duplicate scenario" {
     try testing.expect(!acc4.isSigner()); // Same as acc1
 }
 
+test "AccountInfo borrows follow RefCell rules" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    var data_buffer = [_]u8{ 1, 2, 3, 4 };
+    var info = try createTestAccountInfo(allocator, &Pubkey.ZEROES, &pubkey.SYSTEM_PROGRAM_ID, 500, &data_buffer, false, true, false);
+    defer allocator.destroy(info.data_ptr);
+    // A duplicate entry gets a copy of the same AccountInfo
+    var duplicate = info;
+
+    // Shared borrows stack, up to the limit
+    var shared: [BorrowState.MAX_SHARED]Ref([]const u8) = undefined;
+    for (&shared) |*borrow| borrow.* = try info.tryBorrowData();
+    try testing.expectEqualSlices(u8, &data_buffer, shared[0].value);
+    try testing.expectError(ProgramError.AccountBorrowFailed, duplicate.tryBorrowData());
+    try testing.expectError(ProgramError.AccountBorrowFailed, duplicate.tryBorrowMutData());
+
+    // Lamports are tracked apart from the data
+    {
+        const lamports = try duplicate.tryBorrowMutLamports();
+        defer lamports.deinit();
+        lamports.value.* += 1;
+        try testing.expectError(ProgramError.AccountBorrowFailed, info.tryBorrowLamports());
+        try testing.expectError(ProgramError.AccountBorrowFailed, info.tryBorrowMutLamports());
+    }
+    const lamports = try info.tryBorrowLamports();
+    try testing.expectEqual(@as(u64, 501), lamports.value.*);
+    lamports.deinit();
+
+    for (shared) |borrow| borrow.deinit();
+    {
+        const data = try duplicate.tryBorrowMutData();
+        defer data.deinit();
+        data.value[0] = 9;
+        try testing.expectError(ProgramError.AccountBorrowFailed, info.tryBorrowData());
+    }
+    try testing.expectEqual(@as(u8, 9), data_buffer[0]);
+
+    // Every borrow given back restores the runtime's marker
+    try testing.expectEqual(NON_DUP_MARKER, info.borrow_state.*);
+}
+
+test "AccountInfo mutable borrows need a writable account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    var data_buffer = [_]u8{0} ** 4;
+    var info = try createTestAccountInfo(allocator, &Pubkey.ZEROES, &pubkey.SYSTEM_PROGRAM_ID, 500, &data_buffer, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    try testing.expectError(error.AccountNotWritable, info.tryBorrowMutLamports());
+    try testing.expectError(error.AccountNotWritable, info.tryBorrowMutData());
+    try testing.expectEqual(NON_DUP_MARKER, info.borrow_state.*);
+}
+
 // Include Rust compatibility tests
 test {
     _ = @import("rust_compatibility_test.zig");
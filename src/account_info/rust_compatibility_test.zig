@@ -9,6 +9,14 @@ const parseAccounts = account_info.parseAccounts;
 const NON_DUP_MARKER = account_info.NON_DUP_MARKER;
 const pubkey = @import("../pubkey/pubkey.zig");
 const Pubkey = pubkey.Pubkey;
+const EpochSchedule = @import("../sysvar/epoch_schedule.zig").EpochSchedule;
+const slot_hashes_sysvar = @import("../sysvar/slot_hashes.zig");
+const entrypoint = @import("../entrypoint.zig");
+const program_error = @import("../program_error.zig");
+const ProgramError = program_error.ProgramError;
+const syscalls = @import("../syscalls.zig");
+const bpf = @import("../bpf.zig");
+const borsh = @import("../borsh.zig");
 
 test "parse Rust empty data accounts" {
     const testing = std.testing;
@@ -48,6 +56,36 @@ test "parse Rust empty data accounts" {
     try testing.expectEqual(@as(u64, 2000), parsed.accounts[1].getLamports());
 }
 
+test "parse Rust unallocated account next to an empty one" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/zero_vs_unallocated.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 2), parsed.accounts.len);
+    const unallocated = parsed.accounts[0];
+    const empty = parsed.accounts[1];
+
+    // Both have no data, so the data length alone cannot tell them apart
+    try testing.expectEqual(@as(u64, 0), unallocated.dataLen());
+    try testing.expectEqual(@as(u64, 0), empty.dataLen());
+
+    try testing.expect(unallocated.isOwnedBy(&pubkey.SYSTEM_PROGRAM_ID));
+    try testing.expectEqual(@as(u64, 0), unallocated.getLamports());
+    try testing.expect(unallocated.isUnallocated());
+
+    try testing.expect(empty.isOwnedBy(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+    try testing.expectEqual(@as(u64, 890_880), empty.getLamports());
+    try testing.expect(!empty.isUnallocated());
+}
+
 test "parse Rust Solana format single account" {
     const testing = std.testing;
     const allocator = testing.allocator;
@@ -178,6 +216,1123 @@ test "parse Rust Solana format with duplicates" {
     try testing.expectEqual(@as(u64, 2000), acc4.?.getLamports());
 }
 
+test "deserialize aliases Rust duplicate accounts" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/solana_accounts_with_duplicates.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    // The runtime hands programs an 8-byte aligned buffer
+    const input = try allocator.alignedAlloc(u8, 8, file_content.len);
+    defer allocator.free(input);
+    @memcpy(input, file_content);
+
+    var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try entrypoint.deserialize(input.ptr, &accounts_buf);
+    try testing.expectEqual(@as(usize, 5), parsed.accounts.len);
+
+    // Index 2 duplicates index 0, index 4 duplicates index 1
+    try testing.expect(parsed.accounts[2].key().equals(parsed.accounts[0].key()));
+    try testing.expect(parsed.accounts[4].key().equals(parsed.accounts[1].key()));
+    try testing.expectEqual(@intFromPtr(parsed.accounts[0].getData().ptr), @intFromPtr(parsed.accounts[2].getData().ptr));
+
+    // A lamport change through index 0 is visible through index 2
+    const lamports = try parsed.accounts[0].getLamportsMut();
+    try testing.expectEqual(@as(u64, 1000), lamports.*);
+    lamports.* -= 250;
+    try testing.expectEqual(@as(u64, 750), parsed.accounts[2].getLamports());
+
+    // ...and so is a data write
+    const data = try parsed.accounts[0].getDataMut();
+    data[0] = 0x11;
+    try testing.expectEqual(@as(u8, 0x11), parsed.accounts[2].getData()[0]);
+
+    // The untouched pair is unaffected
+    try testing.expectEqual(@as(u64, 2000), parsed.accounts[4].getLamports());
+}
+
+test "deserialize aliases two duplicates of one Rust account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/self_dup_account.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const input = try allocator.alignedAlloc(u8, 8, file_content.len);
+    defer allocator.free(input);
+    @memcpy(input, file_content);
+
+    var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try entrypoint.deserialize(input.ptr, &accounts_buf);
+    try testing.expectEqual(@as(usize, 3), parsed.accounts.len);
+
+    // Indexes 1 and 2 both duplicate index 0
+    for (parsed.accounts[1..]) |*duplicate| {
+        try testing.expect(duplicate.key().equals(parsed.accounts[0].key()));
+        try testing.expectEqual(@intFromPtr(parsed.accounts[0].getData().ptr), @intFromPtr(duplicate.getData().ptr));
+        try testing.expectEqual(@as(u64, 5000), duplicate.getLamports());
+    }
+
+    // A change through either duplicate is visible through all three
+    const lamports = try parsed.accounts[1].getLamportsMut();
+    lamports.* -= 1000;
+    try testing.expectEqual(@as(u64, 4000), parsed.accounts[0].getLamports());
+    try testing.expectEqual(@as(u64, 4000), parsed.accounts[2].getLamports());
+}
+
+test "parse Rust full entrypoint input" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // Accounts followed by instruction data and the program ID
+    const file = try std.fs.cwd().openFile("test_data/solana_full_entrypoint.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+    var raw_accounts_buf: [entrypoint.MAX_ACCOUNTS]account_info.RawAccountInfo = undefined;
+    const parsed = try entrypoint.parseInput(file_content.ptr, &accounts_buf, &raw_accounts_buf);
+
+    try testing.expectEqual(@as(usize, 3), parsed.num_accounts);
+
+    // Payer
+    try testing.expect(parsed.accounts[0].isSigner());
+    try testing.expect(parsed.accounts[0].isWritable());
+    try testing.expectEqual(@as(u64, 5_000_000), parsed.accounts[0].getLamports());
+    try testing.expectEqual(@as(usize, 0), parsed.accounts[0].getData().len);
+
+    // Program-owned state account
+    try testing.expect(!parsed.accounts[1].isSigner());
+    try testing.expect(parsed.accounts[1].isWritable());
+    try testing.expect(parsed.accounts[1].owner().equals(parsed.program_id));
+    const state_data = parsed.accounts[1].getData();
+    try testing.expectEqual(@as(usize, 13), state_data.len);
+    for (state_data) |byte| {
+        try testing.expectEqual(@as(u8, 0xDD), byte);
+    }
+
+    // Program account
+    try testing.expect(parsed.accounts[2].isExecutable());
+    try testing.expect(parsed.accounts[2].key().equals(parsed.program_id));
+
+    // Instruction 2 with a u64 amount of 1000
+    try testing.expectEqual(@as(usize, 9), parsed.instruction_data.len);
+    try testing.expectEqual(@as(u8, 0x02), parsed.instruction_data[0]);
+    try testing.expectEqual(@as(u64, 1_000), std.mem.readInt(u64, parsed.instruction_data[1..9], .little));
+
+    try testing.expect(parsed.program_id.equals(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+}
+
+test "parse Rust program id fixture" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/solana_program_id.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+    var raw_accounts_buf: [entrypoint.MAX_ACCOUNTS]account_info.RawAccountInfo = undefined;
+    const parsed = try entrypoint.parseInput(file_content.ptr, &accounts_buf, &raw_accounts_buf);
+
+    try testing.expectEqualSlices(u8, &[_]u8{ 0x01, 0x02, 0x03 }, parsed.instruction_data);
+
+    // The program id is the last 32 bytes of the buffer
+    const program_id = Pubkey.fromBytes([_]u8{0x42} ** 32);
+    try testing.expect(parsed.program_id.equals(&program_id));
+    try testing.expectEqual(@intFromPtr(file_content.ptr + file_content.len - 32), @intFromPtr(parsed.program_id));
+}
+
+test "parse Rust Rent sysvar accounts" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const cases = [_]struct { path: []const u8, lamports_per_byte_year: u64, exemption_threshold: f64, burn_percent: u8 }{
+        .{ .path = "test_data/sysvar_rent.bin", .lamports_per_byte_year = 1000, .exemption_threshold = 1.5, .burn_percent = 25 },
+        .{ .path = "test_data/sysvar_rent_mainnet.bin", .lamports_per_byte_year = 3480, .exemption_threshold = 2.0, .burn_percent = 50 },
+    };
+
+    for (cases) |case| {
+        const file = try std.fs.cwd().openFile(case.path, .{});
+        defer file.close();
+
+        const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+        defer allocator.free(file_content);
+
+        var parsed = try parseAccounts(file_content.ptr, allocator);
+        defer parsed.deinit();
+
+        const acc = parsed.accounts[0];
+        try testing.expect(acc.key().equals(&pubkey.RENT_SYSVAR_ID));
+        try testing.expect(acc.owner().equals(&pubkey.SYSVAR_PROGRAM_ID));
+
+        // 17 bytes on the wire: u64, f64, u8 with no padding
+        const data = acc.getData();
+        try testing.expectEqual(@as(usize, 17), data.len);
+        const lamports_per_byte_year = std.mem.readInt(u64, data[0..8], .little);
+        const exemption_threshold: f64 = @bitCast(std.mem.readInt(u64, data[8..16], .little));
+        try testing.expectEqual(case.lamports_per_byte_year, lamports_per_byte_year);
+        try testing.expectEqual(case.exemption_threshold, exemption_threshold);
+        try testing.expectEqual(case.burn_percent, data[16]);
+    }
+
+    // With mainnet rent the sysvar account itself holds exactly the
+    // rent-exempt minimum for its 17 bytes: (128 + 17) * 3480 * 2.0
+    const file = try std.fs.cwd().openFile("test_data/sysvar_rent_mainnet.bin", .{});
+    defer file.close();
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    const minimum_balance: u64 = @intFromFloat(@as(f64, @floatFromInt((128 + 17) * 3480)) * 2.0);
+    try testing.expectEqual(minimum_balance, parsed.accounts[0].getLamports());
+}
+
+test "parse Rust instruction data payloads" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const cases = [_]struct { path: []const u8, len: usize }{
+        .{ .path = "test_data/solana_instruction_data_empty.bin", .len = 0 },
+        .{ .path = "test_data/solana_instruction_data_1_byte.bin", .len = 1 },
+        .{ .path = "test_data/solana_instruction_data_1kb.bin", .len = 1024 },
+    };
+
+    for (cases) |case| {
+        const file = try std.fs.cwd().openFile(case.path, .{});
+        defer file.close();
+
+        const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+        defer allocator.free(file_content);
+
+        var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+        var raw_accounts_buf: [entrypoint.MAX_ACCOUNTS]account_info.RawAccountInfo = undefined;
+        const parsed = try entrypoint.parseInput(file_content.ptr, &accounts_buf, &raw_accounts_buf);
+
+        try testing.expectEqual(@as(usize, 1), parsed.num_accounts);
+        try testing.expectEqual(case.len, parsed.instruction_data.len);
+        if (case.len == 1) {
+            try testing.expectEqual(@as(u8, 0x2A), parsed.instruction_data[0]);
+        } else {
+            // The 1 KiB payload is byte i = i % 256
+            for (parsed.instruction_data, 0..) |byte, i| {
+                try testing.expectEqual(@as(u8, @truncate(i)), byte);
+            }
+        }
+        try testing.expect(parsed.program_id.equals(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+    }
+}
+
+test "parse Rust alignment sweep" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // 17 accounts with data lengths 0 through 16
+    const file = try std.fs.cwd().openFile("test_data/solana_alignment_sweep.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const num_accounts = std.mem.readInt(u64, file_content[0..8], .little);
+    try testing.expectEqual(@as(u64, 17), num_accounts);
+
+    var accounts_buffer: [17]AccountInfo = undefined;
+    var aligned_buffer: [17]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, 17, &accounts_buffer, &aligned_buffer);
+
+    for (0..17) |i| {
+        // Every account header starts on an 8-byte boundary
+        try testing.expectEqual(@as(usize, 0), iter.offset % 8);
+
+        const acc = iter.next() orelse return error.TestUnexpectedResult;
+        try testing.expectEqual(@as(u8, @intCast(i)), acc.key().bytes[0]);
+        try testing.expectEqual(@as(u64, i), acc.dataLen());
+        for (acc.getData()) |byte| {
+            try testing.expectEqual(@as(u8, @intCast(i)), byte);
+        }
+    }
+    try testing.expectEqual(@as(usize, 0), iter.offset % 8);
+}
+
+/// `(position, original)` of each duplicate in solana_max_accounts.bin
+const max_accounts_duplicates = [_][2]usize{ .{ 10, 0 }, .{ 21, 3 }, .{ 32, 17 }, .{ 43, 42 }, .{ 54, 5 }, .{ 63, 62 } };
+
+/// Data lengths the distinct max accounts cycle through
+const max_accounts_data_lens = [_]usize{ 0, 1, 7, 8, 9, 1024 };
+
+test "parse Rust maximum account count" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // 64 accounts, the transaction account lock limit, six of them duplicates
+    const file = try std.fs.cwd().openFile("test_data/solana_max_accounts.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const num_accounts = std.mem.readInt(u64, file_content[0..8], .little);
+    try testing.expectEqual(@as(u64, 64), num_accounts);
+
+    var accounts_buffer: [64]AccountInfo = undefined;
+    var aligned_buffer: [64]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, 64, &accounts_buffer, &aligned_buffer);
+
+    for (0..64) |i| {
+        var original = i;
+        for (max_accounts_duplicates) |dup| {
+            if (dup[0] == i) original = dup[1];
+        }
+        const acc = iter.next() orelse return error.TestUnexpectedResult;
+        try testing.expectEqual(@as(u16, @intCast(original)), std.mem.readInt(u16, acc.key().bytes[0..2], .little));
+        try testing.expectEqual(original % 3 == 0, acc.isSigner());
+        try testing.expectEqual(original % 2 == 1, acc.isWritable());
+        const data = acc.getData();
+        try testing.expectEqual(max_accounts_data_lens[original % max_accounts_data_lens.len], data.len);
+        for (data) |byte| try testing.expectEqual(@as(u8, @intCast(original)), byte);
+    }
+    try testing.expect(iter.next() == null);
+
+    // The instruction data and program id are all that follow the accounts
+    const ix_len: usize = @intCast(std.mem.readInt(u64, file_content[iter.offset..][0..8], .little));
+    try testing.expectEqual(@as(usize, 8), ix_len);
+    try testing.expectEqual(@as(u64, 64), std.mem.readInt(u64, file_content[iter.offset + 8 ..][0..8], .little));
+    try testing.expectEqual(file_content.len, iter.offset + 8 + ix_len + 32);
+}
+
+/// Number of accounts `countMaxAccounts` was last handed
+var handled_accounts: usize = 0;
+
+/// Handler for solana_max_accounts.bin: records the account count and
+/// checks each duplicate aliases its original
+fn countMaxAccounts(program_id: *const Pubkey, accounts: []AccountInfo, instruction_data: []const u8) ProgramError!void {
+    _ = program_id;
+    _ = instruction_data;
+    handled_accounts = accounts.len;
+    for (max_accounts_duplicates) |dup| {
+        const duplicate = &accounts[dup[0]];
+        if (!duplicate.isDuplicate() or duplicate.duplicateIndex() != dup[1]) {
+            return ProgramError.InvalidArgument;
+        }
+        if (duplicate.getData().ptr != accounts[dup[1]].getData().ptr) {
+            return ProgramError.InvalidArgument;
+        }
+    }
+}
+
+/// Read a fixture into an 8-byte aligned buffer, as the runtime passes it
+fn readAlignedFixture(allocator: std.mem.Allocator, path: []const u8) ![]align(8) u8 {
+    const file_content = try std.fs.cwd().readFileAlloc(allocator, path, 1024 * 1024);
+    defer allocator.free(file_content);
+    const input = try allocator.alignedAlloc(u8, 8, file_content.len);
+    @memcpy(input, file_content);
+    return input;
+}
+
+test "processInput takes the Rust maximum account count" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const input = try readAlignedFixture(allocator, "test_data/solana_max_accounts.bin");
+    defer allocator.free(input);
+
+    handled_accounts = 0;
+    try testing.expectEqual(program_error.SUCCESS, entrypoint.processInput(countMaxAccounts, input.ptr));
+    try testing.expectEqual(@as(usize, 64), handled_accounts);
+
+    // One account more is refused before the handler runs
+    const overflow = try readAlignedFixture(allocator, "test_data/solana_max_accounts_overflow.bin");
+    defer allocator.free(overflow);
+
+    handled_accounts = 0;
+    try testing.expectEqual(program_error.INVALID_ARGUMENT, entrypoint.processInput(countMaxAccounts, overflow.ptr));
+    try testing.expectEqual(@as(usize, 0), handled_accounts);
+
+    // So is the full count, for an entrypoint declared with a smaller cap
+    try testing.expectEqual(program_error.INVALID_ARGUMENT, entrypoint.processInputWithMaxAccounts(16, countMaxAccounts, input.ptr));
+    try testing.expectEqual(@as(usize, 0), handled_accounts);
+}
+
+test "Rust fixture past the account limit overflows buffers sized for it" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // A negative fixture: the 64 accounts above plus a 65th
+    const file = try std.fs.cwd().openFile("test_data/solana_max_accounts_overflow.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const num_accounts: usize = @intCast(std.mem.readInt(u64, file_content[0..8], .little));
+    try testing.expectEqual(@as(usize, 65), num_accounts);
+
+    var accounts_buffer: [64]AccountInfo = undefined;
+    var aligned_buffer: [64]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, num_accounts, &accounts_buffer, &aligned_buffer);
+
+    var parsed: usize = 0;
+    while (iter.next()) |_| parsed += 1;
+    try testing.expectEqual(@as(usize, 64), parsed);
+    try testing.expectEqual(@as(usize, 1), iter.remaining);
+    try testing.expect(iter.offset < file_content.len - 8 - 8 - 32);
+}
+
+/// How a negative fixture breaks the layout, named like the `reason` field
+/// of test_data/negative/manifest.json
+const NegativeReason = enum {
+    truncated,
+    data_len_overflow,
+    invalid_duplicate_index,
+    invalid_flag,
+    trailing_bytes,
+};
+
+test "reject every Rust negative fixture for its stated reason" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const json = try std.fs.cwd().readFileAlloc(allocator, "test_data/negative/manifest.json", 64 * 1024);
+    defer allocator.free(json);
+
+    const Manifest = struct {
+        version: u32,
+        fixtures: []const struct {
+            filename: []const u8,
+            byte_length: usize,
+            reason: NegativeReason,
+            description: []const u8,
+        },
+    };
+    const manifest = try std.json.parseFromSlice(Manifest, allocator, json, .{});
+    defer manifest.deinit();
+    try testing.expectEqual(@as(usize, 8), manifest.value.fixtures.len);
+
+    var dir = try std.fs.cwd().openDir("test_data/negative", .{});
+    defer dir.close();
+    for (manifest.value.fixtures) |fixture| {
+        const bytes = try dir.readFileAlloc(allocator, fixture.filename, 1024 * 1024);
+        defer allocator.free(bytes);
+        try testing.expectEqual(fixture.byte_length, bytes.len);
+
+        const expected: entrypoint.InputError = switch (fixture.reason) {
+            .truncated => error.Truncated,
+            .data_len_overflow => error.DataLenOverflow,
+            .invalid_duplicate_index => error.InvalidDuplicateIndex,
+            .invalid_flag => error.InvalidFlag,
+            .trailing_bytes => error.TrailingBytes,
+        };
+        testing.expectError(expected, entrypoint.validateInput(bytes)) catch |err| {
+            std.debug.print("{s}: {s}\n", .{ fixture.filename, fixture.description });
+            return err;
+        };
+
+        // The length-aware deserialize refuses it for the same reason
+        const input = try allocator.alignedAlloc(u8, 8, bytes.len);
+        defer allocator.free(input);
+        @memcpy(input, bytes);
+        var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+        try testing.expectError(expected, entrypoint.deserializeChecked(input, &accounts_buf));
+
+        // The duplicate fixtures are complete buffers, so the unchecked
+        // entrypoint can be run on them and must refuse them too
+        if (fixture.reason == .invalid_duplicate_index) {
+            try testing.expectError(error.DuplicateAccountIndex, entrypoint.deserialize(input.ptr, &accounts_buf));
+        }
+    }
+}
+
+test "parse Rust flag matrix" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // One account per is_signer / is_writable / executable combination,
+    // named by its first key byte: bit 0 signer, bit 1 writable, bit 2
+    // executable. Account i holds i + 1 bytes.
+    const file = try std.fs.cwd().openFile("test_data/flags_matrix.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const num_accounts = std.mem.readInt(u64, file_content[0..8], .little);
+    try testing.expectEqual(@as(u64, 8), num_accounts);
+
+    var accounts_buffer: [8]AccountInfo = undefined;
+    var aligned_buffer: [8]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, 8, &accounts_buffer, &aligned_buffer);
+
+    var seen = [_]bool{false} ** 8;
+    while (iter.next()) |acc| {
+        const combination = acc.key().bytes[0];
+        try testing.expect(combination < 8 and !seen[combination]);
+        seen[combination] = true;
+
+        try testing.expectEqual(combination & 1 != 0, acc.isSigner());
+        try testing.expectEqual(combination & 2 != 0, acc.isWritable());
+        try testing.expectEqual(combination & 4 != 0, acc.isExecutable());
+        try testing.expectEqual(@as(u64, combination) + 1, acc.dataLen());
+        for (acc.getData()) |byte| try testing.expectEqual(0xF0 + combination, byte);
+    }
+    try testing.expectEqual([_]bool{true} ** 8, seen);
+}
+
+test "parse Rust lamports edge cases" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // u64::MAX lamports, and a closed account holding none
+    const cases = [_]struct { path: []const u8, lamports: u64 }{
+        .{ .path = "test_data/solana_max_lamports.bin", .lamports = std.math.maxInt(u64) },
+        .{ .path = "test_data/solana_zero_lamports.bin", .lamports = 0 },
+    };
+
+    for (cases) |case| {
+        const file = try std.fs.cwd().openFile(case.path, .{});
+        defer file.close();
+
+        const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+        defer allocator.free(file_content);
+
+        // Little-endian right after the marker, flags, key and owner
+        try testing.expectEqual(case.lamports, std.mem.readInt(u64, file_content[80..88], .little));
+
+        var parsed = try parseAccounts(file_content.ptr, allocator);
+        defer parsed.deinit();
+
+        try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+        const acc = parsed.accounts[0];
+        try testing.expectEqual(case.lamports, acc.getLamports());
+        try testing.expectEqual(@as(u64, 0), acc.dataLen());
+        try testing.expect(acc.owner().equals(&pubkey.SYSTEM_PROGRAM_ID));
+    }
+}
+
+test "parse Rust edge values" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // Lamports and rent epochs whose bytes expose a wrong byte order or a
+    // sign extension, and data counting up from 1 across all three accounts
+    const file = try std.fs.cwd().openFile("test_data/edge_values.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const lamports = [_]u64{ 0, std.math.maxInt(u64), 0x0102_0304_0506_0708 };
+    const rent_epochs = [_]u64{ std.math.maxInt(u64), 0x8000_0000_0000_0001, 0x0807_0605_0403_0201 };
+    const data_lens = [_]usize{ 5, 8, 3 };
+
+    var accounts_buffer: [3]AccountInfo = undefined;
+    var aligned_buffer: [3]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, 3, &accounts_buffer, &aligned_buffer);
+
+    var next_byte: u8 = 1;
+    for (0..3) |i| {
+        const acc = iter.next() orelse return error.TestUnexpectedResult;
+        try testing.expectEqual(lamports[i], acc.getLamports());
+        try testing.expectEqual(@as(u64, data_lens[i]), acc.dataLen());
+        for (acc.getData()) |byte| {
+            try testing.expectEqual(next_byte, byte);
+            next_byte += 1;
+        }
+        // rent_epoch is the last field of the entry
+        try testing.expectEqual(rent_epochs[i], std.mem.readInt(u64, file_content[iter.offset - 8 ..][0..8], .little));
+    }
+    try testing.expectEqual(@as(u8, 17), next_byte);
+}
+
+/// Handler for permission_mismatch.bin: instruction 0x01 writes its second
+/// byte into the first data byte of account 0, which must be writable
+fn writeFirstByte(program_id: *const Pubkey, accounts: []AccountInfo, instruction_data: []const u8) ProgramError!void {
+    _ = program_id;
+    if (instruction_data.len != 2 or instruction_data[0] != 0x01) {
+        return ProgramError.InvalidInstructionData;
+    }
+    if (accounts.len < 1) {
+        return ProgramError.NotEnoughAccountKeys;
+    }
+    try accounts[0].verifyWritable();
+    const data = try accounts[0].getDataMut();
+    data[0] = instruction_data[1];
+}
+
+test "Rust account passed read-only to an instruction that writes it" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/permission_mismatch.bin", .{});
+    defer file.close();
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    const json = try std.fs.cwd().readFileAlloc(allocator, "test_data/permission_requirements.json", 64 * 1024);
+    defer allocator.free(json);
+    const Requirement = struct {
+        account_index: usize,
+        is_writable: bool,
+        expects_writable: bool,
+        expected_error: []const u8,
+    };
+    const requirements = try std.json.parseFromSlice([]const Requirement, allocator, json, .{});
+    defer requirements.deinit();
+    try testing.expectEqual(@as(usize, 1), requirements.value.len);
+
+    const input = try allocator.alignedAlloc(u8, 8, file_content.len);
+    defer allocator.free(input);
+    @memcpy(input, file_content);
+
+    var accounts_buf: [entrypoint.MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try entrypoint.deserialize(input.ptr, &accounts_buf);
+    for (requirements.value) |requirement| {
+        const account = &parsed.accounts[requirement.account_index];
+        try testing.expectEqual(requirement.is_writable, account.isWritable());
+        try testing.expect(requirement.expects_writable);
+        try testing.expect(account.isSigner());
+
+        // Every way of writing to the account is refused up front
+        const guard_error = if (account.verifyWritable()) |_| return error.TestUnexpectedResult else |err| err;
+        try testing.expectEqualStrings(requirement.expected_error, @errorName(guard_error));
+        try testing.expectError(error.AccountNotWritable, account.getDataMut());
+        try testing.expectError(error.AccountNotWritable, account.getLamportsMut());
+        try testing.expectError(error.AccountNotWritable, account.tryBorrowMutData());
+    }
+
+    // Run through the entrypoint, the handler fails and the buffer the
+    // runtime would read back is untouched. Parsing above already stored
+    // the original data lengths, the only bytes the entrypoint writes.
+    const parsed_input = try allocator.dupe(u8, input);
+    defer allocator.free(parsed_input);
+    // AccountNotWritable has no builtin of its own and reaches the runtime
+    // as InvalidArgument
+    const code = entrypoint.processInput(writeFirstByte, input.ptr);
+    try testing.expectEqual(program_error.INVALID_ARGUMENT, code);
+    try testing.expectEqualSlices(u8, parsed_input, input);
+}
+
+/// Read a `.bin.zst` fixture and decompress it, for the fixtures the Rust
+/// helper writes compressed because they are over 1 MiB
+fn readCompressedFixture(allocator: std.mem.Allocator, path: []const u8, max_len: usize) ![]u8 {
+    const compressed = try std.fs.cwd().readFileAlloc(allocator, path, max_len);
+    defer allocator.free(compressed);
+    return std.compress.zstd.decompress.decodeAlloc(allocator, compressed, true, max_len);
+}
+
+test "parse Rust account with maximum data length" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const max_data_len: u64 = 10 * 1024 * 1024;
+
+    const file_content = try readCompressedFixture(allocator, "test_data/solana_max_data_account.bin.zst", 16 * 1024 * 1024);
+    defer allocator.free(file_content);
+
+    // Data, the realloc space and the instruction data length all made it to disk
+    try testing.expect(file_content.len > max_data_len + 10 * 1024);
+    try testing.expectEqual(max_data_len, std.mem.readInt(u64, file_content[88..96], .little));
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.isWritable());
+    try testing.expectEqual(max_data_len, acc.dataLen());
+
+    // The seeded pattern repeats every 64 KiB, so the last block matches
+    // the first
+    const data = acc.getData();
+    try testing.expectEqual(max_data_len, data.len);
+    const block_len = 64 * 1024;
+    try testing.expectEqualSlices(u8, data[0..block_len], data[data.len - block_len ..]);
+}
+
+test "parse Rust maximum data account in place" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const max_data_len: u64 = 10 * 1024 * 1024;
+
+    // The manifest carries the hash of the 10 MiB of seeded data
+    const json = try std.fs.cwd().readFileAlloc(allocator, "test_data/solana_max_data_account.expected.json", 64 * 1024);
+    defer allocator.free(json);
+    const Manifest = struct {
+        accounts: []const struct { data_len: u64, data_sha256: []const u8 },
+    };
+    const manifest = try std.json.parseFromSlice(Manifest, allocator, json, .{ .ignore_unknown_fields = true });
+    defer manifest.deinit();
+    try testing.expectEqual(max_data_len, manifest.value.accounts[0].data_len);
+    var expected_hash: [32]u8 = undefined;
+    _ = try std.fmt.hexToBytes(&expected_hash, manifest.value.accounts[0].data_sha256);
+
+    // Too big to commit raw, so the generator stores it zstd-compressed
+    const file_content = try readCompressedFixture(allocator, "test_data/solana_max_data_account.bin.zst", 16 * 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var accounts_buffer: [1]AccountInfo = undefined;
+    var aligned_buffer: [1]AccountData = undefined;
+    var iter = AccountIterator.init(file_content.ptr, 1, &accounts_buffer, &aligned_buffer);
+    const acc = iter.next() orelse return error.TestUnexpectedResult;
+    try testing.expectEqual(max_data_len, acc.dataLen());
+    // Zero in the file, as the loader writes it, and stored when parsed
+    try testing.expectEqual(@as(u32, 0), std.mem.readInt(u32, file_content[12..16], .little));
+    try testing.expectEqual(@as(u32, @intCast(max_data_len)), acc.originalDataLen());
+
+    // The data is read where it lies in the input, not copied out
+    const data = acc.getData();
+    try testing.expectEqual(@intFromPtr(file_content.ptr) + 96, @intFromPtr(data.ptr));
+
+    var hash: [32]u8 = undefined;
+    std.crypto.hash.sha2.Sha256.hash(data, &hash, .{});
+    try testing.expectEqual(expected_hash, hash);
+    try testing.expectEqual(file_content.len, iter.offset + 8 + 32);
+}
+
+test "parse Rust Clock sysvar account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/sysvar_clock.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.key().equals(&pubkey.CLOCK_SYSVAR_ID));
+    try testing.expect(acc.owner().equals(&pubkey.SYSVAR_PROGRAM_ID));
+    try testing.expect(!acc.isSigner());
+    try testing.expect(!acc.isWritable());
+    try testing.expect(!acc.isExecutable());
+    try testing.expectEqual(@as(u64, 1169280), acc.getLamports());
+
+    // The account data is the 40-byte Clock layout
+    const data = acc.getData();
+    try testing.expectEqual(@as(usize, @sizeOf(syscalls.Clock)), data.len);
+    const clock = std.mem.bytesToValue(syscalls.Clock, data[0..@sizeOf(syscalls.Clock)]);
+    try testing.expectEqual(@as(u64, 100), clock.slot);
+    try testing.expectEqual(@as(i64, 1700000000), clock.epoch_start_timestamp);
+    try testing.expectEqual(@as(u64, 5), clock.epoch);
+    try testing.expectEqual(@as(u64, 6), clock.leader_schedule_epoch);
+    try testing.expectEqual(@as(i64, 1700000500), clock.unix_timestamp);
+}
+
+test "parse Rust EpochSchedule sysvar account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/sysvar_epoch_schedule.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.key().equals(&pubkey.EPOCH_SCHEDULE_SYSVAR_ID));
+    try testing.expect(acc.owner().equals(&pubkey.SYSVAR_PROGRAM_ID));
+    try testing.expect(!acc.isWritable());
+    try testing.expectEqual(@as(u64, 1120560), acc.getLamports());
+
+    // 33 bytes on the wire, unlike the padded layout the syscall writes
+    const data = acc.getData();
+    try testing.expectEqual(@as(usize, EpochSchedule.LEN), data.len);
+    const schedule = EpochSchedule.fromBytes(data[0..EpochSchedule.LEN]);
+    try testing.expectEqual(EpochSchedule.DEFAULT, schedule);
+    try testing.expectEqual(@as(u64, 14), schedule.getEpoch(524256));
+}
+
+test "parse Rust StakeHistory sysvar account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/sysvar_stake_history.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.key().equals(&pubkey.STAKE_HISTORY_SYSVAR_ID));
+    try testing.expect(acc.owner().equals(&pubkey.SYSVAR_PROGRAM_ID));
+
+    // u64 entry count, then (epoch, effective, activating, deactivating)
+    // records of 32 bytes each, newest epoch first
+    const data = acc.getData();
+    const count = std.mem.readInt(u64, data[0..8], .little);
+    try testing.expectEqual(@as(u64, 3), count);
+    try testing.expectEqual(@as(usize, 8 + 3 * 32), data.len);
+
+    const expected = [_][4]u64{
+        .{ 12, 3_000_000, 250_000, 0 },
+        .{ 11, 2_500_000, 500_000, 100_000 },
+        .{ 10, 2_000_000, 0, 50_000 },
+    };
+    for (expected, 0..) |entry, i| {
+        const record = data[8 + i * 32 ..][0..32];
+        for (entry, 0..) |value, field| {
+            try testing.expectEqual(value, std.mem.readInt(u64, record[field * 8 ..][0..8], .little));
+        }
+    }
+}
+
+test "parse Rust SlotHashes sysvar account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+    const Sha256 = std.crypto.hash.sha2.Sha256;
+
+    const file = try std.fs.cwd().openFile("test_data/sysvar_slot_hashes.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.key().equals(&pubkey.SLOT_HASHES_SYSVAR_ID));
+    try testing.expect(acc.owner().equals(&pubkey.SYSVAR_PROGRAM_ID));
+
+    // u64 entry count, then (u64 slot, 32-byte hash) pairs, newest first.
+    // Each hash is SHA-256 of the slot's little-endian bytes.
+    const data = acc.getData();
+    try testing.expectEqual(@as(u64, 5), std.mem.readInt(u64, data[0..8], .little));
+    try testing.expectEqual(@as(usize, 8 + 5 * 40), data.len);
+
+    for (0..5) |i| {
+        const entry = data[8 + i * 40 ..][0..40];
+        const slot = std.mem.readInt(u64, entry[0..8], .little);
+        try testing.expectEqual(@as(u64, 1004 - i), slot);
+
+        var expected_hash: [32]u8 = undefined;
+        Sha256.hash(entry[0..8], &expected_hash, .{});
+        try testing.expectEqualSlices(u8, &expected_hash, entry[8..40]);
+    }
+
+    const slot_hashes = try slot_hashes_sysvar.load(&acc);
+    try testing.expectEqual(@as(usize, 5), slot_hashes.len());
+    for (1000..1005) |slot| {
+        var expected_hash: [32]u8 = undefined;
+        Sha256.hash(std.mem.asBytes(&std.mem.nativeToLittle(u64, slot)), &expected_hash, .{});
+        try testing.expectEqual(expected_hash, slot_hashes.getHash(slot).?);
+    }
+    try testing.expectEqual(@as(?[32]u8, null), slot_hashes.getHash(1005));
+}
+
+test "parse Rust nonce accounts" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+    const Sha256 = std.crypto.hash.sha2.Sha256;
+
+    const cases = [_]struct { path: []const u8, initialized: bool }{
+        .{ .path = "test_data/nonce_account.bin", .initialized = true },
+        .{ .path = "test_data/nonce_account_uninitialized.bin", .initialized = false },
+    };
+
+    for (cases) |case| {
+        const file = try std.fs.cwd().openFile(case.path, .{});
+        defer file.close();
+
+        const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+        defer allocator.free(file_content);
+
+        var parsed = try parseAccounts(file_content.ptr, allocator);
+        defer parsed.deinit();
+
+        const acc = parsed.accounts[0];
+        try testing.expect(acc.owner().equals(&pubkey.SYSTEM_PROGRAM_ID));
+        try testing.expect(acc.isWritable());
+        // Rent-exempt minimum for 80 bytes: (128 + 80) * 3480 * 2
+        try testing.expectEqual(@as(u64, 1447680), acc.getLamports());
+
+        // Bincode: u32 Versions tag (1 = Current), u32 State tag, then
+        // authority, durable nonce and lamports_per_signature
+        const data = acc.getData();
+        try testing.expectEqual(@as(usize, 80), data.len);
+        try testing.expectEqual(@as(u32, 1), std.mem.readInt(u32, data[0..4], .little));
+        const state = std.mem.readInt(u32, data[4..8], .little);
+        if (!case.initialized) {
+            try testing.expectEqual(@as(u32, 0), state);
+            for (data[8..]) |byte| try testing.expectEqual(@as(u8, 0), byte);
+            continue;
+        }
+
+        try testing.expectEqual(@as(u32, 1), state);
+        try testing.expectEqualSlices(u8, &([_]u8{0xA1} ** 32), data[8..40]);
+
+        // The durable nonce is SHA-256("DURABLE_NONCE" ++ blockhash)
+        var durable_nonce: [32]u8 = undefined;
+        var hasher = Sha256.init(.{});
+        hasher.update("DURABLE_NONCE");
+        hasher.update(&([_]u8{0x5A} ** 32));
+        hasher.final(&durable_nonce);
+        try testing.expectEqualSlices(u8, &durable_nonce, data[40..72]);
+
+        try testing.expectEqual(@as(u64, 5000), std.mem.readInt(u64, data[72..80], .little));
+    }
+}
+
+test "parse Rust vote account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/vote_account.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.key().equals(&Pubkey.fromBytes([_]u8{0x70} ** 32)));
+    try testing.expect(acc.owner().equals(&pubkey.VOTE_PROGRAM_ID));
+    try testing.expect(acc.isWritable());
+    // Rent-exempt minimum for 3762 bytes: (128 + 3762) * 3480 * 2
+    try testing.expectEqual(@as(u64, 27074400), acc.getLamports());
+
+    // Bincode VoteStateVersions::Current: u32 tag 2, node_pubkey,
+    // authorized_withdrawer, commission, then the votes
+    const data = acc.getData();
+    try testing.expectEqual(@as(usize, 3762), data.len);
+    try testing.expectEqual(@as(u32, 2), std.mem.readInt(u32, data[0..4], .little));
+    try testing.expectEqualSlices(u8, &([_]u8{0x71} ** 32), data[4..36]);
+    try testing.expectEqualSlices(u8, &([_]u8{0x73} ** 32), data[36..68]);
+    try testing.expectEqual(@as(u8, 10), data[68]);
+
+    // Ten landed votes: latency u8, slot u64, confirmation_count u32
+    try testing.expectEqual(@as(u64, 10), std.mem.readInt(u64, data[69..77], .little));
+    for (0..10) |i| {
+        const vote = data[77 + i * 13 ..][0..13];
+        try testing.expectEqual(@as(u8, 1), vote[0]);
+        try testing.expectEqual(@as(u64, 1000 + i), std.mem.readInt(u64, vote[1..9], .little));
+        try testing.expectEqual(@as(u32, @intCast(10 - i)), std.mem.readInt(u32, vote[9..13], .little));
+    }
+
+    // No root slot, then one authorized voter since epoch 100
+    try testing.expectEqual(@as(u8, 0), data[207]);
+    try testing.expectEqual(@as(u64, 1), std.mem.readInt(u64, data[208..216], .little));
+    try testing.expectEqual(@as(u64, 100), std.mem.readInt(u64, data[216..224], .little));
+    try testing.expectEqualSlices(u8, &([_]u8{0x72} ** 32), data[224..256]);
+}
+
+test "parse Rust upgradeable loader accounts" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/bpf_loader_upgradeable.bin", .{});
+    defer file.close();
+
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+    try testing.expectEqual(@as(usize, 3), parsed.accounts.len);
+    for (parsed.accounts) |acc| {
+        try testing.expect(acc.owner().equals(&bpf.bpf_upgradeable_loader_program_id));
+    }
+
+    const elf = "\x7fELF" ++ comptime blk: {
+        var bytes: [60]u8 = undefined;
+        for (&bytes, 0..) |*byte, i| byte.* = @intCast(i);
+        break :blk bytes;
+    };
+    const authority = [_]u8{0xB3} ** 32;
+
+    // Program: u32 tag 2, then the programdata address
+    const program = parsed.accounts[0];
+    try testing.expect(program.isExecutable());
+    try testing.expect(program.key().equals(&Pubkey.fromBytes([_]u8{0xB1} ** 32)));
+    const program_data = program.getData();
+    try testing.expectEqual(@as(usize, 36), program_data.len);
+    try testing.expectEqual(@as(u32, 2), std.mem.readInt(u32, program_data[0..4], .little));
+    const programdata_id = try bpf.getUpgradeableLoaderProgramDataId(program.key().*);
+    try testing.expectEqualSlices(u8, &programdata_id.bytes, program_data[4..36]);
+
+    // ProgramData: u32 tag 3, slot, Some(authority), then the ELF
+    const programdata = parsed.accounts[1];
+    try testing.expect(programdata.key().equals(&programdata_id));
+    const data = programdata.getData();
+    try testing.expectEqual(@as(u32, 3), std.mem.readInt(u32, data[0..4], .little));
+    try testing.expectEqual(@as(u64, 123456), std.mem.readInt(u64, data[4..12], .little));
+    try testing.expectEqual(@as(u8, 1), data[12]);
+    try testing.expectEqualSlices(u8, &authority, data[13..45]);
+    try testing.expectEqualSlices(u8, &elf, data[45..]);
+
+    // Buffer: u32 tag 1, Some(authority), then the ELF
+    const buffer = parsed.accounts[2];
+    try testing.expect(buffer.key().equals(&Pubkey.fromBytes([_]u8{0xB2} ** 32)));
+    const buffer_data = buffer.getData();
+    try testing.expectEqual(@as(u32, 1), std.mem.readInt(u32, buffer_data[0..4], .little));
+    try testing.expectEqual(@as(u8, 1), buffer_data[4]);
+    try testing.expectEqualSlices(u8, &authority, buffer_data[5..37]);
+    try testing.expectEqualSlices(u8, &elf, buffer_data[37..]);
+}
+
+test "decode Rust Borsh counter account" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const file = try std.fs.cwd().openFile("test_data/borsh_counter_account.bin", .{});
+    defer file.close();
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+    try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+    const acc = parsed.accounts[0];
+    try testing.expect(acc.owner().equals(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+
+    // Rust's CounterAccount { counter: u64, authority: Pubkey }: the counter
+    // little-endian at 0, the authority's bytes at 8, 40 bytes in all
+    const CounterAccount = struct {
+        counter: u64,
+        authority: Pubkey,
+    };
+    const data = acc.getData();
+    try testing.expectEqual(@as(usize, 40), data.len);
+    var stream = std.io.fixedBufferStream(data);
+    const state = try borsh.deserialize(CounterAccount, stream.reader());
+    try testing.expectEqual(@as(u64, 0x0000_0123_4567_89AB), state.counter);
+    try testing.expect(state.authority.equals(&Pubkey.fromBytes([_]u8{0xA7} ** 32)));
+    try testing.expectEqual(data.len, stream.pos);
+
+    // Encoding it again gives Rust's bytes back
+    var encoded = std.ArrayList(u8).init(allocator);
+    defer encoded.deinit();
+    try borsh.serialize(encoded.writer(), state);
+    try testing.expectEqualSlices(u8, data, encoded.items);
+}
+
+test "derive the PDAs Rust derived" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    const json = try std.fs.cwd().readFileAlloc(allocator, "test_data/pda_expected.json", 64 * 1024);
+    defer allocator.free(json);
+
+    const Case = struct {
+        seeds: []const []const u8,
+        program_id: Pubkey,
+        address: Pubkey,
+        bump: u8,
+    };
+    const cases = try std.json.parseFromSlice([]const Case, allocator, json, .{});
+    defer cases.deinit();
+    try testing.expectEqual(@as(usize, 5), cases.value.len);
+
+    const file = try std.fs.cwd().openFile("test_data/pda_accounts.bin", .{});
+    defer file.close();
+    const file_content = try file.readToEndAlloc(allocator, 1024 * 1024);
+    defer allocator.free(file_content);
+    var parsed = try parseAccounts(file_content.ptr, allocator);
+    defer parsed.deinit();
+    try testing.expectEqual(cases.value.len, parsed.accounts.len);
+
+    for (cases.value, parsed.accounts) |case, acc| {
+        var seed_bufs: [16][32]u8 = undefined;
+        var seeds: [16][]const u8 = undefined;
+        for (case.seeds, 0..) |hex, i| {
+            seeds[i] = try std.fmt.hexToBytes(&seed_bufs[i], hex);
+        }
+
+        const pda = try Pubkey.findProgramAddress(seeds[0..case.seeds.len], case.program_id);
+        try testing.expect(pda.address.equals(&case.address));
+        try testing.expectEqual(case.bump, pda.bump_seed[0]);
+
+        // The fixture account is keyed by the address and carries
+        // bump | seed count | (len | seed)*
+        try testing.expect(acc.key().equals(&case.address));
+        try testing.expect(acc.owner().equals(&case.program_id));
+        const data = acc.getData();
+        try testing.expectEqual(case.bump, data[0]);
+        try testing.expectEqual(case.seeds.len, data[1]);
+        var offset: usize = 2;
+        for (seeds[0..case.seeds.len]) |seed| {
+            try testing.expectEqual(seed.len, data[offset]);
+            try testing.expectEqualSlices(u8, seed, data[offset + 1 ..][0..seed.len]);
+            offset += 1 + seed.len;
+        }
+        try testing.expectEqual(data.len, offset);
+    }
+}
+
+test "every fixture in the index exists with its recorded size and hash" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+    const Sha256 = std.crypto.hash.sha2.Sha256;
+
+    // A generator that writes nothing leaves a hole in the index rather
+    // than a file a glob would quietly skip
+    const json = try std.fs.cwd().readFileAlloc(allocator, "test_data/index.json", 1024 * 1024);
+    defer allocator.free(json);
+
+    const Index = struct {
+        version: u32,
+        files: []const struct {
+            name: []const u8,
+            path: []const u8,
+            size: u64,
+            sha256: []const u8,
+            scenario: []const u8,
+            format: ?[]const u8,
+            generator_version: []const u8,
+            // Only on .bin.zst entries; size and sha256 are of the compressed file
+            compression: ?struct {
+                algorithm: []const u8,
+                uncompressed_size: u64,
+                uncompressed_sha256: []const u8,
+            } = null,
+        },
+    };
+    const index = try std.json.parseFromSlice(Index, allocator, json, .{});
+    defer index.deinit();
+    try testing.expect(index.value.files.len > 0);
+
+    var dir = try std.fs.cwd().openDir("test_data", .{});
+    defer dir.close();
+    for (index.value.files) |entry| {
+        const bytes = try dir.readFileAlloc(allocator, entry.path, 16 * 1024 * 1024);
+        defer allocator.free(bytes);
+        try testing.expectEqual(entry.size, bytes.len);
+
+        var digest: [Sha256.digest_length]u8 = undefined;
+        Sha256.hash(bytes, &digest, .{});
+        const hex = std.fmt.bytesToHex(digest, .lower);
+        try testing.expectEqualStrings(entry.sha256, &hex);
+    }
+}
+
 test "verify Rust AccountData structure size" {
     const testing = std.testing;
 
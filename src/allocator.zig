@@ -0,0 +1,148 @@
+/// Bump allocator over the heap region the runtime maps for each invocation
+///
+/// Allocation advances a pointer and `free` does nothing, which is all a
+/// short-lived program invocation needs. The bump state is kept in the
+/// first bytes of the heap itself rather than in a global, so it starts
+/// over on every invocation: the runtime hands each one a zeroed heap.
+const std = @import("std");
+const bpf = @import("bpf.zig");
+
+/// Address the runtime maps the program heap at
+pub const HEAP_START_ADDRESS: usize = 0x300000000;
+
+/// Default heap size; a transaction can request more with the compute
+/// budget program, but only this much is guaranteed
+pub const HEAP_LENGTH: usize = 32 * 1024;
+
+/// Bump state at the start of the heap. All zeroes means not yet used.
+const Header = extern struct {
+    /// Offset of the next free byte from the start of the heap
+    next: usize,
+    /// Length of the heap
+    end: usize,
+};
+
+const vtable = std.mem.Allocator.VTable{
+    .alloc = alloc,
+    .resize = resize,
+    .remap = remap,
+    .free = free,
+};
+
+/// The program heap as a `std.mem.Allocator`. Outside the VM there is no
+/// heap at `HEAP_START_ADDRESS`, so this falls back to the page allocator.
+pub fn allocator() std.mem.Allocator {
+    if (bpf.is_solana) {
+        const heap: [*]align(@alignOf(Header)) u8 = @ptrFromInt(HEAP_START_ADDRESS);
+        return bumpAllocator(heap[0..HEAP_LENGTH]);
+    }
+    return std.heap.page_allocator;
+}
+
+/// A bump allocator over `heap`, which must be zeroed before first use.
+/// Allocators made from the same `heap` share its state.
+pub fn bumpAllocator(heap: []align(@alignOf(Header)) u8) std.mem.Allocator {
+    std.debug.assert(heap.len >= @sizeOf(Header));
+    const header: *Header = @ptrCast(heap.ptr);
+    if (header.end == 0) {
+        header.* = .{ .next = @sizeOf(Header), .end = heap.len };
+    }
+    return .{ .ptr = header, .vtable = &vtable };
+}
+
+fn alloc(ctx: *anyopaque, len: usize, alignment: std.mem.Alignment, ret_addr: usize) ?[*]u8 {
+    _ = ret_addr;
+    const header: *Header = @ptrCast(@alignCast(ctx));
+    const base = @intFromPtr(header);
+
+    const start = alignment.forward(base + header.next);
+    const end = std.math.add(usize, start, len) catch return null;
+    if (end > base + header.end) {
+        return null;
+    }
+    header.next = end - base;
+    return @ptrFromInt(start);
+}
+
+/// Shrinking always succeeds. Only the most recent allocation can grow,
+/// into the free space right after it.
+fn resize(ctx: *anyopaque, memory: []u8, alignment: std.mem.Alignment, new_len: usize, ret_addr: usize) bool {
+    _ = alignment;
+    _ = ret_addr;
+    if (new_len <= memory.len) {
+        return true;
+    }
+
+    const header: *Header = @ptrCast(@alignCast(ctx));
+    const base = @intFromPtr(header);
+    const start = @intFromPtr(memory.ptr);
+    if (start + memory.len != base + header.next) {
+        return false;
+    }
+    const end = std.math.add(usize, start, new_len) catch return false;
+    if (end > base + header.end) {
+        return false;
+    }
+    header.next = end - base;
+    return true;
+}
+
+fn remap(ctx: *anyopaque, memory: []u8, alignment: std.mem.Alignment, new_len: usize, ret_addr: usize) ?[*]u8 {
+    return if (resize(ctx, memory, alignment, new_len, ret_addr)) memory.ptr else null;
+}
+
+/// Memory is only reclaimed when the invocation ends
+fn free(ctx: *anyopaque, memory: []u8, alignment: std.mem.Alignment, ret_addr: usize) void {
+    _ = ctx;
+    _ = memory;
+    _ = alignment;
+    _ = ret_addr;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+test "allocations advance through the heap and respect alignment" {
+    var heap: [256]u8 align(@alignOf(Header)) = [_]u8{0} ** 256;
+    const a = bumpAllocator(&heap);
+
+    const bytes = try a.alloc(u8, 3);
+    const words = try a.alloc(u64, 2);
+    try std.testing.expectEqual(@intFromPtr(&heap) + @sizeOf(Header), @intFromPtr(bytes.ptr));
+    try std.testing.expectEqual(@as(usize, 0), @intFromPtr(words.ptr) % @alignOf(u64));
+    try std.testing.expect(@intFromPtr(words.ptr) >= @intFromPtr(bytes.ptr) + bytes.len);
+
+    // free is a no-op, the next allocation still comes after `words`
+    a.free(words);
+    const more = try a.alloc(u8, 1);
+    try std.testing.expect(@intFromPtr(more.ptr) >= @intFromPtr(words.ptr) + words.len * @sizeOf(u64));
+
+    // A second allocator over the same heap continues where this one stopped
+    const again = try bumpAllocator(&heap).alloc(u8, 1);
+    try std.testing.expectEqual(@intFromPtr(more.ptr) + 1, @intFromPtr(again.ptr));
+}
+
+test "exhausting the heap returns OutOfMemory" {
+    var heap: [HEAP_LENGTH]u8 align(@alignOf(Header)) = [_]u8{0} ** HEAP_LENGTH;
+    const a = bumpAllocator(&heap);
+
+    const usable = HEAP_LENGTH - @sizeOf(Header);
+    _ = try a.alloc(u8, usable - 10);
+    try std.testing.expectError(error.OutOfMemory, a.alloc(u8, 11));
+    _ = try a.alloc(u8, 10);
+    try std.testing.expectError(error.OutOfMemory, a.alloc(u8, 1));
+    try std.testing.expectError(error.OutOfMemory, a.alloc(u8, std.math.maxInt(usize)));
+}
+
+test "only the last allocation grows in place" {
+    var heap: [128]u8 align(@alignOf(Header)) = [_]u8{0} ** 128;
+    const a = bumpAllocator(&heap);
+
+    const first = try a.alloc(u8, 8);
+    const second = try a.alloc(u8, 8);
+    try std.testing.expect(!a.resize(first, 16));
+    try std.testing.expect(a.resize(second, 16));
+    try std.testing.expect(a.resize(first, 4));
+    try std.testing.expect(!a.resize(second.ptr[0..16], 1024));
+}
@@ -0,0 +1,375 @@
+/// Borsh serialization
+///
+/// Encodes values the way Rust's borsh crate does, so instruction data and
+/// account state can be shared with programs written in Rust
+const std = @import("std");
+
+pub const BorshError = error{
+    /// A slice is longer than the u32 length prefix can hold
+    LengthOverflow,
+    /// A length prefix is above the caller's `max_len`
+    LengthLimitExceeded,
+    /// The input ended before every field was read
+    UnexpectedEnd,
+    /// A bool byte other than 0 or 1
+    InvalidBool,
+    /// An optional tag other than 0 or 1
+    InvalidOptionTag,
+    /// An enum or union discriminant past the last variant
+    InvalidEnum,
+};
+
+pub const DeserializeOptions = struct {
+    /// Longest slice or string accepted, in elements. Checked against the
+    /// length prefix before anything is allocated.
+    max_len: u32 = 10 * 1024,
+};
+
+/// Serialize `value` to `writer`
+///
+/// - integers and floats: little-endian
+/// - bool: one byte, 0 or 1
+/// - arrays: each element in order, no length
+/// - slices: u32 length, then each element
+/// - optionals: 0 for null, or 1 followed by the value
+/// - structs: each field in declaration order
+/// - enums: u8 variant index
+/// - tagged unions: u8 variant index followed by the payload
+pub fn serialize(writer: anytype, value: anytype) !void {
+    const T = @TypeOf(value);
+    switch (@typeInfo(T)) {
+        .void => {},
+        .bool => try writer.writeByte(@intFromBool(value)),
+        .int => try writer.writeInt(T, value, .little),
+        .float => try writer.writeInt(std.meta.Int(.unsigned, @bitSizeOf(T)), @bitCast(value), .little),
+        .array => |info| {
+            if (info.child == u8) {
+                try writer.writeAll(&value);
+            } else {
+                for (value) |item| try serialize(writer, item);
+            }
+        },
+        .pointer => |info| switch (info.size) {
+            .slice => {
+                const len = std.math.cast(u32, value.len) orelse return BorshError.LengthOverflow;
+                try writer.writeInt(u32, len, .little);
+                if (info.child == u8) {
+                    try writer.writeAll(value);
+                } else {
+                    for (value) |item| try serialize(writer, item);
+                }
+            },
+            .one => try serialize(writer, value.*),
+            else => @compileError("borsh cannot serialize " ++ @typeName(T)),
+        },
+        .optional => {
+            if (value) |inner| {
+                try writer.writeByte(1);
+                try serialize(writer, inner);
+            } else {
+                try writer.writeByte(0);
+            }
+        },
+        .@"struct" => |info| {
+            inline for (info.fields) |field| {
+                try serialize(writer, @field(value, field.name));
+            }
+        },
+        .@"enum" => try writer.writeByte(variantIndex(T, value)),
+        .@"union" => |info| {
+            const Tag = info.tag_type orelse @compileError("borsh needs a tagged union, got " ++ @typeName(T));
+            try writer.writeByte(variantIndex(Tag, value));
+            switch (value) {
+                inline else => |payload| try serialize(writer, payload),
+            }
+        },
+        else => @compileError("borsh cannot serialize " ++ @typeName(T)),
+    }
+}
+
+/// Deserialize a `T` from `reader`. `T` must not hold slices, since there
+/// is nowhere to put them; use `deserializeAlloc` for those.
+pub fn deserialize(comptime T: type, reader: anytype) !T {
+    if (comptime hasSlice(T)) @compileError(@typeName(T) ++ " holds slices, use borsh.deserializeAlloc");
+    return read(T, null, .{}, reader);
+}
+
+/// Deserialize a `T` from `reader`. Slices are allocated with `allocator`
+/// and owned by the caller; an arena keeps freeing nested values simple.
+/// A length prefix above `options.max_len` fails before allocating.
+pub fn deserializeAlloc(
+    comptime T: type,
+    allocator: std.mem.Allocator,
+    reader: anytype,
+    options: DeserializeOptions,
+) !T {
+    return read(T, allocator, options, reader);
+}
+
+fn read(comptime T: type, allocator: ?std.mem.Allocator, options: DeserializeOptions, reader: anytype) !T {
+    switch (@typeInfo(T)) {
+        .void => return {},
+        .bool => return switch (try readByte(reader)) {
+            0 => false,
+            1 => true,
+            else => BorshError.InvalidBool,
+        },
+        .int => return reader.readInt(T, .little) catch |err| return mapEnd(err),
+        .float => {
+            const Bits = std.meta.Int(.unsigned, @bitSizeOf(T));
+            return @bitCast(reader.readInt(Bits, .little) catch |err| return mapEnd(err));
+        },
+        .array => |info| {
+            var result: T = undefined;
+            if (info.child == u8) {
+                reader.readNoEof(&result) catch |err| return mapEnd(err);
+            } else {
+                for (&result) |*item| item.* = try read(info.child, allocator, options, reader);
+            }
+            return result;
+        },
+        .pointer => |info| switch (info.size) {
+            .slice => {
+                // deserialize() rejects slice types at compile time
+                const gpa = allocator orelse unreachable;
+                const len = reader.readInt(u32, .little) catch |err| return mapEnd(err);
+                if (len > options.max_len) return BorshError.LengthLimitExceeded;
+                const items = try gpa.alloc(info.child, len);
+                errdefer gpa.free(items);
+                if (info.child == u8) {
+                    reader.readNoEof(items) catch |err| return mapEnd(err);
+                } else {
+                    for (items) |*item| item.* = try read(info.child, allocator, options, reader);
+                }
+                return items;
+            },
+            else => @compileError("borsh cannot deserialize " ++ @typeName(T)),
+        },
+        .optional => |info| return switch (try readByte(reader)) {
+            0 => null,
+            1 => try read(info.child, allocator, options, reader),
+            else => BorshError.InvalidOptionTag,
+        },
+        .@"struct" => |info| {
+            var result: T = undefined;
+            inline for (info.fields) |field| {
+                @field(result, field.name) = try read(field.type, allocator, options, reader);
+            }
+            return result;
+        },
+        .@"enum" => |info| {
+            const index = try readByte(reader);
+            inline for (info.fields, 0..) |field, i| {
+                if (index == i) return @field(T, field.name);
+            }
+            return BorshError.InvalidEnum;
+        },
+        .@"union" => |info| {
+            if (info.tag_type == null) @compileError("borsh needs a tagged union, got " ++ @typeName(T));
+            const index = try readByte(reader);
+            inline for (info.fields, 0..) |field, i| {
+                if (index == i) {
+                    return @unionInit(T, field.name, try read(field.type, allocator, options, reader));
+                }
+            }
+            return BorshError.InvalidEnum;
+        },
+        else => @compileError("borsh cannot deserialize " ++ @typeName(T)),
+    }
+}
+
+fn readByte(reader: anytype) !u8 {
+    return reader.readByte() catch |err| return mapEnd(err);
+}
+
+/// Report a reader running dry as `UnexpectedEnd`
+fn mapEnd(err: anytype) @TypeOf(err) || BorshError {
+    return if (err == error.EndOfStream) BorshError.UnexpectedEnd else err;
+}
+
+/// Whether decoding `T` needs an allocator
+fn hasSlice(comptime T: type) bool {
+    return switch (@typeInfo(T)) {
+        .pointer => true,
+        .array => |info| hasSlice(info.child),
+        .optional => |info| hasSlice(info.child),
+        inline .@"struct", .@"union" => |info| for (info.fields) |field| {
+            if (hasSlice(field.type)) break true;
+        } else false,
+        else => false,
+    };
+}
+
+/// Position of `value` among the variants of `Enum`, which is what borsh
+/// writes regardless of the enum's integer values
+fn variantIndex(comptime Enum: type, value: Enum) u8 {
+    const fields = @typeInfo(Enum).@"enum".fields;
+    comptime std.debug.assert(fields.len <= 256);
+    inline for (fields, 0..) |field, i| {
+        if (value == @field(Enum, field.name)) return i;
+    }
+    unreachable;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const Kind = enum(u32) { first = 10, second = 20 };
+
+const Action = union(enum) {
+    noop: void,
+    transfer: u64,
+    memo: []const u8,
+};
+
+const Inner = struct {
+    flag: bool,
+    tags: [2]u16,
+};
+
+const Outer = struct {
+    id: u32,
+    owner: ?[4]u8,
+    inner: Inner,
+    amounts: []const i64,
+    kind: Kind,
+    action: Action,
+};
+
+test "round-trip nested struct with optional and slice" {
+    var arena = std.heap.ArenaAllocator.init(std.testing.allocator);
+    defer arena.deinit();
+
+    const value = Outer{
+        .id = 7,
+        .owner = .{ 1, 2, 3, 4 },
+        .inner = .{ .flag = true, .tags = .{ 0x0102, 0x0304 } },
+        .amounts = &.{ -1, 2 },
+        .kind = .second,
+        .action = .{ .memo = "hi" },
+    };
+
+    var buffer = std.ArrayList(u8).init(std.testing.allocator);
+    defer buffer.deinit();
+    try serialize(buffer.writer(), value);
+
+    const expected = [_]u8{
+        7, 0, 0, 0, // id
+        1, 1, 2, 3, 4, // owner: Some
+        1, 0x02, 0x01, 0x04, 0x03, // inner
+        2, 0, 0, 0, // amounts length
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // -1
+        2, 0, 0, 0, 0, 0, 0, 0, // 2
+        1, // kind: second variant, not its value 20
+        2, 2, 0, 0, 0, 'h', 'i', // action: memo "hi"
+    };
+    try std.testing.expectEqualSlices(u8, &expected, buffer.items);
+
+    var stream = std.io.fixedBufferStream(buffer.items);
+    const decoded = try deserializeAlloc(Outer, arena.allocator(), stream.reader(), .{});
+    try std.testing.expectEqual(value.id, decoded.id);
+    try std.testing.expectEqual(value.owner, decoded.owner);
+    try std.testing.expectEqual(value.inner, decoded.inner);
+    try std.testing.expectEqualSlices(i64, value.amounts, decoded.amounts);
+    try std.testing.expectEqual(value.kind, decoded.kind);
+    try std.testing.expectEqualStrings("hi", decoded.action.memo);
+}
+
+test "round-trip null optional and void variant" {
+    var arena = std.heap.ArenaAllocator.init(std.testing.allocator);
+    defer arena.deinit();
+
+    const value = Outer{
+        .id = 0,
+        .owner = null,
+        .inner = .{ .flag = false, .tags = .{ 0, 0 } },
+        .amounts = &.{},
+        .kind = .first,
+        .action = .noop,
+    };
+
+    var buffer = std.ArrayList(u8).init(std.testing.allocator);
+    defer buffer.deinit();
+    try serialize(buffer.writer(), value);
+    try std.testing.expectEqual(@as(usize, 4 + 1 + 5 + 4 + 1 + 1), buffer.items.len);
+
+    var stream = std.io.fixedBufferStream(buffer.items);
+    const decoded = try deserializeAlloc(Outer, arena.allocator(), stream.reader(), .{});
+    try std.testing.expectEqual(@as(?[4]u8, null), decoded.owner);
+    try std.testing.expectEqual(@as(usize, 0), decoded.amounts.len);
+    try std.testing.expect(decoded.action == .noop);
+}
+
+test "deserialize without an allocator" {
+    const value = Inner{ .flag = true, .tags = .{ 0x0102, 0x0304 } };
+
+    var buffer = std.ArrayList(u8).init(std.testing.allocator);
+    defer buffer.deinit();
+    try serialize(buffer.writer(), value);
+
+    var stream = std.io.fixedBufferStream(buffer.items);
+    try std.testing.expectEqual(value, try deserialize(Inner, stream.reader()));
+}
+
+test "deserialize rejects invalid tags" {
+    var bad_bool = std.io.fixedBufferStream(&[_]u8{2});
+    try std.testing.expectError(BorshError.InvalidBool, deserialize(bool, bad_bool.reader()));
+
+    var bad_option = std.io.fixedBufferStream(&[_]u8{2});
+    try std.testing.expectError(BorshError.InvalidOptionTag, deserialize(?u8, bad_option.reader()));
+
+    var bad_enum = std.io.fixedBufferStream(&[_]u8{2});
+    try std.testing.expectError(BorshError.InvalidEnum, deserialize(Kind, bad_enum.reader()));
+
+    var bad_union = std.io.fixedBufferStream(&[_]u8{3});
+    try std.testing.expectError(BorshError.InvalidEnum, deserialize(union(enum) { a: u8, b: u16 }, bad_union.reader()));
+}
+
+test "truncated input fails with UnexpectedEnd" {
+    var arena = std.heap.ArenaAllocator.init(std.testing.allocator);
+    defer arena.deinit();
+
+    const value = Outer{
+        .id = 7,
+        .owner = .{ 1, 2, 3, 4 },
+        .inner = .{ .flag = true, .tags = .{ 1, 2 } },
+        .amounts = &.{ 3, 4 },
+        .kind = .second,
+        .action = .{ .memo = "hello" },
+    };
+    var buffer = std.ArrayList(u8).init(std.testing.allocator);
+    defer buffer.deinit();
+    try serialize(buffer.writer(), value);
+
+    // Every proper prefix stops partway through some field
+    for (0..buffer.items.len) |len| {
+        var stream = std.io.fixedBufferStream(buffer.items[0..len]);
+        try std.testing.expectError(
+            BorshError.UnexpectedEnd,
+            deserializeAlloc(Outer, arena.allocator(), stream.reader(), .{}),
+        );
+    }
+}
+
+test "length prefix above max_len is rejected before allocating" {
+    // Claims 4 billion elements but carries none
+    const hostile = [_]u8{ 0xff, 0xff, 0xff, 0xff };
+    var stream = std.io.fixedBufferStream(&hostile);
+    try std.testing.expectError(
+        BorshError.LengthLimitExceeded,
+        deserializeAlloc([]const u8, std.testing.allocator, stream.reader(), .{}),
+    );
+
+    const memo = [_]u8{ 3, 0, 0, 0, 'a', 'b', 'c' };
+    stream = std.io.fixedBufferStream(&memo);
+    try std.testing.expectError(
+        BorshError.LengthLimitExceeded,
+        deserializeAlloc([]const u8, std.testing.allocator, stream.reader(), .{ .max_len = 2 }),
+    );
+
+    stream = std.io.fixedBufferStream(&memo);
+    const decoded = try deserializeAlloc([]const u8, std.testing.allocator, stream.reader(), .{ .max_len = 3 });
+    defer std.testing.allocator.free(decoded);
+    try std.testing.expectEqualStrings("abc", decoded);
+}
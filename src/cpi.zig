@@ -4,15 +4,17 @@
 const std = @import("std");
 const syscalls = @import("syscalls.zig");
 const Pubkey = @import("pubkey/pubkey.zig").Pubkey;
+const system_program_id = @import("pubkey/pubkey.zig").SYSTEM_PROGRAM_ID;
 const account_info = @import("account_info/account_info.zig");
 const AccountInfo = account_info.AccountInfo;
-const AccountData = account_info.AccountData;
 const instruction_mod = @import("instruction/instruction.zig");
 const Instruction = instruction_mod.Instruction;
 const AccountMeta = instruction_mod.AccountMeta;
+const SolAccountMeta = instruction_mod.SolAccountMeta;
+const SolInstruction = instruction_mod.SolInstruction;
 const ProgramError = @import("program_error.zig").ProgramError;
-const fromErrorCode = @import("program_error.zig").fromErrorCode;
-const msg = @import("msg/msg.zig");
+const SUCCESS = @import("program_error.zig").SUCCESS;
+const bpf = @import("bpf.zig");
 
 /// Maximum depth for cross-program invocations
 pub const MAX_CPI_DEPTH = 4;
@@ -20,241 +22,464 @@ pub const MAX_CPI_DEPTH = 4;
 /// Maximum number of account infos per CPI
 pub const MAX_CPI_ACCOUNTS = 32;
 
+/// Maximum number of account metas in a CPI instruction. The runtime allows
+/// more, but the SolAccountMeta copies live on the 4KB SBF stack frame.
+pub const MAX_CPI_INSTRUCTION_ACCOUNTS = 32;
+
 /// Maximum instruction data size for CPI
 pub const MAX_CPI_DATA_SIZE = 10240; // 10KB
 
-/// Rust-compatible StableVec format
-const StableVec = extern struct {
-    addr: u64,  // Pointer as u64
-    cap: u64,   // Capacity
-    len: u64,   // Length
-};
-
-/// Rust-compatible instruction for sol_invoke_signed_rust
-/// This matches the format expected by Rust's CPI implementation
-const StableInstruction = extern struct {
-    accounts: StableVec,     // StableVec<AccountMeta>
-    data: StableVec,         // StableVec<u8>
-    program_id: Pubkey,      // Inline Pubkey, not pointer
-};
-
-/// C-compatible instruction for syscall
-/// This matches the format expected by sol_invoke_signed_c
-const CInstruction = extern struct {
-    program_id: *const Pubkey,
-    accounts: [*]const AccountMeta,
-    accounts_len: u64,
-    data: [*]const u8,
+/// Account info in the layout sol_invoke_signed_c reads (SolAccountInfo
+/// in the C SDK). Every field points back into the input buffer so the
+/// callee's changes are visible to the caller after the call returns.
+pub const CpiAccountInfo = extern struct {
+    id: *const Pubkey,
+    lamports: *align(1) u64,
     data_len: u64,
+    data: [*]u8,
+    owner_id: *const Pubkey,
+    rent_epoch: u64,
+    is_signer: u8,
+    is_writable: u8,
+    is_executable: u8,
+
+    pub fn fromAccountInfo(info: *const AccountInfo) CpiAccountInfo {
+        if (info.raw_ptr) |raw| {
+            return .{
+                .id = raw.id,
+                .lamports = raw.lamports,
+                .data_len = raw.data_len,
+                .data = raw.data,
+                .owner_id = raw.owner_id,
+                .rent_epoch = raw.rent_epoch,
+                .is_signer = raw.is_signer,
+                .is_writable = raw.is_writable,
+                .is_executable = raw.is_executable,
+            };
+        }
+        const data = info.data_ptr;
+        return .{
+            .id = &data.id,
+            .lamports = @ptrCast(&data.lamports),
+            .data_len = data.data_len,
+            .data = info.data_buffer,
+            .owner_id = &data.owner_id,
+            .rent_epoch = 0,
+            .is_signer = data.is_signer,
+            .is_writable = data.is_writable,
+            .is_executable = data.is_executable,
+        };
+    }
 };
 
+/// Maximum number of signers per CPI, the runtime's MAX_SIGNERS
+pub const MAX_CPI_SIGNERS = 16;
 
-// Note: AccountMeta now has inline Pubkey values
-// This ensures the data is self-contained when passed to CPI
+/// Maximum number of signer seeds per CPI, summed over all signers
+pub const MAX_CPI_SIGNER_SEEDS = 32;
 
+/// One seed as sol_invoke_signed_c reads it (SolSignerSeed in the C SDK)
+pub const SignerSeed = extern struct {
+    addr: [*]const u8,
+    len: u64,
+};
+
+/// The seeds of one PDA signer (SolSignerSeeds in the C SDK)
+pub const SignerSeeds = extern struct {
+    addr: [*]const SignerSeed,
+    len: u64,
+};
 
 /// Invoke a cross-program invocation
-pub inline fn invoke(
+///
+/// Passes the instruction as a SolInstruction and one CpiAccountInfo per entry of
+/// `account_infos` to sol_invoke_signed_c with no signer seeds. Every
+/// account the instruction references must be in `account_infos`.
+pub fn invoke(
     instruction: *const Instruction,
     account_infos: []const AccountInfo,
-) !void {
-    return invoke_signed(instruction, account_infos, &[_][]const []const u8{});
+) ProgramError!void {
+    return invokeSigned(instruction, account_infos, &.{});
 }
 
 /// Invoke a cross-program invocation with signer seeds (for PDAs)
-pub inline fn invoke_signed(
+///
+/// `signer_seeds` holds one entry per signer, each the list of seeds that
+/// derives the signing PDA from the calling program's id.
+pub fn invokeSigned(
     instruction: *const Instruction,
     account_infos: []const AccountInfo,
     signer_seeds: []const []const []const u8,
-) !void {
-    if (comptime !@import("bpf.zig").is_solana) {
-        return; // Mock success in test environment
+) ProgramError!void {
+    if (account_infos.len > MAX_CPI_ACCOUNTS or
+        instruction.accounts.len > MAX_CPI_INSTRUCTION_ACCOUNTS)
+    {
+        return ProgramError.TooManyAccounts;
     }
 
-    // Direct delegation without validation or logging for minimum CU
-    try instruction.invoke_signed(account_infos, signer_seeds);
-}
-
-/// Serialized instruction for syscall
-const SerializedInstruction = struct {
-    buffer: [256]u8,  // Reduced from 1024 to save stack space
-    len: usize,
-
-    pub fn ptr(self: *const SerializedInstruction) *const u8 {
-        return &self.buffer[0];
+    var metas: [MAX_CPI_INSTRUCTION_ACCOUNTS]SolAccountMeta = undefined;
+    for (instruction.accounts, 0..) |*meta, i| {
+        metas[i] = SolAccountMeta.fromAccountMeta(meta);
     }
-
-    pub fn deinit(self: *const SerializedInstruction) void {
-        // No-op for stack allocation
-        _ = self;
+    const c_instruction = SolInstruction.fromInstruction(
+        instruction,
+        metas[0..instruction.accounts.len],
+    );
+
+    var cpi_accounts: [MAX_CPI_ACCOUNTS]CpiAccountInfo = undefined;
+    for (account_infos, 0..) |*info, i| {
+        cpi_accounts[i] = CpiAccountInfo.fromAccountInfo(info);
     }
-};
 
-/// Serialize an instruction for the syscall
-fn serializeInstruction(ix: *const Instruction) !SerializedInstruction {
-    var result = SerializedInstruction{
-        .buffer = undefined,
-        .len = 0,
-    };
-
-    var offset: usize = 0;
-
-    // Program ID (32 bytes)
-    @memcpy(result.buffer[offset .. offset + 32], &ix.program_id.bytes);
-    offset += 32;
+    var seeds: [MAX_CPI_SIGNER_SEEDS]SignerSeed = undefined;
+    var signers: [MAX_CPI_SIGNERS]SignerSeeds = undefined;
+    const signer_count = try marshalSignerSeeds(signer_seeds, &seeds, &signers);
+
+    const result = invokeSignedC(
+        &c_instruction,
+        cpi_accounts[0..account_infos.len],
+        signers[0..signer_count],
+    );
+    if (result != SUCCESS) {
+        return ProgramError.CrossProgramInvocationFailed;
+    }
+}
 
-    // Number of accounts (u64)
-    const num_accounts = @as(u64, ix.accounts.len);
-    @memcpy(result.buffer[offset .. offset + 8], std.mem.asBytes(&num_accounts));
-    offset += 8;
+/// Legacy alias for backward compatibility
+pub const invoke_signed = invokeSigned;
 
-    // Account metas
-    for (ix.accounts) |meta| {
-        // Pubkey (32 bytes)
-        @memcpy(result.buffer[offset .. offset + 32], &meta.pubkey.bytes);
-        offset += 32;
+/// Lay `signer_seeds` out as SignerSeeds entries pointing into a flat
+/// `seeds` array, returning the number of signers written
+fn marshalSignerSeeds(
+    signer_seeds: []const []const []const u8,
+    seeds: *[MAX_CPI_SIGNER_SEEDS]SignerSeed,
+    signers: *[MAX_CPI_SIGNERS]SignerSeeds,
+) ProgramError!usize {
+    if (signer_seeds.len > MAX_CPI_SIGNERS) {
+        return ProgramError.InvalidSeeds;
+    }
 
-        // Flags (is_signer, is_writable)
-        result.buffer[offset] = @as(u8, @intFromBool(meta.is_signer));
-        offset += 1;
-        result.buffer[offset] = @as(u8, @intFromBool(meta.is_writable));
-        offset += 1;
+    var seed_count: usize = 0;
+    for (signer_seeds, 0..) |signer, i| {
+        if (seed_count + signer.len > MAX_CPI_SIGNER_SEEDS) {
+            return ProgramError.InvalidSeeds;
+        }
+        for (signer, seed_count..) |seed, j| {
+            seeds[j] = .{ .addr = seed.ptr, .len = seed.len };
+        }
+        signers[i] = .{ .addr = seeds[seed_count..].ptr, .len = signer.len };
+        seed_count += signer.len;
     }
+    return signer_seeds.len;
+}
 
-    // Data length (u64)
-    const data_len = @as(u64, ix.data.len);
-    @memcpy(result.buffer[offset .. offset + 8], std.mem.asBytes(&data_len));
-    offset += 8;
+/// Call sol_invoke_signed_c, or `test_syscall` when not running on Solana
+fn invokeSignedC(
+    instruction: *const SolInstruction,
+    account_infos: []const CpiAccountInfo,
+    signers: []const SignerSeeds,
+) u64 {
+    if (comptime !bpf.is_solana) {
+        return test_syscall(instruction, account_infos, signers);
+    }
 
-    // Data
-    @memcpy(result.buffer[offset .. offset + ix.data.len], ix.data);
-    offset += ix.data.len;
+    return syscalls.sol_invoke_signed_c(
+        @ptrCast(instruction),
+        @ptrCast(account_infos.ptr),
+        account_infos.len,
+        if (signers.len > 0) @ptrCast(signers.ptr) else null,
+        signers.len,
+    );
+}
 
-    result.len = offset;
-    return result;
+/// Stands in for sol_invoke_signed_c outside the VM. Tests swap it out to
+/// inspect the arguments handed to the syscall.
+var test_syscall: *const fn (
+    instruction: *const SolInstruction,
+    account_infos: []const CpiAccountInfo,
+    signers: []const SignerSeeds,
+) u64 = &mockInvokeSuccess;
+
+fn mockInvokeSuccess(
+    instruction: *const SolInstruction,
+    account_infos: []const CpiAccountInfo,
+    signers: []const SignerSeeds,
+) u64 {
+    _ = instruction;
+    _ = account_infos;
+    _ = signers;
+    return SUCCESS; // Mock success in test environment
 }
 
-/// Serialized account infos for syscall
-const SerializedAccountInfos = struct {
-    buffer: [1024]u8,  // Reduced from 4096 to save stack space
+/// Most bytes of return data the runtime keeps (MAX_RETURN_DATA)
+pub const MAX_RETURN_DATA = 1024;
+
+/// Return data left by the last program that set it
+pub const ReturnData = struct {
+    /// Program that set the data
+    program_id: Pubkey,
+    /// The bytes copied out, at most the length of the caller's buffer
+    data: []const u8,
+    /// Length of the return data, which is more than `data.len` when the
+    /// buffer was too small
     len: usize,
+};
 
-    pub fn ptr(self: *const SerializedAccountInfos) *const u8 {
-        return &self.buffer[0];
+/// Set the data returned to the caller of this program, replacing any set
+/// before. Empty data clears it.
+pub fn setReturnData(data: []const u8) void {
+    if (comptime !bpf.is_solana) {
+        return mockSetReturnData(data);
     }
+    syscalls.sol_set_return_data(@ptrCast(data.ptr), data.len);
+}
 
-    pub fn deinit(self: *const SerializedAccountInfos) void {
-        // No-op for stack allocation
-        _ = self;
+/// Read the return data of the last program invoked, or of this program
+/// if it set some since. Copies at most `buf.len` bytes into `buf`; null
+/// when there is no return data.
+pub fn getReturnData(buf: []u8) ?ReturnData {
+    var program_id: Pubkey = undefined;
+    const len = if (comptime !bpf.is_solana)
+        mockGetReturnData(buf, &program_id)
+    else
+        syscalls.sol_get_return_data(@ptrCast(buf.ptr), buf.len, &program_id);
+    if (len == 0) {
+        return null;
     }
-};
-
-/// Serialize account infos for the syscall
-fn serializeAccountInfos(
-    account_infos: []const AccountInfo,
-    account_metas: []const AccountMeta,
-) !SerializedAccountInfos {
-    var result = SerializedAccountInfos{
-        .buffer = undefined,
-        .len = 0,
+    return .{
+        .program_id = program_id,
+        .data = buf[0..@min(buf.len, len)],
+        .len = @intCast(len),
     };
+}
 
-    // Validate that we have matching accounts
-    if (account_infos.len < account_metas.len) {
-        return error.MissingAccounts;
-    }
-
-    var offset: usize = 0;
-
-    // Serialize each account info
-    for (account_infos) |info| {
-        // TODO: Match account info with corresponding meta
-        // This requires proper account matching logic
-
-        // For now, serialize the basic account info structure
-        // This is a simplified version - real implementation needs
-        // to match Solana's exact serialization format
-
-        // Pubkey
-        @memcpy(result.buffer[offset .. offset + 32], &info.key().bytes);
-        offset += 32;
-
-        // Owner
-        @memcpy(result.buffer[offset .. offset + 32], &info.owner().bytes);
-        offset += 32;
-
-        // Lamports (u64)
-        const lamports = info.getLamports();
-        @memcpy(result.buffer[offset .. offset + 8], std.mem.asBytes(&lamports));
-        offset += 8;
-
-        // Data length (u64)
-        const data = info.getData();
-        const data_len = @as(u64, data.len);
-        @memcpy(result.buffer[offset .. offset + 8], std.mem.asBytes(&data_len));
-        offset += 8;
+/// Stands in for the runtime's return data outside the VM. Tests set
+/// `program_id` to play the program that set it.
+var mock_return_data: struct {
+    program_id: Pubkey = Pubkey.ZEROES,
+    bytes: [MAX_RETURN_DATA]u8 = undefined,
+    len: usize = 0,
+} = .{};
+
+fn mockSetReturnData(data: []const u8) void {
+    const len = @min(data.len, MAX_RETURN_DATA);
+    @memcpy(mock_return_data.bytes[0..len], data[0..len]);
+    mock_return_data.len = len;
+}
 
-        // Note: Actual data pointer is passed separately in real CPI
+fn mockGetReturnData(buf: []u8, program_id: *Pubkey) u64 {
+    const len = mock_return_data.len;
+    if (len > 0) {
+        const copied = @min(buf.len, len);
+        @memcpy(buf[0..copied], mock_return_data.bytes[0..copied]);
+        program_id.* = mock_return_data.program_id;
     }
-
-    result.len = offset;
-    return result;
+    return len;
 }
 
-/// Helper to create a simple transfer instruction
-pub fn createTransferInstruction(
-    from: *const Pubkey,
-    to: *const Pubkey,
-    lamports: u64,
-) Instruction {
-    // System program ID
-    const system_program_id = Pubkey.fromBytes([_]u8{0} ** 32);
-
-    // Create account metas
-    const accounts = [_]AccountMeta{
-        AccountMeta.writable(from.*, true), // From account (signer, writable)
-        AccountMeta.writable(to.*, false), // To account (writable)
-    };
+/// System Program transfer instruction. The account metas and data live in
+/// this struct, so keep it alive for as long as the Instruction built from
+/// it is in use.
+pub const TransferInstruction = struct {
+    accounts: [2]AccountMeta,
+    /// u32 discriminator 2 followed by the u64 lamports, little-endian
+    data: [12]u8,
+
+    pub fn init(from: Pubkey, to: Pubkey, lamports: u64) TransferInstruction {
+        var data: [12]u8 = undefined;
+        std.mem.writeInt(u32, data[0..4], 2, .little);
+        std.mem.writeInt(u64, data[4..12], lamports, .little);
+        return .{
+            .accounts = .{
+                AccountMeta.writable(from, true), // From account (signer, writable)
+                AccountMeta.writable(to, false), // To account (writable)
+            },
+            .data = data,
+        };
+    }
 
-    // Serialize transfer instruction data
-    // System program transfer instruction: [4-byte discriminator][8-byte lamports]
-    var data: [12]u8 = undefined;
-    // Transfer discriminator = 2
-    @memcpy(data[0..4], &[_]u8{ 2, 0, 0, 0 });
-    @memcpy(data[4..12], std.mem.asBytes(&lamports));
-
-    return Instruction{
-        .program_id = system_program_id,
-        .accounts = &accounts,
-        .data = &data,
-    };
-}
+    pub fn instruction(self: *const TransferInstruction) Instruction {
+        return Instruction.init(system_program_id, &self.accounts, &self.data);
+    }
+};
 
 // ============================================================================
 // Tests
 // ============================================================================
 
+test "CpiAccountInfo matches SolAccountInfo layout" {
+    try std.testing.expectEqual(@as(usize, 56), @sizeOf(CpiAccountInfo));
+    try std.testing.expectEqual(@as(usize, 8), @offsetOf(CpiAccountInfo, "lamports"));
+    try std.testing.expectEqual(@as(usize, 24), @offsetOf(CpiAccountInfo, "data"));
+    try std.testing.expectEqual(@as(usize, 40), @offsetOf(CpiAccountInfo, "rent_epoch"));
+    try std.testing.expectEqual(@as(usize, 48), @offsetOf(CpiAccountInfo, "is_signer"));
+}
+
 test "create transfer instruction" {
     const from = Pubkey.fromBytes([_]u8{1} ** 32);
     const to = Pubkey.fromBytes([_]u8{2} ** 32);
-    const lamports: u64 = 1000;
 
-    const ix = createTransferInstruction(&from, &to, lamports);
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
 
+    try std.testing.expect(ix.program_id.equals(&system_program_id));
     try std.testing.expectEqual(@as(usize, 2), ix.accounts.len);
     try std.testing.expectEqual(@as(usize, 12), ix.data.len);
     try std.testing.expect(ix.accounts[0].is_signer);
     try std.testing.expect(ix.accounts[0].is_writable);
     try std.testing.expect(!ix.accounts[1].is_signer);
     try std.testing.expect(ix.accounts[1].is_writable);
+    try std.testing.expectEqual(@as(u32, 2), std.mem.readInt(u32, ix.data[0..4], .little));
+    try std.testing.expectEqual(@as(u64, 1000), std.mem.readInt(u64, ix.data[4..12], .little));
 }
 
 test "invoke mock in test environment" {
+    const allocator = std.testing.allocator;
     const from = Pubkey.fromBytes([_]u8{1} ** 32);
     const to = Pubkey.fromBytes([_]u8{2} ** 32);
+    var from_data: [0]u8 = .{};
+    var to_data: [0]u8 = .{};
 
-    const ix = createTransferInstruction(&from, &to, 1000);
+    const from_info = try account_info.createTestAccountInfo(allocator, &from, &system_program_id, 5000, &from_data, true, true, false);
+    defer allocator.destroy(from_info.data_ptr);
+    const to_info = try account_info.createTestAccountInfo(allocator, &to, &system_program_id, 0, &to_data, false, true, false);
+    defer allocator.destroy(to_info.data_ptr);
+
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
 
     // This should succeed in test environment (mocked)
-    try invoke(&ix, &[_]AccountInfo{});
+    try invoke(&ix, &[_]AccountInfo{ from_info, to_info });
+
+    // The account infos handed to the syscall point into the account data
+    const cpi_info = CpiAccountInfo.fromAccountInfo(&from_info);
+    try std.testing.expect(cpi_info.id.equals(&from));
+    try std.testing.expectEqual(@as(u64, 5000), cpi_info.lamports.*);
+    try std.testing.expectEqual(@as(u8, 1), cpi_info.is_signer);
+}
+
+test "invoke rejects too many account infos" {
+    const from = Pubkey.fromBytes([_]u8{1} ** 32);
+    const to = Pubkey.fromBytes([_]u8{2} ** 32);
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
+
+    var account_data: account_info.AccountData = undefined;
+    const info = AccountInfo.fromDataPtr(&account_data, undefined);
+    const infos = [_]AccountInfo{info} ** (MAX_CPI_ACCOUNTS + 1);
+    try std.testing.expectError(ProgramError.TooManyAccounts, invoke(&ix, &infos));
+}
+
+/// Syscall stub for the invoke test: checks the SolInstruction points at
+/// the transfer's program id, account metas and data
+fn expectTransferStub(
+    instruction: *const SolInstruction,
+    account_infos: []const CpiAccountInfo,
+    signers: []const SignerSeeds,
+) u64 {
+    _ = account_infos;
+    _ = signers;
+    if (!instruction.program_id.equals(&system_program_id)) return 1;
+    const metas = instruction.accountsSlice();
+    if (metas.len != 2) return 1;
+    if (metas[0].pubkey.bytes[0] != 1 or !metas[0].is_signer or !metas[0].is_writable) return 1;
+    if (metas[1].pubkey.bytes[0] != 2 or metas[1].is_signer or !metas[1].is_writable) return 1;
+    const data = instruction.dataSlice();
+    if (data.len != 12 or std.mem.readInt(u64, data[4..12], .little) != 1000) return 1;
+    return SUCCESS;
+}
+
+test "invoke hands the syscall a SolInstruction" {
+    const from = Pubkey.fromBytes([_]u8{1} ** 32);
+    const to = Pubkey.fromBytes([_]u8{2} ** 32);
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
+
+    test_syscall = &expectTransferStub;
+    defer test_syscall = &mockInvokeSuccess;
+
+    try ix.invoke(&.{});
+}
+
+/// Syscall stub for the invokeSigned test: checks two signers with two
+/// seeds each point at the test's seed bytes
+fn expectTwoSignersStub(
+    instruction: *const SolInstruction,
+    account_infos: []const CpiAccountInfo,
+    signers: []const SignerSeeds,
+) u64 {
+    _ = instruction;
+    _ = account_infos;
+    const expected = [_][2][]const u8{
+        .{ "vault", &[_]u8{254} },
+        .{ "escrow", &[_]u8{ 1, 2, 3 } },
+    };
+    if (signers.len != expected.len) return 1;
+    for (signers, expected) |signer, expected_seeds| {
+        if (signer.len != expected_seeds.len) return 1;
+        for (signer.addr[0..signer.len], expected_seeds) |seed, expected_seed| {
+            if (!std.mem.eql(u8, seed.addr[0..seed.len], expected_seed)) return 1;
+        }
+    }
+    // The second signer's seeds follow the first's in one flat array
+    if (signers[1].addr != signers[0].addr + 2) return 1;
+    return SUCCESS;
+}
+
+test "invokeSigned marshals signer seeds for the syscall" {
+    const from = Pubkey.fromBytes([_]u8{1} ** 32);
+    const to = Pubkey.fromBytes([_]u8{2} ** 32);
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
+
+    test_syscall = &expectTwoSignersStub;
+    defer test_syscall = &mockInvokeSuccess;
+
+    const vault_seeds = [_][]const u8{ "vault", &[_]u8{254} };
+    const escrow_seeds = [_][]const u8{ "escrow", &[_]u8{ 1, 2, 3 } };
+    try invokeSigned(&ix, &.{}, &.{ &vault_seeds, &escrow_seeds });
+
+    // A signer the stub does not expect makes the call fail
+    try std.testing.expectError(
+        ProgramError.CrossProgramInvocationFailed,
+        invokeSigned(&ix, &.{}, &.{&vault_seeds}),
+    );
+}
+
+test "invokeSigned rejects too many signer seeds" {
+    const from = Pubkey.fromBytes([_]u8{1} ** 32);
+    const to = Pubkey.fromBytes([_]u8{2} ** 32);
+    const transfer = TransferInstruction.init(from, to, 1000);
+    const ix = transfer.instruction();
+
+    const seeds = [_][]const u8{"seed"} ** 16;
+    const signers = [_][]const []const u8{&seeds} ** 3;
+    try std.testing.expectError(ProgramError.InvalidSeeds, invokeSigned(&ix, &.{}, &signers));
+}
+
+test "return data round-trips through the mocked syscalls" {
+    defer mock_return_data = .{};
+    const callee = Pubkey.fromBytes([_]u8{7} ** 32);
+    mock_return_data.program_id = callee;
+
+    var buf: [32]u8 = undefined;
+    try std.testing.expect(getReturnData(&buf) == null);
+
+    var payload: [16]u8 = undefined;
+    for (&payload, 0..) |*byte, i| byte.* = @intCast(i * 3);
+    setReturnData(&payload);
+
+    const result = getReturnData(&buf) orelse return error.TestUnexpectedResult;
+    try std.testing.expect(result.program_id.equals(&callee));
+    try std.testing.expectEqualSlices(u8, &payload, result.data);
+    try std.testing.expectEqual(@as(usize, 16), result.len);
+
+    // A short buffer gets the first bytes and the true length
+    var short: [4]u8 = undefined;
+    const truncated = getReturnData(&short) orelse return error.TestUnexpectedResult;
+    try std.testing.expectEqualSlices(u8, payload[0..4], truncated.data);
+    try std.testing.expectEqual(@as(usize, 16), truncated.len);
+
+    setReturnData(&.{});
+    try std.testing.expect(getReturnData(&buf) == null);
 }
@@ -7,6 +7,8 @@ const account_info = @import("account_info/account_info.zig");
 const pubkey = @import("pubkey/pubkey.zig");
 const program_error = @import("program_error.zig");
 const msg = @import("msg/msg.zig");
+const bpf = @import("bpf.zig");
+const syscalls = @import("syscalls.zig");
 
 const AccountInfo = account_info.AccountInfo;
 const AccountData = account_info.AccountData;
@@ -14,14 +16,19 @@ const Pubkey = pubkey.Pubkey;
 const ProgramError = program_error.ProgramError;
 const ProgramResult = program_error.ProgramResult;
 
-/// Maximum number of accounts that can be passed to a program
-/// Note: Reduced from 64 to 16 to fit within Solana's stack limit
-pub const MAX_ACCOUNTS = 16;
+/// Maximum number of accounts that can be passed to a program: the most a
+/// transaction can lock, and the default capacity of the entrypoint's
+/// account buffer. 64 `AccountInfo`s take 2.5 KiB of the 4 KiB SBF stack
+/// frame; a program that needs the room can declare its entrypoint with a
+/// smaller cap, see `declareEntrypointWithMaxAccounts`.
+pub const MAX_ACCOUNTS = 64;
 
 /// Account data padding - Solana adds 10KB padding after account data
 pub const ACCOUNT_DATA_PADDING = 10 * 1024;
 
-/// Process instruction function type
+/// Process instruction function type. A handler may also widen the error
+/// set with `program_error.CustomCode(n)` sets to fail with program-defined
+/// `Custom(n)` codes; the entrypoint helpers take any such handler.
 pub const ProcessInstruction = fn (
     program_id: *const Pubkey,
     accounts: []AccountInfo,
@@ -29,11 +36,17 @@ pub const ProcessInstruction = fn (
 ) ProgramResult;
 
 /// Input parser - optimized to avoid unnecessary copies
+///
+/// Each account takes an `AccountInfo` and a `RawAccountInfo`, 96 bytes
+/// together, so buffers for all `MAX_ACCOUNTS` need 6 KiB: more than the
+/// 4 KiB SBF stack frame. Size them for the accounts the program takes, or
+/// use `deserialize`, which needs only the `AccountInfo`s. More accounts
+/// than the buffers hold is `MaxAccountsExceeded`.
 pub fn parseInput(
     input: [*]const u8,
-    accounts_buf: *[MAX_ACCOUNTS]AccountInfo,
-    raw_accounts_buf: *[MAX_ACCOUNTS]account_info.RawAccountInfo,
-) struct {
+    accounts_buf: []AccountInfo,
+    raw_accounts_buf: []account_info.RawAccountInfo,
+) ProgramError!struct {
     accounts: []AccountInfo,
     num_accounts: usize,
     instruction_data: []const u8,
@@ -42,8 +55,11 @@ pub fn parseInput(
     var offset: usize = 0;
 
     // Read number of accounts
-    const num_accounts = std.mem.readInt(u64, input[offset..][0..8], .little);
+    const num_accounts: usize = @intCast(std.mem.readInt(u64, input[offset..][0..8], .little));
     offset += 8;
+    if (num_accounts > @min(accounts_buf.len, raw_accounts_buf.len)) {
+        return ProgramError.MaxAccountsExceeded;
+    }
 
     // Parse accounts - skip AccountData entirely!
     for (0..num_accounts) |i| {
@@ -52,8 +68,12 @@ pub fn parseInput(
 
         if (dup_info != 0xFF) {
             // Duplicate
+            if (dup_info >= i) {
+                return ProgramError.DuplicateAccountIndex;
+            }
             offset += 7;
             accounts_buf[i] = accounts_buf[dup_info];
+            accounts_buf[i].duplicate_of = dup_info;
             raw_accounts_buf[i] = raw_accounts_buf[dup_info];
         } else {
             const account_ptr = input + offset;
@@ -83,6 +103,7 @@ pub fn parseInput(
                 .data_ptr = @ptrFromInt(8), // Aligned dummy pointer
                 .data_buffer = data_ptr,
                 .raw_ptr = &raw_accounts_buf[i],
+                .borrow_state = @constCast(&input[offset - 1]),
             };
 
             // Skip to next account
@@ -108,55 +129,229 @@ pub fn parseInput(
     };
 }
 
+/// Result of `deserialize`, pointing into the input buffer
+pub const Deserialized = struct {
+    program_id: *const Pubkey,
+    accounts: []AccountInfo,
+    instruction_data: []const u8,
+};
+
+/// Zero-copy deserialization of the entrypoint input buffer
+///
+/// Each `AccountInfo` points straight at its 88-byte header and data inside
+/// `input`, so writes through it land in the buffer the runtime reads back.
+/// A duplicate entry copies the `AccountInfo` of the account it refers to,
+/// aliasing the same lamports and data. Each account's original_data_len
+/// is set to its data_len, the length realloc bounds growth by. `input`
+/// must be 8-byte aligned, as it is when passed in by the runtime. More
+/// accounts than `accounts_buf` holds is `MaxAccountsExceeded`.
+pub fn deserialize(input: [*]u8, accounts_buf: []AccountInfo) ProgramError!Deserialized {
+    var offset: usize = 0;
+
+    // Number of accounts
+    const num_accounts: usize = @intCast(std.mem.readInt(u64, input[offset..][0..8], .little));
+    offset += 8;
+    if (num_accounts > accounts_buf.len) {
+        return ProgramError.MaxAccountsExceeded;
+    }
+
+    for (0..num_accounts) |i| {
+        const dup_marker = input[offset];
+
+        if (dup_marker != account_info.NON_DUP_MARKER) {
+            // Duplicate: index of the original plus 7 bytes of padding
+            if (dup_marker >= i) {
+                return ProgramError.DuplicateAccountIndex;
+            }
+            accounts_buf[i] = accounts_buf[dup_marker];
+            accounts_buf[i].duplicate_of = dup_marker;
+            offset += 8;
+            continue;
+        }
+
+        const account_data: *align(8) AccountData = @ptrCast(@alignCast(input + offset));
+        offset += @sizeOf(AccountData);
+        // The loader leaves original_data_len zero; store the length on
+        // entry, as solana_program does, so realloc has its bound
+        account_data.original_data_len = @intCast(account_data.data_len);
+        accounts_buf[i] = AccountInfo.fromDataPtr(account_data, input + offset);
+
+        // Skip data, the realloc space, the alignment padding and rent_epoch
+        const data_len: usize = @intCast(account_data.data_len);
+        offset += std.mem.alignForward(usize, data_len, 8) + ACCOUNT_DATA_PADDING + @sizeOf(u64);
+    }
+
+    // Instruction data
+    const data_len: usize = @intCast(std.mem.readInt(u64, input[offset..][0..8], .little));
+    offset += 8;
+    const instruction_data = input[offset .. offset + data_len];
+    offset += data_len;
+
+    // Program ID
+    const program_id: *const Pubkey = @ptrCast(input + offset);
+
+    return .{
+        .program_id = program_id,
+        .accounts = accounts_buf[0..num_accounts],
+        .instruction_data = instruction_data,
+    };
+}
+
+/// Ways `validateInput` finds a buffer breaking the entrypoint layout
+pub const InputError = error{
+    /// The buffer ends before a field the layout promises
+    Truncated,
+    /// An account's data_len runs past the end of the buffer
+    DataLenOverflow,
+    /// A duplicate marker that does not name an earlier account
+    InvalidDuplicateIndex,
+    /// An is_signer, is_writable or executable byte other than 0 or 1
+    InvalidFlag,
+    /// Bytes after the program ID
+    TrailingBytes,
+};
+
+/// Check that `input` is exactly one well-formed entrypoint buffer, with
+/// every read bounds-checked. The runtime always hands `deserialize` a
+/// valid buffer, so it skips these checks; run this first on a buffer from
+/// anywhere else, such as a file or a fuzzer.
+pub fn validateInput(input: []const u8) InputError!void {
+    if (input.len < 8) return error.Truncated;
+    const num_accounts = std.mem.readInt(u64, input[0..8], .little);
+    var offset: usize = 8;
+
+    var i: u64 = 0;
+    while (i < num_accounts) : (i += 1) {
+        if (offset >= input.len) return error.Truncated;
+        const dup_marker = input[offset];
+        if (dup_marker != account_info.NON_DUP_MARKER) {
+            if (dup_marker >= i) return error.InvalidDuplicateIndex;
+            if (input.len - offset < 8) return error.Truncated;
+            offset += 8;
+            continue;
+        }
+
+        if (input.len - offset < @sizeOf(AccountData)) return error.Truncated;
+        const header = input[offset..][0..@sizeOf(AccountData)];
+        for (header[1..4]) |flag| {
+            if (flag > 1) return error.InvalidFlag;
+        }
+        offset += @sizeOf(AccountData);
+
+        // Data, the realloc space, the alignment padding and rent_epoch
+        const data_len = std.mem.readInt(u64, header[80..88], .little);
+        const padded = std.math.add(u64, data_len, 7) catch return error.DataLenOverflow;
+        const needed = std.math.add(u64, padded / 8 * 8, ACCOUNT_DATA_PADDING + @sizeOf(u64)) catch
+            return error.DataLenOverflow;
+        if (needed > input.len - offset) return error.DataLenOverflow;
+        offset += @intCast(needed);
+    }
+
+    if (input.len - offset < 8) return error.Truncated;
+    const instruction_data_len = std.mem.readInt(u64, input[offset..][0..8], .little);
+    offset += 8;
+    if (instruction_data_len > input.len - offset) return error.Truncated;
+    offset += @intCast(instruction_data_len);
+    if (input.len - offset < @sizeOf(Pubkey)) return error.Truncated;
+    offset += @sizeOf(Pubkey);
+    if (offset != input.len) return error.TrailingBytes;
+}
+
+/// `deserialize` for a buffer whose length is known: `validateInput`
+/// checks it first, so a malformed buffer is an error rather than an out
+/// of bounds read. `input` must be 8-byte aligned.
+pub fn deserializeChecked(input: []u8, accounts_buf: []AccountInfo) (InputError || ProgramError)!Deserialized {
+    try validateInput(input);
+    return deserialize(input.ptr, accounts_buf);
+}
+
+/// Run `process_instruction` on a serialized input buffer and return the
+/// value the entrypoint hands back to the runtime: 0 on success, otherwise
+/// the runtime error code (see `ErrorCode`). A buffer that fails to
+/// deserialize is reported the same way as a handler error.
+pub fn processInput(comptime process_instruction: anytype, input: [*]u8) u64 {
+    return processInputWithMaxAccounts(MAX_ACCOUNTS, process_instruction, input);
+}
+
+/// `processInput` with room for `max_accounts` accounts on the stack. An
+/// input with more fails with `MaxAccountsExceeded`.
+pub fn processInputWithMaxAccounts(comptime max_accounts: usize, comptime process_instruction: anytype, input: [*]u8) u64 {
+    var accounts_buf: [max_accounts]AccountInfo = undefined;
+    const parsed = deserialize(input, &accounts_buf) catch |err| {
+        return program_error.toErrorCode(err);
+    };
+
+    return program_error.resultToU64(process_instruction(
+        parsed.program_id,
+        parsed.accounts,
+        parsed.instruction_data,
+    ));
+}
+
 /// Declare a standard entrypoint for a Solana program
-/// Uses fast parsing that avoids AccountData creation for optimal performance
+///
+/// Exports the `entrypoint` symbol the runtime calls. It deserializes the
+/// input buffer in place, calls `process_instruction` and returns 0 or the
+/// runtime error code. Pair it with `panic` so a panic is logged and aborts
+/// the transaction instead of trapping silently.
 ///
 /// Example:
 /// ```zig
-/// const entrypoint = @import("solana_sdk_zig").entrypoint;
+/// const sdk = @import("solana_sdk_zig");
+/// const entrypoint = sdk.entrypoint;
 ///
 /// pub fn process_instruction(
 ///     program_id: *const Pubkey,
-///     accounts: []const AccountInfo,
+///     accounts: []AccountInfo,
 ///     instruction_data: []const u8,
 /// ) ProgramResult {
 ///     // Your program logic here
-///     return .Success;
 /// }
 ///
 /// // Create and export the entrypoint
 /// comptime {
 ///     entrypoint.declareEntrypoint(process_instruction);
 /// }
+///
+/// // Log panics and abort
+/// pub const panic = entrypoint.panic;
 /// ```
-pub fn declareEntrypoint(comptime process_instruction: ProcessInstruction) void {
+pub fn declareEntrypoint(comptime process_instruction: anytype) void {
+    declareEntrypointWithMaxAccounts(MAX_ACCOUNTS, process_instruction);
+}
+
+/// `declareEntrypoint` for a program that takes at most `max_accounts`
+/// accounts, so its account buffer uses less of the stack
+pub fn declareEntrypointWithMaxAccounts(comptime max_accounts: usize, comptime process_instruction: anytype) void {
     const S = struct {
-        pub export fn entrypoint(input: [*]const u8) callconv(.C) u64 {
-            // Fast path: no AccountData buffer for optimal CU usage
-            var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
-            var raw_accounts_buf: [MAX_ACCOUNTS]account_info.RawAccountInfo = undefined;
-
-            // Parse with optimized parser that avoids copying
-            const parsed = parseInput(input, &accounts_buf, &raw_accounts_buf);
-
-            // Direct call
-            const result = process_instruction(
-                parsed.program_id,
-                parsed.accounts,
-                parsed.instruction_data,
-            );
-
-            return program_error.resultToU64(result);
+        pub export fn entrypoint(input: [*]u8) callconv(.C) u64 {
+            return processInputWithMaxAccounts(max_accounts, process_instruction, input);
         }
     };
     _ = &S.entrypoint;
 }
 
 /// Helper macro-like function for simple entrypoint declaration
-pub inline fn entrypoint(comptime process_instruction: ProcessInstruction) void {
+pub inline fn entrypoint(comptime process_instruction: anytype) void {
     declareEntrypoint(process_instruction);
 }
 
+/// Panic handler for Solana programs
+///
+/// Zig only uses the `panic` declared in the program's root source file, so
+/// each program installs it with `pub const panic = entrypoint.panic;`. The
+/// message goes to the program log and the `abort` syscall then fails the
+/// transaction. Outside the VM it defers to the default handler.
+pub const panic = std.debug.FullPanic(panicHandler);
+
+fn panicHandler(message: []const u8, first_trace_addr: ?usize) noreturn {
+    if (bpf.is_solana) {
+        msg.msg(message);
+        syscalls.abort();
+    }
+    std.debug.defaultPanic(message, first_trace_addr);
+}
+
 // ============================================================================
 // Utility functions
 // ============================================================================
@@ -269,7 +464,7 @@ test "basic entrypoint parsing" {
     var raw_accounts_buf: [MAX_ACCOUNTS]account_info.RawAccountInfo = undefined;
 
     // Parse the input
-    const parsed = parseInput(&input_buffer, &accounts_buf, &raw_accounts_buf);
+    const parsed = try parseInput(&input_buffer, &accounts_buf, &raw_accounts_buf);
 
     // Check if parsing succeeded at all
     if (parsed.num_accounts == 0) {
@@ -287,12 +482,196 @@ test "basic entrypoint parsing" {
     try testing.expect(!acc.isExecutable());
     try testing.expectEqual(@as(u64, 1000), acc.getLamports());
 
+    // Borrows are tracked in the entry's marker byte
+    {
+        const data = try acc.tryBorrowMutData();
+        defer data.deinit();
+        try testing.expectEqual(@as(u8, 2), data.value[1]);
+        try testing.expectError(ProgramError.AccountBorrowFailed, acc.tryBorrowData());
+    }
+    try testing.expectEqual(account_info.NON_DUP_MARKER, input_buffer[8]);
+
     try testing.expectEqual(@as(usize, 4), parsed.instruction_data.len);
     try testing.expectEqual(@as(u8, 0xAA), parsed.instruction_data[0]);
 
     try testing.expect(parsed.program_id.equals(&program_pubkey));
 }
 
+test "deserialize points into the input buffer" {
+    const testing = std.testing;
+
+    var input_buffer: [2 * 11 * 1024]u8 align(8) = [_]u8{0} ** (2 * 11 * 1024);
+    var offset: usize = 0;
+
+    // Three entries: A, B and a duplicate of A
+    std.mem.writeInt(u64, input_buffer[offset..][0..8], 3, .little);
+    offset += 8;
+
+    const account_a = AccountData{
+        .duplicate_index = account_info.NON_DUP_MARKER,
+        .is_signer = 1,
+        .is_writable = 1,
+        .is_executable = 0,
+        .original_data_len = 0, // The loader writes zeros here
+        .id = Pubkey.fromBytes([_]u8{1} ** 32),
+        .owner_id = pubkey.SYSTEM_PROGRAM_ID,
+        .lamports = 1000,
+        .data_len = 5,
+    };
+    @memcpy(input_buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_a));
+    offset += @sizeOf(AccountData);
+    const data_a_offset = offset;
+    @memset(input_buffer[offset..][0..5], 0xAA);
+    offset += std.mem.alignForward(usize, 5, 8) + ACCOUNT_DATA_PADDING + @sizeOf(u64);
+
+    const account_b = AccountData{
+        .duplicate_index = account_info.NON_DUP_MARKER,
+        .is_signer = 0,
+        .is_writable = 0,
+        .is_executable = 1,
+        .original_data_len = 0,
+        .id = Pubkey.fromBytes([_]u8{2} ** 32),
+        .owner_id = pubkey.SYSTEM_PROGRAM_ID,
+        .lamports = 2000,
+        .data_len = 0,
+    };
+    @memcpy(input_buffer[offset..][0..@sizeOf(AccountData)], std.mem.asBytes(&account_b));
+    offset += @sizeOf(AccountData) + ACCOUNT_DATA_PADDING + @sizeOf(u64);
+
+    // Duplicate of A: index byte plus 7 bytes of padding
+    input_buffer[offset] = 0;
+    offset += 8;
+
+    // Instruction data and program ID
+    std.mem.writeInt(u64, input_buffer[offset..][0..8], 2, .little);
+    offset += 8;
+    input_buffer[offset] = 0x01;
+    input_buffer[offset + 1] = 0x02;
+    offset += 2;
+    const program_pubkey = Pubkey.fromBytes([_]u8{3} ** 32);
+    @memcpy(input_buffer[offset..][0..32], &program_pubkey.bytes);
+
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try deserialize(&input_buffer, &accounts_buf);
+
+    try testing.expectEqual(@as(usize, 3), parsed.accounts.len);
+    try testing.expect(parsed.accounts[0].key().equals(&account_a.id));
+    try testing.expect(parsed.accounts[1].key().equals(&account_b.id));
+    try testing.expect(parsed.accounts[1].isExecutable());
+    try testing.expectEqual(@as(u64, 2000), parsed.accounts[1].getLamports());
+
+    // Data is not copied
+    try testing.expectEqual(@intFromPtr(&input_buffer[data_a_offset]), @intFromPtr(parsed.accounts[0].getData().ptr));
+
+    // The original data length is stored in the header on entry
+    try testing.expectEqual(@as(u32, 5), parsed.accounts[0].originalDataLen());
+    try testing.expectEqual(@as(u32, 5), std.mem.readInt(u32, input_buffer[8 + 4 ..][0..4], .little));
+
+    // Writes go to the input buffer and are visible through the duplicate
+    const lamports = try parsed.accounts[0].getLamportsMut();
+    lamports.* = 750;
+    try testing.expectEqual(@as(u64, 750), parsed.accounts[2].getLamports());
+    try testing.expectEqual(@as(u64, 750), std.mem.readInt(u64, input_buffer[8 + 72 ..][0..8], .little));
+
+    // Duplicate status survives a borrow, which reuses A's marker byte
+    const borrowed = try parsed.accounts[0].tryBorrowMutData();
+    try testing.expect(!parsed.accounts[0].isDuplicate());
+    try testing.expectEqual(account_info.NON_DUP_MARKER, parsed.accounts[0].duplicateIndex());
+    try testing.expect(parsed.accounts[2].isDuplicate());
+    try testing.expectEqual(@as(u8, 0), parsed.accounts[2].duplicateIndex());
+    borrowed.deinit();
+
+    try testing.expectEqualSlices(u8, &[_]u8{ 0x01, 0x02 }, parsed.instruction_data);
+    try testing.expect(parsed.program_id.equals(&program_pubkey));
+}
+
+test "realloc is bounded by the length the account came in with" {
+    const testing = std.testing;
+    const allocator = testing.allocator;
+
+    // One writable 20 KiB account, its header laid out as the loader writes
+    // it: original_data_len zero
+    const data_len = 20 * 1024;
+    const account_len = @sizeOf(AccountData) + data_len + ACCOUNT_DATA_PADDING + @sizeOf(u64);
+    const input = try allocator.alignedAlloc(u8, 8, 8 + account_len + 8 + 32);
+    defer allocator.free(input);
+    @memset(input, 0);
+
+    std.mem.writeInt(u64, input[0..8], 1, .little);
+    const header = AccountData{
+        .duplicate_index = account_info.NON_DUP_MARKER,
+        .is_signer = 0,
+        .is_writable = 1,
+        .is_executable = 0,
+        .original_data_len = 0,
+        .id = Pubkey.fromBytes([_]u8{1} ** 32),
+        .owner_id = pubkey.SYSTEM_PROGRAM_ID,
+        .lamports = 1000,
+        .data_len = data_len,
+    };
+    @memcpy(input[8..][0..@sizeOf(AccountData)], std.mem.asBytes(&header));
+
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try deserializeChecked(input, &accounts_buf);
+    const account = &parsed.accounts[0];
+
+    // Shrinking is always allowed, and growing back up to the realloc space
+    try account.realloc(15 * 1024, false);
+    try testing.expectEqual(@as(u64, 15 * 1024), account.dataLen());
+    try account.realloc(data_len + account_info.MAX_PERMITTED_DATA_INCREASE, true);
+    try testing.expectError(ProgramError.InvalidRealloc, account.realloc(data_len + account_info.MAX_PERMITTED_DATA_INCREASE + 1, false));
+}
+
+test "validateInput checks every length against the buffer" {
+    const testing = std.testing;
+
+    // No accounts, one byte of instruction data and the program ID
+    var input_buffer: [8 + 8 + 1 + 32 + 1]u8 align(8) = [_]u8{0} ** 50;
+    std.mem.writeInt(u64, input_buffer[8..16], 1, .little);
+    const input = input_buffer[0..49];
+    try validateInput(input);
+
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+    const parsed = try deserializeChecked(input, &accounts_buf);
+    try testing.expectEqual(@as(usize, 0), parsed.accounts.len);
+    try testing.expectEqual(@as(usize, 1), parsed.instruction_data.len);
+
+    try testing.expectError(error.TrailingBytes, validateInput(&input_buffer));
+    try testing.expectError(error.Truncated, validateInput(input_buffer[0..48]));
+    try testing.expectError(error.Truncated, validateInput(input_buffer[0..4]));
+
+    // An instruction data length past the end of the buffer
+    std.mem.writeInt(u64, input_buffer[8..16], 1 << 20, .little);
+    try testing.expectError(error.Truncated, deserializeChecked(input, &accounts_buf));
+}
+
+test "deserialize rejects too many accounts" {
+    var input_buffer: [8]u8 align(8) = undefined;
+    std.mem.writeInt(u64, &input_buffer, MAX_ACCOUNTS + 1, .little);
+
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+    try std.testing.expectError(ProgramError.MaxAccountsExceeded, deserialize(&input_buffer, &accounts_buf));
+
+    // The cap is the buffer's length
+    var small_buf: [4]AccountInfo = undefined;
+    std.mem.writeInt(u64, &input_buffer, small_buf.len + 1, .little);
+    try std.testing.expectError(ProgramError.MaxAccountsExceeded, deserialize(&input_buffer, &small_buf));
+}
+
+test "parseInput rejects too many accounts and forward duplicates" {
+    var input_buffer: [16]u8 align(8) = [_]u8{0} ** 16;
+    var accounts_buf: [4]AccountInfo = undefined;
+    var raw_accounts_buf: [4]account_info.RawAccountInfo = undefined;
+
+    std.mem.writeInt(u64, input_buffer[0..8], accounts_buf.len + 1, .little);
+    try std.testing.expectError(ProgramError.MaxAccountsExceeded, parseInput(&input_buffer, &accounts_buf, &raw_accounts_buf));
+
+    // The first account's marker names account 0, which is not before it
+    std.mem.writeInt(u64, input_buffer[0..8], 1, .little);
+    input_buffer[8] = 0;
+    try std.testing.expectError(ProgramError.DuplicateAccountIndex, parseInput(&input_buffer, &accounts_buf, &raw_accounts_buf));
+}
+
 test "entrypoint macro usage" {
     // This just tests that the macro compiles
     const S = struct {
@@ -313,3 +692,119 @@ test "entrypoint macro usage" {
         entrypoint(S.process);
     }
 }
+
+test "processInput drives a handler with solana_single_account.bin" {
+    const testing = std.testing;
+
+    const file = try std.fs.cwd().openFile("test_data/solana_single_account.bin", .{});
+    defer file.close();
+    var input_buffer: [2 * 11 * 1024]u8 align(8) = undefined;
+    const len = try file.readAll(&input_buffer);
+    try testing.expect(len < input_buffer.len);
+
+    const S = struct {
+        fn process(
+            program_id: *const Pubkey,
+            accounts: []AccountInfo,
+            data: []const u8,
+        ) ProgramResult {
+            if (!program_id.equals(&Pubkey.fromBytes([_]u8{0x42} ** 32))) return ProgramError.IncorrectProgramId;
+            if (accounts.len != 1) return ProgramError.NotEnoughAccountKeys;
+            if (accounts[0].getLamports() != 1000) return ProgramError.InvalidAccountData;
+            if (!std.mem.eql(u8, accounts[0].getData(), &([_]u8{0xAA} ** 10))) return ProgramError.InvalidAccountData;
+            if (!std.mem.eql(u8, data, &[_]u8{0x01})) return ProgramError.InvalidInstructionData;
+
+            // Writes land in the input buffer
+            const lamports = try accounts[0].getLamportsMut();
+            lamports.* -= 1;
+        }
+
+        fn reject(
+            program_id: *const Pubkey,
+            accounts: []AccountInfo,
+            data: []const u8,
+        ) ProgramResult {
+            _ = program_id;
+            _ = accounts;
+            _ = data;
+            return ProgramError.InvalidArgument;
+        }
+
+        fn failCustom(
+            program_id: *const Pubkey,
+            accounts: []AccountInfo,
+            data: []const u8,
+        ) (ProgramError || program_error.CustomCode(6001))!void {
+            _ = program_id;
+            _ = accounts;
+            _ = data;
+            return program_error.custom(6001);
+        }
+    };
+
+    try testing.expectEqual(program_error.SUCCESS, processInput(S.process, &input_buffer));
+    try testing.expectEqual(@as(u64, 999), std.mem.readInt(u64, input_buffer[8 + 72 ..][0..8], .little));
+
+    try testing.expectEqual(program_error.INVALID_ARGUMENT, processInput(S.reject, &input_buffer));
+    try testing.expectEqual(@as(u64, 6001), processInput(S.failCustom, &input_buffer));
+}
+
+test "processInput reports deserialization errors" {
+    var input_buffer: [8]u8 align(8) = undefined;
+    std.mem.writeInt(u64, &input_buffer, MAX_ACCOUNTS + 1, .little);
+
+    try std.testing.expectEqual(program_error.INVALID_ARGUMENT, processInput(noOpProcessor, &input_buffer));
+}
+
+test "deserialize Rust inputs with no accounts or no instruction data" {
+    const testing = std.testing;
+    var input_buffer: [2 * 11 * 1024]u8 align(8) = undefined;
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+
+    // An account count of 0, then 48 bytes of instruction data counting up
+    {
+        const file = try std.fs.cwd().openFile("test_data/solana_no_accounts.bin", .{});
+        defer file.close();
+        const len = try file.readAll(&input_buffer);
+        try testing.expectEqual(@as(usize, 96), len);
+
+        const parsed = try deserialize(&input_buffer, &accounts_buf);
+        try testing.expectEqual(@as(usize, 0), parsed.accounts.len);
+        try testing.expectEqual(@as(usize, 48), parsed.instruction_data.len);
+        for (parsed.instruction_data, 0..) |byte, i| {
+            try testing.expectEqual(@as(u8, @intCast(i)), byte);
+        }
+        try testing.expect(parsed.program_id.equals(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+        try testing.expectEqual(program_error.SUCCESS, processInput(noOpProcessor, &input_buffer));
+    }
+
+    // One account and no instruction data
+    {
+        const file = try std.fs.cwd().openFile("test_data/solana_empty_instruction_data.bin", .{});
+        defer file.close();
+        const len = try file.readAll(&input_buffer);
+        try testing.expect(len < input_buffer.len);
+
+        const parsed = try deserialize(&input_buffer, &accounts_buf);
+        try testing.expectEqual(@as(usize, 1), parsed.accounts.len);
+        try testing.expectEqualSlices(u8, &([_]u8{0x31} ** 4), parsed.accounts[0].getData());
+        try testing.expectEqual(@as(usize, 0), parsed.instruction_data.len);
+        try testing.expect(parsed.program_id.equals(&Pubkey.fromBytes([_]u8{0x42} ** 32)));
+    }
+}
+
+test "deserialize rejects a Rust input whose first account is a duplicate" {
+    const testing = std.testing;
+
+    // Account 0 carries duplicate marker 0x00, with nothing before it to point at
+    const file = try std.fs.cwd().openFile("test_data/invalid_early_duplicate.bin", .{});
+    defer file.close();
+    var input_buffer: [64]u8 align(8) = undefined;
+    const len = try file.readAll(&input_buffer);
+    try testing.expectEqual(@as(usize, 56), len);
+
+    var accounts_buf: [MAX_ACCOUNTS]AccountInfo = undefined;
+    try testing.expectError(ProgramError.DuplicateAccountIndex, deserialize(&input_buffer, &accounts_buf));
+
+    try testing.expectEqual(program_error.INVALID_ARGUMENT, processInput(noOpProcessor, &input_buffer));
+}
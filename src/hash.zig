@@ -0,0 +1,124 @@
+/// SHA-256, Keccak-256 and BLAKE3 hashing
+///
+/// Hashes a list of byte slices with the sol_sha256, sol_keccak256 and
+/// sol_blake3 syscalls on Solana and with std.crypto everywhere else,
+/// equivalent to Rust's solana_program::{hash, keccak, blake3}::hashv
+const std = @import("std");
+const syscalls = @import("syscalls.zig");
+const bpf = @import("bpf.zig");
+
+/// Size of every digest in this module
+pub const HASH_BYTES = 32;
+
+/// SHA-256 of the concatenation of `slices`
+pub fn sha256(slices: []const []const u8) [HASH_BYTES]u8 {
+    return hashv(std.crypto.hash.sha2.Sha256, syscalls.sol_sha256, slices);
+}
+
+/// Keccak-256 (the Ethereum variant, not SHA3-256) of the concatenation
+/// of `slices`
+pub fn keccak256(slices: []const []const u8) [HASH_BYTES]u8 {
+    return hashv(std.crypto.hash.sha3.Keccak256, syscalls.sol_keccak256, slices);
+}
+
+/// BLAKE3 of the concatenation of `slices`
+pub fn blake3(slices: []const []const u8) [HASH_BYTES]u8 {
+    return hashv(std.crypto.hash.Blake3, syscalls.sol_blake3, slices);
+}
+
+/// Hash `slices` with `syscall` on Solana, or with the std.crypto hasher
+/// `Hash` everywhere else. The slices are handed to the syscall as-is: the
+/// runtime reads them as an array of {ptr, len} pairs, which is how a
+/// `[]const u8` is laid out, so nothing is copied.
+fn hashv(comptime Hash: type, comptime syscall: anytype, slices: []const []const u8) [HASH_BYTES]u8 {
+    var result: [HASH_BYTES]u8 = undefined;
+    if (comptime bpf.is_solana) {
+        // The runtime aborts the program instead of returning an error
+        _ = syscall(slices.ptr, slices.len, @ptrCast(&result));
+        return result;
+    }
+
+    var hasher = Hash.init(.{});
+    for (slices) |slice| hasher.update(slice);
+    hasher.final(&result);
+    return result;
+}
+
+/// Collects slices and hashes them in one syscall when `result` is called,
+/// which is cheaper on-chain than a syscall per slice. The slices are
+/// borrowed, so they must outlive the hasher.
+pub const Hasher = struct {
+    slices: [MAX_SLICES][]const u8 = undefined,
+    len: usize = 0,
+
+    /// Most slices one hasher can collect
+    pub const MAX_SLICES = 32;
+
+    pub const Error = error{TooManySlices};
+
+    /// Add `bytes` to the input
+    pub fn hash(self: *Hasher, bytes: []const u8) Error!void {
+        if (self.len == MAX_SLICES) return Error.TooManySlices;
+        self.slices[self.len] = bytes;
+        self.len += 1;
+    }
+
+    /// Add each of `slices` to the input
+    pub fn hashv(self: *Hasher, slices: []const []const u8) Error!void {
+        if (slices.len > MAX_SLICES - self.len) return Error.TooManySlices;
+        for (slices) |bytes| try self.hash(bytes);
+    }
+
+    /// Hash everything collected so far
+    pub fn result(self: Hasher) [HASH_BYTES]u8 {
+        return sha256(self.slices[0..self.len]);
+    }
+};
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+fn expectDigest(comptime hex: []const u8, actual: [HASH_BYTES]u8) !void {
+    var expected: [HASH_BYTES]u8 = undefined;
+    _ = try std.fmt.hexToBytes(&expected, hex);
+    try std.testing.expectEqualSlices(u8, &expected, &actual);
+}
+
+test "sha256 of the empty input" {
+    const empty_digest = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    try expectDigest(empty_digest, sha256(&.{}));
+    try expectDigest(empty_digest, sha256(&.{ "", "" }));
+}
+
+test "sha256 hashes the concatenation of its slices" {
+    const abc_digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    try expectDigest(abc_digest, sha256(&.{"abc"}));
+    try expectDigest(abc_digest, sha256(&.{ "a", "", "bc" }));
+}
+
+test "keccak256 and blake3 match published digests" {
+    try expectDigest("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470", keccak256(&.{}));
+    try expectDigest("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45", keccak256(&.{"abc"}));
+    try expectDigest("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45", keccak256(&.{ "ab", "c" }));
+
+    try expectDigest("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262", blake3(&.{}));
+    try expectDigest("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85", blake3(&.{"abc"}));
+    try expectDigest("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85", blake3(&.{ "a", "bc" }));
+}
+
+test "Hasher matches sha256 and caps the slice count" {
+    var hasher = Hasher{};
+    try hasher.hash("a");
+    try hasher.hashv(&.{ "b", "c" });
+    try std.testing.expectEqual(sha256(&.{"abc"}), hasher.result());
+
+    var full = Hasher{};
+    for (0..Hasher.MAX_SLICES) |_| try full.hash("x");
+    try std.testing.expectError(Hasher.Error.TooManySlices, full.hash("x"));
+
+    var empty = Hasher{};
+    const too_many = [_][]const u8{"x"} ** (Hasher.MAX_SLICES + 1);
+    try std.testing.expectError(Hasher.Error.TooManySlices, empty.hashv(&too_many));
+    try std.testing.expectEqual(@as(usize, 0), empty.len);
+}
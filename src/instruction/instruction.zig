@@ -1,58 +1,47 @@
 const std = @import("std");
 const Pubkey = @import("../pubkey/pubkey.zig").Pubkey;
 
-/// Account metadata for instructions
-/// Must match the layout expected by Solana runtime
-pub const AccountMeta = extern struct {
-    /// Pointer to public key of the account
-    pubkey: *const Pubkey,
-
-    /// Is the account writable
-    is_writable: bool,
+/// Account metadata for instructions, like the Rust SDK's AccountMeta
+pub const AccountMeta = struct {
+    /// Public key of the account
+    pubkey: Pubkey,
 
     /// Is the account a signer
     is_signer: bool,
 
-    /// Create a read-only account meta
-    pub fn readOnly(pubkey: *const Pubkey, is_signer: bool) AccountMeta {
+    /// Is the account writable
+    is_writable: bool,
+
+    /// Create a read-only account meta (`AccountMeta::new_readonly`)
+    pub fn readonly(pubkey: Pubkey, is_signer: bool) AccountMeta {
         return .{
             .pubkey = pubkey,
-            .is_writable = false,
             .is_signer = is_signer,
+            .is_writable = false,
         };
     }
 
-    /// Create a writable account meta
-    pub fn writable(pubkey: *const Pubkey, is_signer: bool) AccountMeta {
+    /// Create a writable account meta (`AccountMeta::new`)
+    pub fn writable(pubkey: Pubkey, is_signer: bool) AccountMeta {
         return .{
             .pubkey = pubkey,
-            .is_writable = true,
             .is_signer = is_signer,
+            .is_writable = true,
         };
     }
 };
 
-/// Instruction data for cross-program invocation - extern struct for C ABI
-pub const Instruction = extern struct {
+/// Instruction for cross-program invocation or off-chain test construction.
+/// The accounts and data are borrowed, so they must outlive the instruction.
+pub const Instruction = struct {
     /// Program ID of the instruction
-    program_id: *const Pubkey,
+    program_id: Pubkey,
 
     /// Accounts involved in the instruction
-    accounts: [*]const AccountMeta,
-    accounts_len: usize,
+    accounts: []const AccountMeta,
 
     /// Instruction data
-    data: [*]const u8,
-    data_len: usize,
-
-    /// Declare syscall directly in the struct like solana-program-sdk-zig
-    extern fn sol_invoke_signed_c(
-        instruction: *const Instruction,
-        account_infos: *const anyopaque,  // Will be cast from AccountInfo array
-        account_infos_len: usize,
-        signer_seeds: ?[*]const []const []const u8,
-        signer_seeds_len: usize,
-    ) callconv(.C) u64;
+    data: []const u8,
 
     /// Create a new instruction
     pub fn init(
@@ -61,122 +50,93 @@ pub const Instruction = extern struct {
         data: []const u8,
     ) Instruction {
         return .{
-            .program_id = &program_id,
-            .accounts = accounts.ptr,
-            .accounts_len = accounts.len,
-            .data = data.ptr,
-            .data_len = data.len,
+            .program_id = program_id,
+            .accounts = accounts,
+            .data = data,
         };
     }
 
     /// Create from parameters (like solana-program-sdk-zig)
     pub fn from(params: struct {
-        program_id: *const Pubkey,
+        program_id: Pubkey,
         accounts: []const AccountMeta,
         data: []const u8,
     }) Instruction {
-        return .{
-            .program_id = params.program_id,
-            .accounts = params.accounts.ptr,
-            .accounts_len = params.accounts.len,
-            .data = params.data.ptr,
-            .data_len = params.data.len,
-        };
+        return init(params.program_id, params.accounts, params.data);
     }
 
-    /// CPI Account Info structure that matches runtime expectations
-    /// MUST have pointers to Pubkeys, not inline values
-    /// NO duplicate_index field!
-    /// Note: This is now only used as a fallback when original pointers aren't available
-    const CPIAccountInfo = extern struct {
-        id: *const Pubkey,
-        lamports: *align(1) u64,  // May be unaligned from input buffer
-        data_len: u64,
-        data: [*]u8,
-        owner_id: *const Pubkey,
-        rent_epoch: u64,
-        is_signer: u8,
-        is_writable: u8,
-        is_executable: u8,
-    };
-
     /// Invoke this instruction (CPI)
-    pub inline fn invoke(self: *const Instruction, account_infos: []const AccountInfo) !void {
-        return invoke_signed(self, account_infos, &.{});
+    pub fn invoke(self: *const Instruction, account_infos: []const AccountInfo) ProgramError!void {
+        return cpi.invoke(self, account_infos);
     }
 
     /// Invoke this instruction with signer seeds (for PDAs)
-    pub inline fn invoke_signed(
+    pub fn invokeSigned(
         self: *const Instruction,
         account_infos: []const AccountInfo,
         signer_seeds: []const []const []const u8,
-    ) !void {
-        if (comptime @import("../bpf.zig").is_solana) {
-            // Fast path: use raw pointers if available
-            if (account_infos.len > 0 and account_infos[0].raw_ptr != null) {
-                // Direct CPI using raw pointers - minimal overhead
-                const first_raw = account_infos[0].raw_ptr.?;
-                const seeds_ptr = if (signer_seeds.len > 0) signer_seeds.ptr else null;
-
-                const result = sol_invoke_signed_c(self, @ptrCast(first_raw), account_infos.len, seeds_ptr, signer_seeds.len);
-                if (result != 0) return error.CrossProgramInvocationFailed;
-                return;
-            }
-
-            // Optimized fallback: use comptime loop unrolling for small arrays
-            if (account_infos.len > 0) {
-                if (account_infos.len <= 8) {
-                    // Stack-allocated small array with unrolled loop
-                    var cpi_accounts: [8]CPIAccountInfo = undefined;
-                    inline for (0..8) |i| {
-                        if (i >= account_infos.len) break;
-                        const info = &account_infos[i];
-                        const data = info.data_ptr;
-                        cpi_accounts[i] = CPIAccountInfo{
-                            .id = &data.id,
-                            .lamports = @ptrCast(&data.lamports),
-                            .data_len = data.data_len,
-                            .data = info.data_buffer,
-                            .owner_id = &data.owner_id,
-                            .rent_epoch = 0,
-                            .is_signer = data.is_signer,
-                            .is_writable = data.is_writable,
-                            .is_executable = data.is_executable,
-                        };
-                    }
-                    const seeds_ptr = if (signer_seeds.len > 0) signer_seeds.ptr else null;
-                    const result = sol_invoke_signed_c(self, @ptrCast(&cpi_accounts[0]), account_infos.len, seeds_ptr, signer_seeds.len);
-                    if (result != 0) return error.CrossProgramInvocationFailed;
-                    return;
-                } else {
-                    // Fallback for larger arrays
-                    var cpi_accounts: [32]CPIAccountInfo = undefined;
-                    for (account_infos, 0..) |*info, i| {
-                        const data = info.data_ptr;
-                        cpi_accounts[i] = CPIAccountInfo{
-                            .id = &data.id,
-                            .lamports = @ptrCast(&data.lamports),
-                            .data_len = data.data_len,
-                            .data = info.data_buffer,
-                            .owner_id = &data.owner_id,
-                            .rent_epoch = 0,
-                            .is_signer = data.is_signer,
-                            .is_writable = data.is_writable,
-                            .is_executable = data.is_executable,
-                        };
-                    }
-                    const seeds_ptr = if (signer_seeds.len > 0) signer_seeds.ptr else null;
-                    const result = sol_invoke_signed_c(self, @ptrCast(&cpi_accounts[0]), account_infos.len, seeds_ptr, signer_seeds.len);
-                    if (result != 0) return error.CrossProgramInvocationFailed;
-                }
-            }
-        }
-        return; // Mock success in test
+    ) ProgramError!void {
+        return cpi.invokeSigned(self, account_infos, signer_seeds);
+    }
+
+    /// Legacy alias for backward compatibility
+    pub const invoke_signed = invokeSigned;
+};
+
+/// Account meta in the layout sol_invoke_signed_c reads (SolAccountMeta in
+/// the C SDK)
+pub const SolAccountMeta = extern struct {
+    pubkey: *const Pubkey,
+    is_writable: bool,
+    is_signer: bool,
+
+    pub fn fromAccountMeta(meta: *const AccountMeta) SolAccountMeta {
+        return .{
+            .pubkey = &meta.pubkey,
+            .is_writable = meta.is_writable,
+            .is_signer = meta.is_signer,
+        };
+    }
+};
+
+/// Instruction in the layout sol_invoke_signed_c reads (SolInstruction in
+/// the C SDK). It points into the Instruction and SolAccountMeta array it
+/// was built from.
+pub const SolInstruction = extern struct {
+    program_id: *const Pubkey,
+    accounts: [*]const SolAccountMeta,
+    account_len: u64,
+    data: [*]const u8,
+    data_len: u64,
+
+    pub fn fromInstruction(
+        instruction: *const Instruction,
+        accounts: []const SolAccountMeta,
+    ) SolInstruction {
+        return .{
+            .program_id = &instruction.program_id,
+            .accounts = accounts.ptr,
+            .account_len = accounts.len,
+            .data = instruction.data.ptr,
+            .data_len = instruction.data.len,
+        };
+    }
+
+    /// Get accounts as slice
+    pub fn accountsSlice(self: *const SolInstruction) []const SolAccountMeta {
+        return self.accounts[0..self.account_len];
+    }
+
+    /// Get data as slice
+    pub fn dataSlice(self: *const SolInstruction) []const u8 {
+        return self.data[0..self.data_len];
     }
 };
 
-// Import AccountInfo for CPI methods
+// Imports for the CPI methods
 const AccountInfo = @import("../account_info/account_info.zig").AccountInfo;
+const ProgramError = @import("../program_error.zig").ProgramError;
+const cpi = @import("../cpi.zig");
 
 /// Compiled instruction for efficient processing
 pub const CompiledInstruction = extern struct {
@@ -239,34 +199,52 @@ pub const InstructionBuilder = struct {
 };
 
 test "account meta creation" {
-    const key = Pubkey.ZEROES;
+    const key = Pubkey.fromBytes([_]u8{7} ** 32);
 
-    const meta1 = AccountMeta.readOnly(&key, true);
+    const meta1 = AccountMeta.readonly(key, true);
+    try std.testing.expect(meta1.pubkey.equals(&key));
     try std.testing.expect(meta1.is_signer);
     try std.testing.expect(!meta1.is_writable);
 
-    const meta2 = AccountMeta.writable(&key, false);
+    const meta2 = AccountMeta.writable(key, false);
     try std.testing.expect(!meta2.is_signer);
     try std.testing.expect(meta2.is_writable);
 }
 
 test "instruction creation" {
-    const program = Pubkey.ZEROES;
-    const key1 = Pubkey.ZEROES;
-    const key2 = Pubkey.ZEROES;
+    const program = Pubkey.fromBytes([_]u8{9} ** 32);
+    const key1 = Pubkey.fromBytes([_]u8{1} ** 32);
+    const key2 = Pubkey.fromBytes([_]u8{2} ** 32);
     const accounts = [_]AccountMeta{
-        AccountMeta.writable(&key1, true),
-        AccountMeta.readOnly(&key2, false),
+        AccountMeta.writable(key1, true),
+        AccountMeta.readonly(key2, false),
     };
     const data = [_]u8{ 1, 2, 3, 4 };
 
-    const ix = Instruction.from(.{
-        .program_id = &program,
-        .accounts = &accounts,
-        .data = &data,
-    });
+    const ix = Instruction.init(program, &accounts, &data);
+
+    try std.testing.expectEqual(program, ix.program_id);
+    try std.testing.expectEqual(@as(usize, 2), ix.accounts.len);
+    try std.testing.expectEqualSlices(u8, &data, ix.data);
+}
 
-    try std.testing.expectEqual(program, ix.program_id.*);
-    try std.testing.expectEqual(@as(usize, 2), ix.accounts_len);
-    try std.testing.expectEqual(@as(usize, 4), ix.data_len);
+test "SolInstruction matches the C SDK layout" {
+    try std.testing.expectEqual(@as(usize, 16), @sizeOf(SolAccountMeta));
+    try std.testing.expectEqual(@as(usize, 8), @offsetOf(SolAccountMeta, "is_writable"));
+    try std.testing.expectEqual(@as(usize, 9), @offsetOf(SolAccountMeta, "is_signer"));
+    try std.testing.expectEqual(@as(usize, 40), @sizeOf(SolInstruction));
+    try std.testing.expectEqual(@as(usize, 16), @offsetOf(SolInstruction, "account_len"));
+    try std.testing.expectEqual(@as(usize, 32), @offsetOf(SolInstruction, "data_len"));
+
+    const program = Pubkey.fromBytes([_]u8{9} ** 32);
+    const accounts = [_]AccountMeta{AccountMeta.readonly(Pubkey.fromBytes([_]u8{1} ** 32), true)};
+    const ix = Instruction.init(program, &accounts, &[_]u8{42});
+
+    const metas = [_]SolAccountMeta{SolAccountMeta.fromAccountMeta(&ix.accounts[0])};
+    const c_ix = SolInstruction.fromInstruction(&ix, &metas);
+    try std.testing.expect(c_ix.program_id.equals(&program));
+    try std.testing.expect(c_ix.accountsSlice()[0].pubkey.equals(&accounts[0].pubkey));
+    try std.testing.expect(c_ix.accountsSlice()[0].is_signer);
+    try std.testing.expect(!c_ix.accountsSlice()[0].is_writable);
+    try std.testing.expectEqualSlices(u8, &[_]u8{42}, c_ix.dataSlice());
 }
@@ -0,0 +1,81 @@
+/// Logging through the sol_log family of syscalls
+///
+/// Formatted messages go through a fixed stack buffer, so a message that
+/// does not fit is cut short instead of overflowing the small SBF stack
+/// frame. Outside the VM the calls fall back to msg.zig's debug printing.
+const std = @import("std");
+const msg_mod = @import("msg/msg.zig");
+const Pubkey = @import("pubkey/pubkey.zig").Pubkey;
+
+/// Longest formatted message; longer ones are truncated to this many bytes
+pub const MAX_MESSAGE_LEN = 256;
+
+/// Format a message and log it with sol_log_
+///
+/// # Examples
+/// ```zig
+/// msg("transfer {d} lamports to {s}", .{ amount, name });
+/// ```
+pub fn msg(comptime fmt: []const u8, args: anytype) void {
+    var buf: [MAX_MESSAGE_LEN]u8 = undefined;
+    msg_mod.msg(formatTruncated(&buf, fmt, args));
+}
+
+/// Log a public key in base58 with sol_log_pubkey
+pub fn logPubkey(key: Pubkey) void {
+    msg_mod.msgPubkey(&key.bytes);
+}
+
+/// Log five integers with sol_log_64_, the cheapest way to log numbers
+pub fn log64(a: u64, b: u64, c: u64, d: u64, e: u64) void {
+    msg_mod.msg64(a, b, c, d, e);
+}
+
+/// Log the remaining compute units with sol_log_compute_units_. Call it
+/// before and after an expensive section; the difference in the program
+/// logs is what the section cost.
+pub fn logComputeUnits() void {
+    msg_mod.msgComputeUnits();
+}
+
+/// Log `fields` with sol_log_data as one "Program data:" line, each field
+/// base64-encoded, for off-chain clients to parse (Anchor events use this)
+pub fn logData(fields: []const []const u8) void {
+    msg_mod.msgData(fields);
+}
+
+/// Format into `buf`, keeping as much of the message as fits
+pub fn formatTruncated(buf: []u8, comptime fmt: []const u8, args: anytype) []const u8 {
+    var stream = std.io.fixedBufferStream(buf);
+    // On NoSpaceLeft the buffer already holds everything that fit
+    std.fmt.format(stream.writer(), fmt, args) catch {};
+    return stream.getWritten();
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+test "formatTruncated keeps messages that fit" {
+    var buf: [32]u8 = undefined;
+    try std.testing.expectEqualStrings("lamports: 42", formatTruncated(&buf, "lamports: {d}", .{42}));
+}
+
+test "formatTruncated cuts oversized messages at the buffer size" {
+    var buf: [8]u8 = undefined;
+    try std.testing.expectEqualStrings("hello wo", formatTruncated(&buf, "hello {s}", .{"world, and more"}));
+
+    var big: [MAX_MESSAGE_LEN]u8 = undefined;
+    const long = [_]u8{'x'} ** (MAX_MESSAGE_LEN * 2);
+    try std.testing.expectEqual(@as(usize, MAX_MESSAGE_LEN), formatTruncated(&big, "{s}", .{&long}).len);
+}
+
+test "logging calls are safe outside the VM" {
+    msg("value {d}", .{1});
+    msg("{s}", .{[_]u8{'y'} ** (MAX_MESSAGE_LEN + 1)});
+    logPubkey(Pubkey.ZEROES);
+    log64(1, 2, 3, 4, 5);
+    logComputeUnits();
+    logData(&.{ "event", &[_]u8{ 1, 2, 3 } });
+    logData(&.{});
+}
@@ -0,0 +1,73 @@
+/// Checked u64 arithmetic for lamports and token amounts
+///
+/// Each operation fails with `ProgramError.ArithmeticOverflow` instead of
+/// wrapping or panicking, like Rust's `checked_*` followed by
+/// `.ok_or(ProgramError::ArithmeticOverflow)`. Solana has no separate
+/// error for division by zero, so `checkedDiv` reports that as an overflow
+/// too.
+///
+/// # Examples
+/// ```zig
+/// const new_balance = try math.checkedAdd(account.getLamports(), deposit);
+/// ```
+const std = @import("std");
+const ProgramError = @import("program_error.zig").ProgramError;
+
+/// `a + b`
+pub fn checkedAdd(a: u64, b: u64) ProgramError!u64 {
+    return std.math.add(u64, a, b) catch ProgramError.ArithmeticOverflow;
+}
+
+/// `a - b`, which fails when `b > a`
+pub fn checkedSub(a: u64, b: u64) ProgramError!u64 {
+    return std.math.sub(u64, a, b) catch ProgramError.ArithmeticOverflow;
+}
+
+/// `a * b`
+pub fn checkedMul(a: u64, b: u64) ProgramError!u64 {
+    return std.math.mul(u64, a, b) catch ProgramError.ArithmeticOverflow;
+}
+
+/// `a / b` rounded down, which fails when `b` is zero
+pub fn checkedDiv(a: u64, b: u64) ProgramError!u64 {
+    if (b == 0) {
+        return ProgramError.ArithmeticOverflow;
+    }
+    return a / b;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const max = std.math.maxInt(u64);
+
+test "checkedAdd at the u64 boundary" {
+    try std.testing.expectEqual(@as(u64, max), try checkedAdd(max - 1, 1));
+    try std.testing.expectEqual(@as(u64, max), try checkedAdd(max, 0));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedAdd(max, 1));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedAdd(max, max));
+}
+
+test "checkedSub at the u64 boundary" {
+    try std.testing.expectEqual(@as(u64, 0), try checkedSub(max, max));
+    try std.testing.expectEqual(@as(u64, 0), try checkedSub(0, 0));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedSub(0, 1));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedSub(max - 1, max));
+}
+
+test "checkedMul at the u64 boundary" {
+    try std.testing.expectEqual(@as(u64, max), try checkedMul(max, 1));
+    try std.testing.expectEqual(@as(u64, 0), try checkedMul(max, 0));
+    try std.testing.expectEqual(@as(u64, 0xFFFF_FFFE_0000_0001), try checkedMul(0xFFFF_FFFF, 0xFFFF_FFFF));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedMul(max / 2 + 1, 2));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedMul(1 << 32, 1 << 32));
+}
+
+test "checkedDiv at the u64 boundary" {
+    try std.testing.expectEqual(@as(u64, 1), try checkedDiv(max, max));
+    try std.testing.expectEqual(@as(u64, max), try checkedDiv(max, 1));
+    try std.testing.expectEqual(@as(u64, 0), try checkedDiv(max - 1, max));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedDiv(max, 0));
+    try std.testing.expectError(ProgramError.ArithmeticOverflow, checkedDiv(0, 0));
+}
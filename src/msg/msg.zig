@@ -5,6 +5,7 @@
 const std = @import("std");
 const syscalls = @import("../syscalls.zig");
 const bpf = @import("../bpf.zig");
+const log_mod = @import("../log.zig");
 const base58 = @import("base58");
 const BASE58_ENDEC = base58.Table.BITCOIN;
 
@@ -64,15 +65,10 @@ pub fn print(comptime fmt: []const u8, args: anytype) void {
 /// ```
 pub fn msgf(comptime fmt: []const u8, args: anytype) void {
     // Stack buffer for formatted message - keep small for Solana's limited stack
-    var buf: [256]u8 = undefined;
+    var buf: [log_mod.MAX_MESSAGE_LEN]u8 = undefined;
 
-    const formatted = std.fmt.bufPrint(&buf, fmt, args) catch {
-        // If formatting fails, log an error message instead
-        msg("msg format error");
-        return;
-    };
-
-    msg(formatted);
+    // Messages that do not fit are truncated rather than dropped
+    msg(log_mod.formatTruncated(&buf, fmt, args));
 }
 
 /// Print multiple values as u64 integers
@@ -174,8 +170,8 @@ pub fn logData(data_slices: []const []const u8) void {
 
 /// Log multiple data slices
 ///
-/// Can log up to 16 data slices in a single syscall.
-/// Each slice is printed as hex bytes.
+/// All slices go out in a single syscall and show up base64-encoded as
+/// one "Program data:" line in the transaction logs.
 ///
 /// # Examples
 /// ```zig
@@ -185,15 +181,7 @@ pub fn logData(data_slices: []const []const u8) void {
 /// ```
 pub fn msgData(data_slices: []const []const u8) void {
     if (bpf.is_solana) {
-        const max_slices = 16;
-        var data_ptrs: [max_slices][*]const u8 = undefined;
-        const len = @min(data_slices.len, max_slices);
-
-        for (data_slices[0..len], 0..) |slice, i| {
-            data_ptrs[i] = slice.ptr;
-        }
-
-        syscalls.sol_log_data(@ptrCast(&data_ptrs), len);
+        syscalls.logData(data_slices);
     } else if (bpf.should_print_debug) {
         if (@import("builtin").os.tag != .solana) {
             std.debug.print("Data slices ({} items):\n", .{data_slices.len});
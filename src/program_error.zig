@@ -1,6 +1,10 @@
 const std = @import("std");
 
 /// Solana program error codes
+///
+/// The numbers are the SDK's own codes. Errors with a runtime builtin are
+/// returned as that builtin; the rest go out as `Custom(code)`, see
+/// `ErrorCode.fromError`.
 pub const ProgramError = error{
     // Standard errors (0-12)
     CustomError, // 0 TODO:(how to handle custom errors with string information)
@@ -56,6 +60,7 @@ pub const ProgramError = error{
     AccountNotSigner, // 46
     AccountNotWritable, // 47
     IllegalRealloc, // 48
+    InvalidRealloc, // 49
 
     // Additional custom errors
     AlreadyBorrowed,
@@ -77,178 +82,390 @@ pub const ProgramError = error{
     MissingAccounts,
     MissingRequiredAccount,
     CrossProgramInvocationFailed,
+
+    // secp256k1 recovery errors
+    InvalidHash,
+    InvalidRecoveryId,
+    InvalidSignature,
 };
 
 /// Success code
 pub const SUCCESS: u64 = 0;
 
-/// Convert error to u64 code for syscalls
+/// Builtin errors live above this bit; anything below it is a custom code
+pub const BUILTIN_BIT_SHIFT = 32;
+
+fn toBuiltin(comptime n: u64) u64 {
+    return n << BUILTIN_BIT_SHIFT;
+}
+
+// Reserved return values of the builtin errors, from Rust's
+// solana_program_error crate. The runtime decodes exactly these.
+pub const CUSTOM_ZERO = toBuiltin(1);
+pub const INVALID_ARGUMENT = toBuiltin(2);
+pub const INVALID_INSTRUCTION_DATA = toBuiltin(3);
+pub const INVALID_ACCOUNT_DATA = toBuiltin(4);
+pub const ACCOUNT_DATA_TOO_SMALL = toBuiltin(5);
+pub const INSUFFICIENT_FUNDS = toBuiltin(6);
+pub const INCORRECT_PROGRAM_ID = toBuiltin(7);
+pub const MISSING_REQUIRED_SIGNATURES = toBuiltin(8);
+pub const ACCOUNT_ALREADY_INITIALIZED = toBuiltin(9);
+pub const UNINITIALIZED_ACCOUNT = toBuiltin(10);
+pub const NOT_ENOUGH_ACCOUNT_KEYS = toBuiltin(11);
+pub const ACCOUNT_BORROW_FAILED = toBuiltin(12);
+pub const MAX_SEED_LENGTH_EXCEEDED = toBuiltin(13);
+pub const INVALID_SEEDS = toBuiltin(14);
+pub const BORSH_IO_ERROR = toBuiltin(15);
+pub const ACCOUNT_NOT_RENT_EXEMPT = toBuiltin(16);
+pub const UNSUPPORTED_SYSVAR = toBuiltin(17);
+pub const ILLEGAL_OWNER = toBuiltin(18);
+pub const MAX_ACCOUNTS_DATA_ALLOCATIONS_EXCEEDED = toBuiltin(19);
+pub const INVALID_REALLOC = toBuiltin(20);
+pub const MAX_INSTRUCTION_TRACE_LENGTH_EXCEEDED = toBuiltin(21);
+pub const BUILTIN_PROGRAMS_MUST_CONSUME_COMPUTE_UNITS = toBuiltin(22);
+pub const INVALID_ACCOUNT_OWNER = toBuiltin(23);
+pub const ARITHMETIC_OVERFLOW = toBuiltin(24);
+pub const IMMUTABLE = toBuiltin(25);
+pub const INCORRECT_AUTHORITY = toBuiltin(26);
+
+/// What a program hands back to the runtime: one of the builtin errors,
+/// or `Custom` with a program-defined code. Equivalent to Rust's
+/// `solana_program::program_error::ProgramError`.
+pub const ErrorCode = union(enum) {
+    Custom: u32,
+    InvalidArgument: void,
+    InvalidInstructionData: void,
+    InvalidAccountData: void,
+    AccountDataTooSmall: void,
+    InsufficientFunds: void,
+    IncorrectProgramId: void,
+    MissingRequiredSignature: void,
+    AccountAlreadyInitialized: void,
+    UninitializedAccount: void,
+    NotEnoughAccountKeys: void,
+    AccountBorrowFailed: void,
+    MaxSeedLengthExceeded: void,
+    InvalidSeeds: void,
+    BorshIoError: void,
+    AccountNotRentExempt: void,
+    UnsupportedSysvar: void,
+    IllegalOwner: void,
+    MaxAccountsDataAllocationsExceeded: void,
+    InvalidRealloc: void,
+    MaxInstructionTraceLengthExceeded: void,
+    BuiltinProgramsMustConsumeComputeUnits: void,
+    InvalidAccountOwner: void,
+    ArithmeticOverflow: void,
+    Immutable: void,
+    IncorrectAuthority: void,
+
+    /// The entrypoint return value. Builtins use their reserved constants;
+    /// a custom code is returned as-is, except `Custom(0)`, which would
+    /// read as success and becomes `CUSTOM_ZERO` instead.
+    pub fn toU64(self: ErrorCode) u64 {
+        return switch (self) {
+            .Custom => |code| if (code == 0) CUSTOM_ZERO else code,
+            .InvalidArgument => INVALID_ARGUMENT,
+            .InvalidInstructionData => INVALID_INSTRUCTION_DATA,
+            .InvalidAccountData => INVALID_ACCOUNT_DATA,
+            .AccountDataTooSmall => ACCOUNT_DATA_TOO_SMALL,
+            .InsufficientFunds => INSUFFICIENT_FUNDS,
+            .IncorrectProgramId => INCORRECT_PROGRAM_ID,
+            .MissingRequiredSignature => MISSING_REQUIRED_SIGNATURES,
+            .AccountAlreadyInitialized => ACCOUNT_ALREADY_INITIALIZED,
+            .UninitializedAccount => UNINITIALIZED_ACCOUNT,
+            .NotEnoughAccountKeys => NOT_ENOUGH_ACCOUNT_KEYS,
+            .AccountBorrowFailed => ACCOUNT_BORROW_FAILED,
+            .MaxSeedLengthExceeded => MAX_SEED_LENGTH_EXCEEDED,
+            .InvalidSeeds => INVALID_SEEDS,
+            .BorshIoError => BORSH_IO_ERROR,
+            .AccountNotRentExempt => ACCOUNT_NOT_RENT_EXEMPT,
+            .UnsupportedSysvar => UNSUPPORTED_SYSVAR,
+            .IllegalOwner => ILLEGAL_OWNER,
+            .MaxAccountsDataAllocationsExceeded => MAX_ACCOUNTS_DATA_ALLOCATIONS_EXCEEDED,
+            .InvalidRealloc => INVALID_REALLOC,
+            .MaxInstructionTraceLengthExceeded => MAX_INSTRUCTION_TRACE_LENGTH_EXCEEDED,
+            .BuiltinProgramsMustConsumeComputeUnits => BUILTIN_PROGRAMS_MUST_CONSUME_COMPUTE_UNITS,
+            .InvalidAccountOwner => INVALID_ACCOUNT_OWNER,
+            .ArithmeticOverflow => ARITHMETIC_OVERFLOW,
+            .Immutable => IMMUTABLE,
+            .IncorrectAuthority => INCORRECT_AUTHORITY,
+        };
+    }
+
+    /// Decode an entrypoint return value the way the runtime does. Returns
+    /// null for `SUCCESS` and for values above the builtin bit that are
+    /// not a known builtin.
+    pub fn fromU64(value: u64) ?ErrorCode {
+        if (value == SUCCESS) return null;
+        if (value >> BUILTIN_BIT_SHIFT == 0) return .{ .Custom = @intCast(value) };
+        if (value == CUSTOM_ZERO) return .{ .Custom = 0 };
+        if (value & std.math.maxInt(u32) != 0) return null;
+        inline for (@typeInfo(ErrorCode).@"union".fields) |field| {
+            if (comptime std.mem.eql(u8, field.name, "Custom")) continue;
+            const code = @unionInit(ErrorCode, field.name, {});
+            if (code.toU64() == value) return code;
+        }
+        return null;
+    }
+
+    /// The code an SDK error is reported as: its builtin, or the closest
+    /// builtin for errors the runtime has no code of its own for. `Custom`
+    /// is left to the program, see `custom`.
+    pub fn fromError(err: ProgramError) ErrorCode {
+        return switch (err) {
+            error.CustomError, error.Custom => .{ .Custom = 0 },
+            error.InvalidArgument => .InvalidArgument,
+            error.InvalidInstructionData => .InvalidInstructionData,
+            error.InvalidAccountData => .InvalidAccountData,
+            error.AccountDataTooSmall => .AccountDataTooSmall,
+            error.InsufficientFunds => .InsufficientFunds,
+            error.IncorrectProgramId => .IncorrectProgramId,
+            error.MissingRequiredSignature => .MissingRequiredSignature,
+            error.AccountAlreadyInitialized => .AccountAlreadyInitialized,
+            error.UninitializedAccount => .UninitializedAccount,
+            error.NotEnoughAccountKeys => .NotEnoughAccountKeys,
+            error.AccountBorrowFailed => .AccountBorrowFailed,
+            error.SeedTooLong => .MaxSeedLengthExceeded,
+            error.InvalidSeeds => .InvalidSeeds,
+            error.BorshIoError => .BorshIoError,
+            error.AccountNotRentExempt => .AccountNotRentExempt,
+            error.UnsupportedSysvar => .UnsupportedSysvar,
+            error.IllegalOwner => .IllegalOwner,
+            error.MaxAccountsDataAllocationsExceeded => .MaxAccountsDataAllocationsExceeded,
+            error.InvalidRealloc => .InvalidRealloc,
+            error.MaxInstructionTraceLengthExceeded => .MaxInstructionTraceLengthExceeded,
+            error.BuiltinProgramsMustConsumeComputeUnits => .BuiltinProgramsMustConsumeComputeUnits,
+            error.InvalidAccountOwner => .InvalidAccountOwner,
+            error.ArithmeticOverflow => .ArithmeticOverflow,
+            error.Immutable => .Immutable,
+            error.IncorrectAuthority => .IncorrectAuthority,
+
+            // Borrow tracking
+            error.AccountBorrowOutstanding,
+            error.AlreadyBorrowed,
+            error.AlreadyBorrowedMut,
+            error.BorrowLimitExceeded,
+            => .AccountBorrowFailed,
+
+            // Accounts the program may not change
+            error.ExternalAccountLamportSpend,
+            error.ExternalAccountDataModified,
+            error.InvalidAccountOwner2,
+            => .InvalidAccountOwner,
+            error.ExecutableModified,
+            error.ExecutableDataModified,
+            error.ExecutableLamportChange,
+            => .Immutable,
+            error.ExecutableAccountNotRentExempt => .AccountNotRentExempt,
+            error.RentEpochModified, error.InvalidDataLength => .InvalidAccountData,
+            error.BufferTooSmall => .AccountDataTooSmall,
+            error.AccountDataSizeChanged,
+            error.IllegalRealloc,
+            error.ExceedsMaxDataIncrease,
+            => .InvalidRealloc,
+
+            // Program and signer checks
+            error.ModifiedProgramId,
+            error.AccountNotExecutable,
+            error.InvalidProgramExecutable,
+            => .IncorrectProgramId,
+            error.AccountNotSigner => .MissingRequiredSignature,
+            error.MissingAccounts, error.MissingRequiredAccount => .NotEnoughAccountKeys,
+            error.NoProgramAddressFound, error.InvalidPDA => .InvalidSeeds,
+            error.UnknownInstruction, error.InstructionDataTooLarge => .InvalidInstructionData,
+            error.AddWithOverflow => .ArithmeticOverflow,
+
+            // No builtin exists for the errors below, so they all reach the
+            // runtime as InvalidArgument. They stay distinct inside the
+            // program; a program that must tell them apart on the wire
+            // catches them and returns its own `custom(n)` codes instead.
+
+            // Accounts passed in a way the instruction cannot use
+            error.UnbalancedInstruction,
+            error.ReadonlyLamportChange,
+            error.ReadonlyDataModified,
+            error.AccountNotWritable,
+            error.DuplicateAccountIndex,
+            error.DuplicateAccountOutOfSync,
+            error.MaxAccountsExceeded,
+            error.TooManyAccounts,
+            => .InvalidArgument,
+
+            // Malformed keys and failed invocations
+            error.InvalidError,
+            error.InvalidPubkey,
+            error.InvalidPubkeyLength,
+            error.CrossProgramInvocationFailed,
+            => .InvalidArgument,
+
+            // secp256k1Recover's failures, distinct only until they leave
+            // the program
+            error.InvalidHash,
+            error.InvalidRecoveryId,
+            error.InvalidSignature,
+            => .InvalidArgument,
+        };
+    }
+
+    /// The SDK error for this code. A program-defined `Custom(n)` has no
+    /// SDK error beyond `Custom` itself.
+    pub fn toError(self: ErrorCode) ProgramError {
+        return switch (self) {
+            .Custom => |code| if (code == 0) error.CustomError else error.Custom,
+            .MaxSeedLengthExceeded => error.SeedTooLong,
+            inline else => |_, tag| @field(ProgramError, @tagName(tag)),
+        };
+    }
+};
+
+/// Convert an error to the u64 the entrypoint returns. Several SDK errors
+/// share a builtin, see `ErrorCode.fromError`.
 pub fn toErrorCode(err: ProgramError) u64 {
-    return switch (err) {
-        error.CustomError => 0,
-        error.InvalidArgument => 1,
-        error.InvalidInstructionData => 2,
-        error.InvalidAccountData => 3,
-        error.AccountDataTooSmall => 4,
-        error.InsufficientFunds => 5,
-        error.IncorrectProgramId => 6,
-        error.MissingRequiredSignature => 7,
-        error.AccountAlreadyInitialized => 8,
-        error.UninitializedAccount => 9,
-        error.UnbalancedInstruction => 10,
-        error.ModifiedProgramId => 11,
-        error.ExternalAccountLamportSpend => 12,
-        error.ExternalAccountDataModified => 13,
-        error.ReadonlyLamportChange => 14,
-        error.ReadonlyDataModified => 15,
-        error.DuplicateAccountIndex => 16,
-        error.ExecutableModified => 17,
-        error.RentEpochModified => 18,
-        error.NotEnoughAccountKeys => 19,
-        error.AccountDataSizeChanged => 20,
-        error.AccountNotExecutable => 21,
-        error.AccountBorrowFailed => 22,
-        error.AccountBorrowOutstanding => 23,
-        error.DuplicateAccountOutOfSync => 24,
-        error.Custom => 25,
-        error.InvalidError => 26,
-        error.ExecutableDataModified => 27,
-        error.ExecutableLamportChange => 28,
-        error.ExecutableAccountNotRentExempt => 29,
-        error.UnsupportedSysvar => 30,
-        error.IllegalOwner => 31,
-        error.MaxAccountsDataAllocationsExceeded => 32,
-        error.MaxAccountsExceeded => 33,
-        error.MaxInstructionTraceLengthExceeded => 34,
-        error.BuiltinProgramsMustConsumeComputeUnits => 35,
-        error.InvalidAccountOwner => 36,
-        error.ArithmeticOverflow => 37,
-        error.Immutable => 38,
-        error.IncorrectAuthority => 39,
-        error.BorshIoError => 40,
-        error.AccountNotRentExempt => 41,
-        error.InvalidAccountOwner2 => 42,
-        error.InvalidSeeds => 43,
-        error.AddWithOverflow => 44,
-        error.InvalidProgramExecutable => 45,
-        error.AccountNotSigner => 46,
-        error.AccountNotWritable => 47,
-        error.IllegalRealloc => 48,
-
-        // Custom errors start at 1000
-        error.AlreadyBorrowed => 1000,
-        error.AlreadyBorrowedMut => 1001,
-        error.BorrowLimitExceeded => 1002,
-        error.InvalidPubkey => 1003,
-        error.InvalidPubkeyLength => 1004,
-        error.InvalidDataLength => 1005,
-        error.BufferTooSmall => 1006,
-        error.ExceedsMaxDataIncrease => 1007,
-        error.SeedTooLong => 1008,
-        error.NoProgramAddressFound => 1009,
-        error.InvalidPDA => 1010,
-        error.UnknownInstruction => 1011,
-        error.TooManyAccounts => 1012,
-        error.InstructionDataTooLarge => 1013,
-        error.MissingAccounts => 1014,
-        error.MissingRequiredAccount => 1015,
-        error.CrossProgramInvocationFailed => 1016,
-    };
+    return ErrorCode.fromError(err).toU64();
 }
 
-/// Convert u64 code to error (for testing/debugging)
+/// Convert an entrypoint return value back to an error (for testing/debugging)
 pub fn fromErrorCode(code: u64) ?ProgramError {
-    return switch (code) {
-        0 => error.CustomError,
-        1 => error.InvalidArgument,
-        2 => error.InvalidInstructionData,
-        3 => error.InvalidAccountData,
-        4 => error.AccountDataTooSmall,
-        5 => error.InsufficientFunds,
-        6 => error.IncorrectProgramId,
-        7 => error.MissingRequiredSignature,
-        8 => error.AccountAlreadyInitialized,
-        9 => error.UninitializedAccount,
-        10 => error.UnbalancedInstruction,
-        11 => error.ModifiedProgramId,
-        12 => error.ExternalAccountLamportSpend,
-        13 => error.ExternalAccountDataModified,
-        14 => error.ReadonlyLamportChange,
-        15 => error.ReadonlyDataModified,
-        16 => error.DuplicateAccountIndex,
-        17 => error.ExecutableModified,
-        18 => error.RentEpochModified,
-        19 => error.NotEnoughAccountKeys,
-        20 => error.AccountDataSizeChanged,
-        21 => error.AccountNotExecutable,
-        22 => error.AccountBorrowFailed,
-        23 => error.AccountBorrowOutstanding,
-        24 => error.DuplicateAccountOutOfSync,
-        25 => error.Custom,
-        26 => error.InvalidError,
-        27 => error.ExecutableDataModified,
-        28 => error.ExecutableLamportChange,
-        29 => error.ExecutableAccountNotRentExempt,
-        30 => error.UnsupportedSysvar,
-        31 => error.IllegalOwner,
-        32 => error.MaxAccountsDataAllocationsExceeded,
-        33 => error.MaxAccountsExceeded,
-        34 => error.MaxInstructionTraceLengthExceeded,
-        35 => error.BuiltinProgramsMustConsumeComputeUnits,
-        36 => error.InvalidAccountOwner,
-        37 => error.ArithmeticOverflow,
-        38 => error.Immutable,
-        39 => error.IncorrectAuthority,
-        40 => error.BorshIoError,
-        41 => error.AccountNotRentExempt,
-        42 => error.InvalidAccountOwner2,
-        43 => error.InvalidSeeds,
-        44 => error.AddWithOverflow,
-        45 => error.InvalidProgramExecutable,
-        46 => error.AccountNotSigner,
-        47 => error.AccountNotWritable,
-        48 => error.IllegalRealloc,
-        1000 => error.AlreadyBorrowed,
-        1001 => error.AlreadyBorrowedMut,
-        1002 => error.BorrowLimitExceeded,
-        1003 => error.InvalidPubkey,
-        1004 => error.InvalidPubkeyLength,
-        1005 => error.InvalidDataLength,
-        1006 => error.BufferTooSmall,
-        1007 => error.ExceedsMaxDataIncrease,
-        1008 => error.SeedTooLong,
-        1009 => error.NoProgramAddressFound,
-        1010 => error.InvalidPDA,
-        1011 => error.UnknownInstruction,
-        1012 => error.TooManyAccounts,
-        1013 => error.InstructionDataTooLarge,
-        1014 => error.MissingAccounts,
-        1015 => error.MissingRequiredAccount,
-        1016 => error.CrossProgramInvocationFailed,
-        else => null,
-    };
+    const decoded = ErrorCode.fromU64(code) orelse return null;
+    return decoded.toError();
+}
+
+const CUSTOM_PREFIX = "Custom";
+
+/// The error set holding only the program-defined error for `code`
+pub fn CustomCode(comptime code: u32) type {
+    return @Type(.{ .error_set = &[_]std.builtin.Type.Error{
+        .{ .name = std.fmt.comptimePrint(CUSTOM_PREFIX ++ "{d}", .{code}) },
+    } });
+}
+
+/// A program-defined error, reported to the runtime as `Custom(code)`.
+/// A handler returning it lists `CustomCode(code)` in its error set:
+///
+/// ```zig
+/// const MyError = ProgramError || CustomCode(6000);
+///
+/// fn process(...) MyError!void {
+///     return custom(6000);
+/// }
+/// ```
+pub fn custom(comptime code: u32) CustomCode(code) {
+    return @field(CustomCode(code), std.fmt.comptimePrint(CUSTOM_PREFIX ++ "{d}", .{code}));
+}
+
+/// The code in the name of an error made by `custom`, or null for any
+/// other error name
+fn customCodeFromName(comptime name: []const u8) ?u32 {
+    if (!std.mem.startsWith(u8, name, CUSTOM_PREFIX)) return null;
+    return std.fmt.parseInt(u32, name[CUSTOM_PREFIX.len..], 10) catch null;
+}
+
+fn isProgramError(comptime name: []const u8) bool {
+    @setEvalBranchQuota(10_000);
+    for (@typeInfo(ProgramError).error_set.?) |err| {
+        if (std.mem.eql(u8, err.name, name)) return true;
+    }
+    return false;
+}
+
+/// Convert any error a handler returns to the u64 the entrypoint returns:
+/// an SDK error through `ErrorCode.fromError`, a `custom` error as its
+/// `Custom` code. Any other error is a compile error, since the runtime
+/// could not tell it apart from the rest.
+pub fn errorToU64(err: anytype) u64 {
+    switch (err) {
+        inline else => |e| {
+            const name = @errorName(e);
+            if (comptime customCodeFromName(name)) |code| {
+                return (ErrorCode{ .Custom = code }).toU64();
+            }
+            if (comptime !isProgramError(name)) {
+                @compileError("error." ++ name ++ " has no runtime code; return a ProgramError or program_error.custom(n)");
+            }
+            return toErrorCode(@field(ProgramError, name));
+        },
+    }
 }
 
 /// Program result type
 pub const ProgramResult = ProgramError!void;
 
-/// Helper to convert result to u64 for entrypoint return
-pub inline fn resultToU64(result: ProgramResult) u64 {
+/// Helper to convert result to u64 for entrypoint return. `result` is a
+/// `ProgramResult`, or any `E!void` whose errors `errorToU64` accepts.
+pub inline fn resultToU64(result: anytype) u64 {
     if (result) |_| {
         return SUCCESS;
     } else |err| {
-        return toErrorCode(err);
+        return errorToU64(err);
     }
 }
 
 test "error code conversion" {
-    try std.testing.expectEqual(@as(u64, 0), toErrorCode(error.CustomError));
-    try std.testing.expectEqual(@as(u64, 7), toErrorCode(error.MissingRequiredSignature));
-    try std.testing.expectEqual(@as(u64, 1000), toErrorCode(error.AlreadyBorrowed));
+    try std.testing.expectEqual(CUSTOM_ZERO, toErrorCode(error.CustomError));
+    try std.testing.expectEqual(@as(u64, 0x8_0000_0000), toErrorCode(error.MissingRequiredSignature));
+    try std.testing.expectEqual(MAX_SEED_LENGTH_EXCEEDED, toErrorCode(error.SeedTooLong));
+    try std.testing.expectEqual(ACCOUNT_BORROW_FAILED, toErrorCode(error.AlreadyBorrowed));
+    try std.testing.expectEqual(INVALID_ARGUMENT, toErrorCode(error.UnbalancedInstruction));
+    try std.testing.expectEqual(INVALID_SEEDS, toErrorCode(error.NoProgramAddressFound));
+
+    try std.testing.expectEqual(@as(?ProgramError, error.CustomError), fromErrorCode(CUSTOM_ZERO));
+    try std.testing.expectEqual(@as(?ProgramError, error.MissingRequiredSignature), fromErrorCode(MISSING_REQUIRED_SIGNATURES));
+    try std.testing.expectEqual(@as(?ProgramError, error.AccountBorrowFailed), fromErrorCode(ACCOUNT_BORROW_FAILED));
+    try std.testing.expectEqual(@as(?ProgramError, error.Custom), fromErrorCode(1000));
+    try std.testing.expectEqual(@as(?ProgramError, null), fromErrorCode(SUCCESS));
+}
 
-    try std.testing.expectEqual(@as(?ProgramError, error.CustomError), fromErrorCode(0));
-    try std.testing.expectEqual(@as(?ProgramError, error.MissingRequiredSignature), fromErrorCode(7));
-    try std.testing.expectEqual(@as(?ProgramError, null), fromErrorCode(999));
+test "every SDK error is reported as a builtin" {
+    // Custom codes belong to the program, so no SDK error may use one
+    // other than Custom(0)
+    inline for (@typeInfo(ProgramError).error_set.?) |err| {
+        const code = ErrorCode.fromError(@field(ProgramError, err.name));
+        switch (code) {
+            .Custom => |n| try std.testing.expectEqual(@as(u32, 0), n),
+            else => try std.testing.expect(toErrorCode(@field(ProgramError, err.name)) >> BUILTIN_BIT_SHIFT != 0),
+        }
+    }
+}
+
+test "handlers fail with program-defined custom codes" {
+    const MyError = ProgramError || CustomCode(6000) || CustomCode(0);
+
+    const failing: MyError!void = custom(6000);
+    try std.testing.expectEqual(@as(u64, 6000), resultToU64(failing));
+    try std.testing.expectEqual(@as(?ErrorCode, .{ .Custom = 6000 }), ErrorCode.fromU64(6000));
+
+    const zero: MyError!void = custom(0);
+    try std.testing.expectEqual(CUSTOM_ZERO, resultToU64(zero));
+
+    const sdk: MyError!void = error.InvalidArgument;
+    try std.testing.expectEqual(INVALID_ARGUMENT, resultToU64(sdk));
+
+    // secp256k1Recover's errors share a builtin, custom codes tell them apart
+    try std.testing.expectEqual(toErrorCode(error.InvalidHash), toErrorCode(error.InvalidSignature));
+    const recover_failed: MyError!void = custom(6000);
+    try std.testing.expect(resultToU64(recover_failed) != toErrorCode(error.InvalidSignature));
+
+    try std.testing.expectEqual(@as(?u32, null), customCodeFromName("CustomError"));
+    try std.testing.expectEqual(@as(?u32, null), customCodeFromName("Custom"));
+}
+
+test "ErrorCode matches the runtime's encoding" {
+    try std.testing.expectEqual(@as(u64, 0x1_0000_0000), (ErrorCode{ .Custom = 0 }).toU64());
+    try std.testing.expectEqual(@as(u64, 42), (ErrorCode{ .Custom = 42 }).toU64());
+    try std.testing.expectEqual(@as(u64, 0x2_0000_0000), (ErrorCode{ .InvalidArgument = {} }).toU64());
+    try std.testing.expectEqual(@as(u64, 0x1A_0000_0000), (ErrorCode{ .IncorrectAuthority = {} }).toU64());
+
+    // Every builtin survives a round trip through its u64
+    inline for (@typeInfo(ErrorCode).@"union".fields) |field| {
+        if (comptime std.mem.eql(u8, field.name, "Custom")) continue;
+        const code = @unionInit(ErrorCode, field.name, {});
+        try std.testing.expectEqual(@as(?ErrorCode, code), ErrorCode.fromU64(code.toU64()));
+    }
+    try std.testing.expectEqual(@as(?ErrorCode, .{ .Custom = 0 }), ErrorCode.fromU64(CUSTOM_ZERO));
+    try std.testing.expectEqual(@as(?ErrorCode, .{ .Custom = 7 }), ErrorCode.fromU64(7));
+    try std.testing.expectEqual(@as(?ErrorCode, null), ErrorCode.fromU64(toBuiltin(27)));
+    try std.testing.expectEqual(@as(?ErrorCode, null), ErrorCode.fromU64(INVALID_ARGUMENT | 1));
 }
 
 test "result conversion" {
@@ -256,5 +473,5 @@ test "result conversion" {
     try std.testing.expectEqual(@as(u64, SUCCESS), resultToU64(ok_result));
 
     const err_result: ProgramResult = error.InvalidArgument;
-    try std.testing.expectEqual(@as(u64, 1), resultToU64(err_result));
+    try std.testing.expectEqual(INVALID_ARGUMENT, resultToU64(err_result));
 }
@@ -11,6 +11,8 @@ pub const PubkeyError = error{
     IllegalOwner,
     /// No viable bump seed found for address generation
     NoViableBumpSeed,
+    /// Base58 string does not decode to exactly 32 bytes
+    InvalidLength,
 };
 
 /// Parse errors for address strings
@@ -30,6 +32,7 @@ pub fn pubkeyErrorToU64(err: PubkeyError) u64 {
         PubkeyError.InvalidSeeds => 1,
         PubkeyError.IllegalOwner => 2,
         PubkeyError.NoViableBumpSeed => 3,
+        PubkeyError.InvalidLength => 4,
     };
 }
 
@@ -39,6 +42,8 @@ pub fn u64ToPubkeyError(code: u64) PubkeyError {
         0 => PubkeyError.MaxSeedLengthExceeded,
         1 => PubkeyError.InvalidSeeds,
         2 => PubkeyError.IllegalOwner,
+        3 => PubkeyError.NoViableBumpSeed,
+        4 => PubkeyError.InvalidLength,
         else => @panic("Invalid error code, Is not a valid PubkeyError"),
     };
 }
@@ -46,10 +51,11 @@ pub fn u64ToPubkeyError(code: u64) PubkeyError {
 /// Convert AddressError to ProgramError
 pub fn addressErrorToProgramError(err: PubkeyError) ProgramError {
     return switch (err) {
-        PubkeyError.MaxSeedLengthExceeded => ProgramError.MaxSeedLengthExceeded,
+        PubkeyError.MaxSeedLengthExceeded => ProgramError.SeedTooLong,
         PubkeyError.InvalidSeeds => ProgramError.InvalidSeeds,
         PubkeyError.IllegalOwner => ProgramError.IllegalOwner,
-        PubkeyError.NoViableBumpSeed => ProgramError.InvalidError, // TODO: Implement error handling for NoViableBumpSeed
+        PubkeyError.NoViableBumpSeed => ProgramError.NoProgramAddressFound,
+        PubkeyError.InvalidLength => ProgramError.InvalidPubkeyLength,
     };
 }
 
@@ -60,6 +66,7 @@ pub fn formatPubkeyError(err: PubkeyError) []const u8 {
         PubkeyError.InvalidSeeds => "Provided seeds do not result in a valid address",
         PubkeyError.IllegalOwner => "Provided owner is not allowed",
         PubkeyError.NoViableBumpSeed => "No viable bump seed found for address generation",
+        PubkeyError.InvalidLength => "Base58 string does not decode to a 32 byte public key",
     };
 }
 
@@ -79,15 +86,27 @@ test "error conversions" {
     try testing.expectEqual(@as(u64, 0), pubkeyErrorToU64(error.MaxSeedLengthExceeded));
     try testing.expectEqual(@as(u64, 1), pubkeyErrorToU64(error.InvalidSeeds));
     try testing.expectEqual(@as(u64, 2), pubkeyErrorToU64(error.IllegalOwner));
+    try testing.expectEqual(@as(u64, 4), pubkeyErrorToU64(error.InvalidLength));
 
     // Test u64 to AddressError conversion
     try testing.expectEqual(error.MaxSeedLengthExceeded, u64ToPubkeyError(0));
     try testing.expectEqual(error.InvalidSeeds, u64ToPubkeyError(1));
     try testing.expectEqual(error.IllegalOwner, u64ToPubkeyError(2));
+    try testing.expectEqual(error.InvalidLength, u64ToPubkeyError(4));
 
     // Test that invalid code panics (can't test panic in unit tests, so skip this)
 }
 
+test "address errors convert to program errors" {
+    const testing = std.testing;
+
+    try testing.expectEqual(ProgramError.SeedTooLong, addressErrorToProgramError(PubkeyError.MaxSeedLengthExceeded));
+    try testing.expectEqual(ProgramError.InvalidSeeds, addressErrorToProgramError(PubkeyError.InvalidSeeds));
+    try testing.expectEqual(ProgramError.IllegalOwner, addressErrorToProgramError(PubkeyError.IllegalOwner));
+    try testing.expectEqual(ProgramError.NoProgramAddressFound, addressErrorToProgramError(PubkeyError.NoViableBumpSeed));
+    try testing.expectEqual(ProgramError.InvalidPubkeyLength, addressErrorToProgramError(PubkeyError.InvalidLength));
+}
+
 test "error messages" {
     const testing = std.testing;
 
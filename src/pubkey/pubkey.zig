@@ -19,6 +19,10 @@ pub const TOKEN_PROGRAM_ID = Pubkey.parse("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss62
 pub const ASSOCIATED_TOKEN_PROGRAM_ID = Pubkey.parse("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 pub const RENT_SYSVAR_ID = Pubkey.parse("SysvarRent111111111111111111111111111111111");
 pub const CLOCK_SYSVAR_ID = Pubkey.parse("SysvarC1ock11111111111111111111111111111111");
+pub const EPOCH_SCHEDULE_SYSVAR_ID = Pubkey.parse("SysvarEpochSchedu1e111111111111111111111111");
+pub const STAKE_HISTORY_SYSVAR_ID = Pubkey.parse("SysvarStakeHistory1111111111111111111111111");
+pub const SLOT_HASHES_SYSVAR_ID = Pubkey.parse("SysvarS1otHashes111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID = Pubkey.parse("Sysvar1nstructions1111111111111111111111111");
 pub const STAKE_PROGRAM_ID = Pubkey.parse("Stake11111111111111111111111111111111111111");
 pub const VOTE_PROGRAM_ID = Pubkey.parse("Vote111111111111111111111111111111111111111");
 pub const BPF_LOADER_PROGRAM_ID = Pubkey.parse("BPFLoader2111111111111111111111111111111111");
@@ -163,6 +167,27 @@ pub const Pubkey = extern struct {
         return buf[0..len];
     }
 
+    /// Encode as a base58 string (Bitcoin alphabet) into `buf`.
+    /// A 32-byte key never encodes to more than 44 characters.
+    pub fn toBase58(self: Pubkey, buf: []u8) ![]const u8 {
+        return self.toString(buf);
+    }
+
+    /// Decode a base58 string (Bitcoin alphabet). Returns
+    /// `PubkeyError.InvalidLength` unless it decodes to exactly 32 bytes.
+    pub fn fromBase58(str: []const u8) !Pubkey {
+        // A 32-byte key never encodes to more than MAX_BASE58_LEN characters
+        if (str.len == 0 or str.len > MAX_BASE58_LEN) {
+            return AddressError.InvalidLength;
+        }
+        var decoded: [MAX_BASE58_LEN]u8 = undefined;
+        const len = try BASE58_ENDEC.decode(&decoded, str);
+        if (len != PUBKEY_BYTES) {
+            return AddressError.InvalidLength;
+        }
+        return Pubkey.fromSlice(decoded[0..PUBKEY_BYTES]);
+    }
+
     /// Convert Pubkey to base58 string (allocates)
     pub fn toStringAlloc(self: Pubkey, allocator: std.mem.Allocator) ![]u8 {
         return try BASE58_ENDEC.encodeAlloc(allocator, &self.bytes);
@@ -227,12 +252,27 @@ pub const Pubkey = extern struct {
     }
 
     /// Check if two pubkeys are equal - optimized version
+    ///
+    /// May return early on the first differing byte. Prefer
+    /// `equalsConstantTime` when checking signers or authorities.
     pub fn equals(self: *const Pubkey, other: *const Pubkey) bool {
         const xx: @Vector(SIZE, u8) = self.bytes;
         const yy: @Vector(SIZE, u8) = other.bytes;
         return @reduce(.And, xx == yy);
     }
 
+    /// Constant-time equality check
+    ///
+    /// XOR-accumulates all 32 bytes without early exit, so the time taken
+    /// does not depend on where the keys differ.
+    pub fn equalsConstantTime(self: Pubkey, other: Pubkey) bool {
+        var diff: u8 = 0;
+        for (self.bytes, other.bytes) |a, b| {
+            diff |= a ^ b;
+        }
+        return diff == 0;
+    }
+
     /// Comptime equality check
     pub fn comptimeEquals(comptime self: Pubkey, comptime other: Pubkey) bool {
         comptime {
@@ -267,47 +307,22 @@ pub const Pubkey = extern struct {
         return .{ .bytes = decoded.constSlice()[0..SIZE].* };
     }
 
-    /// Check if pubkey is on the ed25519 curve
+    /// Check if pubkey is on the ed25519 curve the way the runtime does
+    /// for program addresses: it decompresses, non-canonical encodings
+    /// included. The free function `isOnCurve` is the strict check for
+    /// wallet keys.
     pub inline fn isOnCurve(self: Pubkey) bool {
-        const Y = std.crypto.ecc.Curve25519.Fe.fromBytes(self.bytes);
-        const Z = std.crypto.ecc.Curve25519.Fe.one;
-        const YY = Y.sq();
-        const u = YY.sub(Z);
-        const v = YY.mul(std.crypto.ecc.Curve25519.Fe.edwards25519d).add(Z);
-        if (sqrtRatioM1(u, v) != 1) {
-            return false;
-        }
-        return true;
+        return decompressesToCurvePoint(self.bytes);
     }
 
-    /// Create a Pubkey with a seed (matching Rust's create_with_seed)
+    /// Create a Pubkey with a seed (matching Rust's create_with_seed), see
+    /// the free function `createWithSeed`
     pub fn createWithSeed(
         self: Pubkey,
         seed: []const u8,
         owner: *const Pubkey,
     ) !Pubkey {
-        // Check seed length
-        if (seed.len > MAX_SEED_LEN) {
-            return AddressError.MaxSeedLengthExceeded;
-        }
-
-        // Check for illegal owner (PDA marker check)
-        const owner_bytes = owner.bytes;
-        if (owner_bytes.len >= PDA_MARKER.len) {
-            const slice = owner_bytes[owner_bytes.len - PDA_MARKER.len ..];
-            if (std.mem.eql(u8, slice, PDA_MARKER)) {
-                return AddressError.IllegalOwner;
-            }
-        }
-
-        // Hash the components using SHA256
-        var h = std.crypto.hash.sha2.Sha256.init(.{});
-        h.update(&self.bytes);
-        h.update(seed);
-        h.update(&owner.bytes);
-        var result: [32]u8 = undefined;
-        h.final(&result);
-        return Pubkey.fromBytes(result);
+        return deriveWithSeed(self, seed, owner.*);
     }
 
     /// Create a unique Pubkey for testing (matching Rust's new_unique)
@@ -363,17 +378,12 @@ pub const Pubkey = extern struct {
         }
     }
 
-    fn sqrtRatioM1(u: std.crypto.ecc.Curve25519.Fe, v: std.crypto.ecc.Curve25519.Fe) u32 {
-        const v3 = v.sq().mul(v); // v^3
-        const x = v3.sq().mul(u).mul(v).pow2523().mul(v3).mul(u); // uv^3(uv^7)^((q-5)/8)
-        const vxx = x.sq().mul(v); // vx^2
-        const m_root_check = vxx.sub(u); // vx^2-u
-        const p_root_check = vxx.add(u); // vx^2+u
-        const has_m_root = m_root_check.isZero();
-        const has_p_root = p_root_check.isZero();
-        return @intFromBool(has_m_root) | @intFromBool(has_p_root);
-    }
-
+    /// Create a program address from seeds that already include the bump.
+    ///
+    /// Hashes `seeds || program_id || PDA_MARKER` with sha256 and returns
+    /// `InvalidSeeds` if the result lies on the ed25519 curve. More than 16
+    /// seeds, or a seed longer than 32 bytes, fails with `MaxSeedLengthExceeded`
+    /// exactly like the runtime does.
     pub inline fn createProgramAddress(seeds: []const []const u8, program_id: Pubkey) !Pubkey {
         // Validate input
         if (seeds.len > Pubkey.max_num_seeds) {
@@ -409,7 +419,7 @@ pub const Pubkey = extern struct {
             h.update(seed);
         }
         h.update(&program_id.bytes);
-        h.update("ProgramDerivedAddress");
+        h.update(PDA_MARKER);
         h.final(&address.bytes);
 
         if (address.isOnCurve()) {
@@ -447,8 +457,13 @@ pub const Pubkey = extern struct {
 
         const seeds_final = seeds_with_bump[0 .. seeds.len + 1];
 
+        // Search bumps from 255 down to 0, like the runtime's try_find_program_address.
+        // Only an on-curve result moves on to the next bump; any other error is final.
         while (pda.bump_seed[0] >= 0) : (pda.bump_seed[0] -= 1) {
-            pda.address = Pubkey.createProgramAddress(seeds_final, program_id) catch {
+            pda.address = Pubkey.createProgramAddress(seeds_final, program_id) catch |err| {
+                if (err != AddressError.InvalidSeeds) {
+                    return err;
+                }
                 if (pda.bump_seed[0] == 0) {
                     return AddressError.NoViableBumpSeed;
                 }
@@ -480,6 +495,58 @@ pub fn tryFindProgramAddress(
     return .{ .pubkey = pda.address, .bump = pda.bump_seed[0] };
 }
 
+/// Address of a seed-derived account, `sha256(base || seed || owner)`,
+/// matching Rust's `Pubkey::create_with_seed`. Unlike a PDA it is not
+/// pushed off the curve, and creating it needs `base` to sign.
+///
+/// Fails with `PubkeyError.MaxSeedLengthExceeded` for a seed longer than
+/// `MAX_SEED_LEN`, and with `PubkeyError.IllegalOwner` for an owner ending
+/// in `PDA_MARKER`, whose hash input could then collide with that of
+/// `createProgramAddress`.
+pub fn createWithSeed(base: Pubkey, seed: []const u8, owner: Pubkey) AddressError!Pubkey {
+    return deriveWithSeed(base, seed, owner);
+}
+
+/// Both `createWithSeed`s, the free function and the `Pubkey` method
+fn deriveWithSeed(base: Pubkey, seed: []const u8, owner: Pubkey) AddressError!Pubkey {
+    if (seed.len > MAX_SEED_LEN) {
+        return AddressError.MaxSeedLengthExceeded;
+    }
+    if (std.mem.endsWith(u8, &owner.bytes, PDA_MARKER)) {
+        return AddressError.IllegalOwner;
+    }
+
+    var h = std.crypto.hash.sha2.Sha256.init(.{});
+    h.update(&base.bytes);
+    h.update(seed);
+    h.update(&owner.bytes);
+    var result: [32]u8 = undefined;
+    h.final(&result);
+    return Pubkey.fromBytes(result);
+}
+
+/// Whether `bytes` decompresses to a point on the ed25519 curve. Wallet
+/// keys always do and program derived addresses never do, so this tells a
+/// real signer's key from a PDA. Non-canonical encodings are rejected: a y
+/// coordinate not reduced below the field prime, or the sign bit set on a
+/// point whose x is zero. That makes it stricter than the check program
+/// addresses are derived with, see `Pubkey.isOnCurve`.
+pub fn isOnCurve(bytes: [32]u8) bool {
+    const Edwards25519 = std.crypto.ecc.Edwards25519;
+    Edwards25519.rejectNonCanonical(bytes) catch return false;
+    const point = Edwards25519.fromBytes(bytes) catch return false;
+    return !(point.x.isZero() and bytes[31] & 0x80 != 0);
+}
+
+/// Whether `bytes` decompresses to a curve point, as curve25519-dalek's
+/// `CompressedEdwardsY::decompress` decides for `create_program_address`.
+/// y is reduced mod p and a sign bit on x = 0 is ignored, so encodings
+/// `isOnCurve` rejects can still be on the curve here.
+fn decompressesToCurvePoint(bytes: [32]u8) bool {
+    _ = std.crypto.ecc.Edwards25519.fromBytes(bytes) catch return false;
+    return true;
+}
+
 /// Log a pubkey to the console (for debugging in BPF programs)
 pub fn logPubkey(pubkey: *const Pubkey) void {
     if (bpf.is_bpf_program) {
@@ -518,6 +585,31 @@ test "createWithSeed" {
     try testing.expectError(AddressError.MaxSeedLengthExceeded, result);
 }
 
+test "createWithSeed matches the Rust SDK" {
+    const testing = std.testing;
+
+    // Pubkey::create_with_seed(&Pubkey::new_from_array([1; 32]), seed, &owner)
+    const base = Pubkey.fromBytes([_]u8{1} ** 32);
+    const stake = try createWithSeed(base, "stake:0", STAKE_PROGRAM_ID);
+    try testing.expect(stake.equals(&Pubkey.parse("ArMsympbfhqYJnrMDKhRdCKRuH4cpu58kAvcncZ8nJYa")));
+    const empty = try createWithSeed(base, "", SYSTEM_PROGRAM_ID);
+    try testing.expect(empty.equals(&Pubkey.parse("Ed9uyt75PM2iNPFKb5b3bXCS4PeAim2FHoZGLAqNtha")));
+
+    // The method is the same derivation
+    try testing.expect(stake.equals(&try base.createWithSeed("stake:0", &STAKE_PROGRAM_ID)));
+
+    try testing.expectError(AddressError.MaxSeedLengthExceeded, createWithSeed(base, "a" ** (MAX_SEED_LEN + 1), STAKE_PROGRAM_ID));
+
+    // An owner ending in the PDA marker
+    var pda_owner = Pubkey.ZEROES;
+    @memcpy(pda_owner.bytes[32 - PDA_MARKER.len ..], PDA_MARKER);
+    try testing.expectError(AddressError.IllegalOwner, createWithSeed(base, "x", pda_owner));
+    // ...but only at the very end
+    var shifted_owner = Pubkey.ZEROES;
+    @memcpy(shifted_owner.bytes[32 - PDA_MARKER.len - 1 .. 31], PDA_MARKER);
+    _ = try createWithSeed(base, "x", shifted_owner);
+}
+
 test "newUnique generates different addresses" {
     const testing = std.testing;
 
@@ -546,11 +638,37 @@ test "createProgramAddress" {
     // Test with max seeds
     const seed_bytes = [_][1]u8{ .{1}, .{2}, .{3}, .{4}, .{5}, .{6}, .{7}, .{8}, .{9}, .{10}, .{11}, .{12}, .{13}, .{14}, .{15}, .{16} };
     var max_seeds: [16][]const u8 = undefined;
-    for (&seed_bytes, 0..) |seed, i| {
-        max_seeds[i] = &seed;
+    for (&seed_bytes, 0..) |*seed, i| {
+        max_seeds[i] = seed;
     }
     const pda2 = try Pubkey.createProgramAddress(&max_seeds, program_id);
     _ = pda2;
+
+    // Known address from solana_program's create_program_address
+    try testing.expect(pda.equals(&Pubkey.parse("o8XZKgwHdt3YqzLg9GvTKjWz8N1MouXQfiBwqrTQdKi")));
+
+    // Bump 255 for these seeds lands on the curve (find_program_address picks 254)
+    const on_curve_bump = [_]u8{255};
+    try testing.expectError(
+        AddressError.InvalidSeeds,
+        Pubkey.createProgramAddress(&[_][]const u8{ "Lil'", "Bits", &on_curve_bump }, program_id),
+    );
+
+    // More than 16 seeds
+    var too_many_seeds: [17][]const u8 = undefined;
+    for (&too_many_seeds) |*seed| {
+        seed.* = "seed";
+    }
+    try testing.expectError(
+        AddressError.MaxSeedLengthExceeded,
+        Pubkey.createProgramAddress(&too_many_seeds, program_id),
+    );
+
+    // A single seed longer than 32 bytes
+    try testing.expectError(
+        AddressError.MaxSeedLengthExceeded,
+        Pubkey.createProgramAddress(&[_][]const u8{"a" ** 33}, program_id),
+    );
 }
 
 test "findProgramAddress" {
@@ -568,6 +686,70 @@ test "findProgramAddress" {
     try testing.expect(pda_result.address.equals(&pda_verify));
 }
 
+test "findProgramAddress matches Rust SDK find_program_address" {
+    const testing = std.testing;
+    const program_id = BPF_UPGRADEABLE_LOADER_PROGRAM_ID;
+
+    // Expected values computed with solana_program::pubkey::Pubkey::find_program_address
+    const lil_bits = try Pubkey.findProgramAddress(&[_][]const u8{ "Lil'", "Bits" }, program_id);
+    try testing.expect(lil_bits.address.equals(&Pubkey.parse("H4feCuM8B43jxwbHAsUHDasw1raRkvWF6py4Fx7suB8N")));
+    try testing.expectEqual(@as(u8, 254), lil_bits.bump_seed[0]);
+
+    const test_pda = try Pubkey.findProgramAddress(&[_][]const u8{"test-pda"}, program_id);
+    try testing.expect(test_pda.address.equals(&Pubkey.parse("2r1F7YXUVv7m6uE3GioyYVJBeUk19PtKg1YsiRNoSPYY")));
+    try testing.expectEqual(@as(u8, 255), test_pda.bump_seed[0]);
+
+    // A seed that is too long is rejected instead of exhausting the bump search
+    const long_seed = "a" ** 33;
+    try testing.expectError(
+        AddressError.MaxSeedLengthExceeded,
+        Pubkey.findProgramAddress(&[_][]const u8{long_seed}, program_id),
+    );
+}
+
+test "isOnCurve tells wallet keys from PDAs" {
+    const testing = std.testing;
+
+    // Public key of RFC 8032's first Ed25519 test vector
+    var wallet: [32]u8 = undefined;
+    _ = try std.fmt.hexToBytes(&wallet, "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    try testing.expect(isOnCurve(wallet));
+    try testing.expect(Pubkey.fromBytes(wallet).isOnCurve());
+
+    // A freshly generated signing key
+    const key_pair = try std.crypto.sign.Ed25519.KeyPair.generateDeterministic([_]u8{7} ** 32);
+    try testing.expect(isOnCurve(key_pair.public_key.toBytes()));
+
+    // PDA from solana_program's find_program_address
+    const pda = Pubkey.parse("H4feCuM8B43jxwbHAsUHDasw1raRkvWF6py4Fx7suB8N");
+    try testing.expect(!isOnCurve(pda.bytes));
+    try testing.expect(!pda.isOnCurve());
+}
+
+test "isOnCurve rejects non-canonical encodings" {
+    const testing = std.testing;
+
+    // y = 1 is the identity point (x = 0), on the curve
+    var identity = [_]u8{0} ** 32;
+    identity[0] = 1;
+    try testing.expect(isOnCurve(identity));
+
+    // The same point with the sign bit set encodes x = -0
+    var negative_zero = identity;
+    negative_zero[31] |= 0x80;
+    try testing.expect(!isOnCurve(negative_zero));
+
+    // y = p + 1 reduces to the identity but is not reduced itself
+    var unreduced = [_]u8{0xff} ** 32;
+    unreduced[0] = 0xee;
+    unreduced[31] = 0x7f;
+    try testing.expect(!isOnCurve(unreduced));
+
+    // The runtime decompresses both, so neither may be a program address
+    try testing.expect(Pubkey.fromBytes(negative_zero).isOnCurve());
+    try testing.expect(Pubkey.fromBytes(unreduced).isOnCurve());
+}
+
 test "pubkey equals" {
     const key1 = Pubkey.fromBytes(.{1} ** 32);
     const key2 = Pubkey.fromBytes(.{1} ** 32);
@@ -592,6 +774,21 @@ test "pubkey is zeroes" {
     try std.testing.expect(key.bytes[31] == 0);
 }
 
+test "pubkey equalsConstantTime" {
+    const key1 = Pubkey.fromBytes([_]u8{7} ** 32);
+    var bytes = [_]u8{7} ** 32;
+    try std.testing.expect(key1.equalsConstantTime(Pubkey.fromBytes(bytes)));
+
+    // Keys differing only in the last byte
+    bytes[31] = 8;
+    try std.testing.expect(!key1.equalsConstantTime(Pubkey.fromBytes(bytes)));
+
+    // Keys differing only in the first byte
+    bytes[31] = 7;
+    bytes[0] = 0;
+    try std.testing.expect(!key1.equalsConstantTime(Pubkey.fromBytes(bytes)));
+}
+
 test "pubkey fromBytes and equals" {
     const bytes1 = [_]u8{42} ** 32;
     const bytes2 = [_]u8{42} ** 32;
@@ -664,6 +861,31 @@ test "pubkey fromString with invalid input" {
     try std.testing.expectError(ParseAddressError.InvalidPubkeyLength, result2);
 }
 
+test "pubkey toBase58 and fromBase58" {
+    // System program is all zeros: 32 leading '1's
+    var buf: [MAX_BASE58_LEN]u8 = undefined;
+    const zeros = Pubkey.fromBytes([_]u8{0} ** 32);
+    try std.testing.expectEqualStrings("11111111111111111111111111111111", try zeros.toBase58(&buf));
+
+    const max = Pubkey.fromBytes([_]u8{255} ** 32);
+    const encoded = try max.toBase58(&buf);
+    try std.testing.expectEqualStrings("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG", encoded);
+    try std.testing.expectEqual(@as(usize, 44), encoded.len);
+    try std.testing.expect((try Pubkey.fromBase58(encoded)).equals(&max));
+
+    const token_program = try Pubkey.fromBase58("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    try std.testing.expectEqualStrings(
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        try token_program.toBase58(&buf),
+    );
+
+    // Too short, too long and empty strings are not 32-byte keys
+    try std.testing.expectError(AddressError.InvalidLength, Pubkey.fromBase58("111111111111111"));
+    try std.testing.expectError(AddressError.InvalidLength, Pubkey.fromBase58("1" ** 33));
+    try std.testing.expectError(AddressError.InvalidLength, Pubkey.fromBase58("1" ** 45));
+    try std.testing.expectError(AddressError.InvalidLength, Pubkey.fromBase58(""));
+}
+
 test "pubkey indexIn" {
     const key1 = Pubkey.fromBytes([_]u8{1} ** 32);
     const key2 = Pubkey.fromBytes([_]u8{2} ** 32);
@@ -14,6 +14,14 @@ pub const bpf = @import("bpf.zig");
 pub const msg = @import("msg/msg.zig");
 pub const entrypoint = @import("entrypoint.zig");
 pub const cpi = @import("cpi.zig");
+pub const sysvar = @import("sysvar/sysvar.zig");
+pub const borsh = @import("borsh.zig");
+pub const hash = @import("hash.zig");
+pub const logging = @import("log.zig");
+pub const allocator = @import("allocator.zig");
+pub const system_program = @import("system_program.zig");
+pub const secp256k1 = @import("secp256k1.zig");
+pub const math = @import("math.zig");
 
 // Re-export common types
 pub const Pubkey = pubkey.Pubkey;
@@ -23,6 +31,9 @@ pub const Instruction = instruction.Instruction;
 pub const CompiledInstruction = instruction.CompiledInstruction;
 pub const ProgramError = program_error.ProgramError;
 pub const ProgramResult = program_error.ProgramResult;
+pub const Clock = sysvar.Clock;
+pub const Rent = sysvar.Rent;
+pub const EpochSchedule = sysvar.EpochSchedule;
 
 // Re-export entrypoint types and functions
 pub const declareEntrypoint = entrypoint.declareEntrypoint;
@@ -41,11 +52,14 @@ pub const ASSOCIATED_TOKEN_PROGRAM_ID = pubkey.ASSOCIATED_TOKEN_PROGRAM_ID;
 // Re-export sysvar IDs
 pub const SYSVAR_CLOCK_ID = pubkey.CLOCK_SYSVAR_ID;
 pub const SYSVAR_RENT_ID = pubkey.RENT_SYSVAR_ID;
-pub const SYSVAR_INSTRUCTIONS_ID = pubkey.extensions.SYSVAR_PROGRAM_ID;
+pub const SYSVAR_EPOCH_SCHEDULE_ID = pubkey.EPOCH_SCHEDULE_SYSVAR_ID;
+pub const SYSVAR_INSTRUCTIONS_ID = pubkey.INSTRUCTIONS_SYSVAR_ID;
+pub const SYSVAR_SLOT_HASHES_ID = pubkey.SLOT_HASHES_SYSVAR_ID;
 
 // Helper functions
 pub const toErrorCode = program_error.toErrorCode;
 pub const resultToU64 = program_error.resultToU64;
+pub const ErrorCode = program_error.ErrorCode;
 pub const fromAccount = account_info.fromAccount;
 
 // Re-export msg functions (Rust-compatible and legacy-compatible)
@@ -67,16 +81,22 @@ pub const msgPanic = msg.msgPanic;
 pub const msgAssert = msg.msgAssert;
 pub const msgError = msg.msgError;
 
+// Re-export the sol_log helpers
+pub const logPubkey = logging.logPubkey;
+pub const log64 = logging.log64;
+
 // Re-export syscall helpers
-pub const sha256 = syscalls.sha256;
-pub const keccak256 = syscalls.keccak256;
-pub const blake3 = syscalls.blake3;
+pub const sha256 = hash.sha256;
+pub const Hasher = hash.Hasher;
+pub const keccak256 = hash.keccak256;
+pub const blake3 = hash.blake3;
 pub const poseidon = syscalls.poseidon;
-pub const secp256k1Recover = syscalls.secp256k1Recover;
+pub const secp256k1Recover = secp256k1.secp256k1Recover;
 pub const getRemainingComputeUnits = syscalls.getRemainingComputeUnits;
 pub const getStackHeight = syscalls.getStackHeight;
-pub const setReturnData = syscalls.setReturnData;
-pub const getReturnData = syscalls.getReturnData;
+pub const setReturnData = cpi.setReturnData;
+pub const getReturnData = cpi.getReturnData;
+pub const ReturnData = cpi.ReturnData;
 
 // Re-export BPF loader program IDs
 pub const BPF_LOADER_DEPRECATED_PROGRAM_ID = bpf.bpf_loader_deprecated_program_id;
@@ -84,10 +104,10 @@ pub const BPF_LOADER_PROGRAM_ID = bpf.bpf_loader_program_id;
 pub const BPF_UPGRADEABLE_LOADER_PROGRAM_ID = bpf.bpf_upgradeable_loader_program_id;
 
 // Re-export pubkey extensions
-pub const createWithSeed = pubkey.extensions.createWithSeed;
+pub const createWithSeed = pubkey.createWithSeed;
 pub const tryFindProgramAddress = pubkey.extensions.tryFindProgramAddress;
 pub const newUnique = pubkey.extensions.newUnique;
-pub const isOnCurve = pubkey.extensions.isOnCurve;
+pub const isOnCurve = pubkey.isOnCurve;
 
 // Re-export hasher for HashMap with Pubkey keys
 pub const PubkeyHashContext = pubkey.hasher.PubkeyHashContext;
@@ -124,5 +144,15 @@ test {
     _ = @import("pubkey/hasher.zig");
     _ = @import("pubkey/error.zig");
     _ = @import("entrypoint.zig");
+    _ = @import("instruction/instruction.zig");
+    _ = @import("cpi.zig");
+    _ = @import("sysvar/sysvar.zig");
+    _ = @import("borsh.zig");
+    _ = @import("hash.zig");
+    _ = @import("log.zig");
+    _ = @import("allocator.zig");
+    _ = @import("system_program.zig");
+    _ = @import("secp256k1.zig");
+    _ = @import("math.zig");
     _ = @import("account_info/rust_compatibility_test.zig");
 }
@@ -0,0 +1,137 @@
+/// secp256k1 public key recovery, for checking Ethereum-style signatures
+///
+/// Wraps the sol_secp256k1_recover syscall on Solana. Everywhere else the
+/// key is recovered with std.crypto, accepting and rejecting the same
+/// inputs as the runtime, so programs can be tested off-chain.
+const std = @import("std");
+const syscalls = @import("syscalls.zig");
+const bpf = @import("bpf.zig");
+const ProgramError = @import("program_error.zig").ProgramError;
+
+const Secp256k1 = std.crypto.ecc.Secp256k1;
+const Scalar = Secp256k1.scalar.Scalar;
+
+/// Length of the message hash
+pub const HASH_LENGTH = 32;
+
+/// Length of a signature: r then s, both big-endian
+pub const SIGNATURE_LENGTH = 64;
+
+/// Length of a recovered key: x then y, both big-endian, without the 0x04
+/// prefix of SEC1
+pub const PUBKEY_LENGTH = 64;
+
+// Error codes returned by sol_secp256k1_recover
+const ERROR_INVALID_HASH: u64 = 1;
+const ERROR_INVALID_RECOVERY_ID: u64 = 2;
+const ERROR_INVALID_SIGNATURE: u64 = 3;
+
+/// Recover the public key that produced `signature` over `hash`.
+/// `recovery_id` is 0 to 3: Ethereum's `v` minus 27.
+///
+/// # Examples
+/// ```zig
+/// const hash = sol.hash.keccak256(&.{message});
+/// const key = try secp256k1.secp256k1Recover(hash, v - 27, signature);
+/// const address = sol.hash.keccak256(&.{&key})[12..];
+/// ```
+pub fn secp256k1Recover(
+    hash: [HASH_LENGTH]u8,
+    recovery_id: u8,
+    signature: [SIGNATURE_LENGTH]u8,
+) ProgramError![PUBKEY_LENGTH]u8 {
+    if (recovery_id > 3) {
+        return ProgramError.InvalidRecoveryId;
+    }
+
+    if (comptime bpf.is_solana) {
+        var result: [PUBKEY_LENGTH]u8 = undefined;
+        const code = syscalls.sol_secp256k1_recover(&hash[0], recovery_id, &signature[0], &result[0]);
+        return switch (code) {
+            0 => result,
+            ERROR_INVALID_HASH => ProgramError.InvalidHash,
+            ERROR_INVALID_RECOVERY_ID => ProgramError.InvalidRecoveryId,
+            else => ProgramError.InvalidSignature,
+        };
+    }
+
+    return recover(hash, recovery_id, signature) catch ProgramError.InvalidSignature;
+}
+
+/// Q = r⁻¹(sR - zG), where R is the point with x-coordinate r (plus the
+/// group order when bit 1 of `recovery_id` is set) and y parity given by
+/// bit 0
+fn recover(hash: [HASH_LENGTH]u8, recovery_id: u8, signature: [SIGNATURE_LENGTH]u8) ![PUBKEY_LENGTH]u8 {
+    // r and s must be below the group order and non-zero
+    const r = try Scalar.fromBytes(signature[0..32].*, .big);
+    const s = try Scalar.fromBytes(signature[32..64].*, .big);
+    if (r.isZero() or s.isZero()) {
+        return error.InvalidSignature;
+    }
+
+    var x_int = std.mem.readInt(u256, signature[0..32], .big);
+    if (recovery_id & 2 != 0) {
+        x_int = try std.math.add(u256, x_int, Secp256k1.scalar.field_order);
+    }
+    var x_bytes: [32]u8 = undefined;
+    std.mem.writeInt(u256, &x_bytes, x_int, .big);
+    const x = try Secp256k1.Fe.fromBytes(x_bytes, .big);
+    const y = try Secp256k1.recoverY(x, recovery_id & 1 != 0);
+    const big_r = try Secp256k1.fromAffineCoordinates(.{ .x = x, .y = y });
+
+    // Any 32 bytes are a valid hash; they are reduced modulo the order
+    var wide: [48]u8 = [_]u8{0} ** 48;
+    wide[16..].* = hash;
+    const z = Scalar.fromBytes48(wide, .big);
+
+    const r_inv = r.invert();
+    const g_factor = z.mul(r_inv).neg();
+    const r_factor = s.mul(r_inv);
+    const q = try Secp256k1.mulDoubleBasePublic(Secp256k1.basePoint, g_factor.toBytes(.big), big_r, r_factor.toBytes(.big), .big);
+    const sec1 = q.toUncompressedSec1();
+    return sec1[1..].*;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+fn fromHex(comptime hex: []const u8) [hex.len / 2]u8 {
+    var bytes: [hex.len / 2]u8 = undefined;
+    _ = std.fmt.hexToBytes(&bytes, hex) catch unreachable;
+    return bytes;
+}
+
+// SHA-256("hello secp256k1") signed with a fixed key; checked against
+// solana_program::secp256k1_recover
+const test_hash = fromHex("56a6f81506dedd9d1b611a594e88f2331d6ecc1ef1218157d27fc69cd2fe2ff5");
+const test_signature = fromHex("17f53289eac961e5adc858d3ca50dab056ddca7a1a906c0815a0369312d1aa49" ++
+    "05c590bc535ef30fd290b07d4c8680c1df97f859f63aa62c2c865fb6adc3330e");
+const test_pubkey = fromHex("4c74e6993e770e7ee5fd923f06227fe201051f353b7c6a7da30034600e9e5611" ++
+    "8090aa0854dfef0c0e503a0241b4d8fbc14fdd2e68c81829cc818d99b0d65c8c");
+
+test "recover the signer of a known signature" {
+    const key = try secp256k1Recover(test_hash, 0, test_signature);
+    try std.testing.expectEqualSlices(u8, &test_pubkey, &key);
+
+    // The other parity gives a different, valid key
+    const other = try secp256k1Recover(test_hash, 1, test_signature);
+    try std.testing.expect(!std.mem.eql(u8, &test_pubkey, &other));
+}
+
+test "recovery ids above 3 are rejected" {
+    try std.testing.expectError(ProgramError.InvalidRecoveryId, secp256k1Recover(test_hash, 4, test_signature));
+    try std.testing.expectError(ProgramError.InvalidRecoveryId, secp256k1Recover(test_hash, 27, test_signature));
+}
+
+test "malformed signatures are rejected" {
+    // r above the group order
+    var overflow = test_signature;
+    @memset(overflow[0..32], 0xff);
+    try std.testing.expectError(ProgramError.InvalidSignature, secp256k1Recover(test_hash, 0, overflow));
+
+    // s of zero
+    var zero_s = test_signature;
+    @memset(zero_s[32..], 0);
+    try std.testing.expectError(ProgramError.InvalidSignature, secp256k1Recover(test_hash, 0, zero_s));
+}
@@ -43,7 +43,10 @@ pub extern "C" fn sol_log_(message: [*]const u8, len: u64) void;
 pub extern "C" fn sol_log_64_(p0: u64, p1: u64, p2: u64, p3: u64, p4: u64) void;
 pub extern "C" fn sol_log_pubkey(pubkey: *const u8) void;
 pub extern "C" fn sol_log_compute_units_() void;
-pub extern "C" fn sol_log_data(data: [*]const [*]const u8, len: u64) void;
+pub extern "C" fn sol_log_data(data: [*]const []const u8, len: u64) void;
+
+// Program termination
+pub extern "C" fn abort() noreturn;
 
 // Program address syscalls
 pub extern "C" fn sol_create_program_address(
@@ -61,12 +64,12 @@ pub extern "C" fn sol_try_find_program_address(
     bump_seed: *u8,
 ) u64;
 
-// SHA256 syscall
+// SHA256 syscall, hashing the concatenation of `vals_len` slices
 pub extern "C" fn sol_sha256(
-    vals: [*]const u8,
+    vals: [*]const []const u8,
     vals_len: u64,
     hash_result: *u8,
-) void;
+) u64;
 
 // Memory syscalls
 pub extern "C" fn sol_memcpy_(dst: *u8, src: *const u8, n: u64) void;
@@ -112,8 +115,8 @@ pub extern "C" fn sol_get_fees_sysvar(fees: *u8) u64;
 pub extern "C" fn sol_get_epoch_rewards_sysvar(rewards: *u8) u64;
 
 // Additional cryptographic syscalls
-pub extern "C" fn sol_keccak256(vals: [*]const u8, vals_len: u64, hash_result: *u8) u64;
-pub extern "C" fn sol_blake3(vals: [*]const u8, vals_len: u64, hash_result: *u8) u64;
+pub extern "C" fn sol_keccak256(vals: [*]const []const u8, vals_len: u64, hash_result: *u8) u64;
+pub extern "C" fn sol_blake3(vals: [*]const []const u8, vals_len: u64, hash_result: *u8) u64;
 pub extern "C" fn sol_secp256k1_recover(
     hash: *const u8,
     recovery_id: u64,
@@ -205,19 +208,24 @@ pub inline fn logComputeUnits() void {
     sol_log_compute_units_();
 }
 
+/// Compute the SHA256 hash of a single slice. See hash.zig for hashing
+/// several slices at once.
 pub inline fn sha256(vals: []const u8, hash_result: *[32]u8) void {
-    sol_sha256(vals.ptr, vals.len, @ptrCast(hash_result));
+    const slices = [_][]const u8{vals};
+    _ = sol_sha256(&slices, slices.len, @ptrCast(hash_result));
 }
 
-/// Compute Keccak256 hash
+/// Compute the Keccak256 hash of a single slice
 pub inline fn keccak256(vals: []const u8, hash_result: *[32]u8) !void {
-    const result = sol_keccak256(vals.ptr, vals.len, @ptrCast(hash_result));
+    const slices = [_][]const u8{vals};
+    const result = sol_keccak256(&slices, slices.len, @ptrCast(hash_result));
     if (result != SUCCESS) return error.Keccak256Failed;
 }
 
-/// Compute Blake3 hash
+/// Compute the Blake3 hash of a single slice
 pub inline fn blake3(vals: []const u8, hash_result: *[32]u8) !void {
-    const result = sol_blake3(vals.ptr, vals.len, @ptrCast(hash_result));
+    const slices = [_][]const u8{vals};
+    const result = sol_blake3(&slices, slices.len, @ptrCast(hash_result));
     if (result != SUCCESS) return error.Blake3Failed;
 }
 
@@ -227,22 +235,6 @@ pub inline fn poseidon(endianness: PoseidonEndianness, vals: []const u8, hash_re
     if (result != SUCCESS) return error.PoseidonFailed;
 }
 
-/// Recover secp256k1 public key
-pub inline fn secp256k1Recover(
-    hash: *const [32]u8,
-    recovery_id: u8,
-    signature: *const [64]u8,
-    pubkey_result: *[64]u8,
-) !void {
-    const result = sol_secp256k1_recover(
-        @ptrCast(hash),
-        recovery_id,
-        @ptrCast(signature),
-        @ptrCast(pubkey_result),
-    );
-    if (result != SUCCESS) return error.Secp256k1RecoverFailed;
-}
-
 /// Memory copy
 pub inline fn memcpy(dst: []u8, src: []const u8) void {
     std.debug.assert(dst.len >= src.len);
@@ -281,33 +273,15 @@ pub inline fn getStackHeight() u64 {
     return sol_get_stack_height();
 }
 
-/// Set return data
-pub inline fn setReturnData(data: []const u8) void {
-    sol_set_return_data(data.ptr, data.len);
-}
-
-/// Get return data
-pub inline fn getReturnData(data: []u8, program_id: *Pubkey) !usize {
-    const result = sol_get_return_data(data.ptr, data.len, program_id);
-    if (result == std.math.maxInt(u64)) {
-        return error.ReturnDataTooLarge;
-    }
-    return result;
-}
-
 /// Log 64-bit values
 pub inline fn log64(p0: u64, p1: u64, p2: u64, p3: u64, p4: u64) void {
     sol_log_64_(p0, p1, p2, p3, p4);
 }
 
-/// Log data
+/// Log data. The runtime reads the slices as {ptr, len} pairs, so they are
+/// passed as-is.
 pub inline fn logData(data_vec: []const []const u8) void {
-    var data_ptrs: [16][*]const u8 = undefined;
-    const len = @min(data_vec.len, data_ptrs.len);
-    for (data_vec[0..len], 0..) |data, i| {
-        data_ptrs[i] = data.ptr;
-    }
-    sol_log_data(@ptrCast(&data_ptrs), len);
+    sol_log_data(data_vec.ptr, data_vec.len);
 }
 
 /// Validate curve point
@@ -0,0 +1,153 @@
+/// System Program instruction builders
+///
+/// Each builder encodes the instruction the way the System Program decodes
+/// it: a u32 little-endian discriminant followed by the Borsh-encoded
+/// fields. `Instruction` only borrows its accounts and data, so a builder
+/// returns them by value in a `SystemInstruction`; keep that value alive
+/// and call `instruction()` on it for the CPI.
+///
+/// # Examples
+/// ```zig
+/// const create = system_program.createAccount(payer, new_account, lamports, space, program_id);
+/// try cpi.invoke(&create.instruction(), &.{ payer_info, new_account_info });
+/// ```
+const std = @import("std");
+const borsh = @import("borsh.zig");
+const pubkey_mod = @import("pubkey/pubkey.zig");
+const Pubkey = pubkey_mod.Pubkey;
+const instruction_mod = @import("instruction/instruction.zig");
+const Instruction = instruction_mod.Instruction;
+const AccountMeta = instruction_mod.AccountMeta;
+
+/// The System Program's ID
+pub const ID = pubkey_mod.SYSTEM_PROGRAM_ID;
+
+/// Discriminants of the System Program's `SystemInstruction` enum
+pub const Discriminant = enum(u32) {
+    create_account = 0,
+    assign = 1,
+    transfer = 2,
+    create_account_with_seed = 3,
+    advance_nonce_account = 4,
+    withdraw_nonce_account = 5,
+    initialize_nonce_account = 6,
+    authorize_nonce_account = 7,
+    allocate = 8,
+    allocate_with_seed = 9,
+    assign_with_seed = 10,
+    transfer_with_seed = 11,
+    upgrade_nonce_account = 12,
+};
+
+/// Accounts and data of a System Program instruction, owned by value
+pub fn SystemInstruction(comptime num_accounts: usize, comptime data_len: usize) type {
+    return struct {
+        const Self = @This();
+
+        accounts: [num_accounts]AccountMeta,
+        data: [data_len]u8,
+
+        /// An instruction borrowing `self`'s accounts and data
+        pub fn instruction(self: *const Self) Instruction {
+            return Instruction.init(ID, &self.accounts, &self.data);
+        }
+    };
+}
+
+/// Create `to` with `space` bytes owned by `owner`, funded with `lamports`
+/// from `from`. Both accounts must sign.
+pub fn createAccount(from: Pubkey, to: Pubkey, lamports: u64, space: u64, owner: Pubkey) SystemInstruction(2, 52) {
+    return .{
+        .accounts = .{
+            AccountMeta.writable(from, true),
+            AccountMeta.writable(to, true),
+        },
+        .data = encode(52, .create_account, .{ lamports, space, owner }),
+    };
+}
+
+/// Move `lamports` from `from`, which must sign and be owned by the System
+/// Program, to `to`
+pub fn transfer(from: Pubkey, to: Pubkey, lamports: u64) SystemInstruction(2, 12) {
+    return .{
+        .accounts = .{
+            AccountMeta.writable(from, true),
+            AccountMeta.writable(to, false),
+        },
+        .data = encode(12, .transfer, .{lamports}),
+    };
+}
+
+/// Give `account`, which must sign, `space` bytes of zeroed data
+pub fn allocate(account: Pubkey, space: u64) SystemInstruction(1, 12) {
+    return .{
+        .accounts = .{AccountMeta.writable(account, true)},
+        .data = encode(12, .allocate, .{space}),
+    };
+}
+
+/// Hand `account`, which must sign, over to `owner`
+pub fn assign(account: Pubkey, owner: Pubkey) SystemInstruction(1, 36) {
+    return .{
+        .accounts = .{AccountMeta.writable(account, true)},
+        .data = encode(36, .assign, .{owner}),
+    };
+}
+
+/// The discriminant followed by the Borsh encoding of `fields`, which must
+/// come to exactly `len` bytes
+fn encode(comptime len: usize, discriminant: Discriminant, fields: anytype) [len]u8 {
+    var data: [len]u8 = undefined;
+    var stream = std.io.fixedBufferStream(&data);
+    const writer = stream.writer();
+    writer.writeInt(u32, @intFromEnum(discriminant), .little) catch unreachable;
+    borsh.serialize(writer, fields) catch unreachable;
+    std.debug.assert(stream.pos == len);
+    return data;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const from_key = Pubkey.fromBytes([_]u8{1} ** 32);
+const to_key = Pubkey.fromBytes([_]u8{2} ** 32);
+const owner_key = Pubkey.fromBytes([_]u8{3} ** 32);
+
+test "transfer data is the discriminant 2 then lamports" {
+    const lamports: u64 = 0x0102030405060708;
+    const built = transfer(from_key, to_key, lamports);
+    const ix = built.instruction();
+
+    const expected = [_]u8{ 2, 0, 0, 0, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01 };
+    try std.testing.expectEqualSlices(u8, &expected, ix.data);
+    try std.testing.expect(ix.program_id.equals(&ID));
+    try std.testing.expect(ix.accounts[0].is_signer and ix.accounts[0].is_writable);
+    try std.testing.expect(!ix.accounts[1].is_signer and ix.accounts[1].is_writable);
+}
+
+test "createAccount encodes lamports, space and owner" {
+    const built = createAccount(from_key, to_key, 1_000_000, 165, owner_key);
+    const ix = built.instruction();
+
+    try std.testing.expectEqual(@as(usize, 52), ix.data.len);
+    try std.testing.expectEqual(@as(u32, 0), std.mem.readInt(u32, ix.data[0..4], .little));
+    try std.testing.expectEqual(@as(u64, 1_000_000), std.mem.readInt(u64, ix.data[4..12], .little));
+    try std.testing.expectEqual(@as(u64, 165), std.mem.readInt(u64, ix.data[12..20], .little));
+    try std.testing.expectEqualSlices(u8, &owner_key.bytes, ix.data[20..52]);
+    for (ix.accounts) |meta| {
+        try std.testing.expect(meta.is_signer and meta.is_writable);
+    }
+    try std.testing.expect(ix.accounts[1].pubkey.equals(&to_key));
+}
+
+test "allocate and assign" {
+    const allocated = allocate(to_key, 4096);
+    try std.testing.expectEqualSlices(u8, &[_]u8{ 8, 0, 0, 0, 0x00, 0x10, 0, 0, 0, 0, 0, 0 }, &allocated.data);
+    try std.testing.expect(allocated.accounts[0].is_signer and allocated.accounts[0].is_writable);
+
+    const assigned = assign(to_key, owner_key);
+    try std.testing.expectEqualSlices(u8, &[_]u8{ 1, 0, 0, 0 }, assigned.data[0..4]);
+    try std.testing.expectEqualSlices(u8, &owner_key.bytes, assigned.data[4..]);
+    try std.testing.expect(assigned.instruction().accounts[0].pubkey.equals(&to_key));
+}
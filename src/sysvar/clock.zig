@@ -0,0 +1,95 @@
+/// Clock sysvar
+///
+/// The current slot, epoch and the cluster's estimate of wall-clock time,
+/// equivalent to Rust's solana_clock::Clock
+const std = @import("std");
+const syscalls = @import("../syscalls.zig");
+const bpf = @import("../bpf.zig");
+const ProgramError = @import("../program_error.zig").ProgramError;
+const SUCCESS = @import("../program_error.zig").SUCCESS;
+
+pub const Clock = struct {
+    /// The current slot
+    slot: u64,
+    /// Unix timestamp of the first slot in this epoch
+    epoch_start_timestamp: i64,
+    /// The current epoch
+    epoch: u64,
+    /// The future epoch for which the leader schedule has been generated
+    leader_schedule_epoch: u64,
+    /// Estimated current unix timestamp
+    unix_timestamp: i64,
+
+    /// Size of the runtime's bincode serialization of Clock
+    pub const LEN = 40;
+
+    /// Decode the runtime's little-endian Clock layout
+    pub fn fromBytes(bytes: *const [LEN]u8) Clock {
+        return .{
+            .slot = std.mem.readInt(u64, bytes[0..8], .little),
+            .epoch_start_timestamp = std.mem.readInt(i64, bytes[8..16], .little),
+            .epoch = std.mem.readInt(u64, bytes[16..24], .little),
+            .leader_schedule_epoch = std.mem.readInt(u64, bytes[24..32], .little),
+            .unix_timestamp = std.mem.readInt(i64, bytes[32..40], .little),
+        };
+    }
+
+    /// Read the Clock sysvar with the sol_get_clock_sysvar syscall
+    pub fn get() ProgramError!Clock {
+        var buffer: [LEN]u8 = undefined;
+        if (getClockSysvar(&buffer) != SUCCESS) {
+            return ProgramError.UnsupportedSysvar;
+        }
+        return fromBytes(&buffer);
+    }
+};
+
+/// Module-level shorthand for `Clock.get`
+pub const get = Clock.get;
+
+/// Call sol_get_clock_sysvar, or `test_syscall` when not running on Solana
+fn getClockSysvar(buffer: *[Clock.LEN]u8) u64 {
+    if (comptime !bpf.is_solana) {
+        return test_syscall(buffer);
+    }
+    return syscalls.sol_get_clock_sysvar(@ptrCast(buffer));
+}
+
+/// Stands in for sol_get_clock_sysvar outside the VM. Tests swap it out to
+/// supply the bytes the runtime would write.
+var test_syscall: *const fn (buffer: *[Clock.LEN]u8) u64 = &mockClockUnavailable;
+
+fn mockClockUnavailable(buffer: *[Clock.LEN]u8) u64 {
+    _ = buffer;
+    return 1; // There is no Clock outside the runtime
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+/// Syscall stub writing a Clock with a distinct value in every field
+fn knownClockStub(buffer: *[Clock.LEN]u8) u64 {
+    std.mem.writeInt(u64, buffer[0..8], 250_000_123, .little);
+    std.mem.writeInt(i64, buffer[8..16], 1_700_000_000, .little);
+    std.mem.writeInt(u64, buffer[16..24], 578, .little);
+    std.mem.writeInt(u64, buffer[24..32], 579, .little);
+    std.mem.writeInt(i64, buffer[32..40], -1, .little);
+    return SUCCESS;
+}
+
+test "get decodes every Clock field" {
+    test_syscall = &knownClockStub;
+    defer test_syscall = &mockClockUnavailable;
+
+    const clock = try Clock.get();
+    try std.testing.expectEqual(@as(u64, 250_000_123), clock.slot);
+    try std.testing.expectEqual(@as(i64, 1_700_000_000), clock.epoch_start_timestamp);
+    try std.testing.expectEqual(@as(u64, 578), clock.epoch);
+    try std.testing.expectEqual(@as(u64, 579), clock.leader_schedule_epoch);
+    try std.testing.expectEqual(@as(i64, -1), clock.unix_timestamp);
+}
+
+test "get reports a failed syscall" {
+    try std.testing.expectError(ProgramError.UnsupportedSysvar, get());
+}
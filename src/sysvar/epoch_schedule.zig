@@ -0,0 +1,233 @@
+/// EpochSchedule sysvar
+///
+/// How slots are grouped into epochs, equivalent to Rust's
+/// solana_epoch_schedule::EpochSchedule. With warmup the first epochs are
+/// shorter, starting at `MINIMUM_SLOTS_PER_EPOCH` slots and doubling each
+/// epoch until they reach `slots_per_epoch` at `first_normal_epoch`.
+const std = @import("std");
+const syscalls = @import("../syscalls.zig");
+const bpf = @import("../bpf.zig");
+const ProgramError = @import("../program_error.zig").ProgramError;
+const SUCCESS = @import("../program_error.zig").SUCCESS;
+
+/// Length of the first epoch when warmup is enabled
+pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+/// Slots per epoch on mainnet
+pub const DEFAULT_SLOTS_PER_EPOCH: u64 = 432_000;
+
+pub const EpochSchedule = struct {
+    /// Slots in each epoch once warmup is over
+    slots_per_epoch: u64,
+    /// How many slots before an epoch starts its leader schedule is
+    /// calculated
+    leader_schedule_slot_offset: u64,
+    /// Whether the first epochs are shortened
+    warmup: bool,
+    /// First epoch of `slots_per_epoch` slots
+    first_normal_epoch: u64,
+    /// First slot of `first_normal_epoch`
+    first_normal_slot: u64,
+
+    /// Size of the runtime's bincode serialization of EpochSchedule, as
+    /// stored in the sysvar account
+    pub const LEN = 33;
+
+    /// Size of the C layout sol_get_epoch_schedule_sysvar writes: the same
+    /// fields with `warmup` padded to 8 bytes
+    pub const SYSCALL_LEN = 40;
+
+    /// Mainnet schedule, Rust's `EpochSchedule::default()`
+    pub const DEFAULT = EpochSchedule.init(DEFAULT_SLOTS_PER_EPOCH, DEFAULT_SLOTS_PER_EPOCH, true);
+
+    /// A schedule of `slots_per_epoch`, with the warmup epochs derived the
+    /// way Rust's `EpochSchedule::custom` does
+    pub fn init(slots_per_epoch: u64, leader_schedule_slot_offset: u64, warmup: bool) EpochSchedule {
+        std.debug.assert(slots_per_epoch >= MINIMUM_SLOTS_PER_EPOCH);
+        var first_normal_epoch: u64 = 0;
+        var first_normal_slot: u64 = 0;
+        if (warmup) {
+            const next_power_of_two = std.math.ceilPowerOfTwoAssert(u64, slots_per_epoch);
+            first_normal_epoch = @ctz(next_power_of_two) - @ctz(MINIMUM_SLOTS_PER_EPOCH);
+            first_normal_slot = (next_power_of_two - MINIMUM_SLOTS_PER_EPOCH);
+        }
+        return .{
+            .slots_per_epoch = slots_per_epoch,
+            .leader_schedule_slot_offset = leader_schedule_slot_offset,
+            .warmup = warmup,
+            .first_normal_epoch = first_normal_epoch,
+            .first_normal_slot = first_normal_slot,
+        };
+    }
+
+    /// Decode the sysvar account's packed little-endian layout
+    pub fn fromBytes(bytes: *const [LEN]u8) EpochSchedule {
+        return .{
+            .slots_per_epoch = std.mem.readInt(u64, bytes[0..8], .little),
+            .leader_schedule_slot_offset = std.mem.readInt(u64, bytes[8..16], .little),
+            .warmup = bytes[16] != 0,
+            .first_normal_epoch = std.mem.readInt(u64, bytes[17..25], .little),
+            .first_normal_slot = std.mem.readInt(u64, bytes[25..33], .little),
+        };
+    }
+
+    /// Read the EpochSchedule sysvar with the sol_get_epoch_schedule_sysvar
+    /// syscall
+    pub fn get() ProgramError!EpochSchedule {
+        var buffer: [SYSCALL_LEN]u8 = undefined;
+        if (getEpochScheduleSysvar(&buffer) != SUCCESS) {
+            return ProgramError.UnsupportedSysvar;
+        }
+        return .{
+            .slots_per_epoch = std.mem.readInt(u64, buffer[0..8], .little),
+            .leader_schedule_slot_offset = std.mem.readInt(u64, buffer[8..16], .little),
+            .warmup = buffer[16] != 0,
+            .first_normal_epoch = std.mem.readInt(u64, buffer[24..32], .little),
+            .first_normal_slot = std.mem.readInt(u64, buffer[32..40], .little),
+        };
+    }
+
+    /// Number of slots in `epoch`
+    pub fn getSlotsInEpoch(self: EpochSchedule, epoch: u64) u64 {
+        if (epoch < self.first_normal_epoch) {
+            return std.math.shl(u64, 1, epoch + @ctz(MINIMUM_SLOTS_PER_EPOCH));
+        }
+        return self.slots_per_epoch;
+    }
+
+    /// The epoch `slot` belongs to
+    pub fn getEpoch(self: EpochSchedule, slot: u64) u64 {
+        return self.getEpochAndSlotIndex(slot).epoch;
+    }
+
+    /// The epoch `slot` belongs to and its index within that epoch
+    pub fn getEpochAndSlotIndex(self: EpochSchedule, slot: u64) struct { epoch: u64, slot_index: u64 } {
+        if (slot < self.first_normal_slot) {
+            // Warmup epoch n spans [MIN * (2^n - 1), MIN * (2^(n+1) - 1))
+            const next_power_of_two = std.math.ceilPowerOfTwoAssert(u64, slot + MINIMUM_SLOTS_PER_EPOCH + 1);
+            const epoch: u64 = @ctz(next_power_of_two) - @ctz(MINIMUM_SLOTS_PER_EPOCH) - 1;
+            const epoch_len = self.getSlotsInEpoch(epoch);
+            return .{ .epoch = epoch, .slot_index = slot - (epoch_len - MINIMUM_SLOTS_PER_EPOCH) };
+        }
+
+        const normal_slot_index = slot - self.first_normal_slot;
+        return .{
+            .epoch = self.first_normal_epoch + normal_slot_index / self.slots_per_epoch,
+            .slot_index = normal_slot_index % self.slots_per_epoch,
+        };
+    }
+
+    /// The first slot of `epoch`
+    pub fn getFirstSlotInEpoch(self: EpochSchedule, epoch: u64) u64 {
+        if (epoch <= self.first_normal_epoch) {
+            return (std.math.shl(u64, 1, epoch) - 1) * MINIMUM_SLOTS_PER_EPOCH;
+        }
+        return (epoch - self.first_normal_epoch) * self.slots_per_epoch + self.first_normal_slot;
+    }
+
+    /// The last slot of `epoch`
+    pub fn getLastSlotInEpoch(self: EpochSchedule, epoch: u64) u64 {
+        return self.getFirstSlotInEpoch(epoch) + self.getSlotsInEpoch(epoch) - 1;
+    }
+};
+
+/// Module-level shorthand for `EpochSchedule.get`
+pub const get = EpochSchedule.get;
+
+/// Call sol_get_epoch_schedule_sysvar, or `test_syscall` when not running
+/// on Solana
+fn getEpochScheduleSysvar(buffer: *[EpochSchedule.SYSCALL_LEN]u8) u64 {
+    if (comptime !bpf.is_solana) {
+        return test_syscall(buffer);
+    }
+    return syscalls.sol_get_epoch_schedule_sysvar(@ptrCast(buffer));
+}
+
+/// Stands in for sol_get_epoch_schedule_sysvar outside the VM. Tests swap it
+/// out to supply the bytes the runtime would write.
+var test_syscall: *const fn (buffer: *[EpochSchedule.SYSCALL_LEN]u8) u64 = &mockEpochScheduleUnavailable;
+
+fn mockEpochScheduleUnavailable(buffer: *[EpochSchedule.SYSCALL_LEN]u8) u64 {
+    _ = buffer;
+    return 1; // There is no EpochSchedule outside the runtime
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+test "default schedule matches mainnet" {
+    const schedule = EpochSchedule.DEFAULT;
+    try std.testing.expectEqual(@as(u64, 14), schedule.first_normal_epoch);
+    try std.testing.expectEqual(@as(u64, 524_256), schedule.first_normal_slot);
+}
+
+test "warmup epochs double in length" {
+    const schedule = EpochSchedule.DEFAULT;
+    try std.testing.expectEqual(@as(u64, 32), schedule.getSlotsInEpoch(0));
+    try std.testing.expectEqual(@as(u64, 64), schedule.getSlotsInEpoch(1));
+    try std.testing.expectEqual(@as(u64, 262_144), schedule.getSlotsInEpoch(13));
+    try std.testing.expectEqual(@as(u64, 432_000), schedule.getSlotsInEpoch(14));
+    try std.testing.expectEqual(@as(u64, 432_000), schedule.getSlotsInEpoch(500));
+
+    try std.testing.expectEqual(@as(u64, 0), schedule.getFirstSlotInEpoch(0));
+    try std.testing.expectEqual(@as(u64, 32), schedule.getFirstSlotInEpoch(1));
+    try std.testing.expectEqual(@as(u64, 96), schedule.getFirstSlotInEpoch(2));
+    try std.testing.expectEqual(@as(u64, 524_256), schedule.getFirstSlotInEpoch(14));
+    try std.testing.expectEqual(@as(u64, 956_256), schedule.getFirstSlotInEpoch(15));
+    try std.testing.expectEqual(@as(u64, 524_255), schedule.getLastSlotInEpoch(13));
+}
+
+test "getEpoch across warmup and normal epochs" {
+    const schedule = EpochSchedule.DEFAULT;
+    const cases = [_]struct { slot: u64, epoch: u64, slot_index: u64 }{
+        .{ .slot = 0, .epoch = 0, .slot_index = 0 },
+        .{ .slot = 31, .epoch = 0, .slot_index = 31 },
+        .{ .slot = 32, .epoch = 1, .slot_index = 0 },
+        .{ .slot = 95, .epoch = 1, .slot_index = 63 },
+        .{ .slot = 96, .epoch = 2, .slot_index = 0 },
+        .{ .slot = 524_255, .epoch = 13, .slot_index = 262_143 },
+        .{ .slot = 524_256, .epoch = 14, .slot_index = 0 },
+        .{ .slot = 956_255, .epoch = 14, .slot_index = 431_999 },
+        .{ .slot = 956_256, .epoch = 15, .slot_index = 0 },
+        .{ .slot = 250_000_123, .epoch = 591, .slot_index = 211_867 },
+    };
+    for (cases) |case| {
+        const result = schedule.getEpochAndSlotIndex(case.slot);
+        try std.testing.expectEqual(case.epoch, result.epoch);
+        try std.testing.expectEqual(case.slot_index, result.slot_index);
+        try std.testing.expectEqual(case.epoch, schedule.getEpoch(case.slot));
+        try std.testing.expectEqual(case.slot - case.slot_index, schedule.getFirstSlotInEpoch(case.epoch));
+    }
+}
+
+test "without warmup every epoch is full length" {
+    const schedule = EpochSchedule.init(DEFAULT_SLOTS_PER_EPOCH, DEFAULT_SLOTS_PER_EPOCH, false);
+    try std.testing.expectEqual(@as(u64, 0), schedule.first_normal_epoch);
+    try std.testing.expectEqual(@as(u64, 432_000), schedule.getSlotsInEpoch(0));
+    try std.testing.expectEqual(@as(u64, 1), schedule.getEpoch(432_000));
+    try std.testing.expectEqual(@as(u64, 864_000), schedule.getFirstSlotInEpoch(2));
+}
+
+/// Syscall stub writing the mainnet schedule in the padded C layout
+fn mainnetEpochScheduleStub(buffer: *[EpochSchedule.SYSCALL_LEN]u8) u64 {
+    @memset(buffer, 0);
+    std.mem.writeInt(u64, buffer[0..8], 432_000, .little);
+    std.mem.writeInt(u64, buffer[8..16], 432_000, .little);
+    buffer[16] = 1;
+    std.mem.writeInt(u64, buffer[24..32], 14, .little);
+    std.mem.writeInt(u64, buffer[32..40], 524_256, .little);
+    return SUCCESS;
+}
+
+test "get decodes every EpochSchedule field" {
+    test_syscall = &mainnetEpochScheduleStub;
+    defer test_syscall = &mockEpochScheduleUnavailable;
+
+    const schedule = try EpochSchedule.get();
+    try std.testing.expectEqual(EpochSchedule.DEFAULT, schedule);
+}
+
+test "get reports a failed syscall" {
+    try std.testing.expectError(ProgramError.UnsupportedSysvar, get());
+}
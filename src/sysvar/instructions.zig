@@ -0,0 +1,232 @@
+/// Instructions sysvar
+///
+/// Every instruction of the running transaction, serialized by the runtime
+/// into the account `Sysvar1nstructions1111111111111111111111111`, like
+/// Rust's solana_instructions_sysvar. Unlike Clock and Rent there is no
+/// syscall; the account must be passed to the program.
+///
+/// Layout, all integers little-endian:
+/// ```text
+/// num_instructions u16 | offset u16 * num_instructions
+///   | instruction * num_instructions | current_index u16
+/// instruction = num_accounts u16 | (flags u8, pubkey [32]) * num_accounts
+///   | program_id [32] | data_len u16 | data
+/// ```
+///
+/// # Examples
+/// ```zig
+/// // Was `other_program` invoked earlier in this transaction?
+/// const ixs = try sysvar.instructions.load(instructions_account);
+/// var i: u16 = 0;
+/// while (i < ixs.currentIndex()) : (i += 1) {
+///     const ix = try ixs.getInstructionAtIndex(i);
+///     if (ix.program_id.equals(&other_program)) break;
+/// }
+/// ```
+const std = @import("std");
+const pubkey_mod = @import("../pubkey/pubkey.zig");
+const Pubkey = pubkey_mod.Pubkey;
+const AccountInfo = @import("../account_info/account_info.zig").AccountInfo;
+const AccountMeta = @import("../instruction/instruction.zig").AccountMeta;
+const ProgramError = @import("../program_error.zig").ProgramError;
+
+/// The Instructions sysvar's ID
+pub const ID = pubkey_mod.INSTRUCTIONS_SYSVAR_ID;
+
+const IS_SIGNER_BIT: u8 = 1 << 0;
+const IS_WRITABLE_BIT: u8 = 1 << 1;
+
+/// Size of one serialized account: flags then pubkey
+const ACCOUNT_META_LEN = 1 + 32;
+
+/// One instruction of the transaction, borrowed from the sysvar account.
+/// The accounts stay packed the way the runtime wrote them; `accountMeta`
+/// decodes one at a time.
+pub const Instruction = struct {
+    program_id: Pubkey,
+    /// `numAccounts()` entries of `ACCOUNT_META_LEN` bytes
+    accounts: []const u8,
+    data: []const u8,
+
+    pub fn numAccounts(self: *const Instruction) usize {
+        return self.accounts.len / ACCOUNT_META_LEN;
+    }
+
+    /// The `index`th account of the instruction
+    pub fn accountMeta(self: *const Instruction, index: usize) ProgramError!AccountMeta {
+        if (index >= self.numAccounts()) {
+            return ProgramError.InvalidArgument;
+        }
+        const entry = self.accounts[index * ACCOUNT_META_LEN ..][0..ACCOUNT_META_LEN];
+        return .{
+            .pubkey = Pubkey.fromBytes(entry[1..].*),
+            .is_signer = entry[0] & IS_SIGNER_BIT != 0,
+            .is_writable = entry[0] & IS_WRITABLE_BIT != 0,
+        };
+    }
+};
+
+/// The Instructions sysvar account's data
+pub const Instructions = struct {
+    data: []const u8,
+
+    /// Number of instructions in the transaction
+    pub fn len(self: *const Instructions) u16 {
+        return readU16(self.data, 0) catch unreachable; // checked by load
+    }
+
+    /// Index of the instruction being executed
+    pub fn currentIndex(self: *const Instructions) u16 {
+        return readU16(self.data, self.data.len - 2) catch unreachable; // checked by load
+    }
+
+    /// Decode the `index`th instruction of the transaction. Indexes past the
+    /// last instruction are an `InvalidArgument`, a malformed account
+    /// `InvalidAccountData`.
+    pub fn getInstructionAtIndex(self: *const Instructions, index: u16) ProgramError!Instruction {
+        if (index >= self.len()) {
+            return ProgramError.InvalidArgument;
+        }
+
+        var offset: usize = try readU16(self.data, 2 + @as(usize, index) * 2);
+        const num_accounts = try readU16(self.data, offset);
+        offset += 2;
+
+        const accounts = try take(self.data, &offset, @as(usize, num_accounts) * ACCOUNT_META_LEN);
+        const program_id = try take(self.data, &offset, 32);
+        const data_len = try readU16(self.data, offset);
+        offset += 2;
+        const data = try take(self.data, &offset, data_len);
+
+        return .{
+            .program_id = Pubkey.fromBytes(program_id[0..32].*),
+            .accounts = accounts,
+            .data = data,
+        };
+    }
+};
+
+/// Borrow the Instructions sysvar from `account`, which must be the sysvar
+/// account itself
+pub fn load(account: *const AccountInfo) ProgramError!Instructions {
+    if (!account.key().equals(&ID)) {
+        return ProgramError.InvalidArgument;
+    }
+
+    // At least the instruction count and the current index
+    const data = account.getData();
+    if (data.len < 4) {
+        return ProgramError.InvalidAccountData;
+    }
+    return .{ .data = data };
+}
+
+fn readU16(data: []const u8, offset: usize) ProgramError!u16 {
+    if (offset + 2 > data.len) {
+        return ProgramError.InvalidAccountData;
+    }
+    return std.mem.readInt(u16, data[offset..][0..2], .little);
+}
+
+/// The `n` bytes at `offset.*`, advancing it past them
+fn take(data: []const u8, offset: *usize, n: usize) ProgramError![]const u8 {
+    if (offset.* + n > data.len) {
+        return ProgramError.InvalidAccountData;
+    }
+    const bytes = data[offset.*..][0..n];
+    offset.* += n;
+    return bytes;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const createTestAccountInfo = @import("../account_info/account_info.zig").createTestAccountInfo;
+
+const compute_budget_id = Pubkey.fromBytes([_]u8{0xCB} ** 32);
+const test_program_id = Pubkey.fromBytes([_]u8{0xEE} ** 32);
+const payer = Pubkey.fromBytes([_]u8{1} ** 32);
+const vault = Pubkey.fromBytes([_]u8{2} ** 32);
+
+/// A transaction of two instructions, executing the second: a compute
+/// budget request without accounts, then a call to `test_program_id` with
+/// a signing payer and a writable vault
+fn serializeTestTransaction(buffer: []u8) []u8 {
+    var stream = std.io.fixedBufferStream(buffer);
+    const writer = stream.writer();
+    const first_len = 2 + 32 + 2 + 5;
+    writer.writeInt(u16, 2, .little) catch unreachable;
+    writer.writeInt(u16, 6, .little) catch unreachable;
+    writer.writeInt(u16, 6 + first_len, .little) catch unreachable;
+
+    writer.writeInt(u16, 0, .little) catch unreachable;
+    writer.writeAll(&compute_budget_id.bytes) catch unreachable;
+    writer.writeInt(u16, 5, .little) catch unreachable;
+    writer.writeAll(&.{ 2, 0x40, 0x0D, 0x03, 0x00 }) catch unreachable;
+
+    writer.writeInt(u16, 2, .little) catch unreachable;
+    writer.writeByte(IS_SIGNER_BIT | IS_WRITABLE_BIT) catch unreachable;
+    writer.writeAll(&payer.bytes) catch unreachable;
+    writer.writeByte(IS_WRITABLE_BIT) catch unreachable;
+    writer.writeAll(&vault.bytes) catch unreachable;
+    writer.writeAll(&test_program_id.bytes) catch unreachable;
+    writer.writeInt(u16, 1, .little) catch unreachable;
+    writer.writeByte(7) catch unreachable;
+
+    writer.writeInt(u16, 1, .little) catch unreachable;
+    return stream.getWritten();
+}
+
+test "instructions sysvar exposes every instruction and the current index" {
+    const allocator = std.testing.allocator;
+    var buffer: [256]u8 = undefined;
+    const data = serializeTestTransaction(&buffer);
+    const info = try createTestAccountInfo(allocator, &ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, data, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    const ixs = try load(&info);
+    try std.testing.expectEqual(@as(u16, 2), ixs.len());
+    try std.testing.expectEqual(@as(u16, 1), ixs.currentIndex());
+
+    const budget = try ixs.getInstructionAtIndex(0);
+    try std.testing.expect(budget.program_id.equals(&compute_budget_id));
+    try std.testing.expectEqual(@as(usize, 0), budget.numAccounts());
+    try std.testing.expectEqualSlices(u8, &.{ 2, 0x40, 0x0D, 0x03, 0x00 }, budget.data);
+
+    const current = try ixs.getInstructionAtIndex(ixs.currentIndex());
+    try std.testing.expect(current.program_id.equals(&test_program_id));
+    try std.testing.expectEqual(@as(usize, 2), current.numAccounts());
+    const first = try current.accountMeta(0);
+    try std.testing.expect(first.pubkey.equals(&payer) and first.is_signer and first.is_writable);
+    const second = try current.accountMeta(1);
+    try std.testing.expect(second.pubkey.equals(&vault) and !second.is_signer and second.is_writable);
+    try std.testing.expectError(ProgramError.InvalidArgument, current.accountMeta(2));
+    try std.testing.expectEqualSlices(u8, &.{7}, current.data);
+
+    try std.testing.expectError(ProgramError.InvalidArgument, ixs.getInstructionAtIndex(2));
+}
+
+test "load rejects accounts other than the instructions sysvar" {
+    const allocator = std.testing.allocator;
+    var buffer: [256]u8 = undefined;
+    const data = serializeTestTransaction(&buffer);
+    const info = try createTestAccountInfo(allocator, &payer, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, data, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    try std.testing.expectError(ProgramError.InvalidArgument, load(&info));
+}
+
+test "truncated instructions are invalid account data" {
+    const allocator = std.testing.allocator;
+    var buffer: [256]u8 = undefined;
+    const data = serializeTestTransaction(&buffer);
+    // Cut off in the middle of the second instruction's program id
+    const truncated = data[0 .. 6 + 41 + 2 + 2 * 33 + 10];
+    const info = try createTestAccountInfo(allocator, &ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, truncated, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    const ixs = try load(&info);
+    _ = try ixs.getInstructionAtIndex(0);
+    try std.testing.expectError(ProgramError.InvalidAccountData, ixs.getInstructionAtIndex(1));
+}
@@ -0,0 +1,107 @@
+/// Rent sysvar
+///
+/// The cluster's rent parameters, equivalent to Rust's solana_rent::Rent
+const std = @import("std");
+const syscalls = @import("../syscalls.zig");
+const bpf = @import("../bpf.zig");
+const ProgramError = @import("../program_error.zig").ProgramError;
+const SUCCESS = @import("../program_error.zig").SUCCESS;
+
+/// Bytes the runtime charges rent for on top of an account's data
+pub const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+
+pub const Rent = struct {
+    /// Rental rate in lamports per byte-year
+    lamports_per_byte_year: u64,
+    /// Years of rent an account must hold to be exempt
+    exemption_threshold: f64,
+    /// Percentage of collected rent that is burned
+    burn_percent: u8,
+
+    /// Size of the runtime's bincode serialization of Rent
+    pub const LEN = 17;
+
+    /// Mainnet parameters, Rust's `Rent::default()`
+    pub const DEFAULT = Rent{
+        .lamports_per_byte_year = 3480,
+        .exemption_threshold = 2.0,
+        .burn_percent = 50,
+    };
+
+    /// Decode the runtime's little-endian Rent layout
+    pub fn fromBytes(bytes: *const [LEN]u8) Rent {
+        return .{
+            .lamports_per_byte_year = std.mem.readInt(u64, bytes[0..8], .little),
+            .exemption_threshold = @bitCast(std.mem.readInt(u64, bytes[8..16], .little)),
+            .burn_percent = bytes[16],
+        };
+    }
+
+    /// Read the Rent sysvar with the sol_get_rent_sysvar syscall
+    pub fn get() ProgramError!Rent {
+        var buffer: [LEN]u8 = undefined;
+        if (getRentSysvar(&buffer) != SUCCESS) {
+            return ProgramError.UnsupportedSysvar;
+        }
+        return fromBytes(&buffer);
+    }
+
+    /// Lamports an account with `data_len` bytes of data needs to be rent
+    /// exempt, computed the same way as Rust's `Rent::minimum_balance`
+    pub fn minimumBalance(self: Rent, data_len: usize) u64 {
+        const bytes = ACCOUNT_STORAGE_OVERHEAD + @as(u64, data_len);
+        const per_year: f64 = @floatFromInt(bytes * self.lamports_per_byte_year);
+        return @intFromFloat(per_year * self.exemption_threshold);
+    }
+};
+
+/// Module-level shorthand for `Rent.get`
+pub const get = Rent.get;
+
+/// Call sol_get_rent_sysvar, or `test_syscall` when not running on Solana
+fn getRentSysvar(buffer: *[Rent.LEN]u8) u64 {
+    if (comptime !bpf.is_solana) {
+        return test_syscall(buffer);
+    }
+    return syscalls.sol_get_rent_sysvar(@ptrCast(buffer));
+}
+
+/// Stands in for sol_get_rent_sysvar outside the VM. Tests swap it out to
+/// supply the bytes the runtime would write.
+var test_syscall: *const fn (buffer: *[Rent.LEN]u8) u64 = &mockRentUnavailable;
+
+fn mockRentUnavailable(buffer: *[Rent.LEN]u8) u64 {
+    _ = buffer;
+    return 1; // There is no Rent outside the runtime
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+test "minimumBalance for a token account" {
+    // 165-byte SPL token account on mainnet
+    try std.testing.expectEqual(@as(u64, 2_039_280), Rent.DEFAULT.minimumBalance(165));
+    // An empty account still pays for the storage overhead
+    try std.testing.expectEqual(@as(u64, 890_880), Rent.DEFAULT.minimumBalance(0));
+}
+
+/// Syscall stub writing the mainnet Rent parameters
+fn mainnetRentStub(buffer: *[Rent.LEN]u8) u64 {
+    std.mem.writeInt(u64, buffer[0..8], 3480, .little);
+    std.mem.writeInt(u64, buffer[8..16], @bitCast(@as(f64, 2.0)), .little);
+    buffer[16] = 50;
+    return SUCCESS;
+}
+
+test "get decodes every Rent field" {
+    test_syscall = &mainnetRentStub;
+    defer test_syscall = &mockRentUnavailable;
+
+    const rent = try Rent.get();
+    try std.testing.expectEqual(Rent.DEFAULT, rent);
+}
+
+test "get reports a failed syscall" {
+    try std.testing.expectError(ProgramError.UnsupportedSysvar, get());
+}
@@ -0,0 +1,167 @@
+/// SlotHashes sysvar
+///
+/// The hashes of the most recent slots, like Rust's
+/// solana_slot_hashes::SlotHashes, newest first. There is no syscall for
+/// it, so the account must be passed to the program. At up to 512 entries
+/// it is large, so lookups read the account data in place instead of
+/// copying it out.
+///
+/// Layout, all integers little-endian:
+/// ```text
+/// num_entries u64 | (slot u64, hash [32]) * num_entries
+/// ```
+///
+/// # Examples
+/// ```zig
+/// const slot_hashes = try sysvar.slot_hashes.load(slot_hashes_account);
+/// const hash = slot_hashes.getHash(slot) orelse return ProgramError.InvalidArgument;
+/// ```
+const std = @import("std");
+const pubkey_mod = @import("../pubkey/pubkey.zig");
+const AccountInfo = @import("../account_info/account_info.zig").AccountInfo;
+const ProgramError = @import("../program_error.zig").ProgramError;
+
+/// The SlotHashes sysvar's ID
+pub const ID = pubkey_mod.SLOT_HASHES_SYSVAR_ID;
+
+/// Most entries the runtime keeps
+pub const MAX_ENTRIES = 512;
+
+/// Size of one serialized entry: slot then hash
+const ENTRY_LEN = 8 + 32;
+
+/// The SlotHashes sysvar account's data
+pub const SlotHashes = struct {
+    /// The `len()` entries, without the count in front of them
+    entries: []const u8,
+
+    /// Number of entries
+    pub fn len(self: *const SlotHashes) usize {
+        return self.entries.len / ENTRY_LEN;
+    }
+
+    /// Slot of the `index`th entry, newest first
+    pub fn slotAt(self: *const SlotHashes, index: usize) u64 {
+        return std.mem.readInt(u64, self.entries[index * ENTRY_LEN ..][0..8], .little);
+    }
+
+    /// Hash of the `index`th entry, newest first
+    pub fn hashAt(self: *const SlotHashes, index: usize) [32]u8 {
+        return self.entries[index * ENTRY_LEN + 8 ..][0..32].*;
+    }
+
+    /// Index of the entry for `slot`, by binary search over the slots,
+    /// which the runtime keeps in descending order
+    pub fn position(self: *const SlotHashes, slot: u64) ?usize {
+        var low: usize = 0;
+        var high: usize = self.len();
+        while (low < high) {
+            const mid = low + (high - low) / 2;
+            const mid_slot = self.slotAt(mid);
+            if (mid_slot == slot) return mid;
+            if (mid_slot > slot) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        return null;
+    }
+
+    /// Hash of `slot`, or null when it is not among the recent slots
+    pub fn getHash(self: *const SlotHashes, slot: u64) ?[32]u8 {
+        const index = self.position(slot) orelse return null;
+        return self.hashAt(index);
+    }
+};
+
+/// Borrow the SlotHashes sysvar from `account`, which must be the sysvar
+/// account itself
+pub fn load(account: *const AccountInfo) ProgramError!SlotHashes {
+    if (!account.key().equals(&ID)) {
+        return ProgramError.InvalidArgument;
+    }
+
+    const data = account.getData();
+    if (data.len < 8) {
+        return ProgramError.InvalidAccountData;
+    }
+    const count = std.mem.readInt(u64, data[0..8], .little);
+    if (count > (data.len - 8) / ENTRY_LEN) {
+        return ProgramError.InvalidAccountData;
+    }
+    return .{ .entries = data[8..][0 .. @as(usize, @intCast(count)) * ENTRY_LEN] };
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const createTestAccountInfo = @import("../account_info/account_info.zig").createTestAccountInfo;
+
+/// Test hash of `slot`: its little-endian bytes repeated
+fn testHash(slot: u64) [32]u8 {
+    var hash: [32]u8 = undefined;
+    for (0..4) |i| std.mem.writeInt(u64, hash[i * 8 ..][0..8], slot, .little);
+    return hash;
+}
+
+/// A full sysvar of `MAX_ENTRIES` slots counting down from 10_000 in steps
+/// of 2, so odd slots are missing
+fn serializeTestSlotHashes(buffer: []u8) []u8 {
+    std.mem.writeInt(u64, buffer[0..8], MAX_ENTRIES, .little);
+    for (0..MAX_ENTRIES) |i| {
+        const entry = buffer[8 + i * ENTRY_LEN ..][0..ENTRY_LEN];
+        const slot: u64 = 10_000 - 2 * @as(u64, i);
+        std.mem.writeInt(u64, entry[0..8], slot, .little);
+        entry[8..].* = testHash(slot);
+    }
+    return buffer[0 .. 8 + MAX_ENTRIES * ENTRY_LEN];
+}
+
+test "getHash finds every slot in a full sysvar" {
+    const allocator = std.testing.allocator;
+    const buffer = try allocator.alloc(u8, 8 + MAX_ENTRIES * ENTRY_LEN);
+    defer allocator.free(buffer);
+    const data = serializeTestSlotHashes(buffer);
+    const info = try createTestAccountInfo(allocator, &ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, data, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    const slot_hashes = try load(&info);
+    try std.testing.expectEqual(@as(usize, MAX_ENTRIES), slot_hashes.len());
+
+    for (0..MAX_ENTRIES) |i| {
+        const slot: u64 = 10_000 - 2 * @as(u64, i);
+        try std.testing.expectEqual(@as(?usize, i), slot_hashes.position(slot));
+        try std.testing.expectEqual(testHash(slot), slot_hashes.getHash(slot).?);
+    }
+
+    // Newer than the newest, between two entries, and older than the oldest
+    try std.testing.expectEqual(@as(?[32]u8, null), slot_hashes.getHash(10_001));
+    try std.testing.expectEqual(@as(?[32]u8, null), slot_hashes.getHash(9_999));
+    try std.testing.expectEqual(@as(?[32]u8, null), slot_hashes.getHash(10_000 - 2 * MAX_ENTRIES));
+    try std.testing.expectEqual(@as(?[32]u8, null), slot_hashes.getHash(0));
+}
+
+test "load rejects accounts other than the slot hashes sysvar" {
+    const allocator = std.testing.allocator;
+    var data = [_]u8{0} ** 8;
+    const info = try createTestAccountInfo(allocator, &pubkey_mod.CLOCK_SYSVAR_ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, &data, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    try std.testing.expectError(ProgramError.InvalidArgument, load(&info));
+}
+
+test "load rejects a count past the end of the data" {
+    const allocator = std.testing.allocator;
+    var data = [_]u8{0} ** (8 + ENTRY_LEN);
+    std.mem.writeInt(u64, data[0..8], 2, .little);
+    const info = try createTestAccountInfo(allocator, &ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, &data, false, false, false);
+    defer allocator.destroy(info.data_ptr);
+
+    try std.testing.expectError(ProgramError.InvalidAccountData, load(&info));
+
+    const empty = try createTestAccountInfo(allocator, &ID, &pubkey_mod.SYSVAR_PROGRAM_ID, 0, data[0..4], false, false, false);
+    defer allocator.destroy(empty.data_ptr);
+    try std.testing.expectError(ProgramError.InvalidAccountData, load(&empty));
+}
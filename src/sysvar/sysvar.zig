@@ -0,0 +1,23 @@
+/// Sysvar access
+///
+/// Each sysvar has its own module with a `get` that reads it through the
+/// matching syscall, like Rust's `Sysvar::get`. The Instructions and
+/// SlotHashes sysvars have no syscall and are read from their accounts with
+/// `instructions.load` and `slot_hashes.load`.
+pub const clock = @import("clock.zig");
+pub const rent = @import("rent.zig");
+pub const epoch_schedule = @import("epoch_schedule.zig");
+pub const instructions = @import("instructions.zig");
+pub const slot_hashes = @import("slot_hashes.zig");
+
+pub const Clock = clock.Clock;
+pub const Rent = rent.Rent;
+pub const EpochSchedule = epoch_schedule.EpochSchedule;
+
+test {
+    _ = clock;
+    _ = rent;
+    _ = epoch_schedule;
+    _ = instructions;
+    _ = slot_hashes;
+}
@@ -0,0 +1,3428 @@
+// Generated by rust_test_helper (src/zig_codegen.rs) from the .expected.json
+// manifests. Do not edit, rerun `cargo run` in rust_test_helper instead.
+
+pub const Account = struct {
+    key_offset: usize,
+    lamports_offset: usize,
+    data_offset: usize,
+    lamports: u64,
+    rent_epoch: u64,
+    data_len: usize,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    /// Index of the account this entry duplicates. The offsets are the
+    /// original account's.
+    duplicate_of: ?u8,
+};
+
+pub const Fixture = struct {
+    file_name: []const u8,
+    account_count: usize,
+    accounts: []const Account,
+    instruction_data_offset: usize,
+    program_id_offset: usize,
+    /// Size of the whole input, which a parse should consume exactly
+    byte_length: usize,
+    /// The input breaks a runtime limit on purpose and should be rejected
+    negative: bool,
+};
+
+pub const ata_account = Fixture{
+    .file_name = "ata_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2039280,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 165,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10520,
+    .program_id_offset = 10520,
+    .byte_length = 10552,
+    .negative = false,
+};
+
+pub const borsh_counter_account = Fixture{
+    .file_name = "borsh_counter_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000000,
+            .rent_epoch = 0,
+            .data_len = 40,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10392,
+    .program_id_offset = 10393,
+    .byte_length = 10425,
+    .negative = false,
+};
+
+pub const bpf_loader_upgradeable = Fixture{
+    .file_name = "bpf_loader_upgradeable.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1141440,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 36,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10392,
+            .lamports_offset = 10456,
+            .data_offset = 10472,
+            .lamports = 1649520,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 109,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20840,
+            .lamports_offset = 20904,
+            .data_offset = 20920,
+            .lamports = 1593840,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 101,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 31280,
+    .program_id_offset = 31280,
+    .byte_length = 31312,
+    .negative = false,
+};
+
+pub const deprecated_multiple_accounts = Fixture{
+    .file_name = "deprecated_multiple_accounts.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 11,
+            .lamports_offset = 43,
+            .data_offset = 59,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 5,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 108,
+            .lamports_offset = 140,
+            .data_offset = 156,
+            .lamports = 2000,
+            .rent_epoch = 361,
+            .data_len = 10,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 210,
+            .lamports_offset = 242,
+            .data_offset = 258,
+            .lamports = 3000,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 15,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 322,
+    .program_id_offset = 325,
+    .byte_length = 357,
+    .negative = false,
+};
+
+pub const deprecated_single_account = Fixture{
+    .file_name = "deprecated_single_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 11,
+            .lamports_offset = 43,
+            .data_offset = 59,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 10,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 118,
+    .program_id_offset = 119,
+    .byte_length = 151,
+    .negative = false,
+};
+
+pub const edge_values = Fixture{
+    .file_name = "edge_values.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 0,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 5,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 18446744073709551615,
+            .rent_epoch = 9223372036854775809,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20704,
+            .lamports_offset = 20768,
+            .data_offset = 20784,
+            .lamports = 72623859790382856,
+            .rent_epoch = 578437695752307201,
+            .data_len = 3,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 31048,
+    .program_id_offset = 31049,
+    .byte_length = 31081,
+    .negative = false,
+};
+
+pub const empty_data_accounts = Fixture{
+    .file_name = "empty_data_accounts.bin",
+    .account_count = 2,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 2000,
+            .rent_epoch = 0,
+            .data_len = 4,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 20696,
+    .program_id_offset = 20696,
+    .byte_length = 20728,
+    .negative = false,
+};
+
+pub const flags_matrix = Fixture{
+    .file_name = "flags_matrix.bin",
+    .account_count = 8,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 1001,
+            .rent_epoch = 0,
+            .data_len = 2,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20704,
+            .lamports_offset = 20768,
+            .data_offset = 20784,
+            .lamports = 1002,
+            .rent_epoch = 0,
+            .data_len = 3,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31048,
+            .lamports_offset = 31112,
+            .data_offset = 31128,
+            .lamports = 1003,
+            .rent_epoch = 0,
+            .data_len = 4,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 41392,
+            .lamports_offset = 41456,
+            .data_offset = 41472,
+            .lamports = 1004,
+            .rent_epoch = 0,
+            .data_len = 5,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51736,
+            .lamports_offset = 51800,
+            .data_offset = 51816,
+            .lamports = 1005,
+            .rent_epoch = 0,
+            .data_len = 6,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 62080,
+            .lamports_offset = 62144,
+            .data_offset = 62160,
+            .lamports = 1006,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 72424,
+            .lamports_offset = 72488,
+            .data_offset = 72504,
+            .lamports = 1007,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 82768,
+    .program_id_offset = 82768,
+    .byte_length = 82800,
+    .negative = false,
+};
+
+pub const nonce_account = Fixture{
+    .file_name = "nonce_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1447680,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 80,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10432,
+    .program_id_offset = 10432,
+    .byte_length = 10464,
+    .negative = false,
+};
+
+pub const nonce_account_uninitialized = Fixture{
+    .file_name = "nonce_account_uninitialized.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1447680,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 80,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10432,
+    .program_id_offset = 10432,
+    .byte_length = 10464,
+    .negative = false,
+};
+
+pub const pda_accounts = Fixture{
+    .file_name = "pda_accounts.bin",
+    .account_count = 5,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 44,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20744,
+            .lamports_offset = 20808,
+            .data_offset = 20824,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 51,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31136,
+            .lamports_offset = 31200,
+            .data_offset = 31216,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 2,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 41480,
+            .lamports_offset = 41544,
+            .data_offset = 41560,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 37,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 51856,
+    .program_id_offset = 51856,
+    .byte_length = 51888,
+    .negative = false,
+};
+
+pub const permission_mismatch = Fixture{
+    .file_name = "permission_mismatch.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 10362,
+    .byte_length = 10394,
+    .negative = false,
+};
+
+pub const self_dup_account = Fixture{
+    .file_name = "self_dup_account.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 0,
+        },
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 0,
+        },
+    },
+    .instruction_data_offset = 10376,
+    .program_id_offset = 10376,
+    .byte_length = 10408,
+    .negative = false,
+};
+
+pub const solana_accounts_with_duplicates = Fixture{
+    .file_name = "solana_accounts_with_duplicates.bin",
+    .account_count = 5,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 2000,
+            .rent_epoch = 0,
+            .data_len = 12,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 0,
+        },
+        .{
+            .key_offset = 20720,
+            .lamports_offset = 20784,
+            .data_offset = 20800,
+            .lamports = 3000,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 6,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 2000,
+            .rent_epoch = 0,
+            .data_len = 12,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = true,
+            .duplicate_of = 1,
+        },
+    },
+    .instruction_data_offset = 31072,
+    .program_id_offset = 31076,
+    .byte_length = 31108,
+    .negative = false,
+};
+
+pub const solana_actual_accountinfo = Fixture{
+    .file_name = "solana_actual_accountinfo.bin",
+    .account_count = 2,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 10,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10368,
+            .lamports_offset = 10432,
+            .data_offset = 10448,
+            .lamports = 2000,
+            .rent_epoch = 0,
+            .data_len = 20,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 20728,
+    .program_id_offset = 20729,
+    .byte_length = 20761,
+    .negative = false,
+};
+
+pub const solana_alignment_padding = Fixture{
+    .file_name = "solana_alignment_padding.bin",
+    .account_count = 2,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 5,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 2000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 20704,
+    .program_id_offset = 20709,
+    .byte_length = 20741,
+    .negative = false,
+};
+
+pub const solana_alignment_sweep = Fixture{
+    .file_name = "solana_alignment_sweep.bin",
+    .account_count = 17,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 100,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 200,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20696,
+            .lamports_offset = 20760,
+            .data_offset = 20776,
+            .lamports = 300,
+            .rent_epoch = 0,
+            .data_len = 2,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31040,
+            .lamports_offset = 31104,
+            .data_offset = 31120,
+            .lamports = 400,
+            .rent_epoch = 0,
+            .data_len = 3,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 41384,
+            .lamports_offset = 41448,
+            .data_offset = 41464,
+            .lamports = 500,
+            .rent_epoch = 0,
+            .data_len = 4,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51728,
+            .lamports_offset = 51792,
+            .data_offset = 51808,
+            .lamports = 600,
+            .rent_epoch = 0,
+            .data_len = 5,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 62072,
+            .lamports_offset = 62136,
+            .data_offset = 62152,
+            .lamports = 700,
+            .rent_epoch = 0,
+            .data_len = 6,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 72416,
+            .lamports_offset = 72480,
+            .data_offset = 72496,
+            .lamports = 800,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 82760,
+            .lamports_offset = 82824,
+            .data_offset = 82840,
+            .lamports = 900,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 93104,
+            .lamports_offset = 93168,
+            .data_offset = 93184,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 103456,
+            .lamports_offset = 103520,
+            .data_offset = 103536,
+            .lamports = 1100,
+            .rent_epoch = 0,
+            .data_len = 10,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 113808,
+            .lamports_offset = 113872,
+            .data_offset = 113888,
+            .lamports = 1200,
+            .rent_epoch = 0,
+            .data_len = 11,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 124160,
+            .lamports_offset = 124224,
+            .data_offset = 124240,
+            .lamports = 1300,
+            .rent_epoch = 0,
+            .data_len = 12,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 134512,
+            .lamports_offset = 134576,
+            .data_offset = 134592,
+            .lamports = 1400,
+            .rent_epoch = 0,
+            .data_len = 13,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 144864,
+            .lamports_offset = 144928,
+            .data_offset = 144944,
+            .lamports = 1500,
+            .rent_epoch = 0,
+            .data_len = 14,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 155216,
+            .lamports_offset = 155280,
+            .data_offset = 155296,
+            .lamports = 1600,
+            .rent_epoch = 0,
+            .data_len = 15,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 165568,
+            .lamports_offset = 165632,
+            .data_offset = 165648,
+            .lamports = 1700,
+            .rent_epoch = 0,
+            .data_len = 16,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 175920,
+    .program_id_offset = 175920,
+    .byte_length = 175952,
+    .negative = false,
+};
+
+pub const solana_complex_iteration = Fixture{
+    .file_name = "solana_complex_iteration.bin",
+    .account_count = 10,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 500,
+            .rent_epoch = 0,
+            .data_len = 3,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 1000,
+            .rent_epoch = 100,
+            .data_len = 6,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20704,
+            .lamports_offset = 20768,
+            .data_offset = 20784,
+            .lamports = 1500,
+            .rent_epoch = 200,
+            .data_len = 9,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31056,
+            .lamports_offset = 31120,
+            .data_offset = 31136,
+            .lamports = 2000,
+            .rent_epoch = 300,
+            .data_len = 12,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 1000,
+            .rent_epoch = 100,
+            .data_len = 6,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 1,
+        },
+        .{
+            .key_offset = 41416,
+            .lamports_offset = 41480,
+            .data_offset = 41496,
+            .lamports = 3000,
+            .rent_epoch = 500,
+            .data_len = 6,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = true,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51760,
+            .lamports_offset = 51824,
+            .data_offset = 51840,
+            .lamports = 3500,
+            .rent_epoch = 600,
+            .data_len = 9,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20704,
+            .lamports_offset = 20768,
+            .data_offset = 20784,
+            .lamports = 1500,
+            .rent_epoch = 200,
+            .data_len = 9,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 2,
+        },
+        .{
+            .key_offset = 62120,
+            .lamports_offset = 62184,
+            .data_offset = 62200,
+            .lamports = 4500,
+            .rent_epoch = 800,
+            .data_len = 3,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 72464,
+            .lamports_offset = 72528,
+            .data_offset = 72544,
+            .lamports = 5000,
+            .rent_epoch = 900,
+            .data_len = 6,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 82808,
+    .program_id_offset = 82818,
+    .byte_length = 82850,
+    .negative = false,
+};
+
+pub const solana_empty_instruction_data = Fixture{
+    .file_name = "solana_empty_instruction_data.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5000,
+            .rent_epoch = 0,
+            .data_len = 4,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 10360,
+    .byte_length = 10392,
+    .negative = false,
+};
+
+pub const solana_full_entrypoint = Fixture{
+    .file_name = "solana_full_entrypoint.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5000000,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 1000000,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 13,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20704,
+            .lamports_offset = 20768,
+            .data_offset = 20784,
+            .lamports = 1,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 4,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 31048,
+    .program_id_offset = 31057,
+    .byte_length = 31089,
+    .negative = false,
+};
+
+pub const solana_instruction_data_1_byte = Fixture{
+    .file_name = "solana_instruction_data_1_byte.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 10361,
+    .byte_length = 10393,
+    .negative = false,
+};
+
+pub const solana_instruction_data_1kb = Fixture{
+    .file_name = "solana_instruction_data_1kb.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 11384,
+    .byte_length = 11416,
+    .negative = false,
+};
+
+pub const solana_instruction_data_empty = Fixture{
+    .file_name = "solana_instruction_data_empty.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 10360,
+    .byte_length = 10392,
+    .negative = false,
+};
+
+pub const solana_max_accounts = Fixture{
+    .file_name = "solana_max_accounts.bin",
+    .account_count = 64,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 1001,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20696,
+            .lamports_offset = 20760,
+            .data_offset = 20776,
+            .lamports = 1002,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31040,
+            .lamports_offset = 31104,
+            .data_offset = 31120,
+            .lamports = 1003,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 41384,
+            .lamports_offset = 41448,
+            .data_offset = 41464,
+            .lamports = 1004,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51736,
+            .lamports_offset = 51800,
+            .data_offset = 51816,
+            .lamports = 1005,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 63096,
+            .lamports_offset = 63160,
+            .data_offset = 63176,
+            .lamports = 1006,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 73432,
+            .lamports_offset = 73496,
+            .data_offset = 73512,
+            .lamports = 1007,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 83776,
+            .lamports_offset = 83840,
+            .data_offset = 83856,
+            .lamports = 1008,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 94120,
+            .lamports_offset = 94184,
+            .data_offset = 94200,
+            .lamports = 1009,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 0,
+        },
+        .{
+            .key_offset = 104472,
+            .lamports_offset = 104536,
+            .data_offset = 104552,
+            .lamports = 1011,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 115832,
+            .lamports_offset = 115896,
+            .data_offset = 115912,
+            .lamports = 1012,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 126168,
+            .lamports_offset = 126232,
+            .data_offset = 126248,
+            .lamports = 1013,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 136512,
+            .lamports_offset = 136576,
+            .data_offset = 136592,
+            .lamports = 1014,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 146856,
+            .lamports_offset = 146920,
+            .data_offset = 146936,
+            .lamports = 1015,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 157200,
+            .lamports_offset = 157264,
+            .data_offset = 157280,
+            .lamports = 1016,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 167552,
+            .lamports_offset = 167616,
+            .data_offset = 167632,
+            .lamports = 1017,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 178912,
+            .lamports_offset = 178976,
+            .data_offset = 178992,
+            .lamports = 1018,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 189248,
+            .lamports_offset = 189312,
+            .data_offset = 189328,
+            .lamports = 1019,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 199592,
+            .lamports_offset = 199656,
+            .data_offset = 199672,
+            .lamports = 1020,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31040,
+            .lamports_offset = 31104,
+            .data_offset = 31120,
+            .lamports = 1003,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 3,
+        },
+        .{
+            .key_offset = 209944,
+            .lamports_offset = 210008,
+            .data_offset = 210024,
+            .lamports = 1022,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 220296,
+            .lamports_offset = 220360,
+            .data_offset = 220376,
+            .lamports = 1023,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 231656,
+            .lamports_offset = 231720,
+            .data_offset = 231736,
+            .lamports = 1024,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 241992,
+            .lamports_offset = 242056,
+            .data_offset = 242072,
+            .lamports = 1025,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 252336,
+            .lamports_offset = 252400,
+            .data_offset = 252416,
+            .lamports = 1026,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 262680,
+            .lamports_offset = 262744,
+            .data_offset = 262760,
+            .lamports = 1027,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 273024,
+            .lamports_offset = 273088,
+            .data_offset = 273104,
+            .lamports = 1028,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 283376,
+            .lamports_offset = 283440,
+            .data_offset = 283456,
+            .lamports = 1029,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 294736,
+            .lamports_offset = 294800,
+            .data_offset = 294816,
+            .lamports = 1030,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 305072,
+            .lamports_offset = 305136,
+            .data_offset = 305152,
+            .lamports = 1031,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 167552,
+            .lamports_offset = 167616,
+            .data_offset = 167632,
+            .lamports = 1017,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 17,
+        },
+        .{
+            .key_offset = 315424,
+            .lamports_offset = 315488,
+            .data_offset = 315504,
+            .lamports = 1033,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 325768,
+            .lamports_offset = 325832,
+            .data_offset = 325848,
+            .lamports = 1034,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 336120,
+            .lamports_offset = 336184,
+            .data_offset = 336200,
+            .lamports = 1035,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 347480,
+            .lamports_offset = 347544,
+            .data_offset = 347560,
+            .lamports = 1036,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 357816,
+            .lamports_offset = 357880,
+            .data_offset = 357896,
+            .lamports = 1037,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 368160,
+            .lamports_offset = 368224,
+            .data_offset = 368240,
+            .lamports = 1038,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 378504,
+            .lamports_offset = 378568,
+            .data_offset = 378584,
+            .lamports = 1039,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 388848,
+            .lamports_offset = 388912,
+            .data_offset = 388928,
+            .lamports = 1040,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 399200,
+            .lamports_offset = 399264,
+            .data_offset = 399280,
+            .lamports = 1041,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 410560,
+            .lamports_offset = 410624,
+            .data_offset = 410640,
+            .lamports = 1042,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 410560,
+            .lamports_offset = 410624,
+            .data_offset = 410640,
+            .lamports = 1042,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 42,
+        },
+        .{
+            .key_offset = 420904,
+            .lamports_offset = 420968,
+            .data_offset = 420984,
+            .lamports = 1044,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 431248,
+            .lamports_offset = 431312,
+            .data_offset = 431328,
+            .lamports = 1045,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 441592,
+            .lamports_offset = 441656,
+            .data_offset = 441672,
+            .lamports = 1046,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 451944,
+            .lamports_offset = 452008,
+            .data_offset = 452024,
+            .lamports = 1047,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 463304,
+            .lamports_offset = 463368,
+            .data_offset = 463384,
+            .lamports = 1048,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 473640,
+            .lamports_offset = 473704,
+            .data_offset = 473720,
+            .lamports = 1049,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 483984,
+            .lamports_offset = 484048,
+            .data_offset = 484064,
+            .lamports = 1050,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 494328,
+            .lamports_offset = 494392,
+            .data_offset = 494408,
+            .lamports = 1051,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 504672,
+            .lamports_offset = 504736,
+            .data_offset = 504752,
+            .lamports = 1052,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 515024,
+            .lamports_offset = 515088,
+            .data_offset = 515104,
+            .lamports = 1053,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51736,
+            .lamports_offset = 51800,
+            .data_offset = 51816,
+            .lamports = 1005,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 5,
+        },
+        .{
+            .key_offset = 526392,
+            .lamports_offset = 526456,
+            .data_offset = 526472,
+            .lamports = 1055,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 536736,
+            .lamports_offset = 536800,
+            .data_offset = 536816,
+            .lamports = 1056,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 547080,
+            .lamports_offset = 547144,
+            .data_offset = 547160,
+            .lamports = 1057,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 557424,
+            .lamports_offset = 557488,
+            .data_offset = 557504,
+            .lamports = 1058,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 567776,
+            .lamports_offset = 567840,
+            .data_offset = 567856,
+            .lamports = 1059,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 579136,
+            .lamports_offset = 579200,
+            .data_offset = 579216,
+            .lamports = 1060,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 589472,
+            .lamports_offset = 589536,
+            .data_offset = 589552,
+            .lamports = 1061,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 599816,
+            .lamports_offset = 599880,
+            .data_offset = 599896,
+            .lamports = 1062,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 599816,
+            .lamports_offset = 599880,
+            .data_offset = 599896,
+            .lamports = 1062,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 62,
+        },
+    },
+    .instruction_data_offset = 610168,
+    .program_id_offset = 610176,
+    .byte_length = 610208,
+    .negative = false,
+};
+
+pub const solana_max_accounts_overflow = Fixture{
+    .file_name = "solana_max_accounts_overflow.bin",
+    .account_count = 65,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 1001,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20696,
+            .lamports_offset = 20760,
+            .data_offset = 20776,
+            .lamports = 1002,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31040,
+            .lamports_offset = 31104,
+            .data_offset = 31120,
+            .lamports = 1003,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 41384,
+            .lamports_offset = 41448,
+            .data_offset = 41464,
+            .lamports = 1004,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51736,
+            .lamports_offset = 51800,
+            .data_offset = 51816,
+            .lamports = 1005,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 63096,
+            .lamports_offset = 63160,
+            .data_offset = 63176,
+            .lamports = 1006,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 73432,
+            .lamports_offset = 73496,
+            .data_offset = 73512,
+            .lamports = 1007,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 83776,
+            .lamports_offset = 83840,
+            .data_offset = 83856,
+            .lamports = 1008,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 94120,
+            .lamports_offset = 94184,
+            .data_offset = 94200,
+            .lamports = 1009,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 0,
+        },
+        .{
+            .key_offset = 104472,
+            .lamports_offset = 104536,
+            .data_offset = 104552,
+            .lamports = 1011,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 115832,
+            .lamports_offset = 115896,
+            .data_offset = 115912,
+            .lamports = 1012,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 126168,
+            .lamports_offset = 126232,
+            .data_offset = 126248,
+            .lamports = 1013,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 136512,
+            .lamports_offset = 136576,
+            .data_offset = 136592,
+            .lamports = 1014,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 146856,
+            .lamports_offset = 146920,
+            .data_offset = 146936,
+            .lamports = 1015,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 157200,
+            .lamports_offset = 157264,
+            .data_offset = 157280,
+            .lamports = 1016,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 167552,
+            .lamports_offset = 167616,
+            .data_offset = 167632,
+            .lamports = 1017,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 178912,
+            .lamports_offset = 178976,
+            .data_offset = 178992,
+            .lamports = 1018,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 189248,
+            .lamports_offset = 189312,
+            .data_offset = 189328,
+            .lamports = 1019,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 199592,
+            .lamports_offset = 199656,
+            .data_offset = 199672,
+            .lamports = 1020,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 31040,
+            .lamports_offset = 31104,
+            .data_offset = 31120,
+            .lamports = 1003,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 3,
+        },
+        .{
+            .key_offset = 209944,
+            .lamports_offset = 210008,
+            .data_offset = 210024,
+            .lamports = 1022,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 220296,
+            .lamports_offset = 220360,
+            .data_offset = 220376,
+            .lamports = 1023,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 231656,
+            .lamports_offset = 231720,
+            .data_offset = 231736,
+            .lamports = 1024,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 241992,
+            .lamports_offset = 242056,
+            .data_offset = 242072,
+            .lamports = 1025,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 252336,
+            .lamports_offset = 252400,
+            .data_offset = 252416,
+            .lamports = 1026,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 262680,
+            .lamports_offset = 262744,
+            .data_offset = 262760,
+            .lamports = 1027,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 273024,
+            .lamports_offset = 273088,
+            .data_offset = 273104,
+            .lamports = 1028,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 283376,
+            .lamports_offset = 283440,
+            .data_offset = 283456,
+            .lamports = 1029,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 294736,
+            .lamports_offset = 294800,
+            .data_offset = 294816,
+            .lamports = 1030,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 305072,
+            .lamports_offset = 305136,
+            .data_offset = 305152,
+            .lamports = 1031,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 167552,
+            .lamports_offset = 167616,
+            .data_offset = 167632,
+            .lamports = 1017,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 17,
+        },
+        .{
+            .key_offset = 315424,
+            .lamports_offset = 315488,
+            .data_offset = 315504,
+            .lamports = 1033,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 325768,
+            .lamports_offset = 325832,
+            .data_offset = 325848,
+            .lamports = 1034,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 336120,
+            .lamports_offset = 336184,
+            .data_offset = 336200,
+            .lamports = 1035,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 347480,
+            .lamports_offset = 347544,
+            .data_offset = 347560,
+            .lamports = 1036,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 357816,
+            .lamports_offset = 357880,
+            .data_offset = 357896,
+            .lamports = 1037,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 368160,
+            .lamports_offset = 368224,
+            .data_offset = 368240,
+            .lamports = 1038,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 378504,
+            .lamports_offset = 378568,
+            .data_offset = 378584,
+            .lamports = 1039,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 388848,
+            .lamports_offset = 388912,
+            .data_offset = 388928,
+            .lamports = 1040,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 399200,
+            .lamports_offset = 399264,
+            .data_offset = 399280,
+            .lamports = 1041,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 410560,
+            .lamports_offset = 410624,
+            .data_offset = 410640,
+            .lamports = 1042,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 410560,
+            .lamports_offset = 410624,
+            .data_offset = 410640,
+            .lamports = 1042,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 42,
+        },
+        .{
+            .key_offset = 420904,
+            .lamports_offset = 420968,
+            .data_offset = 420984,
+            .lamports = 1044,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 431248,
+            .lamports_offset = 431312,
+            .data_offset = 431328,
+            .lamports = 1045,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 441592,
+            .lamports_offset = 441656,
+            .data_offset = 441672,
+            .lamports = 1046,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 451944,
+            .lamports_offset = 452008,
+            .data_offset = 452024,
+            .lamports = 1047,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 463304,
+            .lamports_offset = 463368,
+            .data_offset = 463384,
+            .lamports = 1048,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 473640,
+            .lamports_offset = 473704,
+            .data_offset = 473720,
+            .lamports = 1049,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 483984,
+            .lamports_offset = 484048,
+            .data_offset = 484064,
+            .lamports = 1050,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 494328,
+            .lamports_offset = 494392,
+            .data_offset = 494408,
+            .lamports = 1051,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 504672,
+            .lamports_offset = 504736,
+            .data_offset = 504752,
+            .lamports = 1052,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 515024,
+            .lamports_offset = 515088,
+            .data_offset = 515104,
+            .lamports = 1053,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 51736,
+            .lamports_offset = 51800,
+            .data_offset = 51816,
+            .lamports = 1005,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = 5,
+        },
+        .{
+            .key_offset = 526392,
+            .lamports_offset = 526456,
+            .data_offset = 526472,
+            .lamports = 1055,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 536736,
+            .lamports_offset = 536800,
+            .data_offset = 536816,
+            .lamports = 1056,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 547080,
+            .lamports_offset = 547144,
+            .data_offset = 547160,
+            .lamports = 1057,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 557424,
+            .lamports_offset = 557488,
+            .data_offset = 557504,
+            .lamports = 1058,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 567776,
+            .lamports_offset = 567840,
+            .data_offset = 567856,
+            .lamports = 1059,
+            .rent_epoch = 0,
+            .data_len = 1024,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 579136,
+            .lamports_offset = 579200,
+            .data_offset = 579216,
+            .lamports = 1060,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 589472,
+            .lamports_offset = 589536,
+            .data_offset = 589552,
+            .lamports = 1061,
+            .rent_epoch = 0,
+            .data_len = 1,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 599816,
+            .lamports_offset = 599880,
+            .data_offset = 599896,
+            .lamports = 1062,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 599816,
+            .lamports_offset = 599880,
+            .data_offset = 599896,
+            .lamports = 1062,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = 62,
+        },
+        .{
+            .key_offset = 610168,
+            .lamports_offset = 610232,
+            .data_offset = 610248,
+            .lamports = 1064,
+            .rent_epoch = 0,
+            .data_len = 9,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 620520,
+    .program_id_offset = 620528,
+    .byte_length = 620560,
+    .negative = true,
+};
+
+pub const solana_max_data_account = Fixture{
+    .file_name = "solana_max_data_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000000000,
+            .rent_epoch = 0,
+            .data_len = 10485760,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10496112,
+    .program_id_offset = 10496112,
+    .byte_length = 10496144,
+    .negative = false,
+};
+
+pub const solana_max_lamports = Fixture{
+    .file_name = "solana_max_lamports.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 18446744073709551615,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10352,
+    .program_id_offset = 10352,
+    .byte_length = 10384,
+    .negative = false,
+};
+
+pub const solana_multiple_accounts = Fixture{
+    .file_name = "solana_multiple_accounts.bin",
+    .account_count = 3,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 5,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 2000,
+            .rent_epoch = 361,
+            .data_len = 10,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 20712,
+            .lamports_offset = 20776,
+            .data_offset = 20792,
+            .lamports = 3000,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 15,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = true,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 31064,
+    .program_id_offset = 31067,
+    .byte_length = 31099,
+    .negative = false,
+};
+
+pub const solana_no_accounts = Fixture{
+    .file_name = "solana_no_accounts.bin",
+    .account_count = 0,
+    .accounts = &.{
+    },
+    .instruction_data_offset = 16,
+    .program_id_offset = 64,
+    .byte_length = 96,
+    .negative = false,
+};
+
+pub const solana_program_id = Fixture{
+    .file_name = "solana_program_id.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 7,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10360,
+    .program_id_offset = 10363,
+    .byte_length = 10395,
+    .negative = false,
+};
+
+pub const solana_realloc_space = Fixture{
+    .file_name = "solana_realloc_space.bin",
+    .account_count = 2,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 8,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10360,
+            .lamports_offset = 10424,
+            .data_offset = 10440,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 18,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 20720,
+    .program_id_offset = 20728,
+    .byte_length = 20760,
+    .negative = false,
+};
+
+pub const solana_single_account = Fixture{
+    .file_name = "solana_single_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1000,
+            .rent_epoch = 0,
+            .data_len = 10,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10368,
+    .program_id_offset = 10369,
+    .byte_length = 10401,
+    .negative = false,
+};
+
+pub const solana_zero_lamports = Fixture{
+    .file_name = "solana_zero_lamports.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10352,
+    .program_id_offset = 10352,
+    .byte_length = 10384,
+    .negative = false,
+};
+
+pub const spl_token_account = Fixture{
+    .file_name = "spl_token_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2039280,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 165,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10520,
+    .program_id_offset = 10520,
+    .byte_length = 10552,
+    .negative = false,
+};
+
+pub const spl_token_account_frozen = Fixture{
+    .file_name = "spl_token_account_frozen.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2039280,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 165,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10520,
+    .program_id_offset = 10520,
+    .byte_length = 10552,
+    .negative = false,
+};
+
+pub const spl_token_account_native = Fixture{
+    .file_name = "spl_token_account_native.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1002039280,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 165,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10520,
+    .program_id_offset = 10520,
+    .byte_length = 10552,
+    .negative = false,
+};
+
+pub const spl_token_mint = Fixture{
+    .file_name = "spl_token_mint.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1461600,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 82,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10440,
+    .program_id_offset = 10440,
+    .byte_length = 10472,
+    .negative = false,
+};
+
+pub const spl_token_mint_with_freeze_authority = Fixture{
+    .file_name = "spl_token_mint_with_freeze_authority.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1461600,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 82,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10440,
+    .program_id_offset = 10440,
+    .byte_length = 10472,
+    .negative = false,
+};
+
+pub const stake_account_delegated = Fixture{
+    .file_name = "stake_account_delegated.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 5002282880,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 200,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10552,
+    .program_id_offset = 10552,
+    .byte_length = 10584,
+    .negative = false,
+};
+
+pub const stake_account_initialized = Fixture{
+    .file_name = "stake_account_initialized.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2282880,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 200,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10552,
+    .program_id_offset = 10552,
+    .byte_length = 10584,
+    .negative = false,
+};
+
+pub const stake_account_rewards_pool = Fixture{
+    .file_name = "stake_account_rewards_pool.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2282880,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 200,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10552,
+    .program_id_offset = 10552,
+    .byte_length = 10584,
+    .negative = false,
+};
+
+pub const stake_account_uninitialized = Fixture{
+    .file_name = "stake_account_uninitialized.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2282880,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 200,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10552,
+    .program_id_offset = 10552,
+    .byte_length = 10584,
+    .negative = false,
+};
+
+pub const sysvar_clock = Fixture{
+    .file_name = "sysvar_clock.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1169280,
+            .rent_epoch = 0,
+            .data_len = 40,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10392,
+    .program_id_offset = 10392,
+    .byte_length = 10424,
+    .negative = false,
+};
+
+pub const sysvar_epoch_schedule = Fixture{
+    .file_name = "sysvar_epoch_schedule.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1120560,
+            .rent_epoch = 0,
+            .data_len = 33,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10392,
+    .program_id_offset = 10392,
+    .byte_length = 10424,
+    .negative = false,
+};
+
+pub const sysvar_rent = Fixture{
+    .file_name = "sysvar_rent.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1009200,
+            .rent_epoch = 0,
+            .data_len = 17,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10376,
+    .program_id_offset = 10376,
+    .byte_length = 10408,
+    .negative = false,
+};
+
+pub const sysvar_rent_mainnet = Fixture{
+    .file_name = "sysvar_rent_mainnet.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1009200,
+            .rent_epoch = 0,
+            .data_len = 17,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10376,
+    .program_id_offset = 10376,
+    .byte_length = 10408,
+    .negative = false,
+};
+
+pub const sysvar_slot_hashes = Fixture{
+    .file_name = "sysvar_slot_hashes.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 2338560,
+            .rent_epoch = 0,
+            .data_len = 208,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10560,
+    .program_id_offset = 10560,
+    .byte_length = 10592,
+    .negative = false,
+};
+
+pub const sysvar_stake_history = Fixture{
+    .file_name = "sysvar_stake_history.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 1614720,
+            .rent_epoch = 0,
+            .data_len = 104,
+            .is_signer = false,
+            .is_writable = false,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 10456,
+    .program_id_offset = 10456,
+    .byte_length = 10488,
+    .negative = false,
+};
+
+pub const vote_account = Fixture{
+    .file_name = "vote_account.bin",
+    .account_count = 1,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 27074400,
+            .rent_epoch = 18446744073709551615,
+            .data_len = 3762,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 14120,
+    .program_id_offset = 14120,
+    .byte_length = 14152,
+    .negative = false,
+};
+
+pub const zero_vs_unallocated = Fixture{
+    .file_name = "zero_vs_unallocated.bin",
+    .account_count = 2,
+    .accounts = &.{
+        .{
+            .key_offset = 16,
+            .lamports_offset = 80,
+            .data_offset = 96,
+            .lamports = 0,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = true,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+        .{
+            .key_offset = 10352,
+            .lamports_offset = 10416,
+            .data_offset = 10432,
+            .lamports = 890880,
+            .rent_epoch = 0,
+            .data_len = 0,
+            .is_signer = false,
+            .is_writable = true,
+            .executable = false,
+            .duplicate_of = null,
+        },
+    },
+    .instruction_data_offset = 20688,
+    .program_id_offset = 20688,
+    .byte_length = 20720,
+    .negative = false,
+};
+
+pub const all = [_]*const Fixture{
+    &ata_account,
+    &borsh_counter_account,
+    &bpf_loader_upgradeable,
+    &deprecated_multiple_accounts,
+    &deprecated_single_account,
+    &edge_values,
+    &empty_data_accounts,
+    &flags_matrix,
+    &nonce_account,
+    &nonce_account_uninitialized,
+    &pda_accounts,
+    &permission_mismatch,
+    &self_dup_account,
+    &solana_accounts_with_duplicates,
+    &solana_actual_accountinfo,
+    &solana_alignment_padding,
+    &solana_alignment_sweep,
+    &solana_complex_iteration,
+    &solana_empty_instruction_data,
+    &solana_full_entrypoint,
+    &solana_instruction_data_1_byte,
+    &solana_instruction_data_1kb,
+    &solana_instruction_data_empty,
+    &solana_max_accounts,
+    &solana_max_accounts_overflow,
+    &solana_max_data_account,
+    &solana_max_lamports,
+    &solana_multiple_accounts,
+    &solana_no_accounts,
+    &solana_program_id,
+    &solana_realloc_space,
+    &solana_single_account,
+    &solana_zero_lamports,
+    &spl_token_account,
+    &spl_token_account_frozen,
+    &spl_token_account_native,
+    &spl_token_mint,
+    &spl_token_mint_with_freeze_authority,
+    &stake_account_delegated,
+    &stake_account_initialized,
+    &stake_account_rewards_pool,
+    &stake_account_uninitialized,
+    &sysvar_clock,
+    &sysvar_epoch_schedule,
+    &sysvar_rent,
+    &sysvar_rent_mainnet,
+    &sysvar_slot_hashes,
+    &sysvar_stake_history,
+    &vote_account,
+    &zero_vs_unallocated,
+};